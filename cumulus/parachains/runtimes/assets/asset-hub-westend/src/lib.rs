@@ -60,8 +60,8 @@ use frame_support::{
 		fungibles,
 		tokens::{imbalance::ResolveAssetTo, nonfungibles_v2::Inspect},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
-		ConstantStoragePrice, EitherOfDiverse, Equals, InstanceFilter, LinearStoragePrice, Nothing,
-		TransformOrigin, WithdrawReasons,
+		ConstantStoragePrice, Contains, EitherOfDiverse, Equals, EverythingBut, InstanceFilter,
+		LinearStoragePrice, Nothing, TransformOrigin, WithdrawReasons,
 	},
 	weights::{ConstantMultiplier, Weight},
 	BoundedVec, PalletId,
@@ -205,9 +205,20 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// A type to identify calls creating stored multisig accounts. These are filtered until
+/// `pallet_multisig` is benchmarked for this runtime. The other stored multisig calls act on
+/// stored multisig accounts only and fail without them.
+pub struct IsStoredMultisigCall;
+impl Contains<RuntimeCall> for IsStoredMultisigCall {
+	fn contains(c: &RuntimeCall) -> bool {
+		matches!(c, RuntimeCall::Multisig(pallet_multisig::Call::create_multisig { .. }))
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 #[derive_impl(frame_system::config_preludes::ParaChainDefaultConfig)]
 impl frame_system::Config for Runtime {
+	type BaseCallFilter = EverythingBut<IsStoredMultisigCall>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -491,6 +502,9 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type MaxPriceObservations = ConstU32<1024>;
+	type AmplificationOrigin = AssetsForceOrigin;
+	type MinAmplificationRampDuration = ConstU32<{ DAYS }>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = weights::pallet_asset_conversion::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -651,6 +665,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = System;
 }
@@ -918,6 +933,56 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// Reports the native and fungible asset transfers dispatched by proxies with a spending limit.
+///
+/// Such proxies may only dispatch these transfers, remarks and batches of them.
+pub struct ProxySpending;
+impl pallet_proxy::InspectSpending<RuntimeCall, Balance> for ProxySpending {
+	type AssetKind = xcm::v5::Location;
+
+	fn spending(call: &RuntimeCall) -> pallet_proxy::Spending<xcm::v5::Location, Balance> {
+		use pallet_proxy::Spending;
+		use sp_runtime::traits::MaybeEquivalence;
+		type ForeignAssetsCall = pallet_assets::Call<Runtime, ForeignAssetsInstance>;
+		match call {
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer_allow_death { value, .. } |
+				pallet_balances::Call::transfer_keep_alive { value, .. },
+			) => Spending::Amount(WestendLocation::get(), *value),
+			// Approvals let the delegate spend the approved amount later on.
+			RuntimeCall::Assets(
+				TrustBackedAssetsCall::transfer { id, amount, .. } |
+				TrustBackedAssetsCall::transfer_keep_alive { id, amount, .. } |
+				TrustBackedAssetsCall::approve_transfer { id, amount, .. },
+			) => AssetIdForTrustBackedAssetsConvert::<
+				TrustBackedAssetsPalletLocation,
+				xcm::v5::Location,
+			>::convert_back(&id.0)
+			.map_or(Spending::Unknown, |asset| Spending::Amount(asset, *amount)),
+			RuntimeCall::ForeignAssets(
+				ForeignAssetsCall::transfer { id, amount, .. } |
+				ForeignAssetsCall::transfer_keep_alive { id, amount, .. } |
+				ForeignAssetsCall::approve_transfer { id, amount, .. },
+			) => Spending::Amount(id.clone(), *amount),
+			// The calls of a batch are inspected on their own.
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. },
+			) |
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { .. } |
+				pallet_utility::Call::batch_all { .. } |
+				pallet_utility::Call::force_batch { .. },
+			) => Spending::Nothing,
+			_ => Spending::Unknown,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_asset_kind() -> xcm::v5::Location {
+		WestendLocation::get()
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -932,6 +997,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = RelaychainDataProvider<Runtime>;
+	type SpendingInspector = ProxySpending;
 }
 
 parameter_types! {
@@ -1167,6 +1233,7 @@ parameter_types! {
 	pub const NftsMetadataDepositBase: Balance = UniquesMetadataDepositBase::get();
 	pub const NftsAttributeDepositBase: Balance = UniquesAttributeDepositBase::get();
 	pub const NftsDepositPerByte: Balance = UniquesDepositPerByte::get();
+	pub const NftsOperatorDeposit: Balance = deposit(1, 132);
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -1182,6 +1249,7 @@ impl pallet_nfts::Config for Runtime {
 	type MetadataDepositBase = NftsMetadataDepositBase;
 	type AttributeDepositBase = NftsAttributeDepositBase;
 	type DepositPerByte = NftsDepositPerByte;
+	type OperatorDeposit = NftsOperatorDeposit;
 	type StringLimit = ConstU32<256>;
 	type KeyLimit = ConstU32<64>;
 	type ValueLimit = ConstU32<256>;
@@ -1955,6 +2023,7 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		}
 	}
 
+	#[api_version(3)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -1972,6 +2041,14 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		fn get_reserves(asset1: xcm::v5::Location, asset2: xcm::v5::Location) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn get_twap(asset1: xcm::v5::Location, asset2: xcm::v5::Location, from: BlockNumber, to: BlockNumber) -> Option<FixedU128> {
+			AssetConversion::get_twap(asset1, asset2, from, to)
+		}
+
+		fn find_best_route(asset_in: xcm::v5::Location, asset_out: xcm::v5::Location, amount_in: Balance) -> Option<pallet_asset_conversion::SwapRoute<xcm::v5::Location, Balance>> {
+			AssetConversion::find_best_route(asset_in, asset_out, amount_in)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
@@ -2144,6 +2221,17 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		}
 	}
 
+	impl pallet_proxy::ProxyApi<Block, AccountId, ProxyType, BlockNumber, Balance> for Runtime {
+		fn remaining_allowance(
+			real: AccountId,
+			delegate: AccountId,
+			proxy_type: ProxyType,
+			delay: BlockNumber,
+		) -> Option<Balance> {
+			Proxy::remaining_allowance(real, delegate, proxy_type, delay)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
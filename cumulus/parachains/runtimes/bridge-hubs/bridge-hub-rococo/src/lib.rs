@@ -63,7 +63,9 @@ use frame_support::{
 	dispatch::DispatchClass,
 	genesis_builder_helper::{build_state, get_preset},
 	parameter_types,
-	traits::{ConstBool, ConstU32, ConstU64, ConstU8, Get, TransformOrigin},
+	traits::{
+		ConstBool, ConstU32, ConstU64, ConstU8, Contains, EverythingBut, Get, TransformOrigin,
+	},
 	weights::{ConstantMultiplier, Weight},
 	PalletId,
 };
@@ -296,8 +298,19 @@ parameter_types! {
 
 // Configure FRAME pallets to include in runtime.
 
+/// A type to identify calls creating stored multisig accounts. These are filtered until
+/// `pallet_multisig` is benchmarked for this runtime. The other stored multisig calls act on
+/// stored multisig accounts only and fail without them.
+pub struct IsStoredMultisigCall;
+impl Contains<RuntimeCall> for IsStoredMultisigCall {
+	fn contains(c: &RuntimeCall) -> bool {
+		matches!(c, RuntimeCall::Multisig(pallet_multisig::Call::create_multisig { .. }))
+	}
+}
+
 #[derive_impl(frame_system::config_preludes::ParaChainDefaultConfig)]
 impl frame_system::Config for Runtime {
+	type BaseCallFilter = EverythingBut<IsStoredMultisigCall>;
 	/// The identifier used to distinguish between accounts.
 	type AccountId = AccountId;
 	/// The index type for storing how many extrinsics an account has signed.
@@ -559,6 +572,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -177,4 +177,127 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:1)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_multisig(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (3 ±0)`
+		//  Estimated: `6761`
+		// Minimum execution time: 31_936_000 picoseconds.
+		Weight::from_parts(34_691_639, 0)
+			.saturating_add(Weight::from_parts(0, 6761))
+			// Standard Error: 2_520
+			.saturating_add(Weight::from_parts(74_454, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:0)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Proposals` (r:1 w:1)
+	/// Proof: `Multisig::Proposals` (`max_values`: None, `max_size`: Some(19738), added: 22213, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn propose(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `351 + s * (34 ±0)`
+		//  Estimated: `23203`
+		// Minimum execution time: 43_960_000 picoseconds.
+		Weight::from_parts(41_374_812, 0)
+			.saturating_add(Weight::from_parts(0, 23203))
+			// Standard Error: 4_097
+			.saturating_add(Weight::from_parts(77_589, 0).saturating_mul(s.into()))
+			// Standard Error: 40
+			.saturating_add(Weight::from_parts(2_608, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:0)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Proposals` (r:1 w:1)
+	/// Proof: `Multisig::Proposals` (`max_values`: None, `max_size`: Some(19738), added: 22213, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn approve_proposal(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `482 + s * (32 ±0) + z * (1 ±0)`
+		//  Estimated: `23203`
+		// Minimum execution time: 27_305_000 picoseconds.
+		Weight::from_parts(25_528_344, 0)
+			.saturating_add(Weight::from_parts(0, 23203))
+			// Standard Error: 1_407
+			.saturating_add(Weight::from_parts(43_058, 0).saturating_mul(s.into()))
+			// Standard Error: 13
+			.saturating_add(Weight::from_parts(566, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:0)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Proposals` (r:1 w:1)
+	/// Proof: `Multisig::Proposals` (`max_values`: None, `max_size`: Some(19738), added: 22213, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn execute_proposal(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `643 + s * (66 ±0) + z * (1 ±0)`
+		//  Estimated: `23203`
+		// Minimum execution time: 51_454_000 picoseconds.
+		Weight::from_parts(48_793_875, 0)
+			.saturating_add(Weight::from_parts(0, 23203))
+			// Standard Error: 2_173
+			.saturating_add(Weight::from_parts(98_378, 0).saturating_mul(s.into()))
+			// Standard Error: 21
+			.saturating_add(Weight::from_parts(528, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::Proposals` (r:1 w:1)
+	/// Proof: `Multisig::Proposals` (`max_values`: None, `max_size`: Some(19738), added: 22213, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:0)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn cancel_proposal(_s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `10565 + s * (34 ±0)`
+		//  Estimated: `23203`
+		// Minimum execution time: 41_009_000 picoseconds.
+		Weight::from_parts(53_675_850, 0)
+			.saturating_add(Weight::from_parts(0, 23203))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:1)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn update_multisig(_s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4134`
+		//  Estimated: `6761`
+		// Minimum execution time: 52_263_000 picoseconds.
+		Weight::from_parts(56_661_369, 0)
+			.saturating_add(Weight::from_parts(0, 6761))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::MultisigAccounts` (r:1 w:1)
+	/// Proof: `Multisig::MultisigAccounts` (`max_values`: None, `max_size`: Some(3296), added: 5771, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn destroy_multisig() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3843`
+		//  Estimated: `6761`
+		// Minimum execution time: 36_019_000 picoseconds.
+		Weight::from_parts(38_185_000, 0)
+			.saturating_add(Weight::from_parts(0, 6761))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
@@ -269,6 +269,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 }
 
@@ -62,12 +62,16 @@ use sc_consensus::{
 	import_queue::{BasicQueue, Verifier as VerifierT},
 	BlockImportParams, DefaultImportQueue, LongestChain,
 };
-use sc_consensus_manual_seal::consensus::aura::AuraConsensusDataProvider;
+use sc_consensus_manual_seal::{
+	consensus::aura::AuraConsensusDataProvider,
+	rpc::{ManualSeal, ManualSealApiServer, ManualSealDev, ManualSealDevApiServer},
+	TimeWarp,
+};
 use sc_network::{config::FullNetworkConfiguration, NotificationMetrics, PeerId};
 use sc_service::{Configuration, Error, PartialComponents, TaskManager};
 use sc_telemetry::TelemetryHandle;
 use sc_transaction_pool::TransactionPoolHandle;
-use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_consensus::Environment;
 use sp_core::traits::SpawnEssentialNamed;
@@ -291,22 +295,43 @@ where
 
 		let para_id =
 			Self::parachain_id(&client, &config).ok_or("Failed to retrieve the parachain id")?;
-		let create_inherent_data_providers =
-			Self::create_dev_node_inherent_data_providers(client.clone(), para_id, slot_duration);
+		let time_warp = TimeWarp::new();
+		let create_inherent_data_providers = Self::create_dev_node_inherent_data_providers(
+			client.clone(),
+			para_id,
+			slot_duration,
+			time_warp.clone(),
+		);
+		// The engine commands sink exposed over RPC, in both seal modes.
+		let (manual_seal_rpc_sink, manual_seal_stream) = futures::channel::mpsc::channel(1024);
 
 		match mode {
 			DevSealMode::InstantSeal => {
-				let params = sc_consensus_manual_seal::InstantSealParams {
+				// Seal a block for every imported transaction, as `run_instant_seal` does, while
+				// still accepting the commands sent over RPC.
+				let pool_commands = transaction_pool.import_notification_stream().map(|_| {
+					sc_consensus_manual_seal::EngineCommand::SealNewBlock {
+						create_empty: true,
+						finalize: false,
+						parent_hash: None,
+						sender: None,
+					}
+				});
+				let params = sc_consensus_manual_seal::ManualSealParams {
 					block_import: client.clone(),
 					env: proposer,
 					client: client.clone(),
 					pool: transaction_pool.clone(),
 					select_chain: LongestChain::new(backend.clone()),
+					commands_stream: Box::pin(futures::stream::select(
+						pool_commands,
+						manual_seal_stream,
+					)),
 					consensus_data_provider: Some(Box::new(aura_digest_provider)),
 					create_inherent_data_providers,
 				};
 
-				let authorship_future = sc_consensus_manual_seal::run_instant_seal(params);
+				let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
 				task_manager.spawn_essential_handle().spawn_blocking(
 					"instant-seal",
 					None,
@@ -314,8 +339,7 @@ where
 				);
 			},
 			DevSealMode::ManualSeal(block_time) => {
-				let (manual_seal_sink, manual_seal_stream) = futures::channel::mpsc::channel(1024);
-				let mut manual_seal_sink_clone = manual_seal_sink.clone();
+				let mut manual_seal_sink_clone = manual_seal_rpc_sink.clone();
 				task_manager
 					.spawn_essential_handle()
 					.spawn("block_authoring", None, async move {
@@ -359,13 +383,23 @@ where
 			let backend_for_rpc = backend.clone();
 
 			Box::new(move |_| {
-				let module = Self::BuildRpcExtensions::build_rpc_extensions(
+				let mut module = Self::BuildRpcExtensions::build_rpc_extensions(
 					client.clone(),
 					backend_for_rpc.clone(),
 					transaction_pool.clone(),
 					None,
 					spawn_handle.clone(),
 				)?;
+				let manual_seal_dev = ManualSealDev::new(
+					client.clone(),
+					backend_for_rpc.clone(),
+					manual_seal_rpc_sink.clone(),
+					time_warp.clone(),
+				);
+				module
+					.merge(ManualSeal::new(manual_seal_rpc_sink.clone()).into_rpc())
+					.and_then(|_| module.merge(manual_seal_dev.into_rpc()))
+					.map_err(|e| sc_service::Error::Application(e.into()))?;
 				Ok(module)
 			})
 		};
@@ -406,6 +440,7 @@ where
 		client: Arc<ParachainClient<Block, RuntimeApi>>,
 		para_id: ParaId,
 		slot_duration: sp_consensus_aura::SlotDuration,
+		time_warp: TimeWarp,
 	) -> impl Fn(
 		Hash,
 		(),
@@ -454,6 +489,16 @@ where
 			// relay_blocks_per_para_block * block_number
 			let target_relay_slot = initial_relay_slot +
 				u64::from(current_block_number) * u64::from(relay_blocks_per_para_block);
+			let mut timestamp =
+				time_warp.apply(target_relay_slot * RELAY_CHAIN_SLOT_DURATION_MILLIS);
+			// Time warps move the relay slot as well. The para slot derived from the timestamp
+			// must match the one derived from the relay slot, which holds for any timestamp if the
+			// para slots span whole relay slots. Otherwise the timestamp stays aligned with the
+			// relay slots.
+			let target_relay_slot = timestamp / RELAY_CHAIN_SLOT_DURATION_MILLIS;
+			if slot_duration.as_millis() % RELAY_CHAIN_SLOT_DURATION_MILLIS != 0 {
+				timestamp = target_relay_slot * RELAY_CHAIN_SLOT_DURATION_MILLIS;
+			}
 
 			let relay_offset = (target_relay_slot as u32)
 				.saturating_sub(relay_blocks_per_para_block * current_block_number);
@@ -473,8 +518,6 @@ where
 				..Default::default()
 			};
 
-			let timestamp = target_relay_slot * RELAY_CHAIN_SLOT_DURATION_MILLIS;
-
 			let timestamp_provider = sp_timestamp::InherentDataProvider::new(timestamp.into());
 
 			futures::future::ready(Ok((timestamp_provider, mocked_parachain)))
@@ -114,4 +114,11 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Database utilities.
+	#[command(subcommand)]
+	Db(sc_cli::DbSubcommand),
+
+	/// Call a view function of a pallet at a given block.
+	CallViewFunction(sc_cli::CallViewFunctionCmd),
 }
@@ -236,5 +236,26 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::Db(sc_cli::DbSubcommand::StorageStats(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config, None)?;
+				cmd.run(client)
+			})
+		},
+		Some(Subcommand::Db(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run::<Block>(&config))
+		},
+		Some(Subcommand::CallViewFunction(cmd)) => {
+			if cmd.rpc_url.is_some() {
+				return sc_cli::build_runtime()?.block_on(cmd.run_rpc::<Block>());
+			}
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config, None)?;
+				cmd.run(client)
+			})
+		},
 	}
 }
@@ -402,6 +402,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -479,6 +480,46 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// Reports the native and asset transfers dispatched by proxies with a spending limit.
+///
+/// Such proxies may only dispatch these transfers, remarks and batches of them.
+pub struct ProxySpending;
+impl pallet_proxy::InspectSpending<RuntimeCall, Balance> for ProxySpending {
+	type AssetKind = NativeOrWithId<u32>;
+
+	fn spending(call: &RuntimeCall) -> pallet_proxy::Spending<NativeOrWithId<u32>, Balance> {
+		use pallet_proxy::Spending;
+		type AssetsCall = pallet_assets::Call<Runtime, Instance1>;
+		match call {
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer_allow_death { value, .. } |
+				pallet_balances::Call::transfer_keep_alive { value, .. },
+			) => Spending::Amount(NativeOrWithId::Native, *value),
+			// Approvals let the delegate spend the approved amount later on.
+			RuntimeCall::Assets(
+				AssetsCall::transfer { id, amount, .. } |
+				AssetsCall::transfer_keep_alive { id, amount, .. } |
+				AssetsCall::approve_transfer { id, amount, .. },
+			) => Spending::Amount(NativeOrWithId::WithId(id.0), *amount),
+			// The calls of a batch are inspected on their own.
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. },
+			) |
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { .. } |
+				pallet_utility::Call::batch_all { .. } |
+				pallet_utility::Call::force_batch { .. },
+			) => Spending::Nothing,
+			_ => Spending::Unknown,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_asset_kind() -> NativeOrWithId<u32> {
+		NativeOrWithId::Native
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -493,6 +534,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type SpendingInspector = ProxySpending;
 }
 
 parameter_types! {
@@ -1973,6 +2015,9 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type WeightInfo = pallet_asset_conversion::weights::SubstrateWeight<Runtime>;
 	type MaxSwapPathLength = ConstU32<4>;
+	type MaxPriceObservations = ConstU32<1024>;
+	type AmplificationOrigin = EnsureRoot<AccountId>;
+	type MinAmplificationRampDuration = ConstU32<{ DAYS }>;
 	type MintMinLiquidity = MintMinLiquidity;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
@@ -2192,6 +2237,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub Features: PalletFeatures = PalletFeatures::all_enabled();
 	pub const MaxAttributesPerCall: u32 = 10;
+	pub const NftsOperatorDeposit: Balance = deposit(1, 132);
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -2205,6 +2251,7 @@ impl pallet_nfts::Config for Runtime {
 	type MetadataDepositBase = MetadataDepositBase;
 	type AttributeDepositBase = MetadataDepositBase;
 	type DepositPerByte = MetadataDepositPerByte;
+	type OperatorDeposit = NftsOperatorDeposit;
 	type StringLimit = ConstU32<256>;
 	type KeyLimit = ConstU32<64>;
 	type ValueLimit = ConstU32<256>;
@@ -3536,6 +3583,7 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		}
 	}
 
+	#[api_version(3)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -3553,6 +3601,14 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		fn get_reserves(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn get_twap(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>, from: BlockNumber, to: BlockNumber) -> Option<FixedU128> {
+			AssetConversion::get_twap(asset1, asset2, from, to)
+		}
+
+		fn find_best_route(asset_in: NativeOrWithId<u32>, asset_out: NativeOrWithId<u32>, amount_in: Balance) -> Option<pallet_asset_conversion::SwapRoute<NativeOrWithId<u32>, Balance>> {
+			AssetConversion::find_best_route(asset_in, asset_out, amount_in)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
@@ -3771,6 +3827,17 @@ pallet_revive::impl_runtime_apis_plus_revive_traits!(
 		}
 	}
 
+	impl pallet_proxy::ProxyApi<Block, AccountId, ProxyType, BlockNumber, Balance> for Runtime {
+		fn remaining_allowance(
+			real: AccountId,
+			delegate: AccountId,
+			proxy_type: ProxyType,
+			delay: BlockNumber,
+		) -> Option<Balance> {
+			Proxy::remaining_allowance(real, delegate, proxy_type, delay)
+		}
+	}
+
 	impl sp_transaction_storage_proof::runtime_api::TransactionStorageApi<Block> for Runtime {
 		fn retention_period() -> NumberFor<Block> {
 			TransactionStorage::retention_period()
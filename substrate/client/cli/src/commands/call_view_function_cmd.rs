@@ -0,0 +1,171 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_api::{ExecutorProvider, HeaderBackend, UsageProvider};
+use sc_rpc::state::{view_functions, ViewFunctionArgs, ViewFunctionOutput};
+use sp_core::Bytes;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+};
+use std::{fmt::Debug, io::Write, str::FromStr, sync::Arc};
+use substrate_rpc_client::{rpc_params, ws_client, ClientT, StateApi};
+
+/// The `call-view-function` command used to call a view function of a pallet at a given
+/// block, either from the local database or through the RPC of a running node.
+#[derive(Debug, Clone, Parser)]
+pub struct CallViewFunctionCmd {
+	/// Name of the pallet, as found in the runtime metadata.
+	#[arg(value_name = "PALLET")]
+	pub pallet: String,
+
+	/// Name of the view function.
+	#[arg(value_name = "FUNCTION")]
+	pub function: String,
+
+	/// The arguments as a JSON array, with one value per argument.
+	#[arg(long, value_name = "JSON", conflicts_with = "args_scale")]
+	pub args: Option<String>,
+
+	/// The SCALE encoded arguments, as a hex string.
+	#[arg(long, value_name = "HEX")]
+	pub args_scale: Option<Bytes>,
+
+	/// Block hash or number. Defaults to the best block.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Call the view function through the RPC of a running node instead of the local database.
+	#[arg(long, value_name = "URL")]
+	pub rpc_url: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CallViewFunctionCmd {
+	/// Run the `call-view-function` command against the local database.
+	pub fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		C: UsageProvider<B> + HeaderBackend<B> + ExecutorProvider<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let block_id = self.at.as_ref().map(|b| b.parse()).transpose()?;
+		let hash = match block_id {
+			Some(id) => client.expect_block_hash_from_id(&id)?,
+			None => client.usage_info().chain.best_hash,
+		};
+
+		let output = view_functions::call_view_function(
+			&*client,
+			&Default::default(),
+			hash,
+			&self.pallet,
+			&self.function,
+			self.view_function_args()?,
+		)
+		.map_err(|e| error::Error::Application(Box::new(e)))?;
+
+		print_output(&output)
+	}
+
+	/// Run the `call-view-function` command through the RPC of the node at `--rpc-url`.
+	pub async fn run_rpc<B>(&self) -> error::Result<()>
+	where
+		B: BlockT,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let url = self.rpc_url.as_ref().ok_or("`--rpc-url` is required")?;
+		let client = ws_client(url).await?;
+
+		let hash = match self.at.as_ref().map(|b| b.parse::<B>()).transpose()? {
+			Some(BlockId::Hash(hash)) => Some(hash),
+			Some(BlockId::Number(number)) => {
+				let hash: Option<B::Hash> = client
+					.request("chain_getBlockHash", rpc_params![number])
+					.await
+					.map_err(|e| error::Error::Application(Box::new(e)))?;
+				Some(hash.ok_or_else(|| format!("Unknown block #{}", number))?)
+			},
+			None => None,
+		};
+
+		let output = StateApi::<B::Hash>::call_view_function(
+			&client,
+			self.pallet.clone(),
+			self.function.clone(),
+			self.view_function_args()?,
+			hash,
+		)
+		.await
+		.map_err(|e| error::Error::Application(Box::new(e)))?;
+
+		print_output(&output)
+	}
+
+	fn view_function_args(&self) -> error::Result<ViewFunctionArgs> {
+		Ok(match (&self.args, &self.args_scale) {
+			(_, Some(args)) => ViewFunctionArgs::Scale(args.clone()),
+			(Some(args), None) => ViewFunctionArgs::Json(
+				serde_json::from_str(args)
+					.map_err(|e| format!("`--args` must be a JSON array: {}", e))?,
+			),
+			(None, None) => ViewFunctionArgs::Json(Vec::new()),
+		})
+	}
+}
+
+fn print_output(output: &ViewFunctionOutput) -> error::Result<()> {
+	let mut out = std::io::stdout();
+	serde_json::to_writer_pretty(&mut out, output)
+		.map_err(|e| format!("Error writing JSON: {}", e))?;
+	writeln!(out)?;
+	Ok(())
+}
+
+impl CliConfiguration for CallViewFunctionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
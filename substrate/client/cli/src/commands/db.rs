@@ -0,0 +1,113 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database related CLI utilities
+
+use super::{
+	db_convert_cmd::DbConvertCmd, db_get_cmd::DbGetCmd, db_iter_cmd::DbIterCmd,
+	db_set_state_pruning_cmd::DbSetStatePruningCmd, db_stats_cmd::DbStatsCmd,
+	db_storage_stats_cmd::DbStorageStatsCmd,
+};
+use crate::{
+	params::{DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration, Error, Result,
+};
+use sc_service::Configuration;
+use sp_runtime::traits::Block as BlockT;
+
+/// Database utilities for the cli.
+#[derive(Debug, clap::Subcommand)]
+pub enum DbSubcommand {
+	/// Convert a RocksDB database using the `archive` state pruning into ParityDB.
+	///
+	/// ParityDB databases can't be converted back into RocksDB, and databases using another state
+	/// pruning mode can't be converted.
+	Convert(DbConvertCmd),
+	/// Change the state pruning mode of the database, e.g. from archive to pruned.
+	SetStatePruning(DbSetStatePruningCmd),
+	/// Show the number and size of the entries of every column.
+	Stats(DbStatsCmd),
+	/// Show which pallets and storage items use the most space in the state of a block.
+	StorageStats(DbStorageStatsCmd),
+	/// Print the raw value of a database entry.
+	Get(DbGetCmd),
+	/// Print the raw entries of a database column.
+	Iter(DbIterCmd),
+}
+
+impl DbSubcommand {
+	/// Run the db subcommands
+	///
+	/// `storage-stats` reads the state through a client, it must be run with
+	/// [`DbStorageStatsCmd::run`] instead.
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> Result<()> {
+		match self {
+			DbSubcommand::Convert(cmd) => cmd.run::<B>(config),
+			DbSubcommand::SetStatePruning(cmd) => cmd.run::<B>(config),
+			DbSubcommand::Stats(cmd) => cmd.run(config),
+			DbSubcommand::Get(cmd) => cmd.run(config),
+			DbSubcommand::Iter(cmd) => cmd.run(config),
+			DbSubcommand::StorageStats(_) => {
+				Err(Error::Input("`db storage-stats` needs a client to run".into()))
+			},
+		}
+	}
+}
+
+impl CliConfiguration for DbSubcommand {
+	fn shared_params(&self) -> &SharedParams {
+		match self {
+			DbSubcommand::Convert(cmd) => cmd.shared_params(),
+			DbSubcommand::SetStatePruning(cmd) => cmd.shared_params(),
+			DbSubcommand::Stats(cmd) => cmd.shared_params(),
+			DbSubcommand::StorageStats(cmd) => cmd.shared_params(),
+			DbSubcommand::Get(cmd) => cmd.shared_params(),
+			DbSubcommand::Iter(cmd) => cmd.shared_params(),
+		}
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		match self {
+			DbSubcommand::Convert(cmd) => cmd.pruning_params(),
+			DbSubcommand::SetStatePruning(cmd) => cmd.pruning_params(),
+			DbSubcommand::Stats(cmd) => cmd.pruning_params(),
+			DbSubcommand::StorageStats(cmd) => cmd.pruning_params(),
+			DbSubcommand::Get(cmd) => cmd.pruning_params(),
+			DbSubcommand::Iter(cmd) => cmd.pruning_params(),
+		}
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		match self {
+			DbSubcommand::Convert(cmd) => cmd.database_params(),
+			DbSubcommand::SetStatePruning(cmd) => cmd.database_params(),
+			DbSubcommand::Stats(cmd) => cmd.database_params(),
+			DbSubcommand::StorageStats(cmd) => cmd.database_params(),
+			DbSubcommand::Get(cmd) => cmd.database_params(),
+			DbSubcommand::Iter(cmd) => cmd.database_params(),
+		}
+	}
+}
+
+/// Parse a database column given by name or number.
+pub(crate) fn parse_column(column: &str) -> std::result::Result<u32, String> {
+	sc_client_db::parse_column(column).ok_or_else(|| {
+		let names = sc_client_db::COLUMNS.iter().map(|(_, name)| *name).collect::<Vec<_>>();
+		format!("Unknown column `{column}`, expected one of {}", names.join(", "))
+	})
+}
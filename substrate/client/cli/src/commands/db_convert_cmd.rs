@@ -0,0 +1,84 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	arg_enums::Database,
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_service::Configuration;
+use sp_runtime::traits::Block as BlockT;
+use std::path::PathBuf;
+
+/// The `db convert` command used to convert a RocksDB database into ParityDB.
+///
+/// Only RocksDB databases using the `archive` state pruning can be converted into ParityDB, and
+/// only in this direction. The conversion can be interrupted and resumed by running the command
+/// again.
+#[derive(Debug, Clone, Parser)]
+pub struct DbConvertCmd {
+	/// The database backend to convert to. Only `paritydb` is supported.
+	#[arg(long, value_name = "DB", ignore_case = true, value_enum, default_value = "paritydb")]
+	pub to: Database,
+
+	/// Path of the converted database.
+	///
+	/// Defaults to the path used by the node for the `--to` database backend.
+	#[arg(long, value_name = "PATH")]
+	pub to_path: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbConvertCmd {
+	/// Run the `db convert` command.
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> error::Result<()> {
+		let mut destination = self.database_config(&config.data_path, 0, self.to)?;
+		if let Some(path) = &self.to_path {
+			destination.set_path(path);
+		}
+
+		sc_client_db::convert_database::<B>(&config.database, &destination)?;
+
+		if let Some(path) = destination.path() {
+			println!("Converted the database into {}", path.display());
+		}
+		if self.to_path.is_none() {
+			println!("Run the node with `--database paritydb` to use it");
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for DbConvertCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,75 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::db::parse_column;
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_db::DatabaseInspector;
+use sc_service::Configuration;
+use sp_core::Bytes;
+
+/// The `db get` command used to print the raw value of a database entry.
+#[derive(Debug, Clone, Parser)]
+pub struct DbGetCmd {
+	/// The column, by name (e.g. `header`) or number.
+	#[arg(value_name = "COLUMN", value_parser = parse_column)]
+	pub column: u32,
+
+	/// The key, in hex.
+	#[arg(value_name = "KEY")]
+	pub key: Bytes,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbGetCmd {
+	/// Run the `db get` command.
+	pub fn run(&self, config: &Configuration) -> error::Result<()> {
+		let inspector = DatabaseInspector::open(&config.database)?;
+		match inspector.get(self.column, &self.key)? {
+			Some(value) => println!("{}", array_bytes::bytes2hex("0x", &value)),
+			None => {
+				return Err(error::Error::Input(format!(
+					"Key {} not found",
+					array_bytes::bytes2hex("0x", &self.key[..])
+				)))
+			},
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for DbGetCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
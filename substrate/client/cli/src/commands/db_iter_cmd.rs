@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::db::parse_column;
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_db::DatabaseInspector;
+use sc_service::Configuration;
+use sp_core::Bytes;
+
+/// The `db iter` command used to print the raw entries of a database column.
+///
+/// Entries are printed as `KEY VALUE` in hex. ParityDB doesn't keep the keys, so only the values
+/// are printed and a prefix can't be used.
+#[derive(Debug, Clone, Parser)]
+pub struct DbIterCmd {
+	/// The column, by name (e.g. `header`) or number.
+	#[arg(value_name = "COLUMN", value_parser = parse_column)]
+	pub column: u32,
+
+	/// Only print the entries whose key starts with this prefix, in hex.
+	#[arg(long, value_name = "PREFIX")]
+	pub prefix: Option<Bytes>,
+
+	/// Maximum number of entries to print.
+	#[arg(long, value_name = "COUNT", default_value_t = 100)]
+	pub limit: usize,
+
+	/// Only print the keys, or the size of the values for ParityDB.
+	#[arg(long)]
+	pub keys_only: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbIterCmd {
+	/// Run the `db iter` command.
+	pub fn run(&self, config: &Configuration) -> error::Result<()> {
+		let inspector = DatabaseInspector::open(&config.database)?;
+
+		let mut remaining = self.limit;
+		inspector.iter(self.column, self.prefix.as_deref().unwrap_or_default(), |key, value| {
+			if remaining == 0 {
+				return false;
+			}
+			remaining -= 1;
+
+			let value = if self.keys_only {
+				format!("({} bytes)", value.len())
+			} else {
+				array_bytes::bytes2hex("0x", value)
+			};
+			match key {
+				Some(key) if self.keys_only => println!("{}", array_bytes::bytes2hex("0x", key)),
+				Some(key) => println!("{} {value}", array_bytes::bytes2hex("0x", key)),
+				None => println!("{value}"),
+			}
+			true
+		})?;
+		Ok(())
+	}
+}
+
+impl CliConfiguration for DbIterCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
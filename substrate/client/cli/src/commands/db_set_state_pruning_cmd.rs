@@ -0,0 +1,88 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, DatabasePruningMode, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_service::Configuration;
+use sp_runtime::traits::Block as BlockT;
+
+/// The `db set-state-pruning` command used to change the state pruning mode of the database.
+///
+/// An archive database can be pruned, which removes the state of the blocks that are neither the
+/// last finalized block nor built on top of it. The number of blocks kept by a pruned database can
+/// be changed freely. The node must be stopped while the command runs.
+#[derive(Debug, Clone, Parser)]
+pub struct DbSetStatePruningCmd {
+	/// The new state pruning mode.
+	///
+	/// Possible values:
+	///
+	/// - archive-canonical: Keep only the data of finalized blocks.
+	///
+	/// - NUMBER: Keep the data of the last NUMBER of finalized blocks.
+	#[arg(alias = "pruning", long, value_name = "PRUNING_MODE")]
+	pub state_pruning: DatabasePruningMode,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbSetStatePruningCmd {
+	/// Run the `db set-state-pruning` command.
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> error::Result<()> {
+		let migration =
+			sc_client_db::migrate_state_pruning::<B>(&config.database, self.state_pruning.into())?;
+
+		if std::mem::discriminant(&migration.from) == std::mem::discriminant(&migration.to) {
+			let mode = match self.state_pruning {
+				DatabasePruningMode::Archive => "archive".into(),
+				DatabasePruningMode::ArchiveCanonical => "archive-canonical".into(),
+				DatabasePruningMode::Custom(n) => n.to_string(),
+			};
+			println!("No migration needed, run the node with `--state-pruning {mode}`");
+		} else {
+			println!(
+				"Changed the state pruning mode from {:?} to {:?}",
+				migration.from, migration.to
+			);
+		}
+		if migration.removed_nodes > 0 {
+			println!("Removed {} unreachable trie nodes", migration.removed_nodes);
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for DbSetStatePruningCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_db::DatabaseInspector;
+use sc_service::Configuration;
+
+/// The `db stats` command used to show the number and size of the entries of every database
+/// column.
+///
+/// The whole database is read, which can take a while. The node must be stopped while the command
+/// runs.
+#[derive(Debug, Clone, Parser)]
+pub struct DbStatsCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl DbStatsCmd {
+	/// Run the `db stats` command.
+	pub fn run(&self, config: &Configuration) -> error::Result<()> {
+		let inspector = DatabaseInspector::open(&config.database)?;
+		let stats = inspector.column_stats()?;
+
+		println!("{:<16} {:>14} {:>14} {:>14}", "COLUMN", "ENTRIES", "KEYS", "VALUES");
+		for column in &stats {
+			let key_bytes = column.key_bytes.map_or_else(|| "-".into(), format_size);
+			println!(
+				"{:<16} {:>14} {:>14} {:>14}",
+				column.name,
+				column.entries,
+				key_bytes,
+				format_size(column.value_bytes)
+			);
+		}
+		let total = stats.iter().map(|column| column.value_bytes + column.key_bytes.unwrap_or(0));
+		println!("Total size: {}", format_size(total.sum()));
+		Ok(())
+	}
+}
+
+/// Format a size in bytes with a binary unit.
+pub(crate) fn format_size(bytes: u64) -> String {
+	const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+	let mut size = bytes as f64;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < UNITS.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+	if unit == 0 {
+		format!("{bytes} B")
+	} else {
+		format!("{size:.2} {}", UNITS[unit])
+	}
+}
+
+impl CliConfiguration for DbStatsCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::format_size;
+
+	#[test]
+	fn format_sizes() {
+		assert_eq!(format_size(0), "0 B");
+		assert_eq!(format_size(1023), "1023 B");
+		assert_eq!(format_size(1536), "1.50 KiB");
+		assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.00 GiB");
+	}
+}
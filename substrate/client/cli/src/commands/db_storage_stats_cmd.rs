@@ -0,0 +1,290 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::db_stats_cmd::format_size;
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_client_api::{
+	CallExecutor, ExecutorProvider, HeaderBackend, StorageProvider, UsageProvider,
+};
+use sp_core::{
+	hashing::twox_128,
+	storage::{well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo},
+	traits::CallContext,
+	OpaqueMetadata,
+};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{collections::HashMap, fmt::Debug, str::FromStr, sync::Arc};
+
+/// The `db storage-stats` command used to show which pallets and storage items use the most space
+/// in the state of a given block.
+///
+/// Storage items are named using the runtime metadata. The content of a default child trie is
+/// accounted to its key in the top trie.
+#[derive(Debug, Clone, Parser)]
+pub struct DbStorageStatsCmd {
+	/// Block hash or number. Defaults to the best block.
+	#[arg(value_name = "HASH or NUMBER")]
+	pub input: Option<BlockNumberOrHash>,
+
+	/// Number of storage items to print.
+	#[arg(long, value_name = "COUNT", default_value_t = 20)]
+	pub top: usize,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Number and size of the entries under a storage prefix.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Usage {
+	entries: u64,
+	bytes: u64,
+}
+
+impl Usage {
+	fn add(&mut self, key: &[u8], value: &[u8]) {
+		self.entries += 1;
+		self.bytes += (key.len() + value.len()) as u64;
+	}
+}
+
+impl DbStorageStatsCmd {
+	/// Run the `db storage-stats` command.
+	pub fn run<B, BA, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		BA: sc_client_api::backend::Backend<B>,
+		C: UsageProvider<B> + StorageProvider<B, BA> + HeaderBackend<B> + ExecutorProvider<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let block_id = self.input.as_ref().map(|b| b.parse()).transpose()?;
+		let hash = match block_id {
+			Some(id) => client.expect_block_hash_from_id(&id)?,
+			None => client.usage_info().chain.best_hash,
+		};
+
+		let metadata =
+			client.executor().call(hash, "Metadata_metadata", &[], CallContext::Offchain)?;
+		let metadata = OpaqueMetadata::decode(&mut &metadata[..])?;
+		let names =
+			StorageNames::from_metadata(RuntimeMetadataPrefixed::decode(&mut &metadata[..])?.1)?;
+
+		let mut items = HashMap::<Vec<u8>, Usage>::new();
+		for (key, value) in client.storage_pairs(hash, None, None)? {
+			let usage = items.entry(storage_prefix(&key.0).to_vec()).or_default();
+			usage.add(&key.0, &value.0);
+
+			if let Some(child_key) = key.0.strip_prefix(DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+				let child_info = ChildInfo::new_default(child_key);
+				for child_key in client.child_storage_keys(hash, child_info.clone(), None, None)? {
+					let value = client.child_storage(hash, &child_info, &child_key)?;
+					usage.add(&child_key.0, value.as_ref().map_or(&[][..], |value| &value.0[..]));
+				}
+			}
+		}
+
+		let mut pallets = HashMap::<&str, Usage>::new();
+		for (prefix, usage) in &items {
+			let pallet = pallets.entry(names.pallet(prefix).unwrap_or("-")).or_default();
+			pallet.entries += usage.entries;
+			pallet.bytes += usage.bytes;
+		}
+
+		println!("{:<48} {:>14} {:>14}", "PALLET", "ENTRIES", "SIZE");
+		for (pallet, usage) in sorted_by_size(pallets) {
+			println!("{pallet:<48} {:>14} {:>14}", usage.entries, format_size(usage.bytes));
+		}
+		println!();
+		println!("{:<48} {:>14} {:>14}", "STORAGE ITEM", "ENTRIES", "SIZE");
+		for (prefix, usage) in sorted_by_size(items).into_iter().take(self.top) {
+			println!(
+				"{:<48} {:>14} {:>14}",
+				names.describe(&prefix),
+				usage.entries,
+				format_size(usage.bytes)
+			);
+		}
+		Ok(())
+	}
+}
+
+/// The prefix shared by the keys of a storage item.
+///
+/// FRAME storage keys start with the hashes of the pallet and item names, other keys such as the
+/// well known keys or the child tries are reported on their own.
+fn storage_prefix(key: &[u8]) -> &[u8] {
+	if key.starts_with(DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+		key
+	} else {
+		&key[..key.len().min(32)]
+	}
+}
+
+fn sorted_by_size<K>(usage: HashMap<K, Usage>) -> Vec<(K, Usage)> {
+	let mut usage = usage.into_iter().collect::<Vec<_>>();
+	usage.sort_by(|(_, a), (_, b)| b.bytes.cmp(&a.bytes));
+	usage
+}
+
+/// Names of the pallets and storage items, by hashed prefix.
+#[derive(Debug, Default)]
+struct StorageNames {
+	pallets: HashMap<[u8; 16], String>,
+	items: HashMap<[u8; 32], String>,
+}
+
+impl StorageNames {
+	/// Collect the storage names from the pallet prefixes and their storage item names.
+	fn new(pallets: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+		let mut names = Self::default();
+		for (pallet, items) in pallets {
+			let pallet_hash = twox_128(pallet.as_bytes());
+			for item in items {
+				let mut prefix = [0; 32];
+				prefix[..16].copy_from_slice(&pallet_hash);
+				prefix[16..].copy_from_slice(&twox_128(item.as_bytes()));
+				names.items.insert(prefix, format!("{pallet}::{item}"));
+			}
+			names.pallets.insert(pallet_hash, pallet);
+		}
+		names
+	}
+
+	fn from_metadata(metadata: RuntimeMetadata) -> error::Result<Self> {
+		let pallets = match metadata {
+			RuntimeMetadata::V14(metadata) => metadata
+				.pallets
+				.into_iter()
+				.filter_map(|pallet| pallet.storage)
+				.map(|storage| {
+					(storage.prefix, storage.entries.into_iter().map(|e| e.name).collect())
+				})
+				.collect::<Vec<_>>(),
+			RuntimeMetadata::V15(metadata) => metadata
+				.pallets
+				.into_iter()
+				.filter_map(|pallet| pallet.storage)
+				.map(|storage| {
+					(storage.prefix, storage.entries.into_iter().map(|e| e.name).collect())
+				})
+				.collect(),
+			RuntimeMetadata::V16(metadata) => metadata
+				.pallets
+				.into_iter()
+				.filter_map(|pallet| pallet.storage)
+				.map(|storage| {
+					(storage.prefix, storage.entries.into_iter().map(|e| e.name).collect())
+				})
+				.collect(),
+			other => {
+				return Err(error::Error::Input(format!(
+					"Unsupported metadata version: V{}",
+					other.version()
+				)))
+			},
+		};
+		Ok(Self::new(pallets))
+	}
+
+	/// The pallet owning the storage items under `prefix`.
+	fn pallet(&self, prefix: &[u8]) -> Option<&str> {
+		let pallet_hash: [u8; 16] = prefix.get(..16)?.try_into().ok()?;
+		self.pallets.get(&pallet_hash).map(String::as_str)
+	}
+
+	/// Name the storage items under `prefix`.
+	fn describe(&self, prefix: &[u8]) -> String {
+		if let Some(item) = <[u8; 32]>::try_from(prefix).ok().and_then(|p| self.items.get(&p)) {
+			return item.clone();
+		}
+		if let Some(pallet) = self.pallet(prefix) {
+			return format!("{pallet}::{}", array_bytes::bytes2hex("0x", &prefix[16..]));
+		}
+		match std::str::from_utf8(prefix) {
+			Ok(key) if key.starts_with(':') && !key.contains(char::is_control) => key.into(),
+			_ => array_bytes::bytes2hex("0x", prefix),
+		}
+	}
+}
+
+impl CliConfiguration for DbStorageStatsCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn names_storage_prefixes() {
+		let names = StorageNames::new([
+			("System".to_string(), vec!["Account".to_string(), "Number".to_string()]),
+			("Balances".to_string(), vec!["TotalIssuance".to_string()]),
+		]);
+
+		let mut account = twox_128(b"System").to_vec();
+		account.extend(twox_128(b"Account"));
+		account.extend([1; 48]);
+		assert_eq!(storage_prefix(&account), &account[..32]);
+		assert_eq!(names.describe(storage_prefix(&account)), "System::Account");
+		assert_eq!(names.pallet(&account), Some("System"));
+
+		let mut version = twox_128(b"Balances").to_vec();
+		version.extend(twox_128(b":__STORAGE_VERSION__:"));
+		assert_eq!(
+			names.describe(&version),
+			format!(
+				"Balances::{}",
+				array_bytes::bytes2hex("0x", twox_128(b":__STORAGE_VERSION__:"))
+			)
+		);
+
+		assert_eq!(names.describe(storage_prefix(b":code")), ":code");
+		assert_eq!(names.pallet(b":code"), None);
+		let child = b":child_storage:default:crowdloan";
+		assert_eq!(storage_prefix(child), child);
+		assert_eq!(names.describe(&[0xff; 32]), array_bytes::bytes2hex("0x", [0xff; 32]));
+	}
+}
@@ -19,8 +19,16 @@
 //! Various subcommands that can be included in a substrate-based chain's CLI.
 
 mod build_spec_cmd;
+mod call_view_function_cmd;
 mod chain_info_cmd;
 mod check_block_cmd;
+mod db;
+mod db_convert_cmd;
+mod db_get_cmd;
+mod db_iter_cmd;
+mod db_set_state_pruning_cmd;
+mod db_stats_cmd;
+mod db_storage_stats_cmd;
 mod export_blocks_cmd;
 mod export_chain_spec_cmd;
 mod export_state_cmd;
@@ -41,11 +49,14 @@ mod vanity;
 mod verify;
 
 pub use self::{
-	build_spec_cmd::BuildSpecCmd, chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd,
-	export_blocks_cmd::ExportBlocksCmd, export_chain_spec_cmd::ExportChainSpecCmd,
-	export_state_cmd::ExportStateCmd, generate::GenerateCmd,
-	generate_node_key::GenerateKeyCmdCommon, import_blocks_cmd::ImportBlocksCmd,
-	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	build_spec_cmd::BuildSpecCmd, call_view_function_cmd::CallViewFunctionCmd,
+	chain_info_cmd::ChainInfoCmd, check_block_cmd::CheckBlockCmd, db::DbSubcommand,
+	db_convert_cmd::DbConvertCmd, db_get_cmd::DbGetCmd, db_iter_cmd::DbIterCmd,
+	db_set_state_pruning_cmd::DbSetStatePruningCmd, db_stats_cmd::DbStatsCmd,
+	db_storage_stats_cmd::DbStorageStatsCmd, export_blocks_cmd::ExportBlocksCmd,
+	export_chain_spec_cmd::ExportChainSpecCmd, export_state_cmd::ExportStateCmd,
+	generate::GenerateCmd, generate_node_key::GenerateKeyCmdCommon,
+	import_blocks_cmd::ImportBlocksCmd, insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd,
+	inspect_node_key::InspectNodeKeyCmd, key::KeySubcommand, purge_chain_cmd::PurgeChainCmd,
+	revert_cmd::RevertCmd, run_cmd::RunCmd, sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
 };
@@ -188,6 +188,14 @@ pub struct NetworkParams {
 		verbatim_doc_comment
 	)]
 	pub network_backend: NetworkBackendType,
+
+	/// Persist peer reputations and addresses across restarts.
+	///
+	/// Reputations keep decaying while the node is down. Addresses of previously dialed peers
+	/// are used to reconnect quickly after a restart, and banned peers stay banned until their
+	/// reputation recovers.
+	#[arg(long)]
+	pub persist_peers: bool,
 }
 
 impl NetworkParams {
@@ -286,6 +294,8 @@ impl NetworkParams {
 			ipfs_server: self.ipfs_server,
 			sync_mode: self.sync.into(),
 			network_backend: self.network_backend.into(),
+			bandwidth: Default::default(),
+			persist_peers: self.persist_peers,
 		}
 	}
 }
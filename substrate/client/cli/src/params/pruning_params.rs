@@ -31,7 +31,8 @@ pub struct PruningParams {
 	/// will load the pruning mode from the database and will error if the stored mode doesn't
 	/// match this CLI value. It is fine to drop this CLI flag for subsequent runs. The only
 	/// exception is that `NUMBER` can change between subsequent runs (increasing it will not
-	/// lead to restoring pruned state).
+	/// lead to restoring pruned state). An archive database can be pruned with the
+	/// `db set-state-pruning` command.
 	///
 	/// Possible values:
 	///
@@ -17,7 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::{Args, ValueEnum};
-use sc_transaction_pool::TransactionPoolOptions;
+use sc_transaction_pool::{MempoolJournalOptions, MempoolJournalSources, TransactionPoolOptions};
+use std::{path::PathBuf, time::Duration};
 
 /// Type of transaction pool to be used
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -40,6 +41,28 @@ impl Into<sc_transaction_pool::TransactionPoolType> for TransactionPoolType {
 	}
 }
 
+/// Sources of the transactions saved in the transaction pool journal.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum TransactionPoolJournalSources {
+	/// Only the transactions submitted locally.
+	Local,
+	/// Only the transactions received from the network or the RPC.
+	External,
+	/// All the pending transactions.
+	All,
+}
+
+impl Into<MempoolJournalSources> for TransactionPoolJournalSources {
+	fn into(self) -> MempoolJournalSources {
+		match self {
+			TransactionPoolJournalSources::Local => MempoolJournalSources::Local,
+			TransactionPoolJournalSources::External => MempoolJournalSources::External,
+			TransactionPoolJournalSources::All => MempoolJournalSources::All,
+		}
+	}
+}
+
 /// Parameters used to create the pool configuration.
 #[derive(Debug, Clone, Args)]
 pub struct TransactionPoolParams {
@@ -60,17 +83,53 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::ForkAware)]
 	pub pool_type: TransactionPoolType,
+
+	/// Path of the on-disk journal of the pending transactions.
+	///
+	/// When set, the pending transactions are saved periodically and on shutdown, and are
+	/// reloaded and revalidated at startup. Only supported by the fork-aware transaction pool.
+	#[arg(long, value_name = "PATH")]
+	pub pool_journal: Option<PathBuf>,
+
+	/// Interval between two saves of the transaction pool journal.
+	///
+	/// Zero disables the periodic saves, the journal is then only saved on shutdown.
+	#[arg(long, value_name = "SECONDS", default_value_t = 60)]
+	pub pool_journal_interval: u64,
+
+	/// Maximum number of transactions saved in the transaction pool journal.
+	#[arg(long, value_name = "COUNT", default_value_t = 8192)]
+	pub pool_journal_limit: usize,
+
+	/// Maximum number of kilobytes of all transactions saved in the transaction pool journal.
+	#[arg(long, value_name = "COUNT", default_value_t = 20480)]
+	pub pool_journal_kbytes: usize,
+
+	/// The sources of the transactions saved in the transaction pool journal.
+	#[arg(long, value_enum, default_value_t = TransactionPoolJournalSources::All)]
+	pub pool_journal_sources: TransactionPoolJournalSources,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		TransactionPoolOptions::new_with_params(
+		let options = TransactionPoolOptions::new_with_params(
 			self.pool_limit,
 			self.pool_kbytes * 1024,
 			self.tx_ban_seconds,
 			self.pool_type.into(),
 			is_dev,
-		)
+		);
+
+		match self.pool_journal {
+			Some(ref path) => options.with_mempool_journal(MempoolJournalOptions {
+				path: path.clone(),
+				interval: Duration::from_secs(self.pool_journal_interval),
+				max_count: self.pool_journal_limit,
+				max_bytes: self.pool_journal_kbytes * 1024,
+				sources: self.pool_journal_sources.into(),
+			}),
+			None => options,
+		}
 	}
 }
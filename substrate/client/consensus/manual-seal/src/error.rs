@@ -58,6 +58,9 @@ pub enum Error {
 	/// error encountered during finalization
 	#[error("Finalization Error: {0}")]
 	BlockchainError(#[from] BlockchainError),
+	/// error encountered while calling into the runtime or accessing its state
+	#[error("Runtime api error: {0}")]
+	RuntimeApiError(#[from] sp_api::ApiError),
 	/// Supplied parent_hash doesn't exist in chain
 	#[error("Supplied parent_hash: {0} doesn't exist in chain")]
 	BlockNotFound(String),
@@ -40,6 +40,8 @@ use std::{marker::PhantomData, sync::Arc, time::Duration};
 mod error;
 mod finalize_block;
 mod seal_block;
+mod storage_overrides;
+mod time_warp;
 
 pub mod consensus;
 pub mod rpc;
@@ -50,9 +52,11 @@ pub use self::{
 	finalize_block::{finalize_block, FinalizeBlockParams},
 	rpc::{CreatedBlock, EngineCommand},
 	seal_block::{seal_block, SealBlockParams, MAX_PROPOSAL_DURATION},
+	time_warp::TimeWarp,
 };
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{CallApiAt, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
 
 const LOG_TARGET: &str = "manual-seal";
 
@@ -165,7 +169,8 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + CallApiAt<B> + 'static,
+	C::Api: BlockBuilderApi<B>,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B>,
@@ -174,6 +179,8 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP>(
 	TP: TransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
 {
+	let mut storage_overrides = Default::default();
+
 	while let Some(command) = commands_stream.next().await {
 		match command {
 			EngineCommand::SealNewBlock { create_empty, finalize, parent_hash, sender } => {
@@ -189,6 +196,7 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP>(
 					pool: pool.clone(),
 					client: client.clone(),
 					create_inherent_data_providers: &create_inherent_data_providers,
+					storage_overrides: &mut storage_overrides,
 				})
 				.await;
 			},
@@ -203,6 +211,11 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP>(
 				})
 				.await
 			},
+			EngineCommand::SetStorage { changes, mut sender } => {
+				storage_overrides.extend(changes);
+				rpc::send_result(&mut sender, Ok(()))
+			},
+			EngineCommand::Revert { revert, mut sender } => rpc::send_result(&mut sender, revert()),
 		}
 	}
 }
@@ -223,7 +236,8 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + CallApiAt<B> + 'static,
+	C::Api: BlockBuilderApi<B>,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B>,
@@ -272,7 +286,8 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + CallApiAt<B> + 'static,
+	C::Api: BlockBuilderApi<B>,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B>,
@@ -345,10 +360,12 @@ mod tests {
 	use super::*;
 	use assert_matches::assert_matches;
 	use sc_basic_authorship::ProposerFactory;
+	use sc_client_api::StorageProvider;
 	use sc_consensus::ImportedAux;
 	use sc_transaction_pool::{BasicPool, FullChainApi, Options, RevalidationType};
 	use sc_transaction_pool_api::{MaintainedTransactionPool, TransactionPool, TransactionSource};
 	use sp_api::StorageProof;
+	use sp_core::storage::{StorageData, StorageKey};
 	use sp_inherents::InherentData;
 	use sp_runtime::generic::{Digest, DigestItem};
 	use substrate_test_runtime_client::{
@@ -797,4 +814,130 @@ mod tests {
 		let header = client.header(created_block.hash).unwrap().unwrap();
 		assert_eq!(header.number, 1);
 	}
+
+	#[tokio::test]
+	async fn manual_seal_storage_overrides() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		}));
+
+		let key = b"manual-seal-override".to_vec();
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SetStorage {
+			changes: vec![(key.clone(), Some(vec![42]))],
+			sender: Some(tx),
+		})
+		.await
+		.unwrap();
+		rx.await.unwrap().unwrap();
+
+		let mut seal_block = || {
+			let (tx, rx) = futures::channel::oneshot::channel();
+			let command = EngineCommand::SealNewBlock {
+				parent_hash: None,
+				sender: Some(tx),
+				create_empty: true,
+				finalize: false,
+			};
+			(sink.try_send(command), rx)
+		};
+
+		// the override is applied before building the next block, and kept in the state.
+		let (sent, rx) = seal_block();
+		sent.unwrap();
+		let first = rx.await.unwrap().unwrap();
+		let value = client.storage(first.hash, &StorageKey(key.clone())).unwrap();
+		assert_eq!(value, Some(StorageData(vec![42])));
+
+		let (sent, rx) = seal_block();
+		sent.unwrap();
+		let second = rx.await.unwrap().unwrap();
+		let value = client.storage(second.hash, &StorageKey(key)).unwrap();
+		assert_eq!(value, Some(StorageData(vec![42])));
+		assert_eq!(client.header(second.hash).unwrap().unwrap().number, 2);
+	}
+
+	#[tokio::test]
+	async fn manual_seal_revert_to_snapshot() {
+		use rpc::{ManualSealApiServer, ManualSealDevApiServer};
+
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		}));
+
+		let dev = rpc::ManualSealDev::new(client.clone(), backend, sink.clone(), TimeWarp::new());
+		let seal = rpc::ManualSeal::new(sink);
+
+		let snapshot = dev.snapshot().unwrap();
+		seal.create_block(true, false, None).await.unwrap();
+		let later_snapshot = dev.snapshot().unwrap();
+		seal.create_block(true, false, None).await.unwrap();
+		assert_eq!(client.info().best_number, 2);
+
+		// reverting discards the snapshot and the ones taken after it
+		assert!(dev.revert(snapshot).await.unwrap());
+		assert_eq!(client.info().best_number, 0);
+		assert_eq!(client.info().best_hash, genesis_hash);
+		assert!(!dev.revert(snapshot).await.unwrap());
+		assert!(!dev.revert(later_snapshot).await.unwrap());
+
+		// blocks are sealed on top of the reverted chain
+		seal.create_block(true, false, None).await.unwrap();
+		assert_eq!(client.info().best_number, 1);
+	}
 }
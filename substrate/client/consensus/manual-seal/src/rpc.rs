@@ -18,15 +18,31 @@
 
 //! RPC interface for the `ManualSeal` Engine.
 
-use crate::error::Error;
+use crate::{error::Error, TimeWarp};
+use codec::{Decode, DecodeAll, Encode};
 use futures::{
 	channel::{mpsc, oneshot},
 	SinkExt,
 };
 use jsonrpsee::{core::async_trait, proc_macros::rpc};
+use sc_client_api::{backend::Backend as ClientBackend, StorageProvider};
 use sc_consensus::ImportedAux;
 use serde::{Deserialize, Serialize};
-use sp_runtime::EncodedJustification;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hashing::{blake2_128, twox_128},
+	storage::StorageKey,
+	Bytes,
+};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	traits::{Block as BlockT, NumberFor, Saturating, Zero},
+	EncodedJustification,
+};
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex},
+};
 
 /// Sender passed to the authorship task to report errors or successes.
 pub type Sender<T> = Option<oneshot::Sender<std::result::Result<T, Error>>>;
@@ -59,6 +75,24 @@ pub enum EngineCommand<Hash> {
 		/// finalization justification
 		justification: Option<EncodedJustification>,
 	},
+	/// Tells the engine to override the given storage keys on top of the state the next sealed
+	/// block is built on.
+	///
+	/// A value of `None` removes the key.
+	SetStorage {
+		/// the storage keys and their new values.
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
+	/// Tells the engine to revert the chain with the given function, so that no block is sealed
+	/// while the chain is reverted.
+	Revert {
+		/// the function reverting the chain.
+		revert: Box<dyn FnOnce() -> Result<(), Error> + Send>,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
 }
 
 /// RPC trait that provides methods for interacting with the manual-seal authorship task over rpc.
@@ -145,6 +179,225 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 	}
 }
 
+/// RPC trait that provides development helpers on top of the manual-seal authorship task.
+#[rpc(client, server)]
+pub trait ManualSealDevApi {
+	/// Takes a snapshot of the chain at the best block and returns its identifier.
+	#[method(name = "engine_snapshot")]
+	fn snapshot(&self) -> Result<u64, Error>;
+
+	/// Reverts the chain to the given snapshot.
+	///
+	/// The snapshot and all the snapshots taken after it are discarded. Returns `false` if the
+	/// snapshot is unknown, and an error, keeping the snapshots, if not all the blocks since the
+	/// snapshot could be reverted.
+	#[method(name = "engine_revert")]
+	async fn revert(&self, id: u64) -> Result<bool, Error>;
+
+	/// Sets the timestamp of the next sealed block, in milliseconds.
+	#[method(name = "engine_setNextBlockTimestamp")]
+	fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), Error>;
+
+	/// Moves the time forward by the given number of seconds.
+	///
+	/// Returns the total time offset, in seconds.
+	#[method(name = "engine_increaseTime")]
+	fn increase_time(&self, seconds: u64) -> Result<u64, Error>;
+
+	/// Overrides the given storage keys on top of the state the next sealed block is built on.
+	///
+	/// A value of `null` removes the key.
+	#[method(name = "engine_setStorage")]
+	async fn set_storage(&self, changes: Vec<(Bytes, Option<Bytes>)>) -> Result<(), Error>;
+
+	/// Sets the free balance of the given SCALE encoded account, starting from the next sealed
+	/// block.
+	///
+	/// The `System::Account` entry of the account is overwritten directly. This assumes a
+	/// `frame_system::AccountInfo` with `u32` nonce and reference counters holding a
+	/// `pallet_balances::AccountData` with `u128` balances, and fails for accounts stored with a
+	/// different layout. The `TotalIssuance` of `pallet-balances` is left untouched, so it no
+	/// longer matches the sum of the balances afterwards.
+	#[method(name = "engine_setBalance")]
+	async fn set_balance(&self, account: Bytes, free: NumberOrHex) -> Result<(), Error>;
+}
+
+/// The layout of `frame_system::AccountInfo` holding a `pallet_balances::AccountData`, with `u32`
+/// nonce and `u128` balances, as used by most runtimes.
+#[derive(Default, Encode, Decode)]
+struct AccountInfo {
+	nonce: u32,
+	consumers: u32,
+	providers: u32,
+	sufficients: u32,
+	free: u128,
+	reserved: u128,
+	frozen: u128,
+	flags: u128,
+}
+
+/// The storage key of the `frame_system::Account` entry of the given account.
+fn account_storage_key(account: &[u8]) -> Vec<u8> {
+	[&twox_128(b"System")[..], &twox_128(b"Account")[..], &blake2_128(account)[..], account]
+		.concat()
+}
+
+/// A struct that implements the [`ManualSealDevApiServer`].
+///
+/// Snapshots are reverted through [`ClientBackend::revert`], finalized blocks included, so the
+/// state of the snapshot block must not have been pruned.
+pub struct ManualSealDev<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<BE>,
+	import_block_channel: mpsc::Sender<EngineCommand<B::Hash>>,
+	time_warp: TimeWarp,
+	snapshots: Mutex<Snapshots<B>>,
+}
+
+/// The snapshots taken through `engine_snapshot`.
+struct Snapshots<B: BlockT> {
+	next_id: u64,
+	taken: BTreeMap<u64, (NumberFor<B>, B::Hash)>,
+}
+
+impl<B: BlockT, C, BE> ManualSealDev<B, C, BE> {
+	/// Create new `ManualSealDev` sending its commands to the manual-seal authorship task over
+	/// the given channel and warping the time through the given handle.
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		import_block_channel: mpsc::Sender<EngineCommand<B::Hash>>,
+		time_warp: TimeWarp,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			import_block_channel,
+			time_warp,
+			snapshots: Mutex::new(Snapshots { next_id: 0, taken: BTreeMap::new() }),
+		}
+	}
+
+	async fn send_command<T>(
+		&self,
+		command: impl FnOnce(Sender<T>) -> EngineCommand<B::Hash>,
+	) -> Result<T, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		sink.send(command(Some(sender))).await?;
+		receiver.await?
+	}
+
+	async fn set_storage_changes(
+		&self,
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+	) -> Result<(), Error> {
+		self.send_command(|sender| EngineCommand::SetStorage { changes, sender }).await
+	}
+}
+
+/// Reverts the best chain to the snapshot `id`, taken at block `number` with the given `hash`.
+fn revert_to_snapshot<B, C, BE>(
+	client: &C,
+	backend: &BE,
+	id: u64,
+	number: NumberFor<B>,
+	hash: B::Hash,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+	BE: ClientBackend<B>,
+{
+	if client.hash(number)? != Some(hash) {
+		return Err(Error::StringError(format!(
+			"Snapshot {id} at block {hash} is not part of the best chain"
+		)));
+	}
+
+	let to_revert = client.info().best_number.saturating_sub(number);
+	if to_revert.is_zero() {
+		return Ok(());
+	}
+	let (reverted, _) = backend.revert(to_revert, true)?;
+	if reverted < to_revert {
+		return Err(Error::StringError(format!(
+			"Reverted {reverted} of the {to_revert} blocks to snapshot {id}"
+		)));
+	}
+	log::info!(target: crate::LOG_TARGET, "Reverted {reverted} blocks to snapshot {id}");
+	Ok(())
+}
+
+#[async_trait]
+impl<B, C, BE> ManualSealDevApiServer for ManualSealDev<B, C, BE>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + Send + Sync + 'static,
+	BE: ClientBackend<B> + Send + Sync + 'static,
+{
+	fn snapshot(&self) -> Result<u64, Error> {
+		let info = self.client.info();
+		let mut snapshots = self.snapshots.lock().expect("snapshots lock is never poisoned; qed");
+		let id = snapshots.next_id;
+		snapshots.next_id += 1;
+		snapshots.taken.insert(id, (info.best_number, info.best_hash));
+		Ok(id)
+	}
+
+	async fn revert(&self, id: u64) -> Result<bool, Error> {
+		let snapshot = self
+			.snapshots
+			.lock()
+			.expect("snapshots lock is never poisoned; qed")
+			.taken
+			.get(&id)
+			.copied();
+		let Some((number, hash)) = snapshot else { return Ok(false) };
+
+		let (client, backend) = (self.client.clone(), self.backend.clone());
+		let revert = Box::new(move || revert_to_snapshot(&*client, &*backend, id, number, hash));
+		self.send_command(|sender| EngineCommand::Revert { revert, sender }).await?;
+
+		self.snapshots
+			.lock()
+			.expect("snapshots lock is never poisoned; qed")
+			.taken
+			.retain(|snapshot, _| *snapshot < id);
+		Ok(true)
+	}
+
+	fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), Error> {
+		self.time_warp.set_next_timestamp(timestamp);
+		Ok(())
+	}
+
+	fn increase_time(&self, seconds: u64) -> Result<u64, Error> {
+		Ok(self.time_warp.increase_time(seconds.saturating_mul(1000)) / 1000)
+	}
+
+	async fn set_storage(&self, changes: Vec<(Bytes, Option<Bytes>)>) -> Result<(), Error> {
+		let changes = changes.into_iter().map(|(key, value)| (key.0, value.map(|v| v.0))).collect();
+		self.set_storage_changes(changes).await
+	}
+
+	async fn set_balance(&self, account: Bytes, free: NumberOrHex) -> Result<(), Error> {
+		let free = u128::try_from(free)
+			.map_err(|_| Error::StringError("Balance does not fit into u128".into()))?;
+		let key = account_storage_key(&account);
+
+		let best_hash = self.client.info().best_hash;
+		let mut info = match self.client.storage(best_hash, &StorageKey(key.clone()))? {
+			Some(data) => AccountInfo::decode_all(&mut &data.0[..])
+				.map_err(|_| Error::StringError("Unsupported layout of the account info".into()))?,
+			None => AccountInfo { providers: 1, ..Default::default() },
+		};
+		info.free = free;
+
+		self.set_storage_changes(vec![(key, Some(info.encode()))]).await
+	}
+}
+
 /// report any errors or successes encountered by the authorship task back
 /// to the rpc
 pub fn send_result<T: std::fmt::Debug>(
@@ -18,12 +18,17 @@
 
 //! Block sealing utilities
 
-use crate::{rpc, ConsensusDataProvider, CreatedBlock, Error};
+use crate::{
+	rpc,
+	storage_overrides::{apply_storage_overrides, StorageOverrides},
+	ConsensusDataProvider, CreatedBlock, Error,
+};
 use codec::Encode;
 use futures::prelude::*;
 use sc_consensus::{BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult, StateAction};
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::{ProofRecorder, ProvideRuntimeApi};
+use sp_api::{CallApiAt, ProofRecorder, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{self, BlockOrigin, Environment, ProposeArgs, Proposer, SelectChain};
 use sp_externalities::Extensions;
@@ -60,6 +65,9 @@ pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP
 	pub block_import: &'a mut BI,
 	/// Something that can create the inherent data providers.
 	pub create_inherent_data_providers: &'a CIDP,
+	/// Storage overrides applied on top of the state of the parent block, cleared once the block
+	/// is imported.
+	pub storage_overrides: &'a mut StorageOverrides,
 }
 
 /// seals a new block with the given params
@@ -75,12 +83,14 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP>(
 		env,
 		create_inherent_data_providers,
 		consensus_data_provider: digest_provider,
+		storage_overrides,
 		mut sender,
 	}: SealBlockParams<'_, B, BI, SC, C, E, TP, CIDP>,
 ) where
 	B: BlockT,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + ProvideRuntimeApi<B>,
+	C: HeaderBackend<B> + ProvideRuntimeApi<B> + CallApiAt<B>,
+	C::Api: BlockBuilderApi<B>,
 	E: Environment<B>,
 	E::Proposer: Proposer<B>,
 	TP: TransactionPool<Block = B>,
@@ -126,7 +136,7 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP>(
 
 		let propose_args = ProposeArgs {
 			inherent_data: inherent_data.clone(),
-			inherent_digests,
+			inherent_digests: inherent_digests.clone(),
 			max_duration: Duration::from_secs(MAX_PROPOSAL_DURATION),
 			storage_proof_recorder: Some(storage_proof_recorder.clone()),
 			extra_extensions,
@@ -142,17 +152,32 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP>(
 			return Err(Error::EmptyTransactionPool);
 		}
 
-		let proof = storage_proof_recorder.drain_storage_proof();
+		// The proposer builds on the state of the parent block, so the block is executed again
+		// with the storage overrides applied.
+		let (block, storage_changes, proof) = if storage_overrides.is_empty() {
+			(proposal.block, proposal.storage_changes, storage_proof_recorder.drain_storage_proof())
+		} else {
+			let storage_proof_recorder = ProofRecorder::<B>::default();
+			let (_, extrinsics) = proposal.block.deconstruct();
+			let (block, storage_changes) = apply_storage_overrides(
+				&*client,
+				&parent,
+				inherent_digests,
+				extrinsics,
+				storage_overrides,
+				&storage_proof_recorder,
+			)?;
+			(block, storage_changes, storage_proof_recorder.drain_storage_proof())
+		};
 
-		let (header, body) = proposal.block.deconstruct();
+		let (header, body) = block.deconstruct();
 		let proof_size = proof.encoded_size();
 		let mut params = BlockImportParams::new(BlockOrigin::Own, header.clone());
 		params.body = Some(body);
 		params.finalized = finalize;
 		params.fork_choice = Some(ForkChoiceStrategy::LongestChain);
-		params.state_action = StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(
-			proposal.storage_changes,
-		));
+		params.state_action =
+			StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(storage_changes));
 
 		if let Some(digest_provider) = digest_provider {
 			digest_provider.append_block_import(&parent, &mut params, &inherent_data, proof)?;
@@ -164,11 +189,14 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP>(
 		post_header.digest_mut().logs.extend(params.post_digests.iter().cloned());
 
 		match block_import.import_block(params).await? {
-			ImportResult::Imported(aux) => Ok(CreatedBlock {
-				hash: <B as BlockT>::Header::hash(&post_header),
-				aux,
-				proof_size,
-			}),
+			ImportResult::Imported(aux) => {
+				storage_overrides.clear();
+				Ok(CreatedBlock {
+					hash: <B as BlockT>::Header::hash(&post_header),
+					aux,
+					proof_size,
+				})
+			},
 			other => Err(other.into()),
 		}
 	};
@@ -0,0 +1,86 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage overrides applied on top of the state a sealed block is built on.
+
+use crate::Error;
+use sp_api::{
+	ApiExt, CallApiAt, CallContext, Core, ProofRecorder, ProvideRuntimeApi, StorageChanges,
+};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, One},
+	Digest,
+};
+use sp_trie::proof_size_extension::ProofSizeExt;
+use std::collections::BTreeMap;
+
+/// Storage keys to set on top of the state of the parent of the next sealed block.
+///
+/// A value of `None` removes the key.
+pub(crate) type StorageOverrides = BTreeMap<Vec<u8>, Option<Vec<u8>>>;
+
+/// Executes the `extrinsics` of a proposed block again, on top of the state of `parent` with the
+/// `overrides` applied, and returns the resulting block along with its storage changes.
+///
+/// The block is initialized with the `inherent_digests` given to the proposer. The overrides are
+/// part of the storage changes but not of the block, which can therefore only be imported together
+/// with its storage changes.
+pub(crate) fn apply_storage_overrides<B, C>(
+	client: &C,
+	parent: &B::Header,
+	inherent_digests: Digest,
+	extrinsics: Vec<B::Extrinsic>,
+	overrides: &StorageOverrides,
+	proof_recorder: &ProofRecorder<B>,
+) -> Result<(B, StorageChanges<B>), Error>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + CallApiAt<B>,
+	C::Api: BlockBuilderApi<B>,
+{
+	let parent_hash = parent.hash();
+	let mut api = client.runtime_api();
+	api.record_proof_with_recorder(proof_recorder.clone());
+	api.register_extension(ProofSizeExt::new(proof_recorder.clone()));
+	api.set_call_context(CallContext::Onchain);
+	for (key, value) in overrides {
+		api.set_storage(key.clone(), value.clone())?;
+	}
+
+	let header = B::Header::new(
+		*parent.number() + One::one(),
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		inherent_digests,
+	);
+	api.initialize_block(parent_hash, &header)?;
+	for extrinsic in &extrinsics {
+		api.apply_extrinsic(parent_hash, extrinsic.clone())?.map_err(|err| {
+			Error::StringError(format!(
+				"Extrinsic of the proposed block is invalid with the storage overrides: {err:?}"
+			))
+		})?;
+	}
+	let header = api.finalize_block(parent_hash)?;
+
+	let state = client.state_at(parent_hash)?;
+	let storage_changes = api.into_storage_changes(&state, parent_hash)?;
+	Ok((B::new(header, extrinsics), storage_changes))
+}
@@ -0,0 +1,111 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Shared handle allowing to move the time of the manually sealed blocks forward.
+
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+struct TimeWarpState {
+	/// Offset in milliseconds added to every timestamp.
+	offset: u64,
+	/// Timestamp requested for the next block.
+	next: Option<u64>,
+}
+
+/// Shared handle used to warp the timestamps of the manually sealed blocks.
+///
+/// The handle is updated by the `engine_setNextBlockTimestamp` and `engine_increaseTime` RPCs and
+/// shall be consulted by the timestamp inherent data provider of the node through
+/// [`TimeWarp::apply`]. Time only moves forward: a requested timestamp that is lower than the one
+/// the block would get anyway is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct TimeWarp(Arc<Mutex<TimeWarpState>>);
+
+impl TimeWarp {
+	/// Creates a new handle, without any offset.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Requests the next block to be sealed with the given timestamp, in milliseconds.
+	///
+	/// The following blocks keep the same distance to the wall clock.
+	pub fn set_next_timestamp(&self, timestamp: u64) {
+		self.0.lock().expect("time warp lock is never poisoned; qed").next = Some(timestamp);
+	}
+
+	/// Moves the time forward by the given number of milliseconds.
+	///
+	/// Returns the total offset, in milliseconds.
+	pub fn increase_time(&self, millis: u64) -> u64 {
+		let mut state = self.0.lock().expect("time warp lock is never poisoned; qed");
+		state.offset = state.offset.saturating_add(millis);
+		state.offset
+	}
+
+	/// Returns the current offset, in milliseconds.
+	pub fn offset(&self) -> u64 {
+		self.0.lock().expect("time warp lock is never poisoned; qed").offset
+	}
+
+	/// Returns the timestamp to use for a block that would otherwise get `timestamp`.
+	///
+	/// Consumes the timestamp requested by [`TimeWarp::set_next_timestamp`], if any.
+	pub fn apply(&self, timestamp: u64) -> u64 {
+		let mut state = self.0.lock().expect("time warp lock is never poisoned; qed");
+		let warped = timestamp.saturating_add(state.offset);
+		match state.next.take() {
+			Some(next) if next > warped => {
+				state.offset = state.offset.saturating_add(next - warped);
+				next
+			},
+			_ => warped,
+		}
+	}
+
+	/// Creates a timestamp inherent data provider using the current time warped by this handle.
+	pub fn timestamp_inherent_data_provider(&self) -> sp_timestamp::InherentDataProvider {
+		let now = sp_timestamp::Timestamp::current();
+		sp_timestamp::InherentDataProvider::new(self.apply(now.as_millis()).into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn time_only_moves_forward() {
+		let warp = TimeWarp::new();
+		assert_eq!(warp.apply(1_000), 1_000);
+
+		assert_eq!(warp.increase_time(500), 500);
+		assert_eq!(warp.apply(2_000), 2_500);
+
+		warp.set_next_timestamp(10_000);
+		assert_eq!(warp.apply(3_000), 10_000);
+		assert_eq!(warp.offset(), 7_000);
+		assert_eq!(warp.apply(4_000), 11_000);
+
+		// requests in the past are ignored.
+		warp.set_next_timestamp(1_000);
+		assert_eq!(warp.apply(5_000), 12_000);
+		assert_eq!(warp.apply(6_000), 13_000);
+	}
+}
@@ -0,0 +1,219 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Sassafras tickets generation and slot claiming.
+
+use super::{Epoch, LOG_TARGET};
+use codec::{Decode, Encode};
+use log::{debug, warn};
+use sp_application_crypto::AppCrypto;
+use sp_consensus_sassafras::{
+	digests::SlotClaim,
+	ticket_id_threshold,
+	vrf::{self, RingContext},
+	AuthorityId, AuthorityIndex, Randomness, Slot, TicketBody, TicketClaim, TicketEnvelope,
+	TicketId,
+};
+use sp_core::{
+	bandersnatch,
+	crypto::{ByteArray, Wraps},
+	ed25519, Pair,
+};
+use sp_keystore::KeystorePtr;
+use std::collections::BTreeMap;
+
+/// Secret data of a ticket generated by one of the local authorities.
+///
+/// Required to claim the slot the ticket gets assigned to.
+#[derive(Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TicketSecret {
+	/// Index of the authority which generated the ticket.
+	pub authority_idx: AuthorityIndex,
+	/// Seed of the ephemeral key pair committed as `erased_public` in the ticket body.
+	pub erased_seed: [u8; 32],
+}
+
+impl std::fmt::Debug for TicketSecret {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_struct("TicketSecret")
+			.field("authority_idx", &self.authority_idx)
+			.finish()
+	}
+}
+
+/// Secrets of the tickets generated by the local authorities, indexed by epoch.
+pub type EpochTickets = BTreeMap<u64, BTreeMap<TicketId, TicketSecret>>;
+
+/// Get the fallback author for the given slot, i.e. the authority expected to claim a slot
+/// without an associated ticket.
+///
+/// This always assigns the slot to some authority unless the authorities list is empty.
+pub(crate) fn fallback_authority_index(
+	randomness: &Randomness,
+	slot: Slot,
+	authorities_len: usize,
+) -> Option<AuthorityIndex> {
+	if authorities_len == 0 {
+		return None;
+	}
+
+	let hash = (randomness, slot).using_encoded(sp_crypto_hashing::blake2_256);
+	let rand = u64::from_le_bytes(hash[..8].try_into().expect("hash is 32 bytes long; qed"));
+
+	Some((rand % authorities_len as u64) as AuthorityIndex)
+}
+
+/// Message signed with the ticket erased key to claim the slot associated with the ticket.
+pub(crate) fn ticket_claim_message(
+	randomness: &Randomness,
+	slot: Slot,
+	epoch_index: u64,
+) -> Vec<u8> {
+	(b"sassafras-ticket-claim", randomness, slot, epoch_index).encode()
+}
+
+/// Tries to claim the given slot.
+///
+/// `maybe_ticket` is the ticket associated with the slot, as found in the parent block state.
+/// If there is a ticket, the slot can only be claimed if the ticket was generated by one of
+/// the local authorities (i.e. its secret is in `tickets`). Otherwise the slot is claimed only
+/// if one of the local authorities is the fallback author for the slot.
+pub fn claim_slot(
+	slot: Slot,
+	epoch: &Epoch,
+	maybe_ticket: Option<(TicketId, TicketBody)>,
+	tickets: &EpochTickets,
+	keystore: &KeystorePtr,
+) -> Option<(SlotClaim, AuthorityId)> {
+	if epoch.authorities.is_empty() {
+		return None;
+	}
+
+	let epoch_index = epoch.index_for_slot(slot);
+
+	let (authority_idx, ticket_claim) = match maybe_ticket {
+		Some((ticket_id, ticket_body)) => {
+			debug!(target: LOG_TARGET, "Slot {} is assigned to ticket {:032x}", slot, ticket_id);
+			let secret = tickets.get(&epoch_index)?.get(&ticket_id)?;
+			let erased_pair = ed25519::Pair::from_seed(&secret.erased_seed);
+			if erased_pair.public() != ticket_body.erased_public {
+				warn!(target: LOG_TARGET, "Ticket {:032x} doesn't match its secret", ticket_id);
+				return None;
+			}
+			let message = ticket_claim_message(&epoch.randomness, slot, epoch_index);
+			let erased_signature = erased_pair.sign(&message);
+			(secret.authority_idx, Some(TicketClaim { erased_signature }))
+		},
+		None => {
+			debug!(target: LOG_TARGET, "Slot {} has no ticket, using fallback author", slot);
+			let authority_idx =
+				fallback_authority_index(&epoch.randomness, slot, epoch.authorities.len())?;
+			(authority_idx, None)
+		},
+	};
+
+	let authority_id = epoch.authorities.get(authority_idx as usize)?;
+
+	let data = vrf::slot_claim_sign_data(&epoch.randomness, slot, epoch_index);
+	let vrf_signature = keystore
+		.bandersnatch_vrf_sign(AuthorityId::ID, authority_id.as_ref(), &data)
+		.ok()
+		.flatten()?;
+
+	let claim = SlotClaim { authority_idx, slot, vrf_signature, ticket_claim };
+	Some((claim, authority_id.clone()))
+}
+
+/// Generates the tickets of the local authorities for the given epoch.
+///
+/// Returns the ticket identifiers, the envelopes to be submitted on-chain and the secrets to
+/// keep in order to claim the slots the tickets get assigned to. Only the tickets whose
+/// identifier is below the epoch threshold are returned.
+pub fn generate_epoch_tickets(
+	epoch: &Epoch,
+	ring_context: &RingContext,
+	keystore: &KeystorePtr,
+) -> Vec<(TicketId, TicketEnvelope, TicketSecret)> {
+	let mut tickets = Vec::new();
+
+	let threshold = ticket_id_threshold(
+		epoch.config.redundancy_factor,
+		epoch.length,
+		epoch.config.attempts_number,
+		epoch.authorities.len() as u32,
+	);
+
+	let public_keys: Vec<bandersnatch::Public> =
+		epoch.authorities.iter().map(|auth| *auth.as_inner_ref()).collect();
+
+	for (authority_idx, authority_id) in epoch.authorities.iter().enumerate() {
+		if !keystore.has_keys(&[(authority_id.to_raw_vec(), AuthorityId::ID)]) {
+			continue;
+		}
+
+		debug!(target: LOG_TARGET, "Generating tickets for epoch {} as {}", epoch.index, authority_idx);
+
+		// The prover is built lazily, as it is costly and most attempts end up over threshold.
+		let mut prover = None;
+
+		for attempt_idx in 0..epoch.config.attempts_number {
+			let ticket_id_input = vrf::ticket_id_input(&epoch.randomness, attempt_idx, epoch.index);
+
+			let Ok(Some(pre_output)) = keystore.bandersnatch_vrf_pre_output(
+				AuthorityId::ID,
+				authority_id.as_ref(),
+				&ticket_id_input,
+			) else {
+				continue;
+			};
+
+			let ticket_id = vrf::make_ticket_id(&pre_output);
+			if ticket_id >= threshold {
+				continue;
+			}
+
+			let (erased_pair, erased_seed) = ed25519::Pair::generate();
+			let (revealed_pair, _) = ed25519::Pair::generate();
+			let body = TicketBody {
+				attempt_idx,
+				erased_public: erased_pair.public(),
+				revealed_public: revealed_pair.public(),
+			};
+
+			let prover =
+				prover.get_or_insert_with(|| ring_context.prover(&public_keys, authority_idx));
+			let sign_data = vrf::ticket_body_sign_data(&body, ticket_id_input);
+			let Ok(Some(signature)) = keystore.bandersnatch_ring_vrf_sign(
+				AuthorityId::ID,
+				authority_id.as_ref(),
+				&sign_data,
+				prover,
+			) else {
+				continue;
+			};
+
+			debug!(target: LOG_TARGET, "Generated ticket {:032x}", ticket_id);
+
+			let secret =
+				TicketSecret { authority_idx: authority_idx as AuthorityIndex, erased_seed };
+			tickets.push((ticket_id, TicketEnvelope { body, signature }, secret));
+		}
+	}
+
+	tickets
+}
@@ -0,0 +1,2050 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Sassafras
+//!
+//! *Semi Anonymous Sortition of Staked Assignees For Fixed-time Rhythmic Assignment of Slots*
+//!
+//! Client side of the Sassafras slot-based block production protocol, whose on-chain
+//! counterpart is `pallet-sassafras`.
+//!
+//! Slots are assigned to the authorities by mean of *tickets*. During the first half of epoch
+//! `N` every authority generates a bunch of tickets for epoch `N+1`, each one being an
+//! anonymous ring-VRF signature whose output (the ticket identifier) must be below a threshold
+//! derived from the epoch configuration. The tickets are submitted on-chain via an unsigned
+//! extrinsic, where the runtime verifies them and, once the submission period is over, sorts
+//! them and assigns them to the epoch slots.
+//!
+//! When a slot is reached, the node looks up in the parent block state the ticket associated
+//! with the slot. If the ticket was generated by one of the local authorities, the node claims
+//! the slot by signing with the ephemeral key committed in the ticket body, revealing that it
+//! owns the ticket. Slots without a ticket are assigned to a fallback authority picked at index
+//! `blake2_256(epoch_randomness ++ slot) % authorities_len`, so that no slot is left empty.
+//!
+//! In both cases the block pre-runtime digest carries a [`SlotClaim`] with a VRF signature of
+//! the slot, which is verified by the importing nodes together with the ticket claim (or
+//! with the fallback authority).
+//!
+//! The fork choice rule is weight-based, where weight equals the number of ticket-backed
+//! blocks in the chain. We pick the heaviest chain and go with the longest one in case of a
+//! tie.
+//!
+//! Epochs are tracked using [`sc_consensus_epochs`] and block authoring is driven by
+//! [`sc_consensus_slots`], exactly as done by BABE.
+//!
+//! ## Usage
+//!
+//! The runtime must include `pallet-sassafras` and implement the [`SassafrasApi`] runtime API.
+//! A node then wires the block import, the import queue and the authoring worker as follows:
+//!
+//! ```ignore
+//! let config = sc_consensus_sassafras::configuration(&*client)?;
+//! let (block_import, sassafras_link) = sc_consensus_sassafras::block_import(
+//! 	config,
+//! 	grandpa_block_import,
+//! 	client.clone(),
+//! 	create_inherent_data_providers.clone(),
+//! 	select_chain.clone(),
+//! 	OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+//! )?;
+//!
+//! let import_queue = sc_consensus_sassafras::import_queue(ImportQueueParams {
+//! 	link: sassafras_link.clone(),
+//! 	block_import: block_import.clone(),
+//! 	justification_import: Some(Box::new(grandpa_block_import)),
+//! 	client: client.clone(),
+//! 	slot_duration: sassafras_link.config().slot_duration,
+//! 	spawner: &task_manager.spawn_essential_handle(),
+//! 	registry: config.prometheus_registry(),
+//! 	telemetry: telemetry.as_ref().map(|x| x.handle()),
+//! })?;
+//!
+//! if role.is_authority() {
+//! 	let sassafras = sc_consensus_sassafras::start_sassafras(SassafrasParams {
+//! 		keystore: keystore_container.keystore(),
+//! 		client: client.clone(),
+//! 		select_chain,
+//! 		env: proposer_factory,
+//! 		block_import,
+//! 		sync_oracle: sync_service.clone(),
+//! 		justification_sync_link: sync_service.clone(),
+//! 		create_inherent_data_providers,
+//! 		force_authoring,
+//! 		backoff_authoring_blocks: None::<()>,
+//! 		sassafras_link,
+//! 		offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+//! 		block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+//! 		max_block_proposal_slot_portion: None,
+//! 		telemetry: telemetry.as_ref().map(|x| x.handle()),
+//! 	})?;
+//!
+//! 	task_manager.spawn_essential_handle().spawn_blocking(
+//! 		"sassafras-proposer",
+//! 		Some("block-authoring"),
+//! 		sassafras,
+//! 	);
+//! }
+//! ```
+//!
+//! Authorities must have a bandersnatch key of type [`KEY_TYPE`] in their keystore.
+//!
+//! On the runtime side, the [`SassafrasApi`] is implemented on top of `pallet-sassafras`:
+//!
+//! ```ignore
+//! impl_runtime_apis! {
+//! 	impl sp_consensus_sassafras::SassafrasApi<Block> for Runtime {
+//! 		fn slot_duration() -> sp_consensus_sassafras::SlotDuration {
+//! 			sp_consensus_sassafras::SlotDuration::from_millis(SLOT_DURATION_IN_MILLISECONDS)
+//! 		}
+//!
+//! 		fn ring_context() -> Option<sp_consensus_sassafras::vrf::RingContext> {
+//! 			Sassafras::ring_context()
+//! 		}
+//!
+//! 		fn submit_tickets_unsigned_extrinsic(
+//! 			tickets: Vec<sp_consensus_sassafras::TicketEnvelope>,
+//! 		) -> bool {
+//! 			Sassafras::submit_tickets_unsigned_extrinsic(tickets)
+//! 		}
+//!
+//! 		fn slot_ticket_id(slot: Slot) -> Option<sp_consensus_sassafras::TicketId> {
+//! 			Sassafras::slot_ticket_id(slot)
+//! 		}
+//!
+//! 		fn slot_ticket(
+//! 			slot: Slot,
+//! 		) -> Option<(sp_consensus_sassafras::TicketId, sp_consensus_sassafras::TicketBody)> {
+//! 			Sassafras::slot_ticket(slot)
+//! 		}
+//!
+//! 		fn current_epoch() -> sp_consensus_sassafras::Epoch {
+//! 			Sassafras::current_epoch()
+//! 		}
+//!
+//! 		fn next_epoch() -> sp_consensus_sassafras::Epoch {
+//! 			Sassafras::next_epoch()
+//! 		}
+//!
+//! 		// Equivocation reporting is not supported by `pallet-sassafras` yet.
+//! 		fn generate_key_ownership_proof(
+//! 			_authority_id: sp_consensus_sassafras::AuthorityId,
+//! 		) -> Option<sp_consensus_sassafras::OpaqueKeyOwnershipProof> {
+//! 			None
+//! 		}
+//!
+//! 		fn submit_report_equivocation_unsigned_extrinsic(
+//! 			_equivocation_proof: sp_consensus_sassafras::EquivocationProof<<Block as BlockT>::Header>,
+//! 			_key_owner_proof: sp_consensus_sassafras::OpaqueKeyOwnershipProof,
+//! 		) -> bool {
+//! 			false
+//! 		}
+//! 	}
+//! }
+//! ```
+//!
+//! A complete example of a runtime and a node wired this way is found in
+//! `substrate/bin/node-sassafras`.
+//!
+//! The substrate test runtime implements the API without the pallet, and is used by the tests
+//! of this crate to run a network of authorities across epoch changes.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+use std::{
+	collections::HashSet,
+	future::Future,
+	ops::{Deref, DerefMut},
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::Duration,
+};
+
+use codec::{Decode, Encode};
+use futures::prelude::*;
+use log::{debug, info, log, trace, warn};
+use parking_lot::Mutex;
+use prometheus_endpoint::Registry;
+
+use sc_client_api::{
+	backend::AuxStore, AuxDataOperations, Backend as BackendT, FinalityNotification,
+	PreCommitActions, UsageProvider,
+};
+use sc_consensus::{
+	block_import::{
+		BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
+		StateAction,
+	},
+	import_queue::{BasicQueue, BoxJustificationImport, DefaultImportQueue, Verifier},
+};
+use sc_consensus_epochs::{
+	descendent_query, Epoch as EpochT, EpochChangesFor, SharedEpochChanges, ViableEpoch,
+	ViableEpochDescriptor,
+};
+use sc_consensus_slots::{
+	check_equivocation, BackoffAuthoringBlocksStrategy, CheckedHeader, InherentDataProviderExt,
+	SlotInfo, StorageChanges,
+};
+use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_application_crypto::AppCrypto;
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_blockchain::{
+	Backend as _, BlockStatus, Error as ClientError, HeaderBackend, HeaderMetadata,
+	Result as ClientResult,
+};
+use sp_consensus::{BlockOrigin, Environment, Error as ConsensusError, Proposer, SelectChain};
+use sp_consensus_slots::{Slot, SlotDuration};
+use sp_core::traits::SpawnEssentialNamed;
+use sp_inherents::{CreateInherentDataProviders, InherentDataProvider};
+use sp_keystore::KeystorePtr;
+use sp_runtime::{
+	generic::OpaqueDigestItemId,
+	traits::{Block as BlockT, Header, NumberFor, SaturatedConversion, Zero},
+	DigestItem,
+};
+use sp_timestamp::Timestamp;
+
+pub use sc_consensus_slots::SlotProportion;
+pub use sp_consensus::SyncOracle;
+pub use sp_consensus_sassafras::{
+	digests::{ConsensusLog, NextEpochDescriptor, SlotClaim},
+	AuthorityId, AuthorityIndex, AuthorityPair, AuthoritySignature, EpochConfiguration, Randomness,
+	SassafrasApi, SassafrasBlockWeight, TicketBody, TicketClaim, TicketEnvelope, TicketId,
+	KEY_TYPE, SASSAFRAS_ENGINE_ID,
+};
+
+pub use aux_schema::load_block_weight as block_weight;
+
+mod verification;
+
+pub mod authorship;
+pub mod aux_schema;
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "sassafras";
+
+/// Sassafras epoch information.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct Epoch(sp_consensus_sassafras::Epoch);
+
+impl Deref for Epoch {
+	type Target = sp_consensus_sassafras::Epoch;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl DerefMut for Epoch {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+
+impl From<sp_consensus_sassafras::Epoch> for Epoch {
+	fn from(epoch: sp_consensus_sassafras::Epoch) -> Self {
+		Epoch(epoch)
+	}
+}
+
+impl EpochT for Epoch {
+	type NextEpochDescriptor = NextEpochDescriptor;
+	type Slot = Slot;
+
+	fn increment(&self, descriptor: NextEpochDescriptor) -> Epoch {
+		sp_consensus_sassafras::Epoch {
+			index: self.index + 1,
+			start: self.start + self.length as u64,
+			length: self.length,
+			randomness: descriptor.randomness,
+			authorities: descriptor.authorities,
+			// The configuration is announced one epoch in advance, if not present the
+			// current one is kept.
+			config: descriptor.config.unwrap_or(self.config),
+		}
+		.into()
+	}
+
+	fn start_slot(&self) -> Slot {
+		self.start
+	}
+
+	fn end_slot(&self) -> Slot {
+		self.start + self.length as u64
+	}
+}
+
+impl Epoch {
+	/// Create the genesis epoch (epoch #0).
+	///
+	/// This is defined to start at the slot of the first block, so that has to be provided.
+	pub fn genesis(config: &SassafrasConfiguration, slot: Slot) -> Epoch {
+		let mut epoch: Epoch = config.genesis_epoch.clone().into();
+		epoch.index = 0;
+		epoch.start = slot;
+		epoch
+	}
+
+	/// Clone and tweak epoch information to refer to the specified slot.
+	///
+	/// All the information which depends on the slot value is recomputed and assigned
+	/// to the returned epoch instance.
+	///
+	/// The `slot` must be greater than or equal the original epoch start slot,
+	/// if is less this operation is equivalent to a simple clone.
+	pub fn clone_for_slot(&self, slot: Slot) -> Epoch {
+		let mut epoch = self.clone();
+
+		let skipped_epochs = *slot.saturating_sub(self.start) / self.length as u64;
+
+		let index = epoch.index.checked_add(skipped_epochs).expect(
+			"epoch number is u64; it should be strictly smaller than number of slots; \
+				slots relate in some way to wall clock time; \
+				if u64 is not enough we should crash for safety; qed.",
+		);
+
+		let start = skipped_epochs
+			.checked_mul(epoch.length as u64)
+			.and_then(|skipped_slots| epoch.start.checked_add(skipped_slots))
+			.expect(
+				"slot number is u64; it should relate in some way to wall clock time; \
+				 if u64 is not enough we should crash for safety; qed.",
+			);
+
+		epoch.index = index;
+		epoch.start = Slot::from(start);
+
+		epoch
+	}
+
+	/// Index of the epoch the given slot belongs to.
+	///
+	/// This is different from the epoch index only if one or more epochs were skipped.
+	pub(crate) fn index_for_slot(&self, slot: Slot) -> u64 {
+		if self.end_slot() <= slot {
+			// Slot doesn't strictly belong to the epoch, create a clone with fixed values.
+			self.clone_for_slot(slot).index
+		} else {
+			self.index
+		}
+	}
+}
+
+/// Errors encountered by the Sassafras authorship task.
+#[derive(Debug, thiserror::Error)]
+pub enum Error<B: BlockT> {
+	/// Multiple Sassafras pre-runtime digests
+	#[error("Multiple Sassafras pre-runtime digests, rejecting!")]
+	MultiplePreRuntimeDigests,
+	/// No Sassafras pre-runtime digest found
+	#[error("No Sassafras pre-runtime digest found")]
+	NoPreRuntimeDigest,
+	/// Multiple Sassafras epoch change digests
+	#[error("Multiple Sassafras epoch change digests, rejecting!")]
+	MultipleEpochChangeDigests,
+	/// Could not fetch epoch
+	#[error("Could not fetch epoch at {0:?}")]
+	FetchEpoch(B::Hash),
+	/// Header rejected: too far in the future
+	#[error("Header {0:?} rejected: too far in the future")]
+	TooFarInFuture(B::Hash),
+	/// Parent unavailable. Cannot import
+	#[error("Parent ({0}) of {1} unavailable. Cannot import")]
+	ParentUnavailable(B::Hash, B::Hash),
+	/// Slot number must increase
+	#[error("Slot number must increase: parent slot: {0}, this slot: {1}")]
+	SlotMustIncrease(Slot, Slot),
+	/// Header has a bad seal
+	#[error("Header {0:?} has a bad seal")]
+	HeaderBadSeal(B::Hash),
+	/// Header is unsealed
+	#[error("Header {0:?} is unsealed")]
+	HeaderUnsealed(B::Hash),
+	/// Slot author not found
+	#[error("Slot author not found")]
+	SlotAuthorNotFound,
+	/// Bad signature
+	#[error("Bad signature on {0:?}")]
+	BadSignature(B::Hash),
+	/// Invalid author: Expected fallback author
+	#[error("Invalid author: Expected fallback author: {0:?}, got: {1:?}.")]
+	InvalidAuthor(AuthorityId, AuthorityId),
+	/// VRF verification failed
+	#[error("VRF verification failed")]
+	VrfVerificationFailed,
+	/// The slot has an associated ticket but the header doesn't claim it
+	#[error("Missing ticket claim for slot {0}")]
+	MissingTicketClaim(Slot),
+	/// The slot has no associated ticket but the header claims one
+	#[error("Unexpected ticket claim for slot {0}")]
+	UnexpectedTicketClaim(Slot),
+	/// Ticket claim verification failed
+	#[error("Ticket claim verification failed for slot {0}")]
+	TicketClaimVerificationFailed(Slot),
+	/// Could not fetch parent header
+	#[error("Could not fetch parent header: {0}")]
+	FetchParentHeader(sp_blockchain::Error),
+	/// Expected epoch change to happen.
+	#[error("Expected epoch change to happen at {0:?}, s{1}")]
+	ExpectedEpochChange(B::Hash, Slot),
+	/// Unexpected epoch change
+	#[error("Unexpected epoch change")]
+	UnexpectedEpochChange,
+	/// Parent block has no associated weight
+	#[error("Parent block of {0} has no associated weight")]
+	ParentBlockNoAssociatedWeight(B::Hash),
+	/// Check inherents error
+	#[error("Checking inherents failed: {0}")]
+	CheckInherents(sp_inherents::Error),
+	/// Unhandled check inherents error
+	#[error("Checking inherents unhandled error: {}", String::from_utf8_lossy(.0))]
+	CheckInherentsUnhandled(sp_inherents::InherentIdentifier),
+	/// Create inherents error.
+	#[error("Creating inherents failed: {0}")]
+	CreateInherents(sp_inherents::Error),
+	/// Client error
+	#[error(transparent)]
+	Client(sp_blockchain::Error),
+	/// Runtime Api error.
+	#[error(transparent)]
+	RuntimeApi(sp_api::ApiError),
+	/// Fork tree error
+	#[error(transparent)]
+	ForkTree(Box<fork_tree::Error<sp_blockchain::Error>>),
+}
+
+impl<B: BlockT> From<Error<B>> for String {
+	fn from(error: Error<B>) -> String {
+		error.to_string()
+	}
+}
+
+fn sassafras_err<B: BlockT>(error: Error<B>) -> Error<B> {
+	debug!(target: LOG_TARGET, "{}", error);
+	error
+}
+
+/// Intermediate value passed to block importer.
+pub struct SassafrasIntermediate<B: BlockT> {
+	/// The epoch descriptor.
+	pub epoch_descriptor: ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
+}
+
+/// Intermediate key for Sassafras engine.
+pub static INTERMEDIATE_KEY: &[u8] = b"sass1";
+
+/// Sassafras protocol configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SassafrasConfiguration {
+	/// The slot duration.
+	pub slot_duration: SlotDuration,
+	/// Parameters of the genesis epoch (epoch #0).
+	///
+	/// The epoch index and start slot are not meaningful, the genesis epoch always starts at
+	/// the slot of the first block.
+	pub genesis_epoch: sp_consensus_sassafras::Epoch,
+}
+
+/// Read the configuration from the runtime state.
+///
+/// The genesis epoch parameters are read from the genesis state, if available.
+pub fn configuration<B: BlockT, C>(client: &C) -> ClientResult<SassafrasConfiguration>
+where
+	C: ProvideRuntimeApi<B> + UsageProvider<B>,
+	C::Api: SassafrasApi<B>,
+{
+	let info = client.usage_info().chain;
+	let runtime_api = client.runtime_api();
+
+	// The slot duration is only exposed since version 2 of the runtime API.
+	let slot_duration = match runtime_api.api_version::<dyn SassafrasApi<B>>(info.best_hash)? {
+		Some(version) if version >= 2 => runtime_api.slot_duration(info.best_hash)?,
+		_ => {
+			return Err(ClientError::VersionInvalid(
+				"Unsupported or invalid SassafrasApi version, version 2 is required".to_string(),
+			))
+		},
+	};
+
+	// The genesis state is not available if the node has been warp synced. In this case the
+	// epoch changes tree has been initialized from the imported state and the genesis epoch
+	// is never used.
+	let genesis_epoch = match runtime_api.current_epoch(info.genesis_hash) {
+		Ok(epoch) => epoch,
+		Err(_) => {
+			debug!(
+				target: LOG_TARGET,
+				"Genesis state is not available. Reading genesis epoch from best block"
+			);
+			runtime_api.current_epoch(info.best_hash)?
+		},
+	};
+
+	Ok(SassafrasConfiguration { slot_duration, genesis_epoch })
+}
+
+/// Parameters for Sassafras.
+pub struct SassafrasParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
+	/// The keystore that manages the keys of the node.
+	pub keystore: KeystorePtr,
+
+	/// The client to use
+	pub client: Arc<C>,
+
+	/// The SelectChain Strategy
+	pub select_chain: SC,
+
+	/// The environment we are producing blocks for.
+	pub env: E,
+
+	/// The underlying block-import object to supply our produced blocks to.
+	/// This must be a `SassafrasBlockImport` or a wrapper of it, otherwise
+	/// critical consensus logic will be omitted.
+	pub block_import: I,
+
+	/// A sync oracle
+	pub sync_oracle: SO,
+
+	/// Hook into the sync module to control the justification sync process.
+	pub justification_sync_link: L,
+
+	/// Something that can create the inherent data providers.
+	pub create_inherent_data_providers: CIDP,
+
+	/// Force authoring of blocks even if we are offline
+	pub force_authoring: bool,
+
+	/// Strategy and parameters for backing off block production.
+	pub backoff_authoring_blocks: Option<BS>,
+
+	/// The link shared with the block import.
+	pub sassafras_link: SassafrasLink<B>,
+
+	/// The offchain transaction pool factory.
+	///
+	/// Used to submit the tickets generated by the local authorities.
+	pub offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+
+	/// The proportion of the slot dedicated to proposing.
+	///
+	/// The block proposing will be limited to this proportion of the slot from the starting of the
+	/// slot. However, the proposing can still take longer when there is some lenience factor
+	/// applied, because there were no blocks produced for some slots.
+	pub block_proposal_slot_portion: SlotProportion,
+
+	/// The maximum proportion of the slot dedicated to proposing with any lenience factor applied
+	/// due to no blocks being produced.
+	pub max_block_proposal_slot_portion: Option<SlotProportion>,
+
+	/// Handle use to report telemetries.
+	pub telemetry: Option<TelemetryHandle>,
+}
+
+/// Start the Sassafras worker.
+pub fn start_sassafras<B, C, SC, E, I, SO, CIDP, BS, L, Error>(
+	SassafrasParams {
+		keystore,
+		client,
+		select_chain,
+		env,
+		block_import,
+		sync_oracle,
+		justification_sync_link,
+		create_inherent_data_providers,
+		force_authoring,
+		backoff_authoring_blocks,
+		sassafras_link,
+		offchain_tx_pool_factory,
+		block_proposal_slot_portion,
+		max_block_proposal_slot_portion,
+		telemetry,
+	}: SassafrasParams<B, C, SC, E, I, SO, L, CIDP, BS>,
+) -> Result<SassafrasWorker, ConsensusError>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>
+		+ HeaderBackend<B>
+		+ HeaderMetadata<B, Error = ClientError>
+		+ AuxStore
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: SassafrasApi<B>,
+	SC: SelectChain<B> + 'static,
+	E: Environment<B, Error = Error> + Send + Sync + 'static,
+	E::Proposer: Proposer<B, Error = Error>,
+	I: BlockImport<B, Error = ConsensusError> + Send + Sync + 'static,
+	SO: SyncOracle + Send + Sync + Clone + 'static,
+	L: sc_consensus::JustificationSyncLink<B> + 'static,
+	CIDP: CreateInherentDataProviders<B, ()> + Send + Sync + 'static,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send,
+	BS: BackoffAuthoringBlocksStrategy<NumberFor<B>> + Send + Sync + 'static,
+	Error: std::error::Error + Send + From<ConsensusError> + From<I::Error> + 'static,
+{
+	let epoch_tickets = aux_schema::load_epoch_tickets(&*client)
+		.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+	let worker = SassafrasSlotWorker {
+		client,
+		block_import,
+		env,
+		sync_oracle: sync_oracle.clone(),
+		justification_sync_link,
+		force_authoring,
+		backoff_authoring_blocks,
+		keystore,
+		epoch_changes: sassafras_link.epoch_changes.clone(),
+		epoch_tickets: Arc::new(Mutex::new(epoch_tickets)),
+		config: sassafras_link.config.clone(),
+		offchain_tx_pool_factory,
+		block_proposal_slot_portion,
+		max_block_proposal_slot_portion,
+		telemetry,
+	};
+
+	info!(target: LOG_TARGET, "🌳 Starting Sassafras Authorship worker");
+
+	let slot_worker = sc_consensus_slots::start_slot_worker(
+		sassafras_link.config.slot_duration,
+		select_chain,
+		sc_consensus_slots::SimpleSlotWorkerToSlotWorker(worker),
+		sync_oracle,
+		create_inherent_data_providers,
+	);
+
+	Ok(SassafrasWorker { inner: Box::pin(slot_worker) })
+}
+
+// Remove obsolete block's weight data by leveraging finality notifications.
+// This includes data for all finalized blocks (excluding the most recent one)
+// and all stale branches.
+fn aux_storage_cleanup<C: HeaderMetadata<Block> + HeaderBackend<Block>, Block: BlockT>(
+	client: &C,
+	notification: &FinalityNotification<Block>,
+) -> AuxDataOperations {
+	let mut hashes = HashSet::new();
+
+	let first = notification.tree_route.first().unwrap_or(&notification.hash);
+	match client.header_metadata(*first) {
+		Ok(meta) => {
+			hashes.insert(meta.parent);
+		},
+		Err(err) => {
+			warn!(target: LOG_TARGET, "Failed to lookup metadata for block `{:?}`: {}", first, err,)
+		},
+	}
+
+	// Cleans data for finalized block's ancestors
+	hashes.extend(
+		notification
+			.tree_route
+			.iter()
+			// Ensure we don't prune latest finalized block.
+			// This should not happen, but better be safe than sorry!
+			.filter(|h| **h != notification.hash),
+	);
+
+	hashes.extend(notification.stale_blocks.iter().map(|b| b.hash));
+
+	hashes
+		.into_iter()
+		.map(|val| (aux_schema::block_weight_key(val), None))
+		.collect()
+}
+
+/// Worker for Sassafras which implements `Future<Output=()>`. This must be polled.
+#[must_use]
+pub struct SassafrasWorker {
+	inner: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+}
+
+impl Future for SassafrasWorker {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+		self.inner.as_mut().poll(cx)
+	}
+}
+
+struct SassafrasSlotWorker<B: BlockT, C, E, I, SO, L, BS> {
+	client: Arc<C>,
+	block_import: I,
+	env: E,
+	sync_oracle: SO,
+	justification_sync_link: L,
+	force_authoring: bool,
+	backoff_authoring_blocks: Option<BS>,
+	keystore: KeystorePtr,
+	epoch_changes: SharedEpochChanges<B, Epoch>,
+	epoch_tickets: Arc<Mutex<authorship::EpochTickets>>,
+	config: SassafrasConfiguration,
+	offchain_tx_pool_factory: OffchainTransactionPoolFactory<B>,
+	block_proposal_slot_portion: SlotProportion,
+	max_block_proposal_slot_portion: Option<SlotProportion>,
+	telemetry: Option<TelemetryHandle>,
+}
+
+impl<B, C, E, I, SO, L, BS> SassafrasSlotWorker<B, C, E, I, SO, L, BS>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + AuxStore,
+	C::Api: SassafrasApi<B>,
+{
+	/// Generate and submit the tickets of the local authorities for the epoch following the
+	/// one of `slot`.
+	///
+	/// This is done once per epoch, during the first half of the epoch, which is the period
+	/// in which the runtime accepts the tickets.
+	fn generate_tickets(&self, parent_hash: B::Hash, slot: Slot) {
+		let runtime_api = self.client.runtime_api();
+
+		let next_epoch = match runtime_api.next_epoch(parent_hash) {
+			Ok(epoch) => Epoch::from(epoch),
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Unable to fetch next epoch data: {}", err);
+				return;
+			},
+		};
+
+		// The block we are going to author enacts a new epoch, tickets are generated
+		// starting from the next slot.
+		if next_epoch.start <= slot {
+			return;
+		}
+
+		// Tickets are only accepted during the first half of the current epoch.
+		let epoch_start = next_epoch.start.saturating_sub(next_epoch.length);
+		if *slot.saturating_sub(epoch_start) >= next_epoch.length as u64 / 2 {
+			return;
+		}
+
+		if self.epoch_tickets.lock().contains_key(&next_epoch.index) {
+			return;
+		}
+
+		let ring_context = match runtime_api.ring_context(parent_hash) {
+			Ok(Some(ring_context)) => ring_context,
+			Ok(None) => {
+				debug!(target: LOG_TARGET, "Ring context not initialized, skipping tickets");
+				return;
+			},
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Unable to fetch ring context: {}", err);
+				return;
+			},
+		};
+
+		let tickets =
+			authorship::generate_epoch_tickets(&next_epoch, &ring_context, &self.keystore);
+
+		let mut epoch_tickets = self.epoch_tickets.lock();
+		let secrets = epoch_tickets.entry(next_epoch.index).or_default();
+		let mut envelopes = Vec::with_capacity(tickets.len());
+		for (ticket_id, envelope, secret) in tickets {
+			secrets.insert(ticket_id, secret);
+			envelopes.push(envelope);
+		}
+
+		// Tickets of the epochs already gone are not useful anymore.
+		epoch_tickets.retain(|index, _| *index + 1 >= next_epoch.index);
+
+		if let Err(err) = aux_schema::write_epoch_tickets(&epoch_tickets, |insert| {
+			self.client.insert_aux(insert, [])
+		}) {
+			warn!(target: LOG_TARGET, "Unable to persist generated tickets: {}", err);
+		}
+		drop(epoch_tickets);
+
+		if envelopes.is_empty() {
+			return;
+		}
+
+		info!(
+			target: LOG_TARGET,
+			"🌳 Submitting {} tickets for epoch {}",
+			envelopes.len(),
+			next_epoch.index,
+		);
+
+		let mut runtime_api = self.client.runtime_api();
+
+		// Register the offchain tx pool to be able to use it from the runtime.
+		runtime_api.register_extension(
+			self.offchain_tx_pool_factory.offchain_transaction_pool(parent_hash),
+		);
+
+		match runtime_api.submit_tickets_unsigned_extrinsic(parent_hash, envelopes) {
+			Ok(true) => (),
+			Ok(false) => warn!(target: LOG_TARGET, "Unable to submit tickets"),
+			Err(err) => warn!(target: LOG_TARGET, "Unable to submit tickets: {}", err),
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl<B, C, E, I, Error, SO, L, BS> sc_consensus_slots::SimpleSlotWorker<B>
+	for SassafrasSlotWorker<B, C, E, I, SO, L, BS>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>
+		+ HeaderBackend<B>
+		+ HeaderMetadata<B, Error = ClientError>
+		+ AuxStore
+		+ Send
+		+ Sync,
+	C::Api: SassafrasApi<B>,
+	E: Environment<B, Error = Error> + Send + Sync,
+	E::Proposer: Proposer<B, Error = Error>,
+	I: BlockImport<B> + Send + Sync + 'static,
+	SO: SyncOracle + Send + Clone + Sync,
+	L: sc_consensus::JustificationSyncLink<B>,
+	BS: BackoffAuthoringBlocksStrategy<NumberFor<B>> + Send + Sync,
+	Error: std::error::Error + Send + From<ConsensusError> + From<I::Error> + 'static,
+{
+	type Claim = (SlotClaim, AuthorityId);
+	type SyncOracle = SO;
+	type JustificationSyncLink = L;
+	type CreateProposer =
+		Pin<Box<dyn Future<Output = Result<E::Proposer, ConsensusError>> + Send + 'static>>;
+	type Proposer = E::Proposer;
+	type BlockImport = I;
+	type AuxData = ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>;
+
+	fn logging_target(&self) -> &'static str {
+		LOG_TARGET
+	}
+
+	fn block_import(&mut self) -> &mut Self::BlockImport {
+		&mut self.block_import
+	}
+
+	fn aux_data(&self, parent: &B::Header, slot: Slot) -> Result<Self::AuxData, ConsensusError> {
+		self.epoch_changes
+			.shared_data()
+			.epoch_descriptor_for_child_of(
+				descendent_query(&*self.client),
+				&parent.hash(),
+				*parent.number(),
+				slot,
+			)
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?
+			.ok_or(ConsensusError::InvalidAuthoritiesSet)
+	}
+
+	fn authorities_len(&self, epoch_descriptor: &Self::AuxData) -> Option<usize> {
+		self.epoch_changes
+			.shared_data()
+			.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))
+			.map(|epoch| epoch.as_ref().authorities.len())
+	}
+
+	async fn claim_slot(
+		&mut self,
+		parent_header: &B::Header,
+		slot: Slot,
+		epoch_descriptor: &ViableEpochDescriptor<B::Hash, NumberFor<B>, Epoch>,
+	) -> Option<Self::Claim> {
+		debug!(target: LOG_TARGET, "Attempting to claim slot {}", slot);
+
+		let parent_hash = parent_header.hash();
+
+		self.generate_tickets(parent_hash, slot);
+
+		let epoch = self
+			.epoch_changes
+			.shared_data()
+			.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))?
+			.as_ref()
+			.clone();
+
+		let maybe_ticket = match self.client.runtime_api().slot_ticket(parent_hash, slot) {
+			Ok(maybe_ticket) => maybe_ticket,
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Unable to fetch ticket for slot {}: {}", slot, err);
+				return None;
+			},
+		};
+
+		let s = authorship::claim_slot(
+			slot,
+			&epoch,
+			maybe_ticket,
+			&self.epoch_tickets.lock(),
+			&self.keystore,
+		);
+
+		if s.is_some() {
+			debug!(target: LOG_TARGET, "Claimed slot {}", slot);
+		}
+
+		s
+	}
+
+	fn pre_digest_data(&self, _slot: Slot, claim: &Self::Claim) -> Vec<sp_runtime::DigestItem> {
+		vec![DigestItem::from(&claim.0)]
+	}
+
+	async fn block_import_params(
+		&self,
+		header: B::Header,
+		header_hash: &B::Hash,
+		body: Vec<B::Extrinsic>,
+		storage_changes: StorageChanges<B>,
+		(_, public): Self::Claim,
+		epoch_descriptor: Self::AuxData,
+	) -> Result<BlockImportParams<B>, ConsensusError> {
+		let signature: AuthoritySignature = self
+			.keystore
+			.bandersnatch_sign(
+				<AuthorityId as AppCrypto>::ID,
+				public.as_ref(),
+				header_hash.as_ref(),
+			)
+			.map_err(|e| ConsensusError::CannotSign(format!("{}. Key: {:?}", e, public)))?
+			.ok_or_else(|| {
+				ConsensusError::CannotSign(format!(
+					"Could not find key in keystore. Key: {:?}",
+					public
+				))
+			})?
+			.into();
+
+		let digest_item = DigestItem::from(&signature);
+
+		let mut import_block = BlockImportParams::new(BlockOrigin::Own, header);
+		import_block.post_digests.push(digest_item);
+		import_block.body = Some(body);
+		import_block.state_action =
+			StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(storage_changes));
+		import_block
+			.insert_intermediate(INTERMEDIATE_KEY, SassafrasIntermediate::<B> { epoch_descriptor });
+
+		Ok(import_block)
+	}
+
+	fn force_authoring(&self) -> bool {
+		self.force_authoring
+	}
+
+	fn should_backoff(&self, slot: Slot, chain_head: &B::Header) -> bool {
+		if let Some(ref strategy) = self.backoff_authoring_blocks {
+			if let Ok(chain_head_slot) = find_slot::<B>(chain_head) {
+				return strategy.should_backoff(
+					*chain_head.number(),
+					chain_head_slot,
+					self.client.info().finalized_number,
+					slot,
+					self.logging_target(),
+				);
+			}
+		}
+		false
+	}
+
+	fn sync_oracle(&mut self) -> &mut Self::SyncOracle {
+		&mut self.sync_oracle
+	}
+
+	fn justification_sync_link(&mut self) -> &mut Self::JustificationSyncLink {
+		&mut self.justification_sync_link
+	}
+
+	fn proposer(&mut self, block: &B::Header) -> Self::CreateProposer {
+		Box::pin(self.env.init(block).map_err(|e| ConsensusError::ClientImport(e.to_string())))
+	}
+
+	fn telemetry(&self) -> Option<TelemetryHandle> {
+		self.telemetry.clone()
+	}
+
+	fn proposing_remaining_duration(&self, slot_info: &SlotInfo<B>) -> Duration {
+		let parent_slot = find_slot::<B>(&slot_info.chain_head).ok();
+
+		sc_consensus_slots::proposing_remaining_duration(
+			parent_slot,
+			slot_info,
+			&self.block_proposal_slot_portion,
+			self.max_block_proposal_slot_portion.as_ref(),
+			sc_consensus_slots::SlotLenienceType::Exponential,
+			self.logging_target(),
+		)
+	}
+}
+
+/// Extract the Sassafras slot claim from the given header. Pre-runtime digests are
+/// mandatory, the function will return `Err` if none is found.
+pub fn find_slot_claim<B: BlockT>(header: &B::Header) -> Result<SlotClaim, Error<B>> {
+	let mut slot_claim: Option<_> = None;
+	for log in header.digest().logs() {
+		trace!(target: LOG_TARGET, "Checking log {:?}, looking for pre runtime digest", log);
+		match (SlotClaim::try_from(log), slot_claim.is_some()) {
+			(Ok(_), true) => return Err(sassafras_err(Error::MultiplePreRuntimeDigests)),
+			(Err(_), _) => trace!(target: LOG_TARGET, "Ignoring digest not meant for us"),
+			(Ok(claim), false) => slot_claim = Some(claim),
+		}
+	}
+	slot_claim.ok_or_else(|| sassafras_err(Error::NoPreRuntimeDigest))
+}
+
+/// Extract the slot of the given header.
+///
+/// The genesis block doesn't contain a slot claim and is considered to be at slot 0.
+pub fn find_slot<B: BlockT>(header: &B::Header) -> Result<Slot, Error<B>> {
+	if header.number().is_zero() {
+		return Ok(0.into());
+	}
+	find_slot_claim::<B>(header).map(|claim| claim.slot)
+}
+
+/// Extract the Sassafras epoch change digest from the given header, if it exists.
+pub fn find_next_epoch_digest<B: BlockT>(
+	header: &B::Header,
+) -> Result<Option<NextEpochDescriptor>, Error<B>> {
+	let mut epoch_digest: Option<_> = None;
+	for log in header.digest().logs() {
+		trace!(target: LOG_TARGET, "Checking log {:?}, looking for epoch change digest.", log);
+		let log = log.try_to::<ConsensusLog>(OpaqueDigestItemId::Consensus(&SASSAFRAS_ENGINE_ID));
+		match (log, epoch_digest.is_some()) {
+			(Some(ConsensusLog::NextEpochData(_)), true) => {
+				return Err(sassafras_err(Error::MultipleEpochChangeDigests))
+			},
+			(Some(ConsensusLog::NextEpochData(epoch)), false) => epoch_digest = Some(epoch),
+			_ => trace!(target: LOG_TARGET, "Ignoring digest not meant for us"),
+		}
+	}
+
+	Ok(epoch_digest)
+}
+
+/// State that must be shared between the import queue and the authoring logic.
+#[derive(Clone)]
+pub struct SassafrasLink<Block: BlockT> {
+	epoch_changes: SharedEpochChanges<Block, Epoch>,
+	config: SassafrasConfiguration,
+}
+
+impl<Block: BlockT> SassafrasLink<Block> {
+	/// Get the epoch changes of this link.
+	pub fn epoch_changes(&self) -> &SharedEpochChanges<Block, Epoch> {
+		&self.epoch_changes
+	}
+
+	/// Get the config of this link.
+	pub fn config(&self) -> &SassafrasConfiguration {
+		&self.config
+	}
+}
+
+/// A verifier for Sassafras blocks.
+pub struct SassafrasVerifier<Block: BlockT, Client> {
+	client: Arc<Client>,
+	slot_duration: SlotDuration,
+	config: SassafrasConfiguration,
+	epoch_changes: SharedEpochChanges<Block, Epoch>,
+	telemetry: Option<TelemetryHandle>,
+}
+
+#[async_trait::async_trait]
+impl<Block, Client> Verifier<Block> for SassafrasVerifier<Block, Client>
+where
+	Block: BlockT,
+	Client: HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync
+		+ AuxStore,
+	Client::Api: BlockBuilderApi<Block> + SassafrasApi<Block>,
+{
+	async fn verify(
+		&self,
+		mut block: BlockImportParams<Block>,
+	) -> Result<BlockImportParams<Block>, String> {
+		trace!(
+			target: LOG_TARGET,
+			"Verifying origin: {:?} header: {:?} justification(s): {:?} body: {:?}",
+			block.origin,
+			block.header,
+			block.justifications,
+			block.body,
+		);
+
+		let hash = block.header.hash();
+		let parent_hash = *block.header.parent_hash();
+
+		let number = block.header.number();
+
+		if is_state_sync_or_gap_sync_import(&*self.client, &block) {
+			return Ok(block);
+		}
+
+		debug!(
+			target: LOG_TARGET,
+			"We have {:?} logs in this header",
+			block.header.digest().logs().len()
+		);
+
+		let slot_now = Slot::from_timestamp(Timestamp::current(), self.slot_duration);
+
+		let slot_claim = find_slot_claim::<Block>(&block.header)?;
+
+		// The ticket associated with the slot is read from the parent state, exactly as done
+		// by the block author.
+		let maybe_ticket = self
+			.client
+			.runtime_api()
+			.slot_ticket(parent_hash, slot_claim.slot)
+			.map_err(Error::<Block>::RuntimeApi)?;
+
+		let (check_header, epoch_descriptor) = {
+			let (epoch_descriptor, viable_epoch) = query_epoch_changes(
+				&self.epoch_changes,
+				self.client.as_ref(),
+				&self.config,
+				*number,
+				slot_claim.slot,
+				parent_hash,
+			)?;
+
+			// We add one to the current slot to allow for some small drift.
+			let v_params = verification::VerificationParams {
+				header: block.header.clone(),
+				slot_claim,
+				maybe_ticket,
+				slot_now: slot_now + 1,
+				epoch: viable_epoch.as_ref(),
+			};
+
+			(verification::check_header::<Block>(v_params)?, epoch_descriptor)
+		};
+
+		match check_header {
+			CheckedHeader::Checked(pre_header, verified_info) => {
+				trace!(target: LOG_TARGET, "Checked {:?}; importing.", pre_header);
+				telemetry!(
+					self.telemetry;
+					CONSENSUS_TRACE;
+					"sassafras.checked_and_importing";
+					"pre_header" => ?pre_header,
+				);
+
+				block.header = pre_header;
+				block.post_digests.push(verified_info.seal);
+				block.insert_intermediate(
+					INTERMEDIATE_KEY,
+					SassafrasIntermediate::<Block> { epoch_descriptor },
+				);
+				block.post_hash = Some(hash);
+
+				Ok(block)
+			},
+			CheckedHeader::Deferred(a, b) => {
+				debug!(target: LOG_TARGET, "Checking {:?} failed; {:?}, {:?}.", hash, a, b);
+				telemetry!(
+					self.telemetry;
+					CONSENSUS_DEBUG;
+					"sassafras.header_too_far_in_future";
+					"hash" => ?hash, "a" => ?a, "b" => ?b
+				);
+				Err(Error::<Block>::TooFarInFuture(hash).into())
+			},
+		}
+	}
+}
+
+/// Verification for imported blocks is skipped in two cases:
+/// 1. When importing blocks below the last finalized block during network initial synchronization.
+/// 2. When importing whole state we don't calculate epoch descriptor, but rather read it from the
+///    state after import. We also skip all verifications because there's no parent state and we
+///    trust the sync module to verify that the state is correct and finalized.
+fn is_state_sync_or_gap_sync_import<B: BlockT>(
+	client: &impl HeaderBackend<B>,
+	block: &BlockImportParams<B>,
+) -> bool {
+	let number = *block.header.number();
+	let info = client.info();
+	info.block_gap.map_or(false, |gap| gap.start <= number && number <= gap.end) ||
+		block.with_state()
+}
+
+/// A block-import handler for Sassafras.
+///
+/// This scans each imported block for epoch change signals. The signals are
+/// tracked in a tree (of all forks), and the import logic validates all epoch
+/// change transitions, i.e. whether a given epoch change is expected or whether
+/// it is missing.
+///
+/// The epoch change tree should be pruned as blocks are finalized.
+pub struct SassafrasBlockImport<Block: BlockT, Client, I, CIDP, SC> {
+	inner: I,
+	client: Arc<Client>,
+	epoch_changes: SharedEpochChanges<Block, Epoch>,
+	create_inherent_data_providers: CIDP,
+	config: SassafrasConfiguration,
+	// A [`SelectChain`] implementation.
+	//
+	// Used to determine the best block that should be used as basis when sending an equivocation
+	// report.
+	select_chain: SC,
+	// The offchain transaction pool factory.
+	//
+	// Will be used when sending equivocation reports.
+	offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+}
+
+impl<Block: BlockT, I: Clone, Client, CIDP: Clone, SC: Clone> Clone
+	for SassafrasBlockImport<Block, Client, I, CIDP, SC>
+{
+	fn clone(&self) -> Self {
+		SassafrasBlockImport {
+			inner: self.inner.clone(),
+			client: self.client.clone(),
+			epoch_changes: self.epoch_changes.clone(),
+			config: self.config.clone(),
+			create_inherent_data_providers: self.create_inherent_data_providers.clone(),
+			select_chain: self.select_chain.clone(),
+			offchain_tx_pool_factory: self.offchain_tx_pool_factory.clone(),
+		}
+	}
+}
+
+impl<Block: BlockT, Client, I, CIDP, SC> SassafrasBlockImport<Block, Client, I, CIDP, SC> {
+	fn new(
+		client: Arc<Client>,
+		epoch_changes: SharedEpochChanges<Block, Epoch>,
+		block_import: I,
+		config: SassafrasConfiguration,
+		create_inherent_data_providers: CIDP,
+		select_chain: SC,
+		offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+	) -> Self {
+		SassafrasBlockImport {
+			client,
+			inner: block_import,
+			epoch_changes,
+			config,
+			create_inherent_data_providers,
+			select_chain,
+			offchain_tx_pool_factory,
+		}
+	}
+}
+
+impl<Block, Client, Inner, CIDP, SC> SassafrasBlockImport<Block, Client, Inner, CIDP, SC>
+where
+	Block: BlockT,
+	Inner: BlockImport<Block> + Send + Sync,
+	Inner::Error: Into<ConsensusError>,
+	Client: HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ AuxStore
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync,
+	Client::Api: BlockBuilderApi<Block> + SassafrasApi<Block> + ApiExt<Block>,
+	CIDP: CreateInherentDataProviders<Block, ()>,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send,
+	SC: sp_consensus::SelectChain<Block> + 'static,
+{
+	/// Import whole state after warp sync.
+	// This function makes multiple transactions to the DB. If one of them fails we may
+	// end up in an inconsistent state and have to resync.
+	async fn import_state(
+		&self,
+		mut block: BlockImportParams<Block>,
+	) -> Result<ImportResult, ConsensusError> {
+		let hash = block.post_hash();
+		let parent_hash = *block.header.parent_hash();
+		let number = *block.header.number();
+
+		block.fork_choice = Some(ForkChoiceStrategy::Custom(true));
+		// Reset block weight.
+		aux_schema::write_block_weight(hash, 0, |values| {
+			block
+				.auxiliary
+				.extend(values.iter().map(|(k, v)| (k.to_vec(), Some(v.to_vec()))))
+		});
+
+		// First make the client import the state.
+		let import_result = self.inner.import_block(block).await;
+		let aux = match import_result {
+			Ok(ImportResult::Imported(aux)) => aux,
+			Ok(r) => {
+				return Err(ConsensusError::ClientImport(format!(
+					"Unexpected import result: {:?}",
+					r
+				)))
+			},
+			Err(r) => return Err(r.into()),
+		};
+
+		// Read epoch info from the imported state.
+		let current_epoch = self.client.runtime_api().current_epoch(hash).map_err(|e| {
+			ConsensusError::ClientImport(sassafras_err::<Block>(Error::RuntimeApi(e)).into())
+		})?;
+		let next_epoch = self.client.runtime_api().next_epoch(hash).map_err(|e| {
+			ConsensusError::ClientImport(sassafras_err::<Block>(Error::RuntimeApi(e)).into())
+		})?;
+
+		let mut epoch_changes = self.epoch_changes.shared_data_locked();
+		epoch_changes.reset(parent_hash, hash, number, current_epoch.into(), next_epoch.into());
+		aux_schema::write_epoch_changes::<Block, _, _>(&*epoch_changes, |insert| {
+			self.client.insert_aux(insert, [])
+		})
+		.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+		Ok(ImportResult::Imported(aux))
+	}
+
+	/// Check the inherents and equivocations.
+	async fn check_inherents_and_equivocations(
+		&self,
+		block: &mut BlockImportParams<Block>,
+	) -> Result<(), ConsensusError> {
+		if is_state_sync_or_gap_sync_import(&*self.client, block) {
+			return Ok(());
+		}
+
+		let parent_hash = *block.header.parent_hash();
+		let number = *block.header.number();
+
+		let create_inherent_data_providers = self
+			.create_inherent_data_providers
+			.create_inherent_data_providers(parent_hash, ())
+			.await?;
+
+		let slot_now = create_inherent_data_providers.slot();
+
+		let slot_claim = find_slot_claim::<Block>(&block.header)
+			.map_err(|e| ConsensusError::Other(Box::new(e)))?;
+		let slot = slot_claim.slot;
+
+		// Check inherents.
+		self.check_inherents(block, parent_hash, create_inherent_data_providers).await?;
+
+		// Check for equivocation and report it to the runtime if needed.
+		let author = {
+			let viable_epoch = query_epoch_changes(
+				&self.epoch_changes,
+				self.client.as_ref(),
+				&self.config,
+				number,
+				slot,
+				parent_hash,
+			)
+			.map_err(|e| ConsensusError::Other(sassafras_err(e).into()))?
+			.1;
+			match viable_epoch.as_ref().authorities.get(slot_claim.authority_idx as usize) {
+				Some(author) => author.clone(),
+				None => {
+					return Err(ConsensusError::Other(Error::<Block>::SlotAuthorNotFound.into()))
+				},
+			}
+		};
+		if let Err(err) = self
+			.check_and_report_equivocation(slot_now, slot, &block.header, &author, &block.origin)
+			.await
+		{
+			warn!(
+				target: LOG_TARGET,
+				"Error checking/reporting Sassafras equivocation: {}", err
+			);
+		}
+		Ok(())
+	}
+
+	async fn check_inherents(
+		&self,
+		block: &mut BlockImportParams<Block>,
+		at_hash: Block::Hash,
+		create_inherent_data_providers: CIDP::InherentDataProviders,
+	) -> Result<(), ConsensusError> {
+		if block.state_action.skip_execution_checks() {
+			return Ok(());
+		}
+
+		if let Some(inner_body) = block.body.take() {
+			let new_block = Block::new(block.header.clone(), inner_body);
+			let inherent_data = create_inherent_data_providers
+				.create_inherent_data()
+				.await
+				.map_err(|e| ConsensusError::Other(Box::new(e)))?;
+
+			use sp_block_builder::CheckInherentsError;
+
+			sp_block_builder::check_inherents_with_data(
+				self.client.clone(),
+				at_hash,
+				new_block.clone(),
+				&create_inherent_data_providers,
+				inherent_data,
+			)
+			.await
+			.map_err(|e| {
+				ConsensusError::Other(Box::new(match e {
+					CheckInherentsError::CreateInherentData(e) => {
+						Error::<Block>::CreateInherents(e)
+					},
+					CheckInherentsError::Client(e) => Error::RuntimeApi(e),
+					CheckInherentsError::CheckInherents(e) => Error::CheckInherents(e),
+					CheckInherentsError::CheckInherentsUnknownError(id) => {
+						Error::CheckInherentsUnhandled(id)
+					},
+				}))
+			})?;
+			let (_, inner_body) = new_block.deconstruct();
+			block.body = Some(inner_body);
+		}
+
+		Ok(())
+	}
+
+	async fn check_and_report_equivocation(
+		&self,
+		slot_now: Slot,
+		slot: Slot,
+		header: &Block::Header,
+		author: &AuthorityId,
+		origin: &BlockOrigin,
+	) -> Result<(), Error<Block>> {
+		// don't report any equivocations during initial sync
+		// as they are most likely stale.
+		if *origin == BlockOrigin::NetworkInitialSync {
+			return Ok(());
+		}
+
+		// check if authorship of this header is an equivocation and return a proof if so.
+		let Some(equivocation_proof) =
+			check_equivocation(&*self.client, slot_now, slot, header, author)
+				.map_err(Error::Client)?
+		else {
+			return Ok(());
+		};
+
+		info!(
+			"Slot author {:?} is equivocating at slot {} with headers {:?} and {:?}",
+			author,
+			slot,
+			equivocation_proof.first_header.hash(),
+			equivocation_proof.second_header.hash(),
+		);
+
+		// get the best block on which we will build and send the equivocation report.
+		let best_hash = self
+			.select_chain
+			.best_chain()
+			.await
+			.map(|h| h.hash())
+			.map_err(|e| Error::Client(e.into()))?;
+
+		// generate a key ownership proof. we start by trying to generate the
+		// key ownership proof at the parent of the equivocating header, this
+		// will make sure that proof generation is successful since it happens
+		// during the on-going session (i.e. session keys are available in the
+		// state to be able to generate the proof). this might fail if the
+		// equivocation happens on the first block of the session, in which case
+		// its parent would be on the previous session. if generation on the
+		// parent header fails we try with best block as well.
+		let generate_key_owner_proof = |at_hash: Block::Hash| {
+			self.client
+				.runtime_api()
+				.generate_key_ownership_proof(at_hash, equivocation_proof.offender.clone())
+				.map_err(Error::RuntimeApi)
+		};
+
+		let parent_hash = *header.parent_hash();
+		let key_owner_proof = match generate_key_owner_proof(parent_hash)? {
+			Some(proof) => proof,
+			None => match generate_key_owner_proof(best_hash)? {
+				Some(proof) => proof,
+				None => {
+					debug!(
+						target: LOG_TARGET,
+						"Equivocation offender is not part of the authority set."
+					);
+					return Ok(());
+				},
+			},
+		};
+
+		// submit equivocation report at best block.
+		let mut runtime_api = self.client.runtime_api();
+
+		// Register the offchain tx pool to be able to use it from the runtime.
+		runtime_api
+			.register_extension(self.offchain_tx_pool_factory.offchain_transaction_pool(best_hash));
+
+		runtime_api
+			.submit_report_equivocation_unsigned_extrinsic(
+				best_hash,
+				equivocation_proof,
+				key_owner_proof,
+			)
+			.map_err(Error::RuntimeApi)?;
+
+		info!(target: LOG_TARGET, "Submitted equivocation report for author {:?}", author);
+
+		Ok(())
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, Client, Inner, CIDP, SC> BlockImport<Block>
+	for SassafrasBlockImport<Block, Client, Inner, CIDP, SC>
+where
+	Block: BlockT,
+	Inner: BlockImport<Block> + Send + Sync,
+	Inner::Error: Into<ConsensusError>,
+	Client: HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ AuxStore
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync,
+	Client::Api: BlockBuilderApi<Block> + SassafrasApi<Block> + ApiExt<Block>,
+	CIDP: CreateInherentDataProviders<Block, ()>,
+	CIDP::InherentDataProviders: InherentDataProviderExt + Send + Sync,
+	SC: SelectChain<Block> + 'static,
+{
+	type Error = ConsensusError;
+
+	async fn import_block(
+		&self,
+		mut block: BlockImportParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		let hash = block.post_hash();
+		let number = *block.header.number();
+		let info = self.client.info();
+
+		self.check_inherents_and_equivocations(&mut block).await?;
+
+		let block_status = self
+			.client
+			.status(hash)
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+		// Skip Sassafras logic if block already in chain or importing blocks during initial sync,
+		// otherwise the check for epoch changes will error because trying to re-import an
+		// epoch change or because of missing epoch data in the tree, respectively.
+		if info.block_gap.map_or(false, |gap| gap.start <= number && number <= gap.end) ||
+			block_status == BlockStatus::InChain
+		{
+			// When re-importing existing block strip away intermediates.
+			// In case of initial sync intermediates should not be present...
+			let _ = block.remove_intermediate::<SassafrasIntermediate<Block>>(INTERMEDIATE_KEY);
+			block.fork_choice = Some(ForkChoiceStrategy::Custom(false));
+			return self.inner.import_block(block).await.map_err(Into::into);
+		}
+
+		if block.with_state() {
+			return self.import_state(block).await;
+		}
+
+		let slot_claim = find_slot_claim::<Block>(&block.header).expect(
+			"valid sassafras headers must contain a slot claim; header has been already \
+			 verified; qed",
+		);
+		let slot = slot_claim.slot;
+
+		let parent_hash = *block.header.parent_hash();
+		let parent_header = self
+			.client
+			.header(parent_hash)
+			.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?
+			.ok_or_else(|| {
+				ConsensusError::ChainLookup(
+					sassafras_err(Error::<Block>::ParentUnavailable(parent_hash, hash)).into(),
+				)
+			})?;
+
+		let parent_slot = find_slot::<Block>(&parent_header).expect(
+			"parent is non-genesis; valid Sassafras headers contain a slot claim; header has \
+			 already been verified; qed",
+		);
+
+		// make sure that slot number is strictly increasing
+		if slot <= parent_slot {
+			return Err(ConsensusError::ClientImport(
+				sassafras_err(Error::<Block>::SlotMustIncrease(parent_slot, slot)).into(),
+			));
+		}
+
+		// if there's a pending epoch we'll save the previous epoch changes here
+		// this way we can revert it if there's any error
+		let mut old_epoch_changes = None;
+
+		// Use an extra scope to make the compiler happy, because otherwise it complains about the
+		// mutex, even if we dropped it...
+		let mut epoch_changes = {
+			let mut epoch_changes = self.epoch_changes.shared_data_locked();
+
+			// check if there's any epoch change expected to happen at this slot.
+			// `epoch` is the epoch to verify the block under, and `first_in_epoch` is true
+			// if this is the first block in its chain for that epoch.
+			//
+			// also provides the total weight of the chain, including the imported block.
+			let (epoch_descriptor, first_in_epoch, parent_weight) = {
+				let parent_weight = if *parent_header.number() == Zero::zero() {
+					0
+				} else {
+					aux_schema::load_block_weight(&*self.client, parent_hash)
+						.map_err(|e| ConsensusError::ClientImport(e.to_string()))?
+						.ok_or_else(|| {
+							ConsensusError::ClientImport(
+								sassafras_err(Error::<Block>::ParentBlockNoAssociatedWeight(hash))
+									.into(),
+							)
+						})?
+				};
+
+				let intermediate =
+					block.remove_intermediate::<SassafrasIntermediate<Block>>(INTERMEDIATE_KEY)?;
+
+				let epoch_descriptor = intermediate.epoch_descriptor;
+				let first_in_epoch = parent_slot < epoch_descriptor.start_slot();
+				(epoch_descriptor, first_in_epoch, parent_weight)
+			};
+
+			// Only the blocks authored by mean of a ticket add weight to the chain.
+			let added_weight = slot_claim.ticket_claim.is_some() as SassafrasBlockWeight;
+			let total_weight = parent_weight + added_weight;
+
+			// search for this all the time so we can reject unexpected announcements.
+			let next_epoch_digest = find_next_epoch_digest::<Block>(&block.header)
+				.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+			match (first_in_epoch, next_epoch_digest.is_some()) {
+				(true, true) | (false, false) => {},
+				(true, false) => {
+					return Err(ConsensusError::ClientImport(
+						sassafras_err(Error::<Block>::ExpectedEpochChange(hash, slot)).into(),
+					))
+				},
+				(false, true) => {
+					return Err(ConsensusError::ClientImport(
+						sassafras_err(Error::<Block>::UnexpectedEpochChange).into(),
+					))
+				},
+			}
+
+			if let Some(next_epoch_descriptor) = next_epoch_digest {
+				old_epoch_changes = Some((*epoch_changes).clone());
+
+				let mut viable_epoch = epoch_changes
+					.viable_epoch(&epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))
+					.ok_or_else(|| {
+						ConsensusError::ClientImport(Error::<Block>::FetchEpoch(parent_hash).into())
+					})?
+					.into_cloned();
+
+				// restrict info logging during initial sync to avoid spam
+				let log_level = if block.origin == BlockOrigin::NetworkInitialSync {
+					log::Level::Debug
+				} else {
+					log::Level::Info
+				};
+
+				if viable_epoch.as_ref().end_slot() <= slot {
+					// Some epochs must have been skipped as our current slot fits outside the
+					// current epoch. We will figure out which epoch it belongs to and we will
+					// re-use the same data for that epoch. As done by the runtime, the tickets
+					// of the skipped epochs are discarded.
+					// We do not update the original epoch that will be re-used because there might
+					// be other forks (that we haven't imported) where the epoch isn't skipped, and
+					// to import those forks we want to keep the original epoch data.
+					let epoch = viable_epoch.as_mut();
+					let prev_index = epoch.index;
+					*epoch = epoch.clone_for_slot(slot);
+
+					warn!(
+						target: LOG_TARGET,
+						"🌳 Epoch(s) skipped: from {} to {}", prev_index, epoch.index,
+					);
+				}
+
+				log!(
+					target: LOG_TARGET,
+					log_level,
+					"🌳 New epoch {} launching at block {} (block slot {} >= start slot {}).",
+					viable_epoch.as_ref().index,
+					hash,
+					slot,
+					viable_epoch.as_ref().start,
+				);
+
+				let next_epoch = viable_epoch.increment(next_epoch_descriptor);
+
+				log!(
+					target: LOG_TARGET,
+					log_level,
+					"🌳 Next epoch starts at slot {}",
+					next_epoch.as_ref().start,
+				);
+
+				// prune the tree of epochs not part of the finalized chain or
+				// that are not live anymore, and then track the given epoch change
+				// in the tree.
+				// NOTE: it is important that these operations are done in this
+				// order, otherwise if pruning after import the `is_descendent_of`
+				// used by pruning may not know about the block that is being
+				// imported.
+				let prune_and_import = || {
+					prune_finalized(self.client.clone(), &mut epoch_changes)?;
+
+					epoch_changes
+						.import(
+							descendent_query(&*self.client),
+							hash,
+							number,
+							*block.header.parent_hash(),
+							next_epoch,
+						)
+						.map_err(|e| {
+							ConsensusError::ClientImport(format!(
+								"Error importing epoch changes: {}",
+								e
+							))
+						})?;
+					Ok(())
+				};
+
+				if let Err(e) = prune_and_import() {
+					debug!(target: LOG_TARGET, "Failed to launch next epoch: {}", e);
+					*epoch_changes =
+						old_epoch_changes.expect("set `Some` above and not taken; qed");
+					return Err(e);
+				}
+
+				aux_schema::write_epoch_changes::<Block, _, _>(&*epoch_changes, |insert| {
+					block
+						.auxiliary
+						.extend(insert.iter().map(|(k, v)| (k.to_vec(), Some(v.to_vec()))))
+				});
+			}
+
+			aux_schema::write_block_weight(hash, total_weight, |values| {
+				block
+					.auxiliary
+					.extend(values.iter().map(|(k, v)| (k.to_vec(), Some(v.to_vec()))))
+			});
+
+			// The fork choice rule is that we pick the heaviest chain (i.e.
+			// more ticket-backed blocks), if there's a tie we go with the longest
+			// chain.
+			block.fork_choice = {
+				let (last_best, last_best_number) = (info.best_hash, info.best_number);
+
+				let last_best_weight = if &last_best == block.header.parent_hash() {
+					// the parent=genesis case is already covered for loading parent weight,
+					// so we don't need to cover again here.
+					parent_weight
+				} else {
+					aux_schema::load_block_weight(&*self.client, last_best)
+						.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?
+						.ok_or_else(|| {
+							ConsensusError::ChainLookup(
+								"No block weight for parent header.".to_string(),
+							)
+						})?
+				};
+
+				Some(ForkChoiceStrategy::Custom(if total_weight > last_best_weight {
+					true
+				} else if total_weight == last_best_weight {
+					number > last_best_number
+				} else {
+					false
+				}))
+			};
+
+			// Release the mutex, but it stays locked
+			epoch_changes.release_mutex()
+		};
+
+		let import_result = self.inner.import_block(block).await;
+
+		// revert to the original epoch changes in case there's an error
+		// importing the block
+		if import_result.is_err() {
+			if let Some(old_epoch_changes) = old_epoch_changes {
+				*epoch_changes.upgrade() = old_epoch_changes;
+			}
+		}
+
+		import_result.map_err(Into::into)
+	}
+
+	async fn check_block(
+		&self,
+		block: BlockCheckParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		self.inner.check_block(block).await.map_err(Into::into)
+	}
+}
+
+/// Gets the best finalized block and its slot, and prunes the given epoch tree.
+fn prune_finalized<Block, Client>(
+	client: Arc<Client>,
+	epoch_changes: &mut EpochChangesFor<Block, Epoch>,
+) -> Result<(), ConsensusError>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>,
+{
+	let info = client.info();
+
+	let finalized_slot = {
+		let finalized_header = client
+			.header(info.finalized_hash)
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))?
+			.expect(
+				"best finalized hash was given by client; finalized headers must exist in db; qed",
+			);
+
+		find_slot::<Block>(&finalized_header)
+			.expect("finalized header must be valid; valid blocks have a slot claim; qed")
+	};
+
+	epoch_changes
+		.prune_finalized(
+			descendent_query(&*client),
+			&info.finalized_hash,
+			info.finalized_number,
+			finalized_slot,
+		)
+		.map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+	Ok(())
+}
+
+/// Produce a Sassafras block-import object to be used later on in the construction of
+/// an import-queue.
+///
+/// Also returns a link object used to correctly instantiate the import queue
+/// and background worker.
+pub fn block_import<Client, Block: BlockT, I, CIDP, SC>(
+	config: SassafrasConfiguration,
+	wrapped_block_import: I,
+	client: Arc<Client>,
+	create_inherent_data_providers: CIDP,
+	select_chain: SC,
+	offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+) -> ClientResult<(SassafrasBlockImport<Block, Client, I, CIDP, SC>, SassafrasLink<Block>)>
+where
+	Client: AuxStore
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ PreCommitActions<Block>
+		+ 'static,
+{
+	let epoch_changes = aux_schema::load_epoch_changes::<Block, _>(&*client)?;
+	let link = SassafrasLink { epoch_changes: epoch_changes.clone(), config: config.clone() };
+
+	prune_finalized(client.clone(), &mut epoch_changes.shared_data())?;
+
+	let client_weak = Arc::downgrade(&client);
+	let on_finality = move |summary: &FinalityNotification<Block>| {
+		if let Some(client) = client_weak.upgrade() {
+			aux_storage_cleanup(client.as_ref(), summary)
+		} else {
+			Default::default()
+		}
+	};
+	client.register_finality_action(Box::new(on_finality));
+
+	let import = SassafrasBlockImport::new(
+		client,
+		epoch_changes,
+		wrapped_block_import,
+		config,
+		create_inherent_data_providers,
+		select_chain,
+		offchain_tx_pool_factory,
+	);
+
+	Ok((import, link))
+}
+
+/// Parameters passed to [`import_queue`].
+pub struct ImportQueueParams<'a, Block: BlockT, BI, Client, Spawn> {
+	/// The Sassafras link that is created by [`block_import`].
+	pub link: SassafrasLink<Block>,
+	/// The block import that should be wrapped.
+	pub block_import: BI,
+	/// Optional justification import.
+	pub justification_import: Option<BoxJustificationImport<Block>>,
+	/// The client to interact with the internals of the node.
+	pub client: Arc<Client>,
+	/// Slot duration.
+	pub slot_duration: SlotDuration,
+	/// Spawner for spawning futures.
+	pub spawner: &'a Spawn,
+	/// Registry for prometheus metrics.
+	pub registry: Option<&'a Registry>,
+	/// Optional telemetry handle to report telemetry events.
+	pub telemetry: Option<TelemetryHandle>,
+}
+
+/// Start an import queue for the Sassafras consensus algorithm.
+///
+/// The block import object provided must be the `SassafrasBlockImport` or a wrapper
+/// of it, otherwise crucial import logic will be omitted.
+pub fn import_queue<Block: BlockT, Client, BI, Spawn>(
+	ImportQueueParams {
+		link: sassafras_link,
+		block_import,
+		justification_import,
+		client,
+		slot_duration,
+		spawner,
+		registry,
+		telemetry,
+	}: ImportQueueParams<'_, Block, BI, Client, Spawn>,
+) -> ClientResult<DefaultImportQueue<Block>>
+where
+	BI: BlockImport<Block, Error = ConsensusError> + Send + Sync + 'static,
+	Client: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ AuxStore
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: BlockBuilderApi<Block> + SassafrasApi<Block> + ApiExt<Block>,
+	Spawn: SpawnEssentialNamed,
+{
+	let verifier = SassafrasVerifier {
+		slot_duration,
+		config: sassafras_link.config,
+		epoch_changes: sassafras_link.epoch_changes,
+		telemetry,
+		client,
+	};
+
+	Ok(BasicQueue::new(verifier, Box::new(block_import), justification_import, spawner, registry))
+}
+
+/// Reverts protocol aux data to at most the last finalized block.
+/// In particular, epoch-changes and block weights announced after the revert
+/// point are removed.
+pub fn revert<Block, Client, Backend>(
+	client: Arc<Client>,
+	backend: Arc<Backend>,
+	blocks: NumberFor<Block>,
+) -> ClientResult<()>
+where
+	Block: BlockT,
+	Client: AuxStore
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ HeaderBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ UsageProvider<Block>,
+	Client::Api: SassafrasApi<Block>,
+	Backend: BackendT<Block>,
+{
+	let best_number = client.info().best_number;
+	let finalized = client.info().finalized_number;
+
+	let revertible = blocks.min(best_number - finalized);
+	if revertible == Zero::zero() {
+		return Ok(());
+	}
+
+	let revert_up_to_number = best_number - revertible;
+	let revert_up_to_hash = client.hash(revert_up_to_number)?.ok_or(ClientError::Backend(
+		format!("Unexpected hash lookup failure for block number: {}", revert_up_to_number),
+	))?;
+
+	// Revert epoch changes tree.
+
+	let epoch_changes = aux_schema::load_epoch_changes::<Block, Client>(&*client)?;
+	let mut epoch_changes = epoch_changes.shared_data();
+
+	if revert_up_to_number == Zero::zero() {
+		// Special case, no epoch changes data were present on genesis.
+		*epoch_changes = EpochChangesFor::<Block, Epoch>::default();
+	} else {
+		epoch_changes.revert(descendent_query(&*client), revert_up_to_hash, revert_up_to_number);
+	}
+
+	// Remove block weights added after the revert point.
+
+	let mut weight_keys = HashSet::with_capacity(revertible.saturated_into());
+
+	let leaves = backend.blockchain().leaves()?.into_iter().filter(|&leaf| {
+		sp_blockchain::tree_route(&*client, revert_up_to_hash, leaf)
+			.map(|route| route.retracted().is_empty())
+			.unwrap_or_default()
+	});
+
+	for leaf in leaves {
+		let mut hash = leaf;
+		loop {
+			let meta = client.header_metadata(hash)?;
+			if meta.number <= revert_up_to_number ||
+				!weight_keys.insert(aux_schema::block_weight_key(hash))
+			{
+				// We've reached the revert point or an already processed branch, stop here.
+				break;
+			}
+			hash = meta.parent;
+		}
+	}
+
+	let weight_keys: Vec<_> = weight_keys.iter().map(|val| val.as_slice()).collect();
+
+	// Write epoch changes and remove weights in one shot.
+	aux_schema::write_epoch_changes::<Block, _, _>(&epoch_changes, |values| {
+		client.insert_aux(values, weight_keys.iter())
+	})
+}
+
+fn query_epoch_changes<Block, Client>(
+	epoch_changes: &SharedEpochChanges<Block, Epoch>,
+	client: &Client,
+	config: &SassafrasConfiguration,
+	block_number: NumberFor<Block>,
+	slot: Slot,
+	parent_hash: Block::Hash,
+) -> Result<
+	(ViableEpochDescriptor<Block::Hash, NumberFor<Block>, Epoch>, ViableEpoch<Epoch>),
+	Error<Block>,
+>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>,
+{
+	let epoch_changes = epoch_changes.shared_data();
+	let epoch_descriptor = epoch_changes
+		.epoch_descriptor_for_child_of(
+			descendent_query(client),
+			&parent_hash,
+			block_number - 1u32.into(),
+			slot,
+		)
+		.map_err(|e| Error::<Block>::ForkTree(Box::new(e)))?
+		.ok_or(Error::<Block>::FetchEpoch(parent_hash))?;
+	let viable_epoch = epoch_changes
+		.viable_epoch(&epoch_descriptor, |slot| Epoch::genesis(config, slot))
+		.ok_or(Error::<Block>::FetchEpoch(parent_hash))?;
+	Ok((epoch_descriptor, viable_epoch.into_cloned()))
+}
@@ -0,0 +1,948 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Sassafras testsuite.
+//!
+//! A local network of validators, each one with its own keystore, runs the tickets lottery,
+//! then the slots of the epoch are claimed and verified as done by the authoring worker and
+//! the import queue. The on-chain tickets verification and assignment is mimicked following
+//! `pallet-sassafras`.
+//!
+//! The same lottery is then run against `pallet-sassafras` itself, as wired in
+//! `node-sassafras-runtime`: the tickets are submitted on-chain and the slots of the next epoch
+//! are claimed with the tickets assigned by the pallet.
+//!
+//! A test network of full peers then authors and imports blocks across epoch changes, using
+//! the `SassafrasApi` implementation of the substrate test runtime.
+
+use super::*;
+use authorship::{claim_slot, generate_epoch_tickets, EpochTickets};
+use node_sassafras_runtime as runtime;
+use sc_block_builder::BlockBuilderBuilder;
+use sc_client_api::BlockchainEvents;
+use sc_consensus::BoxBlockImport;
+use sc_network_test::{
+	BlockImportAdapter, FullPeerConfig, Hash, Peer, PeersClient, PeersFullClient, TestNetFactory,
+};
+use sc_transaction_pool_api::RejectAllTxPool;
+use sp_consensus::{NoNetwork as DummyOracle, Proposal, ProposeArgs};
+use sp_consensus_sassafras::{
+	ticket_id_threshold,
+	vrf::{self, RingContext},
+	EpochConfiguration,
+};
+use sp_core::{
+	crypto::{VrfPublic, Wraps},
+	storage::Storage,
+	Pair, H256,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore};
+use sp_runtime::{generic, traits::BlakeTwo256, BuildStorage, Digest, OpaqueExtrinsic};
+use std::task::Poll;
+use substrate_test_runtime_client::{
+	runtime::SASSAFRAS_EPOCH_KEY, BlockBuilderExt, DefaultTestClientBuilderExt, TestClientBuilder,
+	TestClientBuilderExt,
+};
+
+type TestBlock = generic::Block<generic::Header<u64, BlakeTwo256>, OpaqueExtrinsic>;
+
+type TestNetBlock = sc_network_test::Block;
+
+const EPOCH_LENGTH: u32 = 6;
+
+struct Validator {
+	keystore: KeystorePtr,
+	tickets: EpochTickets,
+}
+
+fn make_validators(count: usize) -> (Vec<Validator>, Vec<AuthorityId>) {
+	(0..count)
+		.map(|i| {
+			let keystore: KeystorePtr = MemoryKeystore::new().into();
+			let public = keystore
+				.bandersnatch_generate_new(AuthorityId::ID, Some(&format!("//Validator{}", i)))
+				.unwrap();
+			(Validator { keystore, tickets: Default::default() }, public.into())
+		})
+		.unzip()
+}
+
+fn make_epoch(authorities: Vec<AuthorityId>) -> Epoch {
+	sp_consensus_sassafras::Epoch {
+		index: 1,
+		start: 100.into(),
+		length: EPOCH_LENGTH,
+		randomness: [7; 32],
+		authorities,
+		config: EpochConfiguration { redundancy_factor: 1, attempts_number: 2 },
+	}
+	.into()
+}
+
+// Verify the submitted tickets and assign them to the epoch slots, as done by the runtime.
+fn assign_tickets(
+	epoch: &Epoch,
+	ring_context: &RingContext,
+	envelopes: Vec<TicketEnvelope>,
+) -> Vec<Option<(TicketId, TicketBody)>> {
+	let public_keys: Vec<_> = epoch.authorities.iter().map(|a| *a.as_inner_ref()).collect();
+	let verifier = ring_context.verifier(&public_keys);
+	let threshold = ticket_id_threshold(
+		epoch.config.redundancy_factor,
+		epoch.length,
+		epoch.config.attempts_number,
+		epoch.authorities.len() as u32,
+	);
+
+	let mut tickets: Vec<_> = envelopes
+		.into_iter()
+		.filter_map(|envelope| {
+			let ticket_id = vrf::make_ticket_id(&envelope.signature.pre_output);
+			let input =
+				vrf::ticket_id_input(&epoch.randomness, envelope.body.attempt_idx, epoch.index);
+			let sign_data = vrf::ticket_body_sign_data(&envelope.body, input);
+			(ticket_id < threshold && envelope.signature.ring_vrf_verify(&sign_data, &verifier))
+				.then_some((ticket_id, envelope.body))
+		})
+		.collect();
+	tickets.sort_by_key(|(ticket_id, _)| *ticket_id);
+	tickets.truncate(epoch.length as usize);
+
+	// "Outside-in" assignment.
+	(0..epoch.length)
+		.map(|slot_idx| {
+			let ticket_idx = if slot_idx < epoch.length / 2 {
+				2 * slot_idx + 1
+			} else {
+				2 * (epoch.length - (slot_idx + 1))
+			};
+			tickets.get(ticket_idx as usize).cloned()
+		})
+		.collect()
+}
+
+fn sign_pre_hash(
+	validator: &Validator,
+	author: &AuthorityId,
+	pre_hash: H256,
+) -> AuthoritySignature {
+	validator
+		.keystore
+		.bandersnatch_sign(AuthorityId::ID, author.as_ref(), pre_hash.as_ref())
+		.unwrap()
+		.unwrap()
+		.into()
+}
+
+#[test]
+fn epoch_increment_and_skip_works() {
+	let (_, authorities) = make_validators(2);
+	let epoch = make_epoch(authorities.clone());
+
+	let descriptor = NextEpochDescriptor {
+		randomness: [9; 32],
+		authorities: authorities[..1].to_vec(),
+		config: None,
+	};
+	let next = epoch.increment(descriptor);
+	assert_eq!(next.index, 2);
+	assert_eq!(next.start, epoch.end_slot());
+	assert_eq!(next.randomness, [9; 32]);
+	assert_eq!(next.authorities.len(), 1);
+	assert_eq!(next.config, epoch.config);
+
+	let config = EpochConfiguration { redundancy_factor: 2, attempts_number: 4 };
+	let descriptor = NextEpochDescriptor { randomness: [9; 32], authorities, config: Some(config) };
+	assert_eq!(epoch.increment(descriptor).config, config);
+
+	// Two epochs skipped.
+	let slot = epoch.start + 3 * EPOCH_LENGTH as u64 + 1;
+	let skipped = epoch.clone_for_slot(slot);
+	assert_eq!(skipped.index, 4);
+	assert_eq!(skipped.start, epoch.start + 3 * EPOCH_LENGTH as u64);
+	assert_eq!(epoch.index_for_slot(slot), 4);
+	assert_eq!(epoch.index_for_slot(epoch.start + 1), epoch.index);
+}
+
+#[test]
+fn fallback_author_selection_works() {
+	let randomness = [3; 32];
+	let mut hits = [0; 4];
+	for slot in 0..1000u64 {
+		let idx = authorship::fallback_authority_index(&randomness, slot.into(), 4).unwrap();
+		hits[idx as usize] += 1;
+	}
+	// Every authority is picked a fair share of the slots.
+	assert!(hits.iter().all(|hits| *hits > 150), "{:?}", hits);
+
+	assert!(authorship::fallback_authority_index(&randomness, 0.into(), 0).is_none());
+}
+
+#[test]
+fn multi_validator_epoch_works() {
+	sp_tracing::try_init_simple();
+
+	let (mut validators, authorities) = make_validators(4);
+	let epoch = make_epoch(authorities.clone());
+	let ring_context = RingContext::new_testing();
+
+	// Every validator generates and submits its tickets.
+	let mut envelopes = Vec::new();
+	for validator in validators.iter_mut() {
+		for (ticket_id, envelope, secret) in
+			generate_epoch_tickets(&epoch, &ring_context, &validator.keystore)
+		{
+			validator.tickets.entry(epoch.index).or_default().insert(ticket_id, secret);
+			envelopes.push(envelope);
+		}
+	}
+	assert!(!envelopes.is_empty());
+
+	let assignment = assign_tickets(&epoch, &ring_context, envelopes);
+	assert!(assignment.iter().any(Option::is_some));
+
+	for (slot_idx, maybe_ticket) in assignment.into_iter().enumerate() {
+		let slot = epoch.start + slot_idx as u64;
+
+		// Exactly one validator is able to claim each slot.
+		let claims: Vec<_> = validators
+			.iter()
+			.filter_map(|validator| {
+				claim_slot(
+					slot,
+					&epoch,
+					maybe_ticket.clone(),
+					&validator.tickets,
+					&validator.keystore,
+				)
+				.map(|claim| (validator, claim))
+			})
+			.collect();
+		assert_eq!(claims.len(), 1, "slot {} claimed {} times", slot, claims.len());
+
+		let (validator, (slot_claim, author)) = &claims[0];
+		assert_eq!(slot_claim.slot, slot);
+		assert_eq!(slot_claim.ticket_claim.is_some(), maybe_ticket.is_some());
+		assert_eq!(&authorities[slot_claim.authority_idx as usize], author);
+
+		let pre_hash = H256::repeat_byte(slot_idx as u8);
+		let signature = sign_pre_hash(validator, author, pre_hash);
+
+		verification::check_slot_claim::<TestBlock>(
+			pre_hash,
+			slot_claim,
+			&signature,
+			&epoch,
+			maybe_ticket.clone(),
+		)
+		.unwrap();
+
+		// The claim doesn't hold if the slot assignment is different.
+		let other_ticket = match maybe_ticket {
+			Some(_) => None,
+			None => {
+				let (erased_pair, _) = sp_core::ed25519::Pair::generate();
+				Some((
+					0,
+					TicketBody {
+						attempt_idx: 0,
+						erased_public: erased_pair.public(),
+						revealed_public: erased_pair.public(),
+					},
+				))
+			},
+		};
+		assert!(verification::check_slot_claim::<TestBlock>(
+			pre_hash,
+			slot_claim,
+			&signature,
+			&epoch,
+			other_ticket,
+		)
+		.is_err());
+
+		// Nor if the header is not the one which has been signed.
+		assert!(matches!(
+			verification::check_slot_claim::<TestBlock>(
+				H256::repeat_byte(0xff),
+				slot_claim,
+				&signature,
+				&epoch,
+				maybe_ticket,
+			),
+			Err(Error::BadSignature(_))
+		));
+	}
+}
+
+#[test]
+fn forged_ticket_claims_are_rejected() {
+	let (validators, authorities) = make_validators(2);
+	let epoch = make_epoch(authorities);
+	let ring_context = RingContext::new_testing();
+
+	let (ticket_id, envelope, secret) =
+		generate_epoch_tickets(&epoch, &ring_context, &validators[0].keystore)
+			.into_iter()
+			.next()
+			.expect("with two validators every attempt is below threshold; qed");
+	let maybe_ticket = Some((ticket_id, envelope.body));
+	let slot = epoch.start;
+
+	// The second validator doesn't own the ticket.
+	assert!(claim_slot(
+		slot,
+		&epoch,
+		maybe_ticket.clone(),
+		&Default::default(),
+		&validators[1].keystore
+	)
+	.is_none());
+
+	let mut tickets = EpochTickets::default();
+	tickets.entry(epoch.index).or_default().insert(ticket_id, secret);
+	let (slot_claim, author) =
+		claim_slot(slot, &epoch, maybe_ticket.clone(), &tickets, &validators[0].keystore).unwrap();
+
+	let pre_hash = H256::repeat_byte(1);
+	let signature = sign_pre_hash(&validators[0], &author, pre_hash);
+	verification::check_slot_claim::<TestBlock>(
+		pre_hash,
+		&slot_claim,
+		&signature,
+		&epoch,
+		maybe_ticket.clone(),
+	)
+	.unwrap();
+
+	// The claim is bound to the authority which signed the header.
+	let mut forged = slot_claim.clone();
+	forged.authority_idx = 1;
+	assert!(matches!(
+		verification::check_slot_claim::<TestBlock>(
+			pre_hash,
+			&forged,
+			&signature,
+			&epoch,
+			maybe_ticket.clone(),
+		),
+		Err(Error::BadSignature(_))
+	));
+
+	// A tampered ticket claim signature is rejected.
+	let mut tampered = slot_claim;
+	let ticket_claim = tampered.ticket_claim.as_mut().unwrap();
+	AsMut::<[u8]>::as_mut(&mut ticket_claim.erased_signature)[0] ^= 1;
+	assert!(matches!(
+		verification::check_slot_claim::<TestBlock>(
+			pre_hash,
+			&tampered,
+			&signature,
+			&epoch,
+			maybe_ticket,
+		),
+		Err(Error::TicketClaimVerificationFailed(_))
+	));
+}
+
+#[test]
+fn slot_claim_vrf_is_bound_to_epoch() {
+	let (validators, authorities) = make_validators(1);
+	let epoch = make_epoch(authorities);
+	let slot = epoch.start + 1;
+
+	let (slot_claim, author) =
+		claim_slot(slot, &epoch, None, &Default::default(), &validators[0].keystore).unwrap();
+
+	let data = vrf::slot_claim_sign_data(&epoch.randomness, slot, epoch.index);
+	assert!(author.as_inner_ref().vrf_verify(&data, &slot_claim.vrf_signature));
+
+	let data = vrf::slot_claim_sign_data(&epoch.randomness, slot, epoch.index + 1);
+	assert!(!author.as_inner_ref().vrf_verify(&data, &slot_claim.vrf_signature));
+}
+
+// Claim the slot with every validator, checking that exactly one of them succeeds.
+fn claim_slot_once<'a>(
+	validators: &'a [Validator],
+	slot: Slot,
+	epoch: &Epoch,
+	maybe_ticket: Option<(TicketId, TicketBody)>,
+) -> (&'a Validator, (SlotClaim, AuthorityId)) {
+	let mut claims: Vec<_> = validators
+		.iter()
+		.filter_map(|validator| {
+			claim_slot(slot, epoch, maybe_ticket.clone(), &validator.tickets, &validator.keystore)
+				.map(|claim| (validator, claim))
+		})
+		.collect();
+	assert_eq!(claims.len(), 1, "slot {} claimed {} times", slot, claims.len());
+	claims.pop().unwrap()
+}
+
+// Author a block on top of `parent` executing it with `node-sassafras-runtime`, then seal it
+// as done by the authoring worker.
+fn author_runtime_block(
+	parent: &runtime::Header,
+	(validator, (slot_claim, author)): (&Validator, (SlotClaim, AuthorityId)),
+	extrinsics: Vec<runtime::UncheckedExtrinsic>,
+) -> runtime::Header {
+	let header = runtime::Header::new(
+		parent.number + 1,
+		Default::default(),
+		Default::default(),
+		parent.hash(),
+		Digest { logs: vec![DigestItem::from(&slot_claim)] },
+	);
+	runtime::Executive::initialize_block(&header);
+
+	let timestamp = runtime::TimestampCall::set { now: *slot_claim.slot * runtime::SLOT_DURATION };
+	for extrinsic in
+		std::iter::once(runtime::UncheckedExtrinsic::new_bare(timestamp.into())).chain(extrinsics)
+	{
+		runtime::Executive::apply_extrinsic(extrinsic).unwrap().unwrap();
+	}
+
+	let mut header = runtime::Executive::finalize_block();
+	let signature = sign_pre_hash(validator, &author, header.hash());
+	header.digest_mut().push(DigestItem::from(&signature));
+	header
+}
+
+// Verify a block authored by `author_runtime_block` as done by the import queue.
+fn check_runtime_header(
+	header: &runtime::Header,
+	epoch: &Epoch,
+	maybe_ticket: Option<(TicketId, TicketBody)>,
+) {
+	let slot_claim = find_slot_claim::<runtime::Block>(header).unwrap();
+	let checked = verification::check_header::<runtime::Block>(verification::VerificationParams {
+		header: header.clone(),
+		slot_now: slot_claim.slot,
+		slot_claim,
+		maybe_ticket,
+		epoch,
+	})
+	.unwrap();
+	assert!(matches!(checked, CheckedHeader::Checked(..)));
+}
+
+#[test]
+fn runtime_ticket_claims_work() {
+	sp_tracing::try_init_simple();
+
+	let (mut validators, authorities) = make_validators(4);
+	let epoch_config = EpochConfiguration { redundancy_factor: 1, attempts_number: 2 };
+	let mut ext: sp_io::TestExternalities = runtime::RuntimeGenesisConfig {
+		sassafras: runtime::SassafrasConfig { authorities, epoch_config, ..Default::default() },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap()
+	.into();
+
+	ext.execute_with(|| {
+		let epoch_length = runtime::EPOCH_DURATION_IN_SLOTS as u64;
+		let config = SassafrasConfiguration {
+			slot_duration: SlotDuration::from_millis(runtime::SLOT_DURATION),
+			genesis_epoch: runtime::Sassafras::current_epoch(),
+		};
+		let genesis = runtime::Header::new(
+			0,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+
+		// The genesis epoch starts at the slot of the first block, which is claimed by the
+		// fallback authority as there are no tickets yet.
+		let slot = Slot::from(100);
+		let genesis_epoch = Epoch::genesis(&config, slot);
+		let claim = claim_slot_once(&validators, slot, &genesis_epoch, None);
+		let block1 = author_runtime_block(&genesis, claim, vec![]);
+		check_runtime_header(&block1, &genesis_epoch, None);
+
+		// Every validator generates its tickets for the next epoch using the ring context
+		// stored on-chain, then the tickets are submitted in the first half of the epoch.
+		let next_epoch: Epoch = runtime::Sassafras::next_epoch().into();
+		assert_eq!(next_epoch.start, slot + epoch_length);
+		let ring_context = runtime::Sassafras::ring_context().expect("built at genesis; qed");
+		let mut envelopes = Vec::new();
+		for validator in validators.iter_mut() {
+			for (ticket_id, envelope, secret) in
+				generate_epoch_tickets(&next_epoch, &ring_context, &validator.keystore)
+			{
+				validator.tickets.entry(next_epoch.index).or_default().insert(ticket_id, secret);
+				envelopes.push(envelope);
+			}
+		}
+		assert!(!envelopes.is_empty());
+		let tickets_count = envelopes.len();
+
+		let submit_tickets = runtime::UncheckedExtrinsic::new_bare(
+			runtime::SassafrasCall::submit_tickets {
+				tickets: sp_runtime::BoundedVec::truncate_from(envelopes),
+			}
+			.into(),
+		);
+		let claim = claim_slot_once(&validators, slot + 1, &genesis_epoch, None);
+		let block2 = author_runtime_block(&block1, claim, vec![submit_tickets]);
+		check_runtime_header(&block2, &genesis_epoch, None);
+
+		// The tickets are sorted during the second half of the epoch.
+		let slot = slot + epoch_length / 2 + 1;
+		let claim = claim_slot_once(&validators, slot, &genesis_epoch, None);
+		let block3 = author_runtime_block(&block2, claim, vec![]);
+		check_runtime_header(&block3, &genesis_epoch, None);
+
+		// Every submitted ticket has been assigned to a slot of the next epoch, and each slot
+		// can only be claimed by the owner of its ticket, or by the fallback authority.
+		let mut ticketed_slots = Vec::new();
+		for slot in *next_epoch.start..*next_epoch.end_slot() {
+			let slot = Slot::from(slot);
+			let maybe_ticket = runtime::Sassafras::slot_ticket(slot);
+			let (validator, (slot_claim, author)) =
+				claim_slot_once(&validators, slot, &next_epoch, maybe_ticket.clone());
+			assert_eq!(slot_claim.ticket_claim.is_some(), maybe_ticket.is_some());
+			if let Some((ticket_id, _)) = maybe_ticket {
+				assert!(validator.tickets[&next_epoch.index].contains_key(&ticket_id));
+				ticketed_slots.push(slot);
+			}
+
+			let pre_hash = H256::from_low_u64_be(*slot);
+			let signature = sign_pre_hash(validator, &author, pre_hash);
+			verification::check_slot_claim::<runtime::Block>(
+				pre_hash,
+				&slot_claim,
+				&signature,
+				&next_epoch,
+				maybe_ticket,
+			)
+			.unwrap();
+		}
+		assert_eq!(ticketed_slots.len(), tickets_count);
+
+		// A ticket-backed block enacts the next epoch, whose tickets are then served as the
+		// current epoch ones.
+		let slot = ticketed_slots[0];
+		let maybe_ticket = runtime::Sassafras::slot_ticket(slot);
+		let claim = claim_slot_once(&validators, slot, &next_epoch, maybe_ticket.clone());
+		let block4 = author_runtime_block(&block3, claim, vec![]);
+		check_runtime_header(&block4, &next_epoch, maybe_ticket.clone());
+		assert_eq!(runtime::Sassafras::epoch_index(), next_epoch.index);
+		assert_eq!(runtime::Sassafras::slot_ticket(slot), maybe_ticket);
+	});
+}
+
+/// Slot inherent data provider, the test runtime doesn't check any inherent.
+struct SlotInherentDataProvider(Slot);
+
+impl Deref for SlotInherentDataProvider {
+	type Target = Slot;
+
+	fn deref(&self) -> &Slot {
+		&self.0
+	}
+}
+
+#[async_trait::async_trait]
+impl InherentDataProvider for SlotInherentDataProvider {
+	async fn provide_inherent_data(
+		&self,
+		_inherent_data: &mut sp_inherents::InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		Ok(())
+	}
+
+	async fn try_handle_error(
+		&self,
+		_identifier: &sp_inherents::InherentIdentifier,
+		_error: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		None
+	}
+}
+
+type SassafrasCreateInherentDataProviders = Arc<
+	dyn CreateInherentDataProviders<
+		TestNetBlock,
+		(),
+		InherentDataProviders = (SlotInherentDataProvider,),
+	>,
+>;
+
+type TestBlockImport = PanickingBlockImport<
+	SassafrasBlockImport<
+		TestNetBlock,
+		PeersFullClient,
+		Arc<PeersFullClient>,
+		SassafrasCreateInherentDataProviders,
+		sc_consensus::LongestChain<substrate_test_runtime_client::Backend, TestNetBlock>,
+	>,
+>;
+
+#[derive(Clone)]
+struct DummyFactory {
+	client: Arc<PeersFullClient>,
+	epoch_changes: SharedEpochChanges<TestNetBlock, Epoch>,
+	authorities: Vec<AuthorityId>,
+}
+
+struct DummyProposer {
+	factory: DummyFactory,
+	parent_hash: Hash,
+}
+
+impl Environment<TestNetBlock> for DummyFactory {
+	type CreateProposer = future::Ready<Result<DummyProposer, ClientError>>;
+	type Proposer = DummyProposer;
+	type Error = ClientError;
+
+	fn init(&mut self, parent_header: &<TestNetBlock as BlockT>::Header) -> Self::CreateProposer {
+		future::ready(Ok(DummyProposer {
+			factory: self.clone(),
+			parent_hash: parent_header.hash(),
+		}))
+	}
+}
+
+impl DummyProposer {
+	fn propose_with(&self, inherent_digests: Digest) -> ClientResult<Proposal<TestNetBlock>> {
+		let client = &*self.factory.client;
+		let parent_header = client.header(self.parent_hash)?.expect("parent is in the chain");
+		let parent_slot = find_slot::<TestNetBlock>(&parent_header).expect("parent is valid");
+		let slot = inherent_digests
+			.logs()
+			.iter()
+			.find_map(|log| SlotClaim::try_from(log).ok())
+			.expect("slot claim is provided by the authoring worker")
+			.slot;
+
+		let epoch_descriptor = self
+			.factory
+			.epoch_changes
+			.shared_data()
+			.epoch_descriptor_for_child_of(
+				descendent_query(client),
+				&self.parent_hash,
+				*parent_header.number(),
+				slot,
+			)
+			.expect("epoch changes tree is consistent")
+			.expect("slot has been claimed for this epoch");
+
+		let mut block_builder = BlockBuilderBuilder::new(client)
+			.on_parent_block(self.parent_hash)
+			.with_parent_block_number(*parent_header.number())
+			.with_inherent_digests(inherent_digests)
+			.build()?;
+
+		// The test runtime doesn't run Sassafras, the next epoch is announced by the first
+		// block of each epoch as done by `pallet-sassafras`.
+		if parent_slot < epoch_descriptor.start_slot() {
+			let next_epoch = NextEpochDescriptor {
+				randomness: sp_crypto_hashing::blake2_256(&slot.encode()),
+				authorities: self.factory.authorities.clone(),
+				config: None,
+			};
+			block_builder.push_deposit_log_digest_item(DigestItem::Consensus(
+				SASSAFRAS_ENGINE_ID,
+				ConsensusLog::NextEpochData(next_epoch).encode(),
+			))?;
+		}
+
+		let built_block = block_builder.build()?;
+
+		Ok(Proposal { block: built_block.block, storage_changes: built_block.storage_changes })
+	}
+}
+
+impl Proposer<TestNetBlock> for DummyProposer {
+	type Error = ClientError;
+	type Proposal = future::Ready<ClientResult<Proposal<TestNetBlock>>>;
+
+	fn propose(self, args: ProposeArgs<TestNetBlock>) -> Self::Proposal {
+		future::ready(self.propose_with(args.inherent_digests))
+	}
+}
+
+#[derive(Clone)]
+struct PanickingBlockImport<B>(B);
+
+#[async_trait::async_trait]
+impl<BI> BlockImport<TestNetBlock> for PanickingBlockImport<BI>
+where
+	BI: BlockImport<TestNetBlock> + Send + Sync,
+{
+	type Error = BI::Error;
+
+	async fn import_block(
+		&self,
+		block: BlockImportParams<TestNetBlock>,
+	) -> Result<ImportResult, Self::Error> {
+		Ok(self.0.import_block(block).await.expect("importing block failed"))
+	}
+
+	async fn check_block(
+		&self,
+		block: BlockCheckParams<TestNetBlock>,
+	) -> Result<ImportResult, Self::Error> {
+		Ok(self.0.check_block(block).await.expect("checking block failed"))
+	}
+}
+
+struct PeerData {
+	link: SassafrasLink<TestNetBlock>,
+	block_import: Mutex<Option<BoxBlockImport<TestNetBlock>>>,
+}
+
+type SassafrasPeer = Peer<Option<PeerData>, TestBlockImport>;
+
+#[derive(Default)]
+struct SassafrasTestNet {
+	peers: Vec<SassafrasPeer>,
+}
+
+impl SassafrasTestNet {
+	/// Create a network of `n` peers, sharing a genesis state with the given epoch.
+	fn with_genesis_epoch(n: usize, epoch: &sp_consensus_sassafras::Epoch) -> Self {
+		let mut net = Self::default();
+		for _ in 0..n {
+			let mut storage = Storage::default();
+			storage.top.insert(SASSAFRAS_EPOCH_KEY.to_vec(), epoch.encode());
+			net.add_full_peer_with_config(FullPeerConfig {
+				extra_storage: Some(storage),
+				..Default::default()
+			});
+		}
+		net
+	}
+}
+
+impl TestNetFactory for SassafrasTestNet {
+	type Verifier = SassafrasVerifier<TestNetBlock, PeersFullClient>;
+	type PeerData = Option<PeerData>;
+	type BlockImport = TestBlockImport;
+
+	fn make_block_import(
+		&self,
+		client: PeersClient,
+	) -> (
+		BlockImportAdapter<Self::BlockImport>,
+		Option<BoxJustificationImport<TestNetBlock>>,
+		Option<PeerData>,
+	) {
+		let client = client.as_client();
+
+		let config = crate::configuration(&*client).expect("config available");
+		let slot_duration = config.slot_duration;
+		let (_, longest_chain) = TestClientBuilder::new().build_with_longest_chain();
+		let (block_import, link) = crate::block_import(
+			config,
+			client.clone(),
+			client.clone(),
+			Arc::new(move |_, _| async move {
+				let slot = Slot::from_timestamp(Timestamp::current(), slot_duration);
+				Ok((SlotInherentDataProvider(slot),))
+			}) as SassafrasCreateInherentDataProviders,
+			longest_chain,
+			OffchainTransactionPoolFactory::new(RejectAllTxPool::default()),
+		)
+		.expect("can initialize block-import");
+
+		let block_import = PanickingBlockImport(block_import);
+
+		let data_block_import =
+			Mutex::new(Some(Box::new(block_import.clone()) as BoxBlockImport<_>));
+		(
+			BlockImportAdapter::new(block_import),
+			None,
+			Some(PeerData { link, block_import: data_block_import }),
+		)
+	}
+
+	fn make_verifier(&self, client: PeersClient, maybe_link: &Option<PeerData>) -> Self::Verifier {
+		let data = maybe_link.as_ref().expect("link always provided to verifier instantiation");
+
+		SassafrasVerifier {
+			client: client.as_client(),
+			slot_duration: data.link.config.slot_duration,
+			config: data.link.config.clone(),
+			epoch_changes: data.link.epoch_changes.clone(),
+			telemetry: None,
+		}
+	}
+
+	fn peer(&mut self, i: usize) -> &mut SassafrasPeer {
+		&mut self.peers[i]
+	}
+
+	fn peers(&self) -> &Vec<SassafrasPeer> {
+		&self.peers
+	}
+
+	fn peers_mut(&mut self) -> &mut Vec<SassafrasPeer> {
+		&mut self.peers
+	}
+
+	fn mut_peers<F: FnOnce(&mut Vec<SassafrasPeer>)>(&mut self, closure: F) {
+		closure(&mut self.peers);
+	}
+}
+
+#[tokio::test]
+async fn authoring_and_import_across_epochs_works() {
+	sp_tracing::try_init_simple();
+
+	let (keystores, authorities): (Vec<KeystorePtr>, Vec<AuthorityId>) =
+		["//Alice", "//Bob", "//Charlie"]
+			.into_iter()
+			.map(|seed| {
+				let keystore: KeystorePtr = MemoryKeystore::new().into();
+				let public =
+					keystore.bandersnatch_generate_new(AuthorityId::ID, Some(seed)).unwrap();
+				(keystore, public.into())
+			})
+			.unzip();
+
+	let genesis_epoch = sp_consensus_sassafras::Epoch {
+		index: 0,
+		start: 0.into(),
+		length: EPOCH_LENGTH,
+		randomness: [0; 32],
+		authorities: authorities.clone(),
+		config: Default::default(),
+	};
+
+	let net = SassafrasTestNet::with_genesis_epoch(keystores.len(), &genesis_epoch);
+	let net = Arc::new(Mutex::new(net));
+
+	// Blocks are authored in consecutive slots starting from slot 1, so this block number is
+	// reached after two epoch changes.
+	let target_number = 2 * EPOCH_LENGTH as u64 + 1;
+
+	let mut import_notifications = Vec::new();
+	let mut sassafras_futures = Vec::new();
+
+	for (peer_id, keystore) in keystores.into_iter().enumerate() {
+		let mut net = net.lock();
+		let peer = net.peer(peer_id);
+		let client = peer.client().as_client();
+		let select_chain = peer.select_chain().expect("full client has select_chain");
+		let data = peer.data.as_ref().expect("link set up during initialization");
+
+		let environ = DummyFactory {
+			client: client.clone(),
+			epoch_changes: data.link.epoch_changes.clone(),
+			authorities: authorities.clone(),
+		};
+
+		let mut got_own = false;
+		let mut got_other = false;
+
+		import_notifications.push(
+			// Run until the target block is reached and we have seen at least one block of our
+			// own and one of another peer.
+			client
+				.import_notification_stream()
+				.take_while(move |n| {
+					future::ready(
+						n.header.number() < &target_number || {
+							if n.origin == BlockOrigin::Own {
+								got_own = true;
+							} else {
+								got_other = true;
+							}
+							!(got_own && got_other)
+						},
+					)
+				})
+				.for_each(|_| future::ready(())),
+		);
+
+		let client_clone = client.clone();
+		sassafras_futures.push(
+			start_sassafras(SassafrasParams {
+				keystore,
+				client,
+				select_chain,
+				env: environ,
+				block_import: data.block_import.lock().take().expect("import set up during init"),
+				sync_oracle: DummyOracle,
+				justification_sync_link: (),
+				create_inherent_data_providers: move |parent, _| {
+					// Build on the slot following the parent one, to not depend on the wall
+					// clock while all the peers are polled from the same future.
+					let parent_header = client_clone.header(parent).ok().flatten().unwrap();
+					let slot = find_slot::<TestNetBlock>(&parent_header).unwrap() + 1;
+
+					async move { Ok((SlotInherentDataProvider(slot),)) }
+				},
+				force_authoring: false,
+				backoff_authoring_blocks: None::<()>,
+				sassafras_link: data.link.clone(),
+				offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
+					RejectAllTxPool::default(),
+				),
+				block_proposal_slot_portion: SlotProportion::new(0.5),
+				max_block_proposal_slot_portion: None,
+				telemetry: None,
+			})
+			.expect("starts sassafras"),
+		);
+	}
+
+	let net_clone = net.clone();
+	future::select(
+		future::poll_fn(move |cx| {
+			let mut net = net_clone.lock();
+			net.poll(cx);
+			for p in net.peers() {
+				if let Some((h, e)) = p.failed_verifications().into_iter().next() {
+					panic!("Verification failed for {:?}: {}", h, e);
+				}
+			}
+
+			Poll::<()>::Pending
+		}),
+		future::select(future::join_all(import_notifications), future::join_all(sassafras_futures)),
+	)
+	.await;
+
+	// Every peer tracks the epochs announced by the imported blocks.
+	for peer in net.lock().peers() {
+		let client = peer.client().as_client();
+		let link = &peer.data.as_ref().expect("link set up during initialization").link;
+		let best = client.header(client.info().best_hash).unwrap().unwrap();
+		let epoch_changes = link.epoch_changes.shared_data();
+		let epoch_descriptor = epoch_changes
+			.epoch_descriptor_for_child_of(
+				descendent_query(&*client),
+				&best.hash(),
+				*best.number(),
+				find_slot::<TestNetBlock>(&best).unwrap() + 1,
+			)
+			.unwrap()
+			.unwrap();
+		let epoch = epoch_changes
+			.viable_epoch(&epoch_descriptor, |slot| Epoch::genesis(&link.config, slot))
+			.unwrap();
+		assert!(epoch.as_ref().index >= 2);
+		assert_eq!(epoch.as_ref().authorities, authorities);
+	}
+}
@@ -0,0 +1,152 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Verification for Sassafras headers.
+use crate::{
+	authorship::{fallback_authority_index, ticket_claim_message},
+	sassafras_err, BlockT, Epoch, Error, LOG_TARGET,
+};
+use log::{debug, trace};
+use sc_consensus_slots::CheckedHeader;
+use sp_consensus_sassafras::{
+	digests::SlotClaim, vrf, AuthorityPair, AuthoritySignature, TicketBody, TicketId,
+};
+use sp_consensus_slots::Slot;
+use sp_core::{
+	crypto::{VrfPublic, Wraps},
+	ed25519, Pair,
+};
+use sp_runtime::{traits::Header, DigestItem};
+
+/// Sassafras verification parameters
+pub(super) struct VerificationParams<'a, B: 'a + BlockT> {
+	/// The header being verified.
+	pub(super) header: B::Header,
+	/// The slot claim of the header being verified.
+	pub(super) slot_claim: SlotClaim,
+	/// The ticket associated with the claimed slot, as found in the parent block state.
+	pub(super) maybe_ticket: Option<(TicketId, TicketBody)>,
+	/// The slot number of the current time.
+	pub(super) slot_now: Slot,
+	/// Epoch descriptor of the epoch this block _should_ be under, if it's valid.
+	pub(super) epoch: &'a Epoch,
+}
+
+/// Check a header has been signed by the right key. If the slot is too far in
+/// the future, an error will be returned. If successful, returns the pre-header
+/// and the digest item containing the seal.
+///
+/// The seal must be the last digest.  Otherwise, the whole header is considered
+/// unsigned.  This is required for security and must not be changed.
+pub(super) fn check_header<B: BlockT + Sized>(
+	params: VerificationParams<B>,
+) -> Result<CheckedHeader<B::Header, VerifiedHeaderInfo>, Error<B>> {
+	let VerificationParams { mut header, slot_claim, maybe_ticket, slot_now, epoch } = params;
+
+	trace!(target: LOG_TARGET, "Checking header");
+	let seal = header
+		.digest_mut()
+		.pop()
+		.ok_or_else(|| sassafras_err(Error::HeaderUnsealed(header.hash())))?;
+
+	let signature = AuthoritySignature::try_from(&seal)
+		.map_err(|_| sassafras_err(Error::HeaderBadSeal(header.hash())))?;
+
+	// the pre-hash of the header doesn't include the seal
+	// and that's what we sign
+	let pre_hash = header.hash();
+
+	if slot_claim.slot > slot_now {
+		header.digest_mut().push(seal);
+		return Ok(CheckedHeader::Deferred(header, slot_claim.slot));
+	}
+
+	debug!(
+		target: LOG_TARGET,
+		"Verifying block #{} at slot: {}",
+		header.number(),
+		slot_claim.slot,
+	);
+
+	check_slot_claim::<B>(pre_hash, &slot_claim, &signature, epoch, maybe_ticket)?;
+
+	let info = VerifiedHeaderInfo { seal };
+	Ok(CheckedHeader::Checked(header, info))
+}
+
+pub(super) struct VerifiedHeaderInfo {
+	pub(super) seal: DigestItem,
+}
+
+/// Check a slot claim.
+///
+/// We validate that the header is properly signed by the claiming authority and that the
+/// contained VRF proof is valid. Then, if the slot has an associated ticket, the claim must
+/// prove the ownership of the ticket, otherwise the claiming authority must be the fallback
+/// author of the slot.
+pub(super) fn check_slot_claim<B: BlockT>(
+	pre_hash: B::Hash,
+	slot_claim: &SlotClaim,
+	signature: &AuthoritySignature,
+	epoch: &Epoch,
+	maybe_ticket: Option<(TicketId, TicketBody)>,
+) -> Result<(), Error<B>> {
+	let slot = slot_claim.slot;
+	let author = epoch
+		.authorities
+		.get(slot_claim.authority_idx as usize)
+		.ok_or(Error::SlotAuthorNotFound)?;
+
+	let epoch_index = epoch.index_for_slot(slot);
+
+	if !AuthorityPair::verify(signature, pre_hash, author) {
+		return Err(sassafras_err(Error::BadSignature(pre_hash)));
+	}
+
+	let data = vrf::slot_claim_sign_data(&epoch.randomness, slot, epoch_index);
+	if !author.as_inner_ref().vrf_verify(&data, &slot_claim.vrf_signature) {
+		return Err(sassafras_err(Error::VrfVerificationFailed));
+	}
+
+	match (maybe_ticket, &slot_claim.ticket_claim) {
+		(Some((ticket_id, ticket_body)), Some(ticket_claim)) => {
+			debug!(target: LOG_TARGET, "Checking claim of ticket {:032x}", ticket_id);
+			let message = ticket_claim_message(&epoch.randomness, slot, epoch_index);
+			if !ed25519::Pair::verify(
+				&ticket_claim.erased_signature,
+				&message,
+				&ticket_body.erased_public,
+			) {
+				return Err(sassafras_err(Error::TicketClaimVerificationFailed(slot)));
+			}
+		},
+		(None, None) => {
+			let expected_idx =
+				fallback_authority_index(&epoch.randomness, slot, epoch.authorities.len())
+					.ok_or(Error::SlotAuthorNotFound)?;
+			if expected_idx != slot_claim.authority_idx {
+				let expected_author = epoch.authorities[expected_idx as usize].clone();
+				return Err(sassafras_err(Error::InvalidAuthor(expected_author, author.clone())));
+			}
+		},
+		(Some(_), None) => return Err(sassafras_err(Error::MissingTicketClaim(slot))),
+		(None, Some(_)) => return Err(sassafras_err(Error::UnexpectedTicketClaim(slot))),
+	}
+
+	Ok(())
+}
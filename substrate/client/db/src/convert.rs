@@ -0,0 +1,490 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline conversion of a RocksDB database into a ParityDB one.
+//!
+//! The columns are streamed in key order and written in batches. Every batch also records the
+//! last copied key in the destination, so that an interrupted conversion resumes where it
+//! stopped when started again. Once everything is copied, the state of the best and finalized
+//! blocks is read back from the destination and checked against their state roots.
+//!
+//! ParityDB implements the reference counting of the `STATE` and `TRANSACTION` columns, which
+//! is emulated on top of RocksDB, so these columns are translated:
+//! - Trie nodes are stored under their hash prefixed by their position in the trie, while ParityDB
+//!   only uses the hash. The pruning journals refer to the prefixed keys, hence only databases
+//!   using the `ArchiveAll` state pruning mode can be converted.
+//! - Indexed transactions have their reference counter stored under their key followed by a zero
+//!   byte, the value is inserted as many times in ParityDB.
+//!
+//! The opposite conversion is not possible, as ParityDB doesn't keep the keys of the reference
+//! counted columns.
+
+use crate::{
+	columns,
+	utils::{self, meta_keys, DatabaseType, NUM_COLUMNS},
+	DatabaseSource, DbHash, DB_HASH_LEN,
+};
+use codec::{Decode, Encode};
+use hash_db::{Hasher, Prefix};
+use kvdb::KeyValueDB;
+use log::info;
+use sc_state_db::PruningMode;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashingFor, Header as HeaderT},
+};
+use sp_trie::DBValue;
+use std::{marker::PhantomData, path::Path, sync::Arc};
+
+/// Key of the conversion progress in the `META` column of the destination.
+const PROGRESS_KEY: &[u8] = b"convert";
+
+/// Size in bytes of the entries written to the destination in a single transaction.
+const BATCH_SIZE: usize = 64 * 1024 * 1024;
+
+/// Position reached by a conversion.
+#[derive(Debug, Default, Encode, Decode)]
+struct Progress {
+	/// Index in [`column_order`] of the column being copied.
+	column: u32,
+	/// Last key copied from the column.
+	last_key: Option<Vec<u8>>,
+}
+
+/// The order in which the columns are copied.
+///
+/// `META` is copied last, so that the destination isn't a valid database before the other columns
+/// are complete.
+fn column_order() -> Vec<u32> {
+	(0..NUM_COLUMNS)
+		.filter(|column| *column != columns::META)
+		.chain(std::iter::once(columns::META))
+		.collect()
+}
+
+fn backend_err(msg: impl Into<String>) -> ClientError {
+	ClientError::Backend(msg.into())
+}
+
+/// Convert the database at `source` into a new database at `destination`.
+///
+/// Only the conversion of a RocksDB database into a ParityDB database is supported.
+pub fn convert_database<Block: BlockT>(
+	source: &DatabaseSource,
+	destination: &DatabaseSource,
+) -> ClientResult<()> {
+	match (source, destination) {
+		#[cfg(feature = "rocksdb")]
+		(DatabaseSource::RocksDb { path, cache_size }, DatabaseSource::ParityDb { path: to }) |
+		(
+			DatabaseSource::Auto { rocksdb_path: path, cache_size, .. },
+			DatabaseSource::ParityDb { path: to },
+		) => {
+			let db = utils::open_raw_kvdb_rocksdb::<Block>(
+				path,
+				DatabaseType::Full,
+				false,
+				*cache_size,
+			)?;
+			convert_kvdb_to_parity_db::<Block>(&db, to)
+		},
+		#[cfg(not(feature = "rocksdb"))]
+		(DatabaseSource::Auto { .. }, DatabaseSource::ParityDb { .. }) => {
+			Err(utils::OpenDbError::NotEnabled("with-kvdb-rocksdb").into())
+		},
+		(DatabaseSource::ParityDb { .. }, _) => Err(backend_err(
+			"ParityDB databases can't be converted, as they don't keep the keys of the state \
+			 and transaction columns",
+		)),
+		(from, to) => {
+			Err(backend_err(format!("Conversion from {from} to {to} databases is not supported")))
+		},
+	}
+}
+
+/// Convert a RocksDB database, opened as a [`KeyValueDB`], into a ParityDB database at
+/// `destination`.
+///
+/// `source` must iterate its columns in key order. If `destination` holds an interrupted
+/// conversion, it is resumed.
+pub fn convert_kvdb_to_parity_db<Block: BlockT>(
+	source: &dyn KeyValueDB,
+	destination: &Path,
+) -> ClientResult<()> {
+	match sc_state_db::fetch_stored_pruning_mode(&KvdbMetaDb(source)) {
+		Ok(Some(PruningMode::ArchiveAll)) => (),
+		Ok(Some(mode)) => {
+			return Err(backend_err(format!(
+				"Only databases with archive state pruning can be converted, found {mode:?}"
+			)))
+		},
+		Ok(None) => return Err(backend_err("Source database has no state pruning mode")),
+		Err(e) => return Err(backend_err(format!("Failed to read the state pruning mode: {e:?}"))),
+	}
+
+	let db = crate::parity_db::open(destination, DatabaseType::Full, true, false)
+		.map_err(|e| backend_err(format!("Failed to open {}: {e}", destination.display())))?;
+
+	let progress = match db.get(columns::META, PROGRESS_KEY) {
+		Some(progress) => {
+			let progress = Progress::decode(&mut &progress[..])
+				.map_err(|e| backend_err(format!("Invalid conversion progress: {e}")))?;
+			info!("Resuming the conversion into {}", destination.display());
+			progress
+		},
+		None if db.get(columns::META, meta_keys::TYPE).is_some() => {
+			return Err(backend_err(format!(
+				"{} already contains a database",
+				destination.display()
+			)))
+		},
+		None => Progress::default(),
+	};
+
+	copy_columns(source, &*db, progress)?;
+	verify_state::<Block>(&db)?;
+
+	let mut transaction = Transaction::new();
+	transaction.remove(columns::META, PROGRESS_KEY);
+	db.commit(transaction)?;
+	info!("Converted the database into {}", destination.display());
+	Ok(())
+}
+
+/// Copy the columns of `source`, starting at `progress`.
+fn copy_columns(
+	source: &dyn KeyValueDB,
+	destination: &dyn Database<DbHash>,
+	mut progress: Progress,
+) -> ClientResult<()> {
+	let order = column_order();
+	while let Some(&column) = order.get(progress.column as usize) {
+		info!("Copying column {column}");
+		let mut transaction = Transaction::new();
+		let mut batch_size = 0;
+		let mut copied = 0u64;
+
+		for entry in source.iter(column) {
+			let (key, value) = entry.map_err(|e| backend_err(e.to_string()))?;
+			// Copied before being interrupted.
+			if progress.last_key.as_ref().map_or(false, |last_key| key[..] <= last_key[..]) {
+				continue;
+			}
+
+			batch_size += key.len() + value.len();
+			copy_entry(source, column, &key, value, &mut transaction)?;
+			progress.last_key = Some(key.to_vec());
+			copied += 1;
+
+			if batch_size >= BATCH_SIZE {
+				transaction.set_from_vec(columns::META, PROGRESS_KEY, progress.encode());
+				destination.commit(std::mem::take(&mut transaction))?;
+				batch_size = 0;
+			}
+		}
+
+		progress = Progress { column: progress.column + 1, last_key: None };
+		transaction.set_from_vec(columns::META, PROGRESS_KEY, progress.encode());
+		destination.commit(transaction)?;
+		info!("Copied {copied} entries of column {column}");
+	}
+	Ok(())
+}
+
+/// Translate an entry of the source into the changes applied to the destination.
+fn copy_entry(
+	source: &dyn KeyValueDB,
+	column: u32,
+	key: &[u8],
+	value: Vec<u8>,
+	transaction: &mut Transaction<DbHash>,
+) -> ClientResult<()> {
+	match column {
+		columns::STATE => {
+			if key.len() < DB_HASH_LEN {
+				return Err(backend_err(format!("Invalid state key {key:02x?}")));
+			}
+			transaction.set_from_vec(columns::STATE, &key[key.len() - DB_HASH_LEN..], value);
+		},
+		columns::TRANSACTION => {
+			// Reference counter, read together with its value.
+			if key.len() == DB_HASH_LEN + 1 && key[DB_HASH_LEN] == 0 {
+				return Ok(());
+			}
+
+			let mut counter_key = key.to_vec();
+			counter_key.push(0);
+			let counter = source
+				.get(columns::TRANSACTION, &counter_key)
+				.map_err(|e| backend_err(e.to_string()))?
+				.and_then(|counter| <[u8; 4]>::try_from(counter).ok())
+				.map(u32::from_le_bytes)
+				.ok_or_else(|| {
+					backend_err(format!("Missing reference counter of transaction {key:02x?}"))
+				})?;
+			// Every insertion of a value in a reference counted column increments its counter.
+			for _ in 0..counter {
+				transaction.set(columns::TRANSACTION, key, &value);
+			}
+		},
+		_ => transaction.set_from_vec(column, key, value),
+	}
+	Ok(())
+}
+
+/// Check that the whole state of the best and finalized blocks can be read from `db`.
+fn verify_state<Block: BlockT>(db: &Arc<dyn Database<DbHash>>) -> ClientResult<()> {
+	let meta = utils::read_meta::<Block>(&**db, columns::HEADER)?;
+	let mut blocks = vec![meta.best_hash];
+	if meta.finalized_hash != meta.best_hash {
+		blocks.push(meta.finalized_hash);
+	}
+
+	for hash in blocks {
+		let header = utils::read_header::<Block>(
+			&**db,
+			columns::KEY_LOOKUP,
+			columns::HEADER,
+			BlockId::Hash(hash),
+		)?
+		.ok_or_else(|| backend_err(format!("Missing header of block {hash:?}")))?;
+		info!("Verifying the state of block #{} ({hash:?})", header.number());
+
+		let storage: Arc<dyn sp_state_machine::Storage<HashingFor<Block>>> =
+			Arc::new(VerifyingStorage { db: db.clone(), _phantom: PhantomData });
+		utils::read_state::<Block>(storage, *header.state_root())
+			.map_err(|e| backend_err(format!("Invalid state of block {hash:?}: {e}")))?;
+	}
+	Ok(())
+}
+
+/// Trie node storage checking that the nodes match their hash.
+struct VerifyingStorage<H> {
+	db: Arc<dyn Database<DbHash>>,
+	_phantom: PhantomData<H>,
+}
+
+impl<H: Hasher> sp_state_machine::Storage<H> for VerifyingStorage<H> {
+	fn get(&self, key: &H::Out, _prefix: Prefix) -> Result<Option<DBValue>, String> {
+		match self.db.get(columns::STATE, key.as_ref()) {
+			Some(node) if H::hash(&node) != *key => {
+				Err(format!("Trie node {key:?} doesn't match its hash"))
+			},
+			node => Ok(node),
+		}
+	}
+}
+
+/// Access to the state metadata of the source.
+struct KvdbMetaDb<'a>(&'a dyn KeyValueDB);
+
+impl sc_state_db::MetaDb for KvdbMetaDb<'_> {
+	type Error = std::io::Error;
+
+	fn get_meta(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.0.get(columns::STATE_META, key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{tests::Block, Backend, BlocksPruning, DatabaseSettings};
+	use kvdb::{DBKeyValue, DBTransaction};
+	use sc_client_api::{
+		backend::{Backend as _, BlockImportOperation as _, NewBlockState},
+		blockchain::HeaderBackend,
+		TrieCacheContext,
+	};
+	use sp_core::{
+		storage::{ChildInfo, Storage, StorageChild},
+		H256,
+	};
+	use sp_runtime::{testing::Header, StateVersion};
+	use std::io;
+
+	/// In-memory database shared with a [`Backend`], failing to iterate `fail_at` if set.
+	struct TestDb {
+		db: Arc<kvdb_memorydb::InMemory>,
+		fail_at: Option<u32>,
+	}
+
+	impl KeyValueDB for TestDb {
+		fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+			self.db.get(col, key)
+		}
+
+		fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<Vec<u8>>> {
+			self.db.get_by_prefix(col, prefix)
+		}
+
+		fn write(&self, transaction: DBTransaction) -> io::Result<()> {
+			self.db.write(transaction)
+		}
+
+		fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = io::Result<DBKeyValue>> + 'a> {
+			if self.fail_at == Some(col) {
+				return Box::new(std::iter::once(Err(io::Error::other("Interrupted"))));
+			}
+			self.db.iter(col)
+		}
+
+		fn iter_with_prefix<'a>(
+			&'a self,
+			col: u32,
+			prefix: &'a [u8],
+		) -> Box<dyn Iterator<Item = io::Result<DBKeyValue>> + 'a> {
+			self.db.iter_with_prefix(col, prefix)
+		}
+	}
+
+	fn header(number: u64, parent_hash: H256, state_root: H256) -> Header {
+		Header {
+			number,
+			parent_hash,
+			state_root,
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		}
+	}
+
+	#[test]
+	fn convert_and_resume() {
+		let source = Arc::new(kvdb_memorydb::create(NUM_COLUMNS));
+		let db = sp_database::as_database(TestDb { db: source.clone(), fail_at: None });
+		let backend = Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(PruningMode::ArchiveAll),
+				source: DatabaseSource::Custom { db: db.clone(), require_create_flag: true },
+				blocks_pruning: BlocksPruning::KeepAll,
+				metrics_registry: None,
+			},
+			0,
+		)
+		.unwrap();
+
+		let child_info = ChildInfo::new_default(b"child");
+		let mut op = backend.begin_operation().unwrap();
+		let root = op
+			.reset_storage(
+				Storage {
+					top: [(vec![1], vec![1]), (vec![2], vec![2])].into(),
+					children_default: [(
+						child_info.storage_key().to_vec(),
+						StorageChild {
+							data: [(vec![3], vec![3])].into(),
+							child_info: child_info.clone(),
+						},
+					)]
+					.into(),
+				},
+				StateVersion::V1,
+			)
+			.unwrap();
+		let genesis = header(0, Default::default(), root);
+		op.set_block_data(genesis.clone(), Some(vec![]), None, None, NewBlockState::Final)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, genesis.hash()).unwrap();
+		let changes = vec![(vec![1], None), (vec![4], Some(vec![4]))];
+		let (root, overlay) = op
+			.state()
+			.unwrap()
+			.unwrap()
+			.storage_root(changes.iter().map(|(k, v)| (&k[..], v.as_deref())), StateVersion::V1);
+		op.update_db_storage(overlay).unwrap();
+		op.update_storage(changes, Vec::new()).unwrap();
+		let best = header(1, genesis.hash(), root);
+		op.set_block_data(best.clone(), Some(vec![]), None, None, NewBlockState::Best)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+
+		// An indexed transaction referenced twice.
+		let transaction_hash = H256::repeat_byte(1);
+		for _ in 0..2 {
+			let mut transaction = Transaction::new();
+			transaction.store(columns::TRANSACTION, transaction_hash, vec![5]);
+			db.commit(transaction).unwrap();
+		}
+		drop(backend);
+
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("paritydb");
+		let interrupted = TestDb { db: source.clone(), fail_at: Some(columns::BODY_INDEX) };
+		assert!(convert_kvdb_to_parity_db::<Block>(&interrupted, &path).is_err());
+		convert_kvdb_to_parity_db::<Block>(&*source, &path).unwrap();
+		assert!(convert_kvdb_to_parity_db::<Block>(&*source, &path).is_err());
+
+		{
+			let backend = Backend::<Block>::new(
+				DatabaseSettings {
+					trie_cache_maximum_size: None,
+					state_pruning: None,
+					source: DatabaseSource::ParityDb { path: path.clone() },
+					blocks_pruning: BlocksPruning::KeepAll,
+					metrics_registry: None,
+				},
+				0,
+			)
+			.unwrap();
+			let info = backend.blockchain().info();
+			assert_eq!(info.best_hash, best.hash());
+			assert_eq!(info.finalized_hash, genesis.hash());
+
+			let state = backend.state_at(genesis.hash(), TrieCacheContext::Untrusted).unwrap();
+			assert_eq!(state.storage(&[1]).unwrap(), Some(vec![1]));
+			assert_eq!(state.child_storage(&child_info, &[3]).unwrap(), Some(vec![3]));
+			let state = backend.state_at(best.hash(), TrieCacheContext::Untrusted).unwrap();
+			assert_eq!(state.storage(&[1]).unwrap(), None);
+			assert_eq!(state.storage(&[2]).unwrap(), Some(vec![2]));
+			assert_eq!(state.storage(&[4]).unwrap(), Some(vec![4]));
+			assert_eq!(state.child_storage(&child_info, &[3]).unwrap(), Some(vec![3]));
+		}
+
+		// The transaction was copied once, with its reference count. Dereferences are applied
+		// in the background, so the database is reopened to observe them.
+		let open =
+			|| crate::parity_db::open::<DbHash>(&path, DatabaseType::Full, false, false).unwrap();
+		for present in [true, false] {
+			let db = open();
+			let mut transaction = Transaction::new();
+			transaction.release(columns::TRANSACTION, transaction_hash);
+			db.commit(transaction).unwrap();
+			drop(db);
+			assert_eq!(open().contains(columns::TRANSACTION, transaction_hash.as_ref()), present);
+		}
+	}
+
+	#[test]
+	fn rejects_pruned_database() {
+		let db = kvdb_memorydb::create(NUM_COLUMNS);
+		let mut transaction = DBTransaction::new();
+		transaction.put(columns::STATE_META, b"mode", b"constrained");
+		db.write(transaction).unwrap();
+
+		let dir = tempfile::tempdir().unwrap();
+		let err =
+			convert_kvdb_to_parity_db::<Block>(&db, &dir.path().join("paritydb")).unwrap_err();
+		assert!(err.to_string().contains("archive state pruning"));
+	}
+}
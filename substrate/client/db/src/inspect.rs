@@ -0,0 +1,279 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Read-only access to the raw columns of a database, for debugging and to find out what is
+//! using the disk space.
+//!
+//! RocksDB columns are iterated in key order. ParityDB doesn't keep the keys of its columns, only
+//! their values can be iterated, in no particular order.
+
+use crate::{
+	columns,
+	utils::{self, DatabaseType},
+	DatabaseSource,
+};
+use kvdb::KeyValueDB;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use std::path::Path;
+
+/// Names of the columns used by the database.
+pub const COLUMNS: &[(u32, &str)] = &[
+	(columns::META, "meta"),
+	(columns::STATE, "state"),
+	(columns::STATE_META, "state-meta"),
+	(columns::KEY_LOOKUP, "key-lookup"),
+	(columns::HEADER, "header"),
+	(columns::BODY, "body"),
+	(columns::JUSTIFICATIONS, "justifications"),
+	(columns::AUX, "aux"),
+	(columns::OFFCHAIN, "offchain"),
+	(columns::TRANSACTION, "transaction"),
+	(columns::BODY_INDEX, "body-index"),
+];
+
+/// Find a column by name or number.
+pub fn parse_column(column: &str) -> Option<u32> {
+	COLUMNS
+		.iter()
+		.find(|(_, name)| *name == column)
+		.map(|(column, _)| *column)
+		.or_else(|| column.parse().ok().filter(|column| *column < utils::NUM_COLUMNS))
+}
+
+/// Number and size of the entries of a column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnStats {
+	/// The column.
+	pub column: u32,
+	/// Name of the column.
+	pub name: &'static str,
+	/// Number of entries.
+	pub entries: u64,
+	/// Total size of the keys, unknown for ParityDB.
+	pub key_bytes: Option<u64>,
+	/// Total size of the values, before compression.
+	pub value_bytes: u64,
+}
+
+enum Inner {
+	Kvdb {
+		db: Box<dyn KeyValueDB>,
+		/// The directory of the files of a RocksDB secondary instance, removed once the database
+		/// is closed.
+		_secondary_dir: Option<tempfile::TempDir>,
+	},
+	ParityDb(parity_db::Db),
+}
+
+/// Read-only access to the raw columns of a database.
+pub struct DatabaseInspector(Inner);
+
+fn backend_err(msg: impl Into<String>) -> ClientError {
+	ClientError::Backend(msg.into())
+}
+
+impl DatabaseInspector {
+	/// Open the database at `source` in read-only mode.
+	///
+	/// The database is neither upgraded nor reconfigured, opening it fails if it doesn't have the
+	/// current version or columns. RocksDB databases are opened as a secondary instance, which
+	/// keeps its files in a temporary directory.
+	pub fn open(source: &DatabaseSource) -> ClientResult<Self> {
+		let inner = match source {
+			DatabaseSource::ParityDb { path } => Self::open_parity_db(path)?,
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::RocksDb { path, .. } => Self::open_rocksdb(path)?,
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::Auto { rocksdb_path, .. } if rocksdb_path.exists() => {
+				Self::open_rocksdb(rocksdb_path)?
+			},
+			DatabaseSource::Auto { paritydb_path, .. } => Self::open_parity_db(paritydb_path)?,
+			DatabaseSource::Custom { .. } => {
+				return Err(backend_err("Custom databases can't be inspected"))
+			},
+		};
+		Ok(Self(inner))
+	}
+
+	fn open_parity_db(path: &Path) -> ClientResult<Inner> {
+		crate::parity_db::open_read_only(path, DatabaseType::Full)
+			.map(Inner::ParityDb)
+			.map_err(|e| backend_err(format!("Failed to open ParityDB database: {e}")))
+	}
+
+	#[cfg(feature = "rocksdb")]
+	fn open_rocksdb(path: &Path) -> ClientResult<Inner> {
+		let secondary_dir = tempfile::tempdir().map_err(|e| backend_err(e.to_string()))?;
+		let db = utils::open_read_only_kvdb_rocksdb(path, secondary_dir.path())?;
+		Ok(Inner::Kvdb { db: Box::new(db), _secondary_dir: Some(secondary_dir) })
+	}
+
+	/// Inspect a RocksDB database opened as a [`KeyValueDB`].
+	pub fn from_kvdb(db: impl KeyValueDB + 'static) -> Self {
+		Self(Inner::Kvdb { db: Box::new(db), _secondary_dir: None })
+	}
+
+	/// Read the value of `key` in `column`.
+	pub fn get(&self, column: u32, key: &[u8]) -> ClientResult<Option<Vec<u8>>> {
+		match &self.0 {
+			Inner::Kvdb { db, .. } => db.get(column, key).map_err(|e| backend_err(e.to_string())),
+			Inner::ParityDb(db) => {
+				db.get(column as u8, key).map_err(|e| backend_err(e.to_string()))
+			},
+		}
+	}
+
+	/// Call `f` with the entries of `column` whose key starts with `prefix`, until it returns
+	/// `false`.
+	///
+	/// The keys are only given for RocksDB, ParityDB columns can only be iterated without a
+	/// prefix.
+	pub fn iter(
+		&self,
+		column: u32,
+		prefix: &[u8],
+		mut f: impl FnMut(Option<&[u8]>, &[u8]) -> bool,
+	) -> ClientResult<()> {
+		match &self.0 {
+			Inner::Kvdb { db, .. } => {
+				for entry in db.iter_with_prefix(column, prefix) {
+					let (key, value) = entry.map_err(|e| backend_err(e.to_string()))?;
+					if !f(Some(&key), &value) {
+						break;
+					}
+				}
+				Ok(())
+			},
+			Inner::ParityDb(_) if !prefix.is_empty() => {
+				Err(backend_err("ParityDB doesn't keep the keys, a prefix can't be used"))
+			},
+			Inner::ParityDb(db) => db
+				.iter_column_while(column as u8, |state| f(None, &state.value))
+				.map_err(|e| backend_err(e.to_string())),
+		}
+	}
+
+	/// Count the entries of every column.
+	pub fn column_stats(&self) -> ClientResult<Vec<ColumnStats>> {
+		COLUMNS
+			.iter()
+			.map(|(column, name)| {
+				let mut stats = ColumnStats {
+					column: *column,
+					name,
+					entries: 0,
+					key_bytes: matches!(self.0, Inner::Kvdb { .. }).then_some(0),
+					value_bytes: 0,
+				};
+				self.iter(*column, &[], |key, value| {
+					stats.entries += 1;
+					if let (Some(key_bytes), Some(key)) = (&mut stats.key_bytes, key) {
+						*key_bytes += key.len() as u64;
+					}
+					stats.value_bytes += value.len() as u64;
+					true
+				})?;
+				Ok(stats)
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{tests::Block, Backend, BlocksPruning, DatabaseSettings, PruningMode};
+	use kvdb::DBTransaction;
+
+	#[test]
+	fn inspect_kvdb() {
+		let db = kvdb_memorydb::create(utils::NUM_COLUMNS);
+		let mut transaction = DBTransaction::new();
+		transaction.put(columns::AUX, b"a1", b"1");
+		transaction.put(columns::AUX, b"a2", b"22");
+		transaction.put(columns::AUX, b"b1", b"333");
+		transaction.put(columns::META, b"type", b"full");
+		db.write(transaction).unwrap();
+		let inspector = DatabaseInspector::from_kvdb(db);
+
+		let stats = inspector.column_stats().unwrap();
+		assert_eq!(stats.len(), COLUMNS.len());
+		let aux = stats.iter().find(|stats| stats.name == "aux").unwrap();
+		assert_eq!(
+			*aux,
+			ColumnStats {
+				column: columns::AUX,
+				name: "aux",
+				entries: 3,
+				key_bytes: Some(6),
+				value_bytes: 6
+			}
+		);
+		assert_eq!(stats.iter().find(|stats| stats.name == "header").unwrap().entries, 0);
+
+		assert_eq!(inspector.get(columns::AUX, b"a2").unwrap(), Some(b"22".to_vec()));
+		assert_eq!(inspector.get(columns::AUX, b"a3").unwrap(), None);
+
+		let mut entries = Vec::new();
+		inspector
+			.iter(columns::AUX, b"a", |key, value| {
+				entries.push((key.unwrap().to_vec(), value.to_vec()));
+				true
+			})
+			.unwrap();
+		assert_eq!(
+			entries,
+			vec![(b"a1".to_vec(), b"1".to_vec()), (b"a2".to_vec(), b"22".to_vec())]
+		);
+	}
+
+	#[test]
+	fn inspect_parity_db() {
+		let dir = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		drop(
+			Backend::<Block>::new(
+				DatabaseSettings {
+					trie_cache_maximum_size: None,
+					state_pruning: Some(PruningMode::ArchiveAll),
+					source: source.clone(),
+					blocks_pruning: BlocksPruning::KeepAll,
+					metrics_registry: None,
+				},
+				0,
+			)
+			.unwrap(),
+		);
+
+		let inspector = DatabaseInspector::open(&source).unwrap();
+		let stats = inspector.column_stats().unwrap();
+		let meta = stats.iter().find(|stats| stats.column == columns::META).unwrap();
+		assert!(meta.entries > 0);
+		assert_eq!(meta.key_bytes, None);
+		assert_eq!(inspector.get(columns::META, b"type").unwrap(), Some(b"full".to_vec()));
+		assert!(inspector.iter(columns::META, b"type", |_, _| true).is_err());
+	}
+
+	#[test]
+	fn parse_columns() {
+		assert_eq!(parse_column("state"), Some(columns::STATE));
+		assert_eq!(parse_column("11"), Some(columns::TRANSACTION));
+		assert_eq!(parse_column("13"), None);
+		assert_eq!(parse_column("unknown"), None);
+	}
+}
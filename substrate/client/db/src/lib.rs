@@ -33,9 +33,12 @@ pub mod offchain;
 pub mod bench;
 
 mod children;
+mod convert;
+mod inspect;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
+mod state_pruning;
 mod stats;
 #[cfg(any(feature = "rocksdb", test))]
 mod upgrade;
@@ -100,6 +103,9 @@ pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
+pub use convert::{convert_database, convert_kvdb_to_parity_db};
+pub use inspect::{parse_column, ColumnStats, DatabaseInspector, COLUMNS};
+pub use state_pruning::{migrate_kvdb_state_pruning, migrate_state_pruning, StatePruningMigration};
 
 const CACHE_HEADERS: usize = 8;
 
@@ -21,8 +21,9 @@ use crate::{
 };
 /// A `Database` adapter for parity-db.
 use sp_database::{error::DatabaseError, Change, ColumnId, Database, Transaction};
+use std::sync::Arc;
 
-struct DbAdapter(parity_db::Db);
+struct DbAdapter(Arc<parity_db::Db>);
 
 fn handle_err<T>(result: parity_db::Result<T>) -> T {
 	match result {
@@ -39,7 +40,52 @@ pub fn open<H: Clone + AsRef<[u8]>>(
 	db_type: DatabaseType,
 	create: bool,
 	upgrade: bool,
-) -> parity_db::Result<std::sync::Arc<dyn Database<H>>> {
+) -> parity_db::Result<Arc<dyn Database<H>>> {
+	Ok(as_database(Arc::new(open_raw(path, db_type, create, upgrade)?)))
+}
+
+/// Wrap an opened parity-db database into a trait object that implements
+/// `sp_database::Database`.
+pub(crate) fn as_database<H: Clone + AsRef<[u8]>>(db: Arc<parity_db::Db>) -> Arc<dyn Database<H>> {
+	Arc::new(DbAdapter(db))
+}
+
+/// Open the parity-db database with the columns configured for `db_type`.
+pub(crate) fn open_raw(
+	path: &std::path::Path,
+	db_type: DatabaseType,
+	create: bool,
+	upgrade: bool,
+) -> parity_db::Result<parity_db::Db> {
+	let config = options(path, db_type);
+
+	if upgrade {
+		log::info!("Upgrading database metadata.");
+		if let Some(meta) = parity_db::Options::load_metadata(path)? {
+			config.write_metadata_with_version(path, &meta.salt, Some(meta.version))?;
+		}
+	}
+
+	if create {
+		parity_db::Db::open_or_create(&config)
+	} else {
+		parity_db::Db::open(&config)
+	}
+}
+
+/// Open the parity-db database with the columns configured for `db_type` in read-only mode.
+///
+/// Fails if the metadata of the database doesn't match the configured columns, without updating
+/// it.
+pub(crate) fn open_read_only(
+	path: &std::path::Path,
+	db_type: DatabaseType,
+) -> parity_db::Result<parity_db::Db> {
+	parity_db::Db::open_read_only(&options(path, db_type))
+}
+
+/// The options of the parity-db database with the columns configured for `db_type`.
+fn options(path: &std::path::Path, db_type: DatabaseType) -> parity_db::Options {
 	let mut config = parity_db::Options::with_columns(path, NUM_COLUMNS as u8);
 
 	match db_type {
@@ -70,20 +116,7 @@ pub fn open<H: Clone + AsRef<[u8]>>(
 		},
 	}
 
-	if upgrade {
-		log::info!("Upgrading database metadata.");
-		if let Some(meta) = parity_db::Options::load_metadata(path)? {
-			config.write_metadata_with_version(path, &meta.salt, Some(meta.version))?;
-		}
-	}
-
-	let db = if create {
-		parity_db::Db::open_or_create(&config)?
-	} else {
-		parity_db::Db::open(&config)?
-	};
-
-	Ok(std::sync::Arc::new(DbAdapter(db)))
+	config
 }
 
 fn ref_counted_column(col: u32) -> bool {
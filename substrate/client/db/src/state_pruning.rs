@@ -0,0 +1,521 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline migration of the state pruning mode of an existing database.
+//!
+//! The pruning mode is stored when the database is created and a node refuses to start with an
+//! incompatible one. The constraints of [`PruningMode::Constrained`] can be changed freely, while
+//! an archive database can be migrated to a pruned one:
+//! - The last finalized (or canonicalized) block starts the pruning window, and the blocks built on
+//!   top of it are kept as non-canonical. Their state is already in the database, so their journals
+//!   are empty.
+//! - The trie nodes reachable from the state of these blocks are collected, and every other node of
+//!   the `STATE` column is removed.
+//!
+//! The pruning metadata is committed before any trie node is removed, so an interrupted migration
+//! leaves a valid pruned database behind, with some unreachable nodes that are never removed.
+//! Nodes still referenced by the retained blocks may be kept longer than needed for the same
+//! reason, but a reachable node is never removed.
+
+use crate::{
+	apply_state_commit, columns,
+	utils::{self, DatabaseType},
+	Backend, BlocksPruning, DatabaseSettings, DatabaseSource, DbHash, StateMetaDb, StorageDb,
+	DB_HASH_LEN,
+};
+use hash_db::{Hasher, Prefix};
+use kvdb::{DBKeyValue, DBTransaction, KeyValueDB};
+use log::info;
+use parking_lot::Mutex;
+use sc_client_api::blockchain::{Backend as _, HeaderBackend, HeaderMetadata};
+use sc_state_db::{LastCanonicalized, PruningMode};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT, UniqueSaturatedInto};
+use sp_trie::DBValue;
+use std::{
+	collections::{BTreeSet, HashSet},
+	io,
+	path::Path,
+	sync::Arc,
+};
+
+/// Number of trie nodes removed in a single transaction.
+const BATCH_SIZE: usize = 64 * 1024;
+
+/// Outcome of a state pruning migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatePruningMigration {
+	/// Pruning mode stored in the database before the migration.
+	pub from: PruningMode,
+	/// Pruning mode stored in the database after the migration.
+	pub to: PruningMode,
+	/// Number of unreachable trie nodes removed from the database.
+	pub removed_nodes: u64,
+}
+
+fn backend_err(msg: impl Into<String>) -> ClientError {
+	ClientError::Backend(msg.into())
+}
+
+/// Change the state pruning mode of the database at `source` to `requested`.
+///
+/// The database must not be used by a running node.
+pub fn migrate_state_pruning<Block: BlockT>(
+	source: &DatabaseSource,
+	requested: PruningMode,
+) -> ClientResult<StatePruningMigration> {
+	match source {
+		DatabaseSource::ParityDb { path } => migrate_parity_db::<Block>(path, requested),
+		#[cfg(feature = "rocksdb")]
+		DatabaseSource::RocksDb { path, cache_size } => {
+			migrate_rocksdb::<Block>(path, *cache_size, requested)
+		},
+		#[cfg(feature = "rocksdb")]
+		DatabaseSource::Auto { rocksdb_path, cache_size, .. } if rocksdb_path.exists() => {
+			migrate_rocksdb::<Block>(rocksdb_path, *cache_size, requested)
+		},
+		DatabaseSource::Auto { paritydb_path, .. } => {
+			migrate_parity_db::<Block>(paritydb_path, requested)
+		},
+		DatabaseSource::Custom { .. } => {
+			Err(backend_err("The state pruning of custom databases can't be migrated"))
+		},
+	}
+}
+
+#[cfg(feature = "rocksdb")]
+fn migrate_rocksdb<Block: BlockT>(
+	path: &Path,
+	cache_size: usize,
+	requested: PruningMode,
+) -> ClientResult<StatePruningMigration> {
+	let db = Arc::new(utils::open_raw_kvdb_rocksdb::<Block>(
+		path,
+		DatabaseType::Full,
+		false,
+		cache_size,
+	)?);
+	let migration = migrate_kvdb_state_pruning::<Block, _>(db.clone(), requested)?;
+	if migration.removed_nodes > 0 {
+		info!("Compacting the state column");
+		db.force_compact(columns::STATE).map_err(|e| backend_err(e.to_string()))?;
+	}
+	Ok(migration)
+}
+
+fn migrate_parity_db<Block: BlockT>(
+	path: &Path,
+	requested: PruningMode,
+) -> ClientResult<StatePruningMigration> {
+	let db = Arc::new(utils::open_raw_parity_db(path, DatabaseType::Full, false)?);
+	migrate::<Block>(crate::parity_db::as_database(db.clone()), requested, |reachable| {
+		sweep_parity_db::<Block>(&db, reachable)
+	})
+}
+
+/// Change the state pruning mode of a RocksDB database, opened as a [`KeyValueDB`], to
+/// `requested`.
+pub fn migrate_kvdb_state_pruning<Block: BlockT, D: KeyValueDB + 'static>(
+	db: Arc<D>,
+	requested: PruningMode,
+) -> ClientResult<StatePruningMigration> {
+	migrate::<Block>(sp_database::as_database(SharedKvdb(db.clone())), requested, |reachable| {
+		sweep_kvdb(&*db, reachable)
+	})
+}
+
+/// Update the pruning metadata of `db`, then call `sweep` with the trie nodes reachable from the
+/// retained blocks if the state of the other blocks has to be removed.
+fn migrate<Block: BlockT>(
+	db: Arc<dyn Database<DbHash>>,
+	requested: PruningMode,
+	sweep: impl FnOnce(&HashSet<DbHash>) -> ClientResult<u64>,
+) -> ClientResult<StatePruningMigration> {
+	let from = sc_state_db::fetch_stored_pruning_mode(&StateMetaDb(db.clone()))
+		.map_err(ClientError::from_state_db)?
+		.ok_or_else(|| backend_err("The database has no state pruning mode"))?;
+	let backend = Backend::<Block>::new(
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: None,
+			source: DatabaseSource::Custom { db: db.clone(), require_create_flag: false },
+			blocks_pruning: BlocksPruning::KeepAll,
+			metrics_registry: None,
+		},
+		0,
+	)?;
+	let blockchain = &backend.blockchain;
+
+	let canonical_number = match backend.storage.state_db.last_canonicalized() {
+		LastCanonicalized::Block(number) => number,
+		LastCanonicalized::None | LastCanonicalized::NotCanonicalizing => {
+			blockchain.info().finalized_number.unique_saturated_into()
+		},
+	};
+	let canonical_hash = blockchain
+		.hash(canonical_number.unique_saturated_into())?
+		.ok_or_else(|| backend_err(format!("Missing canonical block #{canonical_number}")))?;
+
+	// The blocks descending from the canonical block, parents first.
+	let mut non_canonical = BTreeSet::new();
+	for leaf in blockchain.leaves()? {
+		let mut route = Vec::new();
+		let mut hash = leaf;
+		loop {
+			let meta = blockchain.header_metadata(hash)?;
+			let number: u64 = meta.number.unique_saturated_into();
+			if number <= canonical_number {
+				break;
+			}
+			route.push((number, hash, meta.parent));
+			if number == canonical_number + 1 {
+				if meta.parent == canonical_hash {
+					non_canonical.extend(route);
+				}
+				break;
+			}
+			hash = meta.parent;
+		}
+	}
+	let non_canonical = non_canonical
+		.into_iter()
+		.map(|(number, hash, parent)| (hash, number, parent))
+		.collect::<Vec<_>>();
+
+	let commit = sc_state_db::migrate_pruning_mode::<Block::Hash, Vec<u8>, _>(
+		&StateMetaDb(db.clone()),
+		requested.clone(),
+		(canonical_hash, canonical_number),
+		&non_canonical,
+	)
+	.map_err(ClientError::from_state_db)?;
+
+	let reachable = if from.is_archive() && !requested.is_archive() {
+		let storage = Arc::new(MarkingStorage {
+			storage: backend.storage.clone(),
+			reachable: Default::default(),
+		});
+		for hash in std::iter::once(canonical_hash).chain(non_canonical.iter().map(|b| b.0)) {
+			let header = blockchain
+				.header(hash)?
+				.ok_or_else(|| backend_err(format!("Missing header of block {hash:?}")))?;
+			info!("Collecting the state of block #{} ({hash:?})", header.number());
+			utils::read_state::<Block>(storage.clone(), *header.state_root())
+				.map_err(|e| backend_err(format!("Failed to read the state of {hash:?}: {e}")))?;
+		}
+		let reachable = std::mem::take(&mut *storage.reachable.lock());
+		Some(reachable)
+	} else {
+		None
+	};
+
+	let mut transaction = Transaction::new();
+	apply_state_commit(&mut transaction, commit);
+	db.commit(transaction)?;
+	drop(backend);
+	info!("Changed the state pruning mode from {from:?} to {requested:?}");
+
+	let removed_nodes = match reachable {
+		Some(reachable) => {
+			info!("Removing the trie nodes not reachable from {} nodes", reachable.len());
+			sweep(&reachable)?
+		},
+		None => 0,
+	};
+	Ok(StatePruningMigration { from, to: requested, removed_nodes })
+}
+
+/// Remove the trie nodes of a RocksDB `STATE` column whose hash is not in `reachable`.
+///
+/// The nodes are stored under their hash prefixed by their position in the trie.
+fn sweep_kvdb(db: &dyn KeyValueDB, reachable: &HashSet<DbHash>) -> ClientResult<u64> {
+	let mut transaction = DBTransaction::new();
+	let mut removed = 0;
+	for entry in db.iter(columns::STATE) {
+		let (key, _) = entry.map_err(|e| backend_err(e.to_string()))?;
+		if key.len() < DB_HASH_LEN ||
+			reachable.contains(&DbHash::from_slice(&key[key.len() - DB_HASH_LEN..]))
+		{
+			continue;
+		}
+
+		transaction.delete(columns::STATE, &key);
+		removed += 1;
+		if transaction.ops.len() >= BATCH_SIZE {
+			db.write(std::mem::take(&mut transaction))
+				.map_err(|e| backend_err(e.to_string()))?;
+		}
+	}
+	db.write(transaction).map_err(|e| backend_err(e.to_string()))?;
+	Ok(removed)
+}
+
+/// Release the trie nodes of a ParityDB `STATE` column whose hash is not in `reachable`.
+///
+/// ParityDB doesn't give the keys of the column, so they are computed from the values. Every
+/// reference to the removed nodes is released.
+fn sweep_parity_db<Block: BlockT>(
+	db: &parity_db::Db,
+	reachable: &HashSet<DbHash>,
+) -> ClientResult<u64> {
+	let mut unreachable = Vec::new();
+	db.iter_column_while(columns::STATE as u8, |state| {
+		let hash = DbHash::from_slice(HashingFor::<Block>::hash(&state.value).as_ref());
+		if !reachable.contains(&hash) {
+			unreachable.push((hash, state.rc));
+		}
+		true
+	})
+	.map_err(|e| backend_err(e.to_string()))?;
+
+	let removed = unreachable.len() as u64;
+	let mut changes = Vec::new();
+	for (hash, rc) in unreachable {
+		changes.extend((0..rc).map(|_| (columns::STATE as u8, hash.as_ref().to_vec(), None)));
+		if changes.len() >= BATCH_SIZE {
+			db.commit(std::mem::take(&mut changes))
+				.map_err(|e| backend_err(e.to_string()))?;
+		}
+	}
+	db.commit(changes).map_err(|e| backend_err(e.to_string()))?;
+	Ok(removed)
+}
+
+/// Trie node storage recording the hashes of the nodes read.
+struct MarkingStorage<Block: BlockT> {
+	storage: Arc<StorageDb<Block>>,
+	reachable: Mutex<HashSet<DbHash>>,
+}
+
+impl<Block: BlockT> sp_state_machine::Storage<HashingFor<Block>> for MarkingStorage<Block> {
+	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		self.reachable.lock().insert(DbHash::from_slice(key.as_ref()));
+		sp_state_machine::Storage::get(&*self.storage, key, prefix)
+	}
+}
+
+/// A [`KeyValueDB`] shared between the backend and the sweep of the `STATE` column.
+struct SharedKvdb<D>(Arc<D>);
+
+impl<D: KeyValueDB> KeyValueDB for SharedKvdb<D> {
+	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<DBValue>> {
+		self.0.get(col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
+		self.0.get_by_prefix(col, prefix)
+	}
+
+	fn write(&self, transaction: DBTransaction) -> io::Result<()> {
+		self.0.write(transaction)
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = io::Result<DBKeyValue>> + 'a> {
+		self.0.iter(col)
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = io::Result<DBKeyValue>> + 'a> {
+		self.0.iter_with_prefix(col, prefix)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::Block;
+	use sc_client_api::{
+		backend::{Backend as _, BlockImportOperation as _, NewBlockState},
+		TrieCacheContext,
+	};
+	use sp_core::storage::{ChildInfo, Storage, StorageChild};
+	use sp_runtime::{testing::Header, StateVersion};
+	use sp_state_machine::Backend as _;
+
+	fn open_backend(source: DatabaseSource, state_pruning: PruningMode) -> Backend<Block> {
+		Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(state_pruning),
+				source,
+				blocks_pruning: BlocksPruning::KeepAll,
+				metrics_registry: None,
+			},
+			0,
+		)
+		.unwrap()
+	}
+
+	fn import_block(
+		backend: &Backend<Block>,
+		parent: Option<&Header>,
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+		state: NewBlockState,
+	) -> Header {
+		let mut op = backend.begin_operation().unwrap();
+		let (number, parent_hash, root) = match parent {
+			Some(parent) => {
+				backend.begin_state_operation(&mut op, parent.hash()).unwrap();
+				let (root, overlay) = op.state().unwrap().unwrap().storage_root(
+					changes.iter().map(|(k, v)| (&k[..], v.as_deref())),
+					StateVersion::V1,
+				);
+				op.update_db_storage(overlay).unwrap();
+				op.update_storage(changes, Vec::new()).unwrap();
+				(parent.number + 1, parent.hash(), root)
+			},
+			None => {
+				let child_info = ChildInfo::new_default(b"child");
+				let root = op
+					.reset_storage(
+						Storage {
+							top: changes.into_iter().map(|(k, v)| (k, v.unwrap())).collect(),
+							children_default: [(
+								child_info.storage_key().to_vec(),
+								StorageChild {
+									data: [(vec![3], vec![3])].into(),
+									child_info: child_info.clone(),
+								},
+							)]
+							.into(),
+						},
+						StateVersion::V1,
+					)
+					.unwrap();
+				(0, Default::default(), root)
+			},
+		};
+		let header = Header {
+			number,
+			parent_hash,
+			state_root: root,
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(header.clone(), Some(vec![]), None, None, state).unwrap();
+		backend.commit_operation(op).unwrap();
+		header
+	}
+
+	/// Import a finalized chain of two blocks, a best block on top of it and a stale fork.
+	fn import_blocks(backend: &Backend<Block>) -> [Header; 4] {
+		let genesis = import_block(
+			backend,
+			None,
+			vec![(vec![1], Some(vec![1])), (vec![2], Some(vec![2]))],
+			NewBlockState::Final,
+		);
+		let stale = import_block(
+			backend,
+			Some(&genesis),
+			vec![(vec![5], Some(vec![5]))],
+			NewBlockState::Normal,
+		);
+		let finalized = import_block(
+			backend,
+			Some(&genesis),
+			vec![(vec![1], None), (vec![4], Some(vec![4]))],
+			NewBlockState::Final,
+		);
+		let best = import_block(
+			backend,
+			Some(&finalized),
+			vec![(vec![2], Some(vec![22]))],
+			NewBlockState::Best,
+		);
+		[genesis, stale, finalized, best]
+	}
+
+	fn check_migrated(backend: &Backend<Block>, [genesis, stale, finalized, best]: &[Header; 4]) {
+		assert!(!backend.have_state_at(genesis.hash(), 0));
+		assert!(!backend.have_state_at(stale.hash(), 1));
+
+		let state = backend.state_at(finalized.hash(), TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(&[1]).unwrap(), None);
+		assert_eq!(state.storage(&[2]).unwrap(), Some(vec![2]));
+		assert_eq!(state.storage(&[4]).unwrap(), Some(vec![4]));
+		assert_eq!(
+			state.child_storage(&ChildInfo::new_default(b"child"), &[3]).unwrap(),
+			Some(vec![3])
+		);
+		let state = backend.state_at(best.hash(), TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(&[2]).unwrap(), Some(vec![22]));
+		assert_eq!(state.storage(&[4]).unwrap(), Some(vec![4]));
+
+		// The migrated window keeps going.
+		backend.finalize_block(best.hash(), None).unwrap();
+		let next = import_block(
+			backend,
+			Some(best),
+			vec![(vec![4], Some(vec![44]))],
+			NewBlockState::Final,
+		);
+		let state = backend.state_at(next.hash(), TrieCacheContext::Untrusted).unwrap();
+		assert_eq!(state.storage(&[2]).unwrap(), Some(vec![22]));
+		assert_eq!(state.storage(&[4]).unwrap(), Some(vec![44]));
+	}
+
+	#[test]
+	fn migrate_kvdb_archive_to_pruned() {
+		let db = Arc::new(kvdb_memorydb::create(utils::NUM_COLUMNS));
+		let source = || DatabaseSource::Custom {
+			db: sp_database::as_database(SharedKvdb(db.clone())),
+			require_create_flag: true,
+		};
+		let headers = import_blocks(&open_backend(source(), PruningMode::ArchiveAll));
+
+		let mode = PruningMode::blocks_pruning(2);
+		let migration = migrate_kvdb_state_pruning::<Block, _>(db.clone(), mode.clone()).unwrap();
+		assert_eq!(migration.from, PruningMode::ArchiveAll);
+		assert_eq!(migration.to, mode);
+		assert!(migration.removed_nodes > 0);
+
+		let source = DatabaseSource::Custom {
+			db: sp_database::as_database(SharedKvdb(db.clone())),
+			require_create_flag: false,
+		};
+		check_migrated(&open_backend(source, mode.clone()), &headers);
+
+		// Pruned databases can't be migrated back to archive.
+		assert!(
+			migrate_kvdb_state_pruning::<Block, _>(db.clone(), PruningMode::ArchiveAll).is_err()
+		);
+		// Changing the window doesn't require any migration.
+		let migration =
+			migrate_kvdb_state_pruning::<Block, _>(db, PruningMode::blocks_pruning(1000)).unwrap();
+		assert_eq!(migration.removed_nodes, 0);
+	}
+
+	#[test]
+	fn migrate_parity_db_archive_to_pruned() {
+		let dir = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		let headers = import_blocks(&open_backend(source.clone(), PruningMode::ArchiveAll));
+
+		let mode = PruningMode::blocks_pruning(2);
+		let migration = migrate_state_pruning::<Block>(&source, mode.clone()).unwrap();
+		assert_eq!(migration.from, PruningMode::ArchiveAll);
+		assert!(migration.removed_nodes > 0);
+
+		check_migrated(&open_backend(source, mode), &headers);
+	}
+}
@@ -26,14 +26,16 @@ use log::{debug, info};
 use crate::{Database, DatabaseSource, DbHash};
 use codec::Decode;
 use sc_client_api::blockchain::{BlockGap, BlockGapType};
+use sp_core::storage::{well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo};
 use sp_database::Transaction;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{
-		Block as BlockT, Header as HeaderT, NumberFor, UniqueSaturatedFrom, UniqueSaturatedInto,
-		Zero,
+		Block as BlockT, HashingFor, Header as HeaderT, NumberFor, UniqueSaturatedFrom,
+		UniqueSaturatedInto, Zero,
 	},
 };
+use sp_state_machine::{Backend as StateBackend, IterArgs, Storage};
 use sp_trie::DBValue;
 
 /// Number of columns in the db. Must be the same for both full && light dbs.
@@ -293,12 +295,22 @@ impl From<io::Error> for OpenDbError {
 }
 
 fn open_parity_db<Block: BlockT>(path: &Path, db_type: DatabaseType, create: bool) -> OpenDbResult {
-	match crate::parity_db::open(path, db_type, create, false) {
+	let db = open_raw_parity_db(path, db_type, create)?;
+	Ok(crate::parity_db::as_database(Arc::new(db)))
+}
+
+/// Opens the ParityDB database without wrapping it into a [`Database`].
+pub(crate) fn open_raw_parity_db(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+) -> Result<parity_db::Db, OpenDbError> {
+	match crate::parity_db::open_raw(path, db_type, create, false) {
 		Ok(db) => Ok(db),
 		Err(parity_db::Error::InvalidConfiguration(_)) => {
 			log::warn!("Invalid parity db configuration, attempting database metadata update.");
 			// Try to update the database with the new config
-			Ok(crate::parity_db::open(path, db_type, create, true)?)
+			Ok(crate::parity_db::open_raw(path, db_type, create, true)?)
 		},
 		Err(e) => Err(e.into()),
 	}
@@ -311,6 +323,18 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	create: bool,
 	cache_size: usize,
 ) -> OpenDbResult {
+	let db = open_raw_kvdb_rocksdb::<Block>(path, db_type, create, cache_size)?;
+	Ok(sp_database::as_rocksdb_database(db))
+}
+
+/// Opens the RocksDB database without wrapping it into a [`Database`].
+#[cfg(any(feature = "rocksdb", test))]
+pub(crate) fn open_raw_kvdb_rocksdb<Block: BlockT>(
+	path: &Path,
+	db_type: DatabaseType,
+	create: bool,
+	cache_size: usize,
+) -> Result<kvdb_rocksdb::Database, OpenDbError> {
 	// first upgrade database to required version
 	match crate::upgrade::upgrade_db::<Block>(path, db_type) {
 		// in case of missing version file, assume that database simply does not exist at given
@@ -351,7 +375,25 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
 	// write database version only after the database is successfully opened
 	crate::upgrade::update_version(path)?;
-	Ok(sp_database::as_rocksdb_database(db))
+	Ok(db)
+}
+
+/// Opens the RocksDB database as a secondary instance, which can only read the database.
+///
+/// The secondary instance keeps its own files in `secondary_path`. The database isn't upgraded,
+/// opening it fails if it doesn't have the current version.
+#[cfg(feature = "rocksdb")]
+pub(crate) fn open_read_only_kvdb_rocksdb(
+	path: &Path,
+	secondary_path: &Path,
+) -> Result<kvdb_rocksdb::Database, OpenDbError> {
+	crate::upgrade::ensure_current_version(path)
+		.map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+
+	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.create_if_missing = false;
+	db_config.secondary = Some(secondary_path.to_owned());
+	Ok(kvdb_rocksdb::Database::open(&db_config, path)?)
 }
 
 #[cfg(not(any(feature = "rocksdb", test)))]
@@ -427,6 +469,31 @@ fn maybe_migrate_to_type_subdir<Block: BlockT>(
 	Ok(())
 }
 
+/// Read every key and value of the state at `root` from `storage`, including the default child
+/// tries.
+pub(crate) fn read_state<Block: BlockT>(
+	storage: Arc<dyn Storage<HashingFor<Block>>>,
+	root: Block::Hash,
+) -> Result<(), String> {
+	let state = crate::DbStateBuilder::<HashingFor<Block>>::new(storage, root).build();
+
+	let mut child_infos = Vec::new();
+	for pair in state.pairs(Default::default())? {
+		let (key, _) = pair?;
+		if let Some(storage_key) = key.strip_prefix(DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			child_infos.push(ChildInfo::new_default(storage_key));
+		}
+	}
+	for child_info in child_infos {
+		let mut args = IterArgs::default();
+		args.child_info = Some(child_info);
+		for pair in state.pairs(args)? {
+			pair?;
+		}
+	}
+	Ok(())
+}
+
 /// Read database column entry for the given block.
 pub fn read_db<Block>(
 	db: &dyn Database<DbHash>,
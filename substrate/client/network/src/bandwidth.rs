@@ -0,0 +1,579 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-protocol bandwidth budgets.
+//!
+//! [`BandwidthLimiter`] enforces the budgets of [`BandwidthConfig`] for both networking backends.
+//! It is shared through [`NotificationMetrics`](crate::service::metrics::NotificationMetrics) so
+//! that notification protocols built before the backend can be throttled too, and is configured
+//! by the backend when it starts.
+
+use crate::{
+	config::{BandwidthConfig, ProtocolPriority},
+	service::metrics::Metrics,
+	types::ProtocolName,
+};
+
+use parking_lot::Mutex;
+use prometheus_endpoint::{CounterVec, U64};
+
+use std::{
+	collections::HashMap,
+	num::NonZeroU64,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// Logging target for the file.
+const LOG_TARGET: &str = "sub-libp2p::bandwidth";
+
+/// How many seconds worth of traffic a budget may accumulate.
+const BURST_SECONDS: f64 = 1.0;
+
+/// Shortest time an asynchronous sender waits before checking the budget again.
+const MIN_DELAY: Duration = Duration::from_millis(1);
+
+/// Direction of the traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficDirection {
+	/// Traffic received from remote peers.
+	Inbound,
+
+	/// Traffic sent to remote peers.
+	Outbound,
+}
+
+impl TrafficDirection {
+	/// Label of the direction in metrics.
+	fn label(&self) -> &'static str {
+		match self {
+			Self::Inbound => "in",
+			Self::Outbound => "out",
+		}
+	}
+}
+
+/// Share of the total budget that must be left for a protocol of `priority` to use it.
+fn reserve(priority: ProtocolPriority) -> f64 {
+	match priority {
+		ProtocolPriority::Low => 0.5,
+		ProtocolPriority::Normal => 0.25,
+		ProtocolPriority::High => 0.0,
+	}
+}
+
+/// Token bucket refilled at a constant rate.
+///
+/// The balance may go negative so that messages larger than the bucket still get through once
+/// the bucket has been refilled, at the cost of delaying the following ones.
+#[derive(Debug)]
+struct TokenBucket {
+	/// Refill rate, in bytes per second.
+	rate: f64,
+
+	/// Maximum balance, in bytes.
+	capacity: f64,
+
+	/// Current balance, in bytes.
+	tokens: f64,
+
+	/// When `tokens` was last refilled.
+	updated: Instant,
+}
+
+impl TokenBucket {
+	/// Create new full [`TokenBucket`].
+	fn new(rate: NonZeroU64, now: Instant) -> Self {
+		let rate = rate.get() as f64;
+		let capacity = rate * BURST_SECONDS;
+
+		Self { rate, capacity, tokens: capacity, updated: now }
+	}
+
+	/// Refill the bucket for the time elapsed since the last refill.
+	fn refill(&mut self, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+
+		self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+		self.updated = self.updated.max(now);
+	}
+
+	/// Time until the balance exceeds `reserve` share of the capacity.
+	fn wait_time(&self, reserve: f64) -> Option<Duration> {
+		let threshold = self.capacity * reserve;
+
+		(self.tokens <= threshold)
+			.then(|| Duration::from_secs_f64((threshold - self.tokens) / self.rate).max(MIN_DELAY))
+	}
+
+	/// Charge `bytes` to the bucket.
+	fn consume(&mut self, bytes: usize) {
+		self.tokens -= bytes as f64;
+	}
+}
+
+/// Inbound and outbound budgets.
+#[derive(Debug, Default)]
+struct Budget {
+	/// Budget of the received traffic.
+	inbound: Option<TokenBucket>,
+
+	/// Budget of the sent traffic.
+	outbound: Option<TokenBucket>,
+}
+
+impl Budget {
+	/// Create new [`Budget`] from the configured rates.
+	fn new(inbound: Option<NonZeroU64>, outbound: Option<NonZeroU64>, now: Instant) -> Self {
+		Self {
+			inbound: inbound.map(|rate| TokenBucket::new(rate, now)),
+			outbound: outbound.map(|rate| TokenBucket::new(rate, now)),
+		}
+	}
+
+	/// Get the bucket of `direction`, if limited.
+	fn bucket(&mut self, direction: TrafficDirection) -> Option<&mut TokenBucket> {
+		match direction {
+			TrafficDirection::Inbound => self.inbound.as_mut(),
+			TrafficDirection::Outbound => self.outbound.as_mut(),
+		}
+	}
+}
+
+/// Budget of a single protocol.
+#[derive(Debug, Default)]
+struct ProtocolBudget {
+	/// Protocol-specific limits.
+	budget: Budget,
+
+	/// Priority against the total budget.
+	priority: ProtocolPriority,
+}
+
+/// State shared by all clones of [`BandwidthLimiter`].
+#[derive(Debug, Default)]
+struct Inner {
+	/// Set if any budget is configured.
+	enabled: bool,
+
+	/// Budget of all protocols combined.
+	total: Budget,
+
+	/// Budgets of individual protocols.
+	protocols: HashMap<ProtocolName, ProtocolBudget>,
+
+	/// Number of throttled messages, if metrics are enabled.
+	metrics: Option<CounterVec<U64>>,
+}
+
+impl Inner {
+	/// Get the protocol-specific and the total bucket `protocol` draws from, together with the
+	/// priority of `protocol`.
+	///
+	/// Protocols without a configured budget are considered of high priority, as the limiter
+	/// cannot tell whether they carry critical traffic such as GRANDPA votes.
+	fn buckets(
+		&mut self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+	) -> (Option<&mut TokenBucket>, Option<&mut TokenBucket>, ProtocolPriority) {
+		let (bucket, priority) = match self.protocols.get_mut(protocol) {
+			Some(ProtocolBudget { budget, priority }) => (budget.bucket(direction), *priority),
+			None => (None, ProtocolPriority::High),
+		};
+
+		(bucket, self.total.bucket(direction), priority)
+	}
+
+	/// Refill the buckets `protocol` draws from and return how long to wait until `protocol` may
+	/// use them, or `None` if it may use them now.
+	fn wait_time(
+		&mut self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+		now: Instant,
+	) -> Option<Duration> {
+		let (bucket, total, priority) = self.buckets(protocol, direction);
+
+		let protocol_wait = bucket.and_then(|bucket| {
+			bucket.refill(now);
+			bucket.wait_time(0.0)
+		});
+		// High priority traffic is charged to the total budget but never throttled by it.
+		let total_wait = total.filter(|_| priority != ProtocolPriority::High).and_then(|bucket| {
+			bucket.refill(now);
+			bucket.wait_time(reserve(priority))
+		});
+
+		protocol_wait.max(total_wait)
+	}
+
+	/// Refill the buckets `protocol` draws from and charge `bytes` to them.
+	fn consume(
+		&mut self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+		bytes: usize,
+		now: Instant,
+	) {
+		let (bucket, total, _) = self.buckets(protocol, direction);
+
+		for bucket in bucket.into_iter().chain(total) {
+			bucket.refill(now);
+			bucket.consume(bytes);
+		}
+	}
+
+	/// Register a throttled message to Prometheus.
+	fn register_throttled(&self, protocol: &ProtocolName, direction: TrafficDirection) {
+		if let Some(metrics) = &self.metrics {
+			metrics.with_label_values(&[direction.label(), protocol]).inc();
+		}
+	}
+}
+
+/// Enforces the bandwidth budgets of the notification and request-response protocols.
+///
+/// Clones share the same budgets. Without configured budgets every message is allowed.
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthLimiter(Arc<Mutex<Inner>>);
+
+impl BandwidthLimiter {
+	/// Create new [`BandwidthLimiter`] enforcing `config`.
+	pub fn new(config: &BandwidthConfig) -> Self {
+		let limiter = Self::default();
+		limiter.configure(config);
+		limiter
+	}
+
+	/// Replace the enforced budgets with `config`.
+	pub fn configure(&self, config: &BandwidthConfig) {
+		let now = Instant::now();
+		let mut inner = self.0.lock();
+
+		inner.enabled = !config.is_unlimited();
+		inner.total = Budget::new(config.inbound, config.outbound, now);
+		inner.protocols = config
+			.protocols
+			.iter()
+			.map(|(protocol, config)| {
+				(
+					protocol.clone(),
+					ProtocolBudget {
+						budget: Budget::new(config.inbound, config.outbound, now),
+						priority: config.priority,
+					},
+				)
+			})
+			.collect();
+
+		if inner.enabled {
+			log::debug!(target: LOG_TARGET, "bandwidth budgets configured: {config:?}");
+		}
+	}
+
+	/// Report the number of throttled messages through `metrics`.
+	pub fn set_metrics(&self, metrics: &Metrics) {
+		self.0.lock().metrics = Some(metrics.bandwidth_throttled_total.clone());
+	}
+
+	/// Charge `bytes` of `protocol` traffic if the budget allows it.
+	///
+	/// Returns `false` and charges nothing if the message must be throttled.
+	pub fn try_consume(
+		&self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+		bytes: usize,
+	) -> bool {
+		let mut inner = self.0.lock();
+
+		if !inner.enabled {
+			return true;
+		}
+
+		if inner.wait_time(protocol, direction, Instant::now()).is_some() {
+			log::trace!(
+				target: LOG_TARGET,
+				"{protocol}: {direction:?} budget exhausted, throttling {bytes} bytes",
+			);
+
+			inner.register_throttled(protocol, direction);
+			return false;
+		}
+
+		inner.consume(protocol, direction, bytes, Instant::now());
+		true
+	}
+
+	/// Returns `true` if the budget allows more `protocol` traffic now, without charging anything.
+	///
+	/// Returns `false` and registers the message as throttled otherwise.
+	pub fn has_budget(&self, protocol: &ProtocolName, direction: TrafficDirection) -> bool {
+		let mut inner = self.0.lock();
+
+		if !inner.enabled {
+			return true;
+		}
+
+		if inner.wait_time(protocol, direction, Instant::now()).is_some() {
+			log::trace!(target: LOG_TARGET, "{protocol}: {direction:?} budget exhausted");
+
+			inner.register_throttled(protocol, direction);
+			return false;
+		}
+
+		true
+	}
+
+	/// Return how long `protocol` must wait until the budget allows more traffic, or `None` if
+	/// the budget allows it now.
+	pub fn wait_time(
+		&self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+	) -> Option<Duration> {
+		let mut inner = self.0.lock();
+
+		if !inner.enabled {
+			return None;
+		}
+
+		inner.wait_time(protocol, direction, Instant::now())
+	}
+
+	/// Return how long an outbound request of `protocol` must wait until both the outbound budget
+	/// and the inbound budget its response is charged to allow it, or `None` if it may be sent now.
+	pub fn request_wait_time(&self, protocol: &ProtocolName) -> Option<Duration> {
+		let mut inner = self.0.lock();
+
+		if !inner.enabled {
+			return None;
+		}
+
+		let now = Instant::now();
+		let outbound = inner.wait_time(protocol, TrafficDirection::Outbound, now);
+		let inbound = inner.wait_time(protocol, TrafficDirection::Inbound, now);
+
+		outbound.max(inbound)
+	}
+
+	/// Charge `bytes` of `protocol` traffic, waiting until the budget allows it.
+	pub async fn consume(
+		&self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+		bytes: usize,
+	) {
+		let mut throttled = false;
+
+		loop {
+			let delay = {
+				let mut inner = self.0.lock();
+
+				if !inner.enabled {
+					return;
+				}
+
+				match inner.wait_time(protocol, direction, Instant::now()) {
+					None => {
+						inner.consume(protocol, direction, bytes, Instant::now());
+						return;
+					},
+					Some(delay) => {
+						if !throttled {
+							inner.register_throttled(protocol, direction);
+							throttled = true;
+						}
+						delay
+					},
+				}
+			};
+
+			futures_timer::Delay::new(delay).await;
+		}
+	}
+
+	/// Charge `bytes` of `protocol` traffic regardless of the budget.
+	///
+	/// Used for traffic whose size is only known once it can no longer be throttled, such as
+	/// responses. Their budget is checked with [`BandwidthLimiter::has_budget`] before the request
+	/// is sent or handed to the request handler.
+	pub fn force_consume(
+		&self,
+		protocol: &ProtocolName,
+		direction: TrafficDirection,
+		bytes: usize,
+	) {
+		let mut inner = self.0.lock();
+
+		if inner.enabled {
+			inner.consume(protocol, direction, bytes, Instant::now());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::ProtocolBandwidthConfig;
+
+	fn rate(bytes: u64) -> Option<NonZeroU64> {
+		NonZeroU64::new(bytes)
+	}
+
+	fn is_allowed(limiter: &BandwidthLimiter, protocol: &ProtocolName, now: Instant) -> bool {
+		limiter.0.lock().wait_time(protocol, TrafficDirection::Outbound, now).is_none()
+	}
+
+	#[test]
+	fn unlimited_by_default() {
+		let limiter = BandwidthLimiter::default();
+		let protocol = ProtocolName::from("/foo/1");
+
+		for _ in 0..100 {
+			assert!(limiter.try_consume(&protocol, TrafficDirection::Inbound, 1 << 20));
+		}
+	}
+
+	#[test]
+	fn protocol_budget_is_refilled() {
+		let protocol = ProtocolName::from("/foo/1");
+		let other = ProtocolName::from("/bar/1");
+		let limiter = BandwidthLimiter::new(&BandwidthConfig::default().with_protocol(
+			protocol.clone(),
+			ProtocolBandwidthConfig { outbound: rate(1000), ..Default::default() },
+		));
+		let now = Instant::now();
+
+		// a message larger than the bucket is allowed while the bucket is full
+		assert!(limiter.try_consume(&protocol, TrafficDirection::Outbound, 1500));
+		assert!(!limiter.try_consume(&protocol, TrafficDirection::Outbound, 1));
+
+		// inbound traffic and other protocols are not limited
+		assert!(limiter.try_consume(&protocol, TrafficDirection::Inbound, 1500));
+		assert!(limiter.try_consume(&other, TrafficDirection::Outbound, 1500));
+
+		// the debt of 500 bytes takes half a second to pay off
+		assert!(!is_allowed(&limiter, &protocol, now + Duration::from_millis(400)));
+		assert!(is_allowed(&limiter, &protocol, now + Duration::from_millis(600)));
+	}
+
+	#[test]
+	fn priorities_share_total_budget() {
+		let low = ProtocolName::from("/low/1");
+		let normal = ProtocolName::from("/normal/1");
+		let high = ProtocolName::from("/high/1");
+		let config = BandwidthConfig { outbound: rate(1000), ..Default::default() }
+			.with_protocol(
+				low.clone(),
+				ProtocolBandwidthConfig { priority: ProtocolPriority::Low, ..Default::default() },
+			)
+			.with_protocol(normal.clone(), Default::default())
+			.with_protocol(
+				high.clone(),
+				ProtocolBandwidthConfig { priority: ProtocolPriority::High, ..Default::default() },
+			);
+		let limiter = BandwidthLimiter::new(&config);
+		let now = Instant::now();
+
+		// 400 bytes left: only low priority protocols are throttled
+		limiter.force_consume(&high, TrafficDirection::Outbound, 600);
+		assert!(!is_allowed(&limiter, &low, now));
+		assert!(is_allowed(&limiter, &normal, now));
+		assert!(is_allowed(&limiter, &high, now));
+
+		// 200 bytes left: only high priority protocols may send
+		limiter.force_consume(&high, TrafficDirection::Outbound, 200);
+		assert!(!is_allowed(&limiter, &low, now));
+		assert!(!is_allowed(&limiter, &normal, now));
+		assert!(is_allowed(&limiter, &high, now));
+
+		// budget exhausted, high priority protocols are still not throttled
+		limiter.force_consume(&high, TrafficDirection::Outbound, 300);
+		assert!(!is_allowed(&limiter, &normal, now));
+		assert!(is_allowed(&limiter, &high, now));
+		assert!(limiter.try_consume(&high, TrafficDirection::Outbound, 1000));
+	}
+
+	#[test]
+	fn unconfigured_protocols_are_not_throttled_by_total_budget() {
+		let grandpa = ProtocolName::from("/grandpa/1");
+		let low = ProtocolName::from("/low/1");
+		let limiter = BandwidthLimiter::new(
+			&BandwidthConfig { outbound: rate(1000), ..Default::default() }.with_protocol(
+				low.clone(),
+				ProtocolBandwidthConfig { priority: ProtocolPriority::Low, ..Default::default() },
+			),
+		);
+
+		for _ in 0..10 {
+			assert!(limiter.try_consume(&grandpa, TrafficDirection::Outbound, 1000));
+		}
+
+		// the traffic is still charged to the total budget
+		assert!(!limiter.try_consume(&low, TrafficDirection::Outbound, 1));
+	}
+
+	#[test]
+	fn reconfiguring_replaces_budgets() {
+		let protocol = ProtocolName::from("/foo/1");
+		let limiter = BandwidthLimiter::new(
+			&BandwidthConfig { inbound: rate(10), ..Default::default() }
+				.with_protocol(protocol.clone(), Default::default()),
+		);
+
+		assert!(limiter.try_consume(&protocol, TrafficDirection::Inbound, 100));
+		assert!(!limiter.try_consume(&protocol, TrafficDirection::Inbound, 100));
+
+		limiter.configure(&BandwidthConfig::default());
+		assert!(limiter.try_consume(&protocol, TrafficDirection::Inbound, 100));
+	}
+
+	#[test]
+	fn has_budget_charges_nothing() {
+		let protocol = ProtocolName::from("/foo/1");
+		let limiter = BandwidthLimiter::new(&BandwidthConfig::default().with_protocol(
+			protocol.clone(),
+			ProtocolBandwidthConfig { outbound: rate(1000), ..Default::default() },
+		));
+
+		for _ in 0..10 {
+			assert!(limiter.has_budget(&protocol, TrafficDirection::Outbound));
+		}
+
+		limiter.force_consume(&protocol, TrafficDirection::Outbound, 1500);
+		assert!(!limiter.has_budget(&protocol, TrafficDirection::Outbound));
+		assert!(limiter.has_budget(&protocol, TrafficDirection::Inbound));
+	}
+
+	#[tokio::test]
+	async fn consume_waits_for_budget() {
+		let protocol = ProtocolName::from("/foo/1");
+		let limiter = BandwidthLimiter::new(
+			&BandwidthConfig { outbound: rate(1000), ..Default::default() }
+				.with_protocol(protocol.clone(), Default::default()),
+		);
+
+		limiter.force_consume(&protocol, TrafficDirection::Outbound, 1050);
+
+		let started = Instant::now();
+		limiter.consume(&protocol, TrafficDirection::Outbound, 10).await;
+		assert!(started.elapsed() >= Duration::from_millis(40));
+	}
+}
@@ -41,7 +41,10 @@ use sc_network_types::{
 	PeerId,
 };
 
-use crate::service::{ensure_addresses_consistent_with_transport, traits::NetworkBackend};
+use crate::{
+	peer_database::{PeerDatabase, PEER_DATABASE_FILE},
+	service::{ensure_addresses_consistent_with_transport, traits::NetworkBackend},
+};
 use codec::Encode;
 use prometheus_endpoint::Registry;
 use zeroize::Zeroize;
@@ -55,13 +58,14 @@ pub use sc_network_common::{
 use sp_runtime::traits::Block as BlockT;
 
 use std::{
+	collections::HashMap,
 	error::Error,
 	fmt, fs,
 	future::Future,
 	io::{self, Write},
 	iter,
 	net::Ipv4Addr,
-	num::NonZeroUsize,
+	num::{NonZeroU64, NonZeroUsize},
 	path::{Path, PathBuf},
 	pin::Pin,
 	str::{self, FromStr},
@@ -681,6 +685,16 @@ pub struct NetworkConfiguration {
 
 	/// Networking backend used for P2P communication.
 	pub network_backend: NetworkBackendType,
+
+	/// Bandwidth budgets and priorities of the notification and request-response protocols.
+	///
+	/// Unlimited by default.
+	pub bandwidth: BandwidthConfig,
+
+	/// Persist peer reputations and addresses in `net_config_path` across restarts.
+	///
+	/// See [`PeerDatabase`] for more details.
+	pub persist_peers: bool,
 }
 
 impl NetworkConfiguration {
@@ -715,6 +729,8 @@ impl NetworkConfiguration {
 				.expect("value is a constant; constant is non-zero; qed."),
 			ipfs_server: false,
 			network_backend: NetworkBackendType::Litep2p,
+			bandwidth: BandwidthConfig::default(),
+			persist_peers: false,
 		}
 	}
 
@@ -800,6 +816,9 @@ pub struct FullNetworkConfiguration<B: BlockT + 'static, H: ExHashT, N: NetworkB
 	/// Handle to [`PeerStore`](crate::peer_store::PeerStore).
 	peer_store_handle: Arc<dyn PeerStoreProvider>,
 
+	/// Database of peer reputations and addresses, if persisting peers is enabled.
+	peer_database: Option<PeerDatabase>,
+
 	/// Registry for recording prometheus metrics to.
 	pub metrics_registry: Option<Registry>,
 }
@@ -808,12 +827,29 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 	/// Create new [`FullNetworkConfiguration`].
 	pub fn new(network_config: &NetworkConfiguration, metrics_registry: Option<Registry>) -> Self {
 		let bootnodes = network_config.boot_nodes.iter().map(|bootnode| bootnode.peer_id).collect();
-		let peer_store = N::peer_store(bootnodes, metrics_registry.clone());
+		let mut peer_store = N::peer_store(bootnodes, metrics_registry.clone());
+
+		let peer_database = match (network_config.persist_peers, &network_config.net_config_path) {
+			(true, Some(path)) => Some(PeerDatabase::open(path.join(PEER_DATABASE_FILE))),
+			(true, None) => {
+				log::warn!(
+					target: "sub-libp2p",
+					"Peers are not persisted because no network configuration directory is set",
+				);
+				None
+			},
+			(false, _) => None,
+		};
+		if let Some(database) = &peer_database {
+			peer_store.set_database(database.clone());
+		}
+
 		let peer_store_handle = peer_store.handle();
 
 		Self {
 			peer_store: Some(peer_store),
 			peer_store_handle,
+			peer_database,
 			notification_protocols: Vec::new(),
 			request_response_protocols: Vec::new(),
 			network_config: network_config.clone(),
@@ -841,6 +877,11 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 		Arc::clone(&self.peer_store_handle)
 	}
 
+	/// Get the database of peer reputations and addresses, if persisting peers is enabled.
+	pub fn peer_database(&self) -> Option<PeerDatabase> {
+		self.peer_database.clone()
+	}
+
 	/// Take [`PeerStore`].
 	///
 	/// `PeerStore` is created when `FullNetworkConfig` is initialized so that `PeerStoreHandle`s
@@ -908,7 +949,7 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 		})
 	}
 
-	/// Collect all reserved nodes and bootnodes addresses.
+	/// Collect all reserved nodes, bootnodes and persisted peer addresses.
 	pub fn known_addresses(&self) -> Vec<(PeerId, Multiaddr)> {
 		let mut addresses: Vec<_> = self
 			.network_config
@@ -929,6 +970,7 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 					.iter()
 					.map(|bootnode| (bootnode.peer_id, bootnode.multiaddr.clone())),
 			)
+			.chain(self.peer_database.iter().flat_map(|database| database.addresses()))
 			.collect();
 
 		// Remove possible duplicates.
@@ -957,6 +999,81 @@ pub enum NetworkBackendType {
 	Libp2p,
 }
 
+/// Priority of a protocol's traffic when the total bandwidth budget is contended.
+///
+/// Lower priorities are throttled earlier: they may only draw from the total budget while a
+/// share of it is still left for the higher priorities. Protocols not listed in
+/// [`BandwidthConfig::protocols`] are of high priority.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProtocolPriority {
+	/// Throttled once less than half of the total budget is left.
+	Low,
+
+	/// Throttled once less than a quarter of the total budget is left.
+	#[default]
+	Normal,
+
+	/// Never throttled by the total budget, only charged to it.
+	High,
+}
+
+/// Bandwidth budget of a single notification or request-response protocol.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtocolBandwidthConfig {
+	/// Maximum rate, in bytes per second, of the traffic received over the protocol.
+	pub inbound: Option<NonZeroU64>,
+
+	/// Maximum rate, in bytes per second, of the traffic sent over the protocol.
+	pub outbound: Option<NonZeroU64>,
+
+	/// Priority of the protocol against the total budget.
+	pub priority: ProtocolPriority,
+}
+
+/// Bandwidth budgets of the networking protocols.
+///
+/// Budgets are token buckets refilled at the configured rate and allowed to burst up to one
+/// second worth of traffic. The total budget only throttles the protocols listed in `protocols`
+/// with a priority below [`ProtocolPriority::High`], the traffic of other protocols is charged to
+/// it but never throttled. Inbound requests exceeding the budget are dropped, outbound requests
+/// are queued until the budget allows them, synchronous notifications are dropped and
+/// asynchronous ones wait until the budget allows them. Received notifications exceeding the
+/// inbound budget are dropped before reaching the protocol. The size of a response is only known
+/// once it can no longer be throttled, so it is charged afterwards, and requests are only sent or
+/// accepted while the budget their response is charged to is not exhausted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BandwidthConfig {
+	/// Maximum rate, in bytes per second, of the traffic received over all protocols.
+	pub inbound: Option<NonZeroU64>,
+
+	/// Maximum rate, in bytes per second, of the traffic sent over all protocols.
+	pub outbound: Option<NonZeroU64>,
+
+	/// Budgets of individual protocols, keyed by their main protocol name.
+	pub protocols: HashMap<ProtocolName, ProtocolBandwidthConfig>,
+}
+
+impl BandwidthConfig {
+	/// Set the budget of `protocol`, replacing any previous one.
+	pub fn with_protocol(
+		mut self,
+		protocol: ProtocolName,
+		config: ProtocolBandwidthConfig,
+	) -> Self {
+		self.protocols.insert(protocol, config);
+		self
+	}
+
+	/// Returns `true` if no budget is configured.
+	pub fn is_unlimited(&self) -> bool {
+		self.inbound.is_none() &&
+			self.outbound.is_none() &&
+			self.protocols
+				.values()
+				.all(|config| config.inbound.is_none() && config.outbound.is_none())
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
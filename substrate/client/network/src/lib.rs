@@ -250,11 +250,13 @@ mod protocol;
 #[cfg(test)]
 mod mock;
 
+pub mod bandwidth;
 pub mod config;
 pub mod discovery;
 pub mod error;
 pub mod event;
 pub mod network_state;
+pub mod peer_database;
 pub mod peer_info;
 pub mod peer_store;
 pub mod protocol_controller;
@@ -38,6 +38,7 @@ use crate::{
 			request_response::{RequestResponseConfig, RequestResponseProtocol},
 		},
 	},
+	peer_database::PeerDatabase,
 	peer_store::PeerStoreProvider,
 	service::{
 		metrics::{register_without_sources, MetricSources, Metrics, NotificationMetrics},
@@ -186,6 +187,9 @@ pub struct Litep2pNetworkBackend {
 	/// Peerstore.
 	peerstore_handle: Arc<dyn PeerStoreProvider>,
 
+	/// Database recording the addresses of dialed peers, if persisting peers is enabled.
+	peer_database: Option<PeerDatabase>,
+
 	/// Block announce protocol name.
 	block_announce_protocol: ProtocolName,
 
@@ -396,6 +400,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 			Self::configure_transport(&params.network_config).with_keypair(keypair.clone());
 		let known_addresses = params.network_config.known_addresses();
 		let peer_store_handle = params.network_config.peer_store_handle();
+		let peer_database = params.network_config.peer_database();
 		let executor = Arc::new(Litep2pExecutor { executor: params.executor });
 
 		let FullNetworkConfiguration {
@@ -433,6 +438,15 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 			None => None,
 		};
 
+		// the same limiter is shared by notification protocols, which received it through
+		// `NotificationMetrics` when they were created, and request-response protocols
+		let bandwidth_limiter = params.notification_metrics.bandwidth().clone();
+		bandwidth_limiter.configure(&network_config.bandwidth);
+
+		if let Some(metrics) = &metrics {
+			bandwidth_limiter.set_metrics(metrics);
+		}
+
 		// create channels that are used to send request before initializing protocols so the
 		// senders can be passed onto all request-response protocols
 		//
@@ -470,6 +484,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 						.expect("receiver exists as it was just added and there are no duplicate protocols; qed"),
 					request_response_senders.clone(),
 					metrics.clone(),
+					bandwidth_limiter.clone(),
 				);
 
 				executor.run(Box::pin(async move {
@@ -584,6 +599,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 			discovery,
 			pending_queries: HashMap::new(),
 			peerstore_handle: peer_store_handle,
+			peer_database,
 			block_announce_protocol,
 			event_streams: out_events::OutChannels::new(None)?,
 			peers: HashMap::new(),
@@ -1164,6 +1180,12 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 				},
 				event = self.litep2p.next_event() => match event {
 					Some(Litep2pEvent::ConnectionEstablished { peer, endpoint }) => {
+						if let (Some(database), Endpoint::Dialer { address, .. }) =
+							(&self.peer_database, &endpoint)
+						{
+							database.add_address(peer.into(), address.clone().into());
+						}
+
 						let Some(metrics) = &self.metrics else {
 							continue;
 						};
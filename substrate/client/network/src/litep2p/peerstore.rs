@@ -22,7 +22,8 @@
 //! such as their addresses, reputations, supported protocols etc.
 
 use crate::{
-	peer_store::{PeerStoreProvider, ProtocolHandle},
+	peer_database::{PeerDatabase, PersistOnDrop, PERSIST_INTERVAL},
+	peer_store::{decay_reputation, PeerStoreProvider, ProtocolHandle},
 	service::{metrics::PeerStoreMetrics, traits::PeerStore},
 	ObservedRole, ReputationChange,
 };
@@ -45,19 +46,6 @@ const LOG_TARGET: &str = "sub-libp2p::peerstore";
 /// We don't accept nodes whose reputation is under this value.
 pub const BANNED_THRESHOLD: i32 = 71 * (i32::MIN / 100);
 
-/// Relative decrement of a reputation value that is applied every second. I.e., for inverse
-/// decrement of 200 we decrease absolute value of the reputation by 1/200.
-///
-/// This corresponds to a factor of `k = 0.995`, where k = 1 - 1 / INVERSE_DECREMENT.
-///
-/// It takes ~ `ln(0.5) / ln(k)` seconds to reduce the reputation by half, or 138.63 seconds for the
-/// values above.
-///
-/// In this setup:
-/// - `i32::MAX` becomes 0 in exactly 3544 seconds, or approximately 59 minutes
-/// - `i32::MIN` escapes the banned threshold in 69 seconds
-const INVERSE_DECREMENT: i32 = 200;
-
 /// Amount of time between the moment we last updated the [`PeerStore`] entry and the moment we
 /// remove it, once the reputation value reaches 0.
 const FORGET_AFTER: Duration = Duration::from_secs(3600);
@@ -94,20 +82,7 @@ impl PeerInfo {
 	fn decay_reputation(&mut self, seconds_passed: u64) {
 		// Note that decaying the reputation value happens "on its own",
 		// so we don't do `bump_last_updated()`.
-		for _ in 0..seconds_passed {
-			let mut diff = self.reputation / INVERSE_DECREMENT;
-			if diff == 0 && self.reputation < 0 {
-				diff = -1;
-			} else if diff == 0 && self.reputation > 0 {
-				diff = 1;
-			}
-
-			self.reputation = self.reputation.saturating_sub(diff);
-
-			if self.reputation == 0 {
-				break;
-			}
-		}
+		self.reputation = decay_reputation(self.reputation, seconds_passed);
 	}
 
 	fn bump_last_updated(&mut self) {
@@ -120,6 +95,8 @@ pub struct PeerstoreHandleInner {
 	peers: HashMap<PeerId, PeerInfo>,
 	protocols: Vec<Arc<dyn ProtocolHandle>>,
 	metrics: Option<PeerStoreMetrics>,
+	/// Whether a peer got banned since the reputations were last persisted.
+	new_ban: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -132,7 +109,12 @@ impl PeerstoreHandle {
 		protocols: Vec<Arc<dyn ProtocolHandle>>,
 		metrics: Option<PeerStoreMetrics>,
 	) -> Self {
-		Self(Arc::new(Mutex::new(PeerstoreHandleInner { peers, protocols, metrics })))
+		Self(Arc::new(Mutex::new(PeerstoreHandleInner {
+			peers,
+			protocols,
+			metrics,
+			new_ban: false,
+		})))
 	}
 
 	/// Add known peer to [`Peerstore`].
@@ -147,6 +129,30 @@ impl PeerstoreHandle {
 		self.0.lock().peers.len()
 	}
 
+	/// Set the reputations of peers, adding them to the [`Peerstore`] if they are not known.
+	fn load_reputations(&self, reputations: Vec<(PeerId, i32)>) {
+		let mut lock = self.0.lock();
+
+		for (peer, reputation) in reputations {
+			lock.peers.entry(peer).or_default().reputation = reputation;
+		}
+	}
+
+	/// Check whether a peer got banned since the last call, resetting the flag.
+	fn take_new_ban(&self) -> bool {
+		std::mem::take(&mut self.0.lock().new_ban)
+	}
+
+	/// Get the reputations of known peers and when they were last updated.
+	fn reputations(&self) -> Vec<(PeerId, i32, Instant)> {
+		self.0
+			.lock()
+			.peers
+			.iter()
+			.map(|(peer, info)| (*peer, info.reputation, info.last_updated))
+			.collect()
+	}
+
 	fn progress_time(&self, seconds_passed: u64) {
 		if seconds_passed == 0 {
 			return;
@@ -227,6 +233,7 @@ impl PeerStoreProvider for PeerstoreHandle {
 
 		// The peer is banned for the first time.
 		if !was_banned {
+			lock.new_ban = true;
 			log::warn!(
 				target: LOG_TARGET,
 				"Report {}: {:+} to {}. Reason: {}. Banned, disconnecting.",
@@ -304,6 +311,9 @@ pub fn peerstore_handle_test() -> PeerstoreHandle {
 pub struct Peerstore {
 	/// Handle to `Peerstore`.
 	peerstore_handle: PeerstoreHandle,
+
+	/// Database the reputations are persisted to, if enabled.
+	database: Option<PeerDatabase>,
 }
 
 impl Peerstore {
@@ -326,7 +336,7 @@ impl Peerstore {
 			metrics,
 		);
 
-		Self { peerstore_handle }
+		Self { peerstore_handle, database: None }
 	}
 
 	/// Get mutable reference to the underlying [`PeerstoreHandle`].
@@ -339,10 +349,24 @@ impl Peerstore {
 		self.peerstore_handle.add_known_peer(peer);
 	}
 
+	/// Load the reputations persisted in `database` and keep it updated while running.
+	pub fn set_database(&mut self, database: PeerDatabase) {
+		self.peerstore_handle.load_reputations(database.reputations());
+		self.database = Some(database);
+	}
+
 	/// Start [`Peerstore`] event loop.
+	///
+	/// If a [`PeerDatabase`] was set, the reputations are persisted to it periodically, when a peer
+	/// gets banned and when the [`Peerstore`] stops.
 	async fn run(self) {
 		let started = Instant::now();
 		let mut latest_time_update = started;
+		let mut latest_persist = started;
+		let _persist_on_drop = self.database.clone().map(|database| {
+			let peerstore_handle = self.peerstore_handle.clone();
+			PersistOnDrop::new(database, move || peerstore_handle.reputations())
+		});
 
 		loop {
 			let now = Instant::now();
@@ -356,6 +380,15 @@ impl Peerstore {
 			};
 
 			self.peerstore_handle.progress_time(seconds_passed);
+
+			if let Some(database) = &self.database {
+				let new_ban = self.peerstore_handle.take_new_ban();
+				if new_ban || now - latest_persist >= PERSIST_INTERVAL {
+					latest_persist = now;
+					database.persist_in_background(self.peerstore_handle.reputations());
+				}
+			}
+
 			let _ = Delay::new(Duration::from_secs(1)).await;
 		}
 	}
@@ -368,6 +401,11 @@ impl PeerStore for Peerstore {
 		Arc::new(self.peerstore_handle.clone())
 	}
 
+	/// Load persisted reputations from `database` and persist them to it while running.
+	fn set_database(&mut self, database: PeerDatabase) {
+		self.set_database(database);
+	}
+
 	/// Start running `PeerStore` event loop.
 	async fn run(self) {
 		self.run().await;
@@ -20,6 +20,7 @@
 //! with `NotificationService`.
 
 use crate::{
+	bandwidth::TrafficDirection,
 	error::Error,
 	litep2p::shim::notification::peerset::{OpenResult, Peerset, PeersetNotificationCommand},
 	service::{
@@ -82,6 +83,20 @@ impl MessageSink for Litep2pMessageSink {
 	fn send_sync_notification(&self, notification: Vec<u8>) {
 		let size = notification.len();
 
+		if !self
+			.metrics
+			.bandwidth()
+			.try_consume(&self.protocol, TrafficDirection::Outbound, size)
+		{
+			log::trace!(
+				target: LOG_TARGET,
+				"{}: dropping sync notification to {:?}, bandwidth budget exhausted",
+				self.protocol,
+				self.peer,
+			);
+			return;
+		}
+
 		match self.sink.send_sync_notification(notification) {
 			Ok(_) => self.metrics.register_notification_sent(&self.protocol, size),
 			Err(error) => log::trace!(
@@ -100,6 +115,11 @@ impl MessageSink for Litep2pMessageSink {
 	async fn send_async_notification(&self, notification: Vec<u8>) -> Result<(), Error> {
 		let size = notification.len();
 
+		self.metrics
+			.bandwidth()
+			.consume(&self.protocol, TrafficDirection::Outbound, size)
+			.await;
+
 		match self.sink.send_async_notification(notification).await {
 			Ok(_) => {
 				self.metrics.register_notification_sent(&self.protocol, size);
@@ -203,6 +223,19 @@ impl NotificationService for NotificationProtocol {
 	fn send_sync_notification(&mut self, peer: &PeerId, notification: Vec<u8>) {
 		let size = notification.len();
 
+		if !self
+			.metrics
+			.bandwidth()
+			.try_consume(&self.protocol, TrafficDirection::Outbound, size)
+		{
+			log::trace!(
+				target: LOG_TARGET,
+				"{}: dropping sync notification to {peer:?}, bandwidth budget exhausted",
+				self.protocol,
+			);
+			return;
+		}
+
 		if let Ok(_) = self.handle.send_sync_notification(peer.into(), notification) {
 			self.metrics.register_notification_sent(&self.protocol, size);
 		}
@@ -215,6 +248,11 @@ impl NotificationService for NotificationProtocol {
 	) -> Result<(), Error> {
 		let size = notification.len();
 
+		self.metrics
+			.bandwidth()
+			.consume(&self.protocol, TrafficDirection::Outbound, size)
+			.await;
+
 		match self.handle.send_async_notification(peer.into(), notification).await {
 			Ok(_) => {
 				self.metrics.register_notification_sent(&self.protocol, size);
@@ -349,6 +387,15 @@ impl NotificationService for NotificationProtocol {
 					} => {
 						self.metrics.register_notification_received(&self.protocol, notification.len());
 
+						if !self.metrics.bandwidth().try_consume(&self.protocol, TrafficDirection::Inbound, notification.len()) {
+							log::debug!(
+								target: LOG_TARGET,
+								"{}: dropping notification from {peer:?}, bandwidth budget exhausted",
+								self.protocol,
+							);
+							continue
+						}
+
 						if !self.pending_cancels.contains(&peer) {
 							return Some(SubstrateNotificationEvent::NotificationReceived {
 								peer: peer.into(),
@@ -20,6 +20,7 @@
 //! request-response API.
 
 use crate::{
+	bandwidth::{BandwidthLimiter, TrafficDirection},
 	litep2p::shim::request_response::metrics::RequestResponseMetrics,
 	peer_store::PeerStoreProvider,
 	request_responses::{IncomingRequest, OutgoingResponse},
@@ -41,7 +42,8 @@ use sc_network_types::PeerId;
 use sc_utils::mpsc::{TracingUnboundedReceiver, TracingUnboundedSender};
 
 use std::{
-	collections::HashMap,
+	collections::{HashMap, VecDeque},
+	mem,
 	sync::Arc,
 	time::{Duration, Instant},
 };
@@ -196,6 +198,15 @@ pub struct RequestResponseProtocol {
 
 	/// Metrics, if enabled.
 	metrics: RequestResponseMetrics,
+
+	/// Bandwidth budgets of the protocols.
+	bandwidth: BandwidthLimiter,
+
+	/// Outbound requests waiting for the bandwidth budget, in the order they were issued.
+	throttled_requests: VecDeque<OutboundRequest>,
+
+	/// Fires when the next throttled request may be sent.
+	throttled_requests_delay: futures_timer::Delay,
 }
 
 impl RequestResponseProtocol {
@@ -208,9 +219,11 @@ impl RequestResponseProtocol {
 		request_rx: TracingUnboundedReceiver<OutboundRequest>,
 		request_tx: HashMap<ProtocolName, TracingUnboundedSender<OutboundRequest>>,
 		metrics: Option<Metrics>,
+		bandwidth: BandwidthLimiter,
 	) -> Self {
 		Self {
 			handle,
+			bandwidth,
 			request_rx,
 			request_tx,
 			inbound_queue,
@@ -219,10 +232,15 @@ impl RequestResponseProtocol {
 			pending_inbound_responses: HashMap::new(),
 			pending_outbound_responses: FuturesUnordered::new(),
 			metrics: RequestResponseMetrics::new(metrics, protocol),
+			throttled_requests: VecDeque::new(),
+			throttled_requests_delay: futures_timer::Delay::new(Duration::ZERO),
 		}
 	}
 
 	/// Send `request` to `peer`.
+	///
+	/// If the bandwidth budget is exhausted, the request is queued and sent once the budget
+	/// allows it.
 	async fn on_send_request(
 		&mut self,
 		peer: PeerId,
@@ -245,7 +263,84 @@ impl RequestResponseProtocol {
 			dial_options,
 		);
 
+		// requests queued earlier are sent first, and the response is charged to the inbound
+		// budget once received
+		let throttled = !self.throttled_requests.is_empty() ||
+			!self.bandwidth.has_budget(&self.protocol, TrafficDirection::Inbound) ||
+			!self.bandwidth.try_consume(
+				&self.protocol,
+				TrafficDirection::Outbound,
+				request.len(),
+			);
+
+		if throttled {
+			log::debug!(
+				target: LOG_TARGET,
+				"{}: bandwidth budget exhausted, delaying request to {peer:?}",
+				self.protocol,
+			);
+
+			if self.throttled_requests.is_empty() {
+				let delay = self.bandwidth.request_wait_time(&self.protocol).unwrap_or_default();
+				self.throttled_requests_delay.reset(delay);
+			}
+
+			self.throttled_requests.push_back(OutboundRequest::new(
+				peer,
+				request,
+				tx,
+				fallback_request,
+				connect,
+			));
+			return;
+		}
+
+		self.send_request(peer, request, fallback_request, tx, dial_options);
+	}
+
+	/// Send the throttled requests the bandwidth budget allows again.
+	fn send_throttled_requests(&mut self) {
+		for outbound_request in mem::take(&mut self.throttled_requests) {
+			// the local node is no longer interested in the result
+			if outbound_request.sender.is_canceled() {
+				continue;
+			}
+
+			if !self.throttled_requests.is_empty() {
+				self.throttled_requests.push_back(outbound_request);
+				continue;
+			}
+
+			if let Some(delay) = self.bandwidth.request_wait_time(&self.protocol) {
+				self.throttled_requests_delay.reset(delay);
+				self.throttled_requests.push_back(outbound_request);
+				continue;
+			}
+
+			let OutboundRequest { peer, request, sender, dial_behavior, fallback_request } =
+				outbound_request;
+			let dial_options = match dial_behavior {
+				IfDisconnected::TryConnect => DialOptions::Dial,
+				IfDisconnected::ImmediateError => DialOptions::Reject,
+			};
+
+			self.bandwidth
+				.force_consume(&self.protocol, TrafficDirection::Outbound, request.len());
+			self.send_request(peer, request, fallback_request, sender, dial_options);
+		}
+	}
+
+	/// Send `request` to `peer` without checking the bandwidth budget.
+	fn send_request(
+		&mut self,
+		peer: PeerId,
+		request: Vec<u8>,
+		fallback_request: Option<(Vec<u8>, ProtocolName)>,
+		tx: oneshot::Sender<Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+		dial_options: DialOptions,
+	) {
 		let request_len = request.len();
+
 		match self.handle.try_send_request(peer.into(), request, dial_options) {
 			Ok(request_id) => {
 				self.metrics.register_outbound_request_bytes(request_len);
@@ -307,6 +402,23 @@ impl RequestResponseProtocol {
 			return;
 		}
 
+		// the response is charged to the outbound budget once built
+		if !self.bandwidth.has_budget(&self.protocol, TrafficDirection::Outbound) ||
+			!self
+				.bandwidth
+				.try_consume(&self.protocol, TrafficDirection::Inbound, request.len())
+		{
+			log::debug!(
+				target: LOG_TARGET,
+				"{}: rejecting inbound request from {peer:?} ({request_id:?}), bandwidth budget exhausted",
+				self.protocol,
+			);
+
+			self.handle.reject_request(request_id);
+			self.metrics.register_inbound_request_failure("throttled");
+			return;
+		}
+
 		let (tx, rx) = oneshot::channel();
 
 		match inbound_queue.try_send(IncomingRequest {
@@ -355,6 +467,12 @@ impl RequestResponseProtocol {
 				);
 
 				self.metrics.register_inbound_response_bytes(response.len());
+				// the budget was checked before sending the request
+				self.bandwidth.force_consume(
+					&self.protocol,
+					TrafficDirection::Inbound,
+					response.len(),
+				);
 				let _ = tx.send(Ok((response, self.protocol.clone())));
 				self.metrics.register_outbound_request_success(started.elapsed());
 			},
@@ -527,6 +645,12 @@ impl RequestResponseProtocol {
 				);
 
 				self.metrics.register_outbound_response_bytes(response.len());
+				// the budget was checked before accepting the request
+				self.bandwidth.force_consume(
+					&self.protocol,
+					TrafficDirection::Outbound,
+					response.len(),
+				);
 
 				match sent_feedback {
 					None => self.handle.send_response(request_id, response),
@@ -571,6 +695,9 @@ impl RequestResponseProtocol {
 						self.on_outbound_response(peer, request_id, response, started);
 					}
 				},
+				_ = &mut self.throttled_requests_delay, if !self.throttled_requests.is_empty() => {
+					self.send_throttled_requests();
+				},
 				event = self.request_rx.next() => match event {
 					None => return,
 					Some(outbound_request) => {
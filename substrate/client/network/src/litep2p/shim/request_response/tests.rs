@@ -17,6 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	bandwidth::{BandwidthLimiter, TrafficDirection},
+	config::{BandwidthConfig, ProtocolBandwidthConfig},
 	litep2p::{
 		peerstore::peerstore_handle_test,
 		shim::request_response::{OutboundRequest, RequestResponseProtocol},
@@ -39,7 +41,13 @@ use litep2p::{
 use sc_network_types::PeerId;
 use sc_utils::mpsc::tracing_unbounded;
 
-use std::{collections::HashMap, sync::Arc, task::Poll};
+use std::{
+	collections::HashMap,
+	num::NonZeroU64,
+	sync::Arc,
+	task::Poll,
+	time::{Duration, Instant},
+};
 
 /// Create `litep2p` for testing.
 async fn make_litep2p() -> (Litep2p, RequestResponseHandle) {
@@ -110,6 +118,7 @@ async fn dial_failure() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -146,6 +155,7 @@ async fn send_request_to_disconnected_peer() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -167,6 +177,57 @@ async fn send_request_to_disconnected_peer() {
 	assert!(std::matches!(result_rx.await, Ok(Err(RequestFailure::NotConnected))));
 }
 
+#[tokio::test]
+async fn throttled_request_is_delayed() {
+	let (mut litep2p, handle) = make_litep2p().await;
+	let (tx, _rx) = async_channel::bounded(64);
+	let (outbound_tx, outbound_rx) = tracing_unbounded("outbound-request", 1000);
+	let senders = HashMap::from_iter([(ProtocolName::from("/protocol/1"), outbound_tx.clone())]);
+	let bandwidth = BandwidthLimiter::new(&BandwidthConfig::default().with_protocol(
+		ProtocolName::from("/protocol/1"),
+		ProtocolBandwidthConfig { outbound: NonZeroU64::new(500), ..Default::default() },
+	));
+
+	let protocol = RequestResponseProtocol::new(
+		ProtocolName::from("/protocol/1"),
+		handle,
+		Arc::new(peerstore_handle_test()),
+		Some(tx),
+		outbound_rx,
+		senders,
+		None,
+		bandwidth,
+	);
+
+	tokio::spawn(protocol.run());
+	tokio::spawn(async move { while let Some(_) = litep2p.next_event().await {} });
+
+	let peer = PeerId::random();
+	let started = Instant::now();
+	let mut results = Vec::new();
+
+	// the first request leaves a debt of 500 bytes, which takes a second to pay off
+	for request in [vec![0u8; 1000], vec![1, 2, 3, 4]] {
+		let (result_tx, result_rx) = oneshot::channel();
+
+		outbound_tx
+			.unbounded_send(OutboundRequest {
+				peer,
+				request,
+				sender: result_tx,
+				fallback_request: None,
+				dial_behavior: IfDisconnected::ImmediateError,
+			})
+			.unwrap();
+		results.push(result_rx);
+	}
+
+	for result_rx in results {
+		assert!(std::matches!(result_rx.await, Ok(Err(RequestFailure::NotConnected))));
+	}
+	assert!(started.elapsed() >= Duration::from_millis(900));
+}
+
 #[tokio::test]
 async fn send_request_to_disconnected_peer_and_dial() {
 	let (mut litep2p1, handle1) = make_litep2p().await;
@@ -192,6 +253,7 @@ async fn send_request_to_disconnected_peer_and_dial() {
 		outbound_rx1,
 		senders,
 		None,
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -206,6 +268,7 @@ async fn send_request_to_disconnected_peer_and_dial() {
 		outbound_rx2,
 		senders,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -253,6 +316,7 @@ async fn too_many_inbound_requests() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -289,6 +353,62 @@ async fn too_many_inbound_requests() {
 	.await;
 }
 
+#[tokio::test]
+async fn inbound_request_rejected_without_response_budget() {
+	let (mut litep2p1, handle1) = make_litep2p().await;
+	let (mut litep2p2, mut handle2) = make_litep2p().await;
+	let peer1 = *litep2p1.local_peer_id();
+
+	connect_peers(&mut litep2p1, &mut litep2p2).await;
+
+	let (outbound_tx, outbound_rx) = tracing_unbounded("outbound-request", 1000);
+	let senders = HashMap::from_iter([(ProtocolName::from("/protocol/1"), outbound_tx)]);
+	let (tx, rx) = async_channel::bounded(4);
+	let bandwidth = BandwidthLimiter::new(&BandwidthConfig::default().with_protocol(
+		ProtocolName::from("/protocol/1"),
+		ProtocolBandwidthConfig { outbound: NonZeroU64::new(500), ..Default::default() },
+	));
+
+	// the outbound budget is exhausted, so no response could be sent
+	bandwidth.force_consume(&ProtocolName::from("/protocol/1"), TrafficDirection::Outbound, 1000);
+
+	let protocol = RequestResponseProtocol::new(
+		ProtocolName::from("/protocol/1"),
+		handle1,
+		Arc::new(peerstore_handle_test()),
+		Some(tx),
+		outbound_rx,
+		senders,
+		None,
+		bandwidth,
+	);
+
+	tokio::spawn(protocol.run());
+	tokio::spawn(async move { while let Some(_) = litep2p1.next_event().await {} });
+	tokio::spawn(async move { while let Some(_) = litep2p2.next_event().await {} });
+
+	handle2
+		.send_request(peer1, vec![1, 2, 3, 4], DialOptions::Reject)
+		.await
+		.unwrap();
+
+	match handle2.next().await {
+		Some(RequestResponseEvent::RequestFailed { peer, error, .. }) => {
+			assert_eq!(peer, peer1);
+			assert_eq!(
+				error,
+				RequestResponseError::Rejected(
+					litep2p::protocol::request_response::RejectReason::SubstreamClosed
+				)
+			);
+		},
+		event => panic!("invalid event: {event:?}"),
+	}
+
+	// the request was never handed to the request handler
+	assert!(rx.is_empty());
+}
+
 #[tokio::test]
 async fn feedback_works() {
 	let (mut litep2p1, handle1) = make_litep2p().await;
@@ -311,6 +431,7 @@ async fn feedback_works() {
 		outbound_rx,
 		senders,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol.run());
@@ -428,6 +549,7 @@ async fn fallback_request_compatible_peers() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
 	);
 
 	let (tx_fallback, _rx_fallback) = async_channel::bounded(4);
@@ -439,6 +561,7 @@ async fn fallback_request_compatible_peers() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -453,6 +576,7 @@ async fn fallback_request_compatible_peers() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -570,6 +694,7 @@ async fn fallback_request_old_peer_receives() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
 	);
 
 	let (tx_fallback, _rx_fallback) = async_channel::bounded(4);
@@ -581,6 +706,7 @@ async fn fallback_request_old_peer_receives() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -595,6 +721,7 @@ async fn fallback_request_old_peer_receives() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -712,6 +839,7 @@ async fn fallback_request_old_peer_sends() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
 	);
 
 	let (tx_fallback, rx_fallback) = async_channel::bounded(4);
@@ -723,6 +851,7 @@ async fn fallback_request_old_peer_sends() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -737,6 +866,7 @@ async fn fallback_request_old_peer_sends() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -853,6 +983,7 @@ async fn old_protocol_supported_but_no_fallback_provided() {
 		outbound_rx1,
 		senders1.clone(),
 		None,
+		Default::default(),
 	);
 
 	let (tx_fallback, _rx_fallback) = async_channel::bounded(4);
@@ -864,6 +995,7 @@ async fn old_protocol_supported_but_no_fallback_provided() {
 		outbound_rx_fallback,
 		senders1,
 		None,
+		Default::default(),
 	);
 
 	let (outbound_tx2, outbound_rx2) = tracing_unbounded("outbound-request", 1000);
@@ -878,6 +1010,7 @@ async fn old_protocol_supported_but_no_fallback_provided() {
 		outbound_rx2,
 		senders2,
 		None,
+		Default::default(),
 	);
 
 	tokio::spawn(protocol1.run());
@@ -0,0 +1,528 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk database of peer reputations and addresses.
+//!
+//! [`PeerDatabase`] lets the node remember, across restarts, the reputations tracked by the peer
+//! store and the addresses of peers it managed to dial. Reputations keep decaying while the node
+//! is down, so bans on abusive peers only outlive a restart for as long as they would have lasted
+//! had the node kept running. Persisted addresses are handed to the networking backend as known
+//! addresses on startup so the node can reconnect to known-good peers without waiting for
+//! discovery.
+//!
+//! The database is shared by both networking backends: the peer store persists its reputations
+//! periodically, when a peer gets banned and when it stops, and the backend records the addresses
+//! of established outbound connections. The periodic writes happen on a dedicated thread, off the
+//! async tasks of the networking.
+
+use crate::peer_store::{decay_reputation, BANNED_THRESHOLD, LOG_TARGET};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use sc_network_types::{
+	multiaddr::{Multiaddr, Protocol},
+	PeerId,
+};
+
+use std::{
+	collections::HashMap,
+	fs, io,
+	path::{Path, PathBuf},
+	sync::{mpsc, Arc},
+	thread,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the peer database file in the network configuration directory.
+pub const PEER_DATABASE_FILE: &str = "peers.json";
+
+/// Version of the on-disk format.
+const VERSION: u32 = 1;
+
+/// How often the peer store persists its reputations.
+pub(crate) const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum number of persisted peers.
+const MAX_PEERS: usize = 1000;
+
+/// Maximum number of persisted addresses per peer.
+const MAX_ADDRESSES_PER_PEER: usize = 4;
+
+/// Addresses of peers not seen for this long are forgotten.
+const ADDRESS_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Peer entry of the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerEntry {
+	/// Reputation of the peer.
+	reputation: i32,
+
+	/// Addresses the peer was successfully dialed at, most recent last.
+	addresses: Vec<Multiaddr>,
+
+	/// UNIX timestamp, in seconds, of the last time the peer was seen.
+	last_seen: u64,
+}
+
+/// Content of the database file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerDatabaseFile {
+	/// Version of the format.
+	version: u32,
+
+	/// UNIX timestamp, in seconds, of when the file was written.
+	saved_at: u64,
+
+	/// Persisted peers.
+	peers: HashMap<PeerId, PeerEntry>,
+}
+
+/// Current UNIX time in seconds.
+fn unix_time() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+/// Remove the trailing `/p2p/<peer>` component of `address`, if any.
+fn strip_peer_id(mut address: Multiaddr) -> Multiaddr {
+	if let Some(Protocol::P2p(_)) = address.iter().last() {
+		address.pop();
+	}
+
+	address
+}
+
+/// State shared by all clones of [`PeerDatabase`].
+#[derive(Debug)]
+struct Inner {
+	/// Path of the database file.
+	path: PathBuf,
+
+	/// Persisted peers.
+	peers: HashMap<PeerId, PeerEntry>,
+}
+
+impl Inner {
+	/// Forget peers that carry no information anymore and enforce the size limits.
+	fn prune(&mut self, now: u64) {
+		let ttl = ADDRESS_TTL.as_secs();
+
+		self.peers.retain(|_, entry| {
+			if entry.last_seen.saturating_add(ttl) < now {
+				entry.addresses.clear();
+			}
+
+			entry.reputation != 0 || !entry.addresses.is_empty()
+		});
+
+		if self.peers.len() > MAX_PEERS {
+			// keep the peers with the strongest opinion, good or bad, then the most recent ones
+			let mut peers = self.peers.drain().collect::<Vec<_>>();
+			peers.sort_by(|(_, a), (_, b)| {
+				b.reputation
+					.unsigned_abs()
+					.cmp(&a.reputation.unsigned_abs())
+					.then(b.last_seen.cmp(&a.last_seen))
+			});
+			peers.truncate(MAX_PEERS);
+			self.peers = peers.into_iter().collect();
+		}
+	}
+}
+
+/// Handle to the on-disk peer database.
+///
+/// Clones share the same database.
+#[derive(Debug, Clone)]
+pub struct PeerDatabase {
+	/// Persisted peers.
+	inner: Arc<Mutex<Inner>>,
+
+	/// Held while writing the database file, so that writes happen in the order of the snapshots
+	/// they write.
+	save_lock: Arc<Mutex<()>>,
+
+	/// Requests to the writer thread to save the database. The thread exits once all the clones
+	/// of the database are dropped.
+	save_requests: mpsc::SyncSender<()>,
+}
+
+impl PeerDatabase {
+	/// Open the database stored at `path`, applying the reputation decay for the time elapsed
+	/// since it was last saved.
+	///
+	/// A missing or unreadable file results in an empty database.
+	pub fn open(path: impl Into<PathBuf>) -> Self {
+		let path = path.into();
+		let now = unix_time();
+
+		let peers = match Self::load(&path) {
+			Ok(Some(file)) => {
+				let downtime = now.saturating_sub(file.saved_at);
+
+				log::debug!(
+					target: LOG_TARGET,
+					"Loaded {} peers from {}, saved {downtime} seconds ago",
+					file.peers.len(),
+					path.display(),
+				);
+
+				file.peers
+					.into_iter()
+					.map(|(peer, mut entry)| {
+						entry.reputation = decay_reputation(entry.reputation, downtime);
+						(peer, entry)
+					})
+					.collect()
+			},
+			Ok(None) => HashMap::new(),
+			Err(error) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to load peer database from {}, starting empty: {error}",
+					path.display(),
+				);
+				HashMap::new()
+			},
+		};
+
+		let mut inner = Inner { path, peers };
+		inner.prune(now);
+
+		let inner = Arc::new(Mutex::new(inner));
+		let save_lock = Arc::new(Mutex::new(()));
+		// A single pending request is enough, as each save writes the latest state.
+		let (save_requests, pending_saves) = mpsc::sync_channel(1);
+
+		let writer = {
+			let inner = inner.clone();
+			let save_lock = save_lock.clone();
+			move || {
+				while pending_saves.recv().is_ok() {
+					if let Err(error) = Self::save_state(&inner, &save_lock) {
+						log::warn!(target: LOG_TARGET, "Failed to persist peer database: {error}");
+					}
+				}
+			}
+		};
+		if let Err(error) = thread::Builder::new().name("peer-database".into()).spawn(writer) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to spawn the peer database writer, it is only saved on shutdown: {error}",
+			);
+		}
+
+		Self { inner, save_lock, save_requests }
+	}
+
+	/// Read the database file at `path`, if it exists.
+	fn load(path: &Path) -> io::Result<Option<PeerDatabaseFile>> {
+		let content = match fs::read(path) {
+			Ok(content) => content,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+			Err(error) => return Err(error),
+		};
+
+		let file: PeerDatabaseFile = serde_json::from_slice(&content)?;
+		if file.version != VERSION {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("unsupported version {}", file.version),
+			));
+		}
+
+		Ok(Some(file))
+	}
+
+	/// Get the persisted reputations.
+	pub fn reputations(&self) -> Vec<(PeerId, i32)> {
+		self.inner
+			.lock()
+			.peers
+			.iter()
+			.map(|(peer, entry)| (*peer, entry.reputation))
+			.collect()
+	}
+
+	/// Get the persisted addresses of peers which are not banned.
+	pub fn addresses(&self) -> Vec<(PeerId, Multiaddr)> {
+		self.inner
+			.lock()
+			.peers
+			.iter()
+			.filter(|(_, entry)| entry.reputation >= BANNED_THRESHOLD)
+			.flat_map(|(peer, entry)| {
+				entry.addresses.iter().map(|address| (*peer, address.clone()))
+			})
+			.collect()
+	}
+
+	/// Record that `peer` was successfully dialed at `address`.
+	pub fn add_address(&self, peer: PeerId, address: Multiaddr) {
+		let address = strip_peer_id(address);
+		let mut inner = self.inner.lock();
+		let entry = inner.peers.entry(peer).or_default();
+
+		entry.addresses.retain(|known| known != &address);
+		entry.addresses.push(address);
+		if entry.addresses.len() > MAX_ADDRESSES_PER_PEER {
+			entry.addresses.remove(0);
+		}
+		entry.last_seen = unix_time();
+	}
+
+	/// Replace the persisted reputations with `reputations` and write the database to disk.
+	///
+	/// See [`Self::update_reputations`] for the content of `reputations`.
+	pub fn persist(
+		&self,
+		reputations: impl IntoIterator<Item = (PeerId, i32, Instant)>,
+	) -> io::Result<()> {
+		self.update_reputations(reputations);
+		self.save()
+	}
+
+	/// Replace the persisted reputations with `reputations` and write the database to disk on the
+	/// writer thread.
+	///
+	/// See [`Self::update_reputations`] for the content of `reputations`.
+	pub fn persist_in_background(
+		&self,
+		reputations: impl IntoIterator<Item = (PeerId, i32, Instant)>,
+	) {
+		self.update_reputations(reputations);
+
+		// A full channel means a save is already pending, which will include this update.
+		let _ = self.save_requests.try_send(());
+	}
+
+	/// Replace the persisted reputations with `reputations`, without writing them to disk.
+	///
+	/// `reputations` contains the reputation of each peer known to the peer store and when it
+	/// was last updated. Peers missing from it are considered to have a reputation of 0.
+	fn update_reputations(&self, reputations: impl IntoIterator<Item = (PeerId, i32, Instant)>) {
+		let now = unix_time();
+		let instant_now = Instant::now();
+		let mut inner = self.inner.lock();
+
+		inner.peers.values_mut().for_each(|entry| entry.reputation = 0);
+
+		for (peer, reputation, last_updated) in reputations {
+			if reputation == 0 {
+				continue;
+			}
+
+			let entry = inner.peers.entry(peer).or_default();
+			let last_updated =
+				now.saturating_sub(instant_now.saturating_duration_since(last_updated).as_secs());
+
+			entry.reputation = reputation;
+			entry.last_seen = entry.last_seen.max(last_updated);
+		}
+
+		inner.prune(now);
+	}
+
+	/// Write the database to disk.
+	fn save(&self) -> io::Result<()> {
+		Self::save_state(&self.inner, &self.save_lock)
+	}
+
+	/// Write the current content of `inner` to disk.
+	fn save_state(inner: &Mutex<Inner>, save_lock: &Mutex<()>) -> io::Result<()> {
+		let _save_guard = save_lock.lock();
+
+		let (path, file) = {
+			let inner = inner.lock();
+			let file = PeerDatabaseFile {
+				version: VERSION,
+				saved_at: unix_time(),
+				peers: inner.peers.clone(),
+			};
+			(inner.path.clone(), file)
+		};
+
+		Self::write(&path, &file)
+	}
+
+	/// Atomically write `file` to `path`.
+	fn write(path: &Path, file: &PeerDatabaseFile) -> io::Result<()> {
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		let tmp_path = path.with_extension("tmp");
+		fs::write(&tmp_path, serde_json::to_vec(file)?)?;
+		fs::rename(&tmp_path, path)
+	}
+}
+
+/// Persists the reputations of a peer store when dropped, that is when its task stops.
+pub(crate) struct PersistOnDrop<F: Fn() -> Vec<(PeerId, i32, Instant)>> {
+	database: PeerDatabase,
+	reputations: F,
+}
+
+impl<F: Fn() -> Vec<(PeerId, i32, Instant)>> PersistOnDrop<F> {
+	/// Persist the reputations returned by `reputations` to `database` once dropped.
+	pub(crate) fn new(database: PeerDatabase, reputations: F) -> Self {
+		Self { database, reputations }
+	}
+}
+
+impl<F: Fn() -> Vec<(PeerId, i32, Instant)>> Drop for PersistOnDrop<F> {
+	fn drop(&mut self) {
+		// The peer store is shutting down, so writing synchronously is fine here.
+		if let Err(error) = self.database.persist((self.reputations)()) {
+			log::warn!(target: LOG_TARGET, "Failed to persist peer database: {error}");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn address(port: u16) -> Multiaddr {
+		format!("/ip4/127.0.0.1/tcp/{port}").parse().unwrap()
+	}
+
+	#[test]
+	fn peers_survive_restart() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_DATABASE_FILE);
+		let good = PeerId::random();
+		let banned = PeerId::random();
+		let forgotten = PeerId::random();
+
+		let database = PeerDatabase::open(&path);
+		assert!(database.reputations().is_empty());
+
+		database.add_address(good, address(1));
+		database.add_address(banned, address(2));
+		database
+			.persist(vec![
+				(good, 1000, Instant::now()),
+				(banned, i32::MIN, Instant::now()),
+				(forgotten, 0, Instant::now()),
+			])
+			.unwrap();
+
+		let database = PeerDatabase::open(&path);
+		let reputations = database.reputations().into_iter().collect::<HashMap<_, _>>();
+		assert_eq!(reputations.len(), 2);
+		assert!(reputations[&good] > 0);
+		assert!(reputations[&banned] < BANNED_THRESHOLD);
+
+		// addresses of banned peers are not handed out
+		assert_eq!(database.addresses(), vec![(good, address(1))]);
+	}
+
+	#[test]
+	fn reputations_decay_during_downtime() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_DATABASE_FILE);
+		let good = PeerId::random();
+		let banned = PeerId::random();
+
+		let saved_at = unix_time() - 1800;
+		let file = PeerDatabaseFile {
+			version: VERSION,
+			saved_at,
+			peers: HashMap::from([
+				(
+					good,
+					PeerEntry {
+						reputation: 1000,
+						addresses: vec![address(1)],
+						last_seen: saved_at,
+					},
+				),
+				(
+					banned,
+					PeerEntry { reputation: i32::MIN, addresses: vec![], last_seen: saved_at },
+				),
+			]),
+		};
+		PeerDatabase::write(&path, &file).unwrap();
+
+		let database = PeerDatabase::open(&path);
+		let reputations = database.reputations().into_iter().collect::<HashMap<_, _>>();
+
+		// the good peer's reputation decayed to 0 but its address is kept
+		assert_eq!(reputations[&good], 0);
+		assert_eq!(database.addresses(), vec![(good, address(1))]);
+
+		// the ban expired
+		assert!(reputations[&banned] >= BANNED_THRESHOLD);
+		assert!(reputations[&banned] < 0);
+	}
+
+	#[test]
+	fn addresses_are_deduplicated_and_bounded() {
+		let dir = tempfile::tempdir().unwrap();
+		let database = PeerDatabase::open(dir.path().join(PEER_DATABASE_FILE));
+		let peer = PeerId::random();
+
+		for port in 0..10 {
+			database.add_address(peer, address(port));
+		}
+		database.add_address(peer, address(9).with(Protocol::P2p(peer.into())));
+
+		let addresses =
+			database.addresses().into_iter().map(|(_, address)| address).collect::<Vec<_>>();
+		assert_eq!(addresses, vec![address(6), address(7), address(8), address(9)]);
+	}
+
+	#[test]
+	fn peers_are_persisted_in_background_and_on_drop() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_DATABASE_FILE);
+		let first = PeerId::random();
+		let second = PeerId::random();
+
+		let database = PeerDatabase::open(&path);
+		database.persist_in_background(vec![(first, -100, Instant::now())]);
+
+		let started = Instant::now();
+		while !path.exists() {
+			assert!(started.elapsed() < Duration::from_secs(10), "database was never written");
+			thread::sleep(Duration::from_millis(10));
+		}
+
+		drop(PersistOnDrop::new(database, || {
+			vec![(first, -100, Instant::now()), (second, 100, Instant::now())]
+		}));
+		assert_eq!(PeerDatabase::open(&path).reputations().len(), 2);
+	}
+
+	#[test]
+	fn corrupted_database_is_ignored() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_DATABASE_FILE);
+		fs::write(&path, b"garbage").unwrap();
+
+		let database = PeerDatabase::open(&path);
+		assert!(database.reputations().is_empty());
+
+		database.persist(vec![(PeerId::random(), -100, Instant::now())]).unwrap();
+		assert_eq!(PeerDatabase::open(&path).reputations().len(), 1);
+	}
+}
@@ -19,7 +19,10 @@
 //! [`PeerStore`] manages peer reputations and provides connection candidates to
 //! [`crate::protocol_controller::ProtocolController`].
 
-use crate::service::{metrics::PeerStoreMetrics, traits::PeerStore as PeerStoreT};
+use crate::{
+	peer_database::{PeerDatabase, PersistOnDrop, PERSIST_INTERVAL},
+	service::{metrics::PeerStoreMetrics, traits::PeerStore as PeerStoreT},
+};
 
 use libp2p::PeerId;
 use log::trace;
@@ -60,6 +63,26 @@ const INVERSE_DECREMENT: i32 = 200;
 /// remove it, once the reputation value reaches 0.
 const FORGET_AFTER: Duration = Duration::from_secs(3600);
 
+/// Drive `reputation` towards 0 as it happens every second for `seconds_passed` seconds.
+pub(crate) fn decay_reputation(mut reputation: i32, seconds_passed: u64) -> i32 {
+	for _ in 0..seconds_passed {
+		let mut diff = reputation / INVERSE_DECREMENT;
+		if diff == 0 && reputation < 0 {
+			diff = -1;
+		} else if diff == 0 && reputation > 0 {
+			diff = 1;
+		}
+
+		reputation = reputation.saturating_sub(diff);
+
+		if reputation == 0 {
+			break;
+		}
+	}
+
+	reputation
+}
+
 /// Trait describing the required functionality from a `Peerset` handle.
 pub trait ProtocolHandle: Debug + Send + Sync {
 	/// Disconnect peer.
@@ -208,20 +231,7 @@ impl PeerInfo {
 	fn decay_reputation(&mut self, seconds_passed: u64) {
 		// Note that decaying the reputation value happens "on its own",
 		// so we don't do `bump_last_updated()`.
-		for _ in 0..seconds_passed {
-			let mut diff = self.reputation / INVERSE_DECREMENT;
-			if diff == 0 && self.reputation < 0 {
-				diff = -1;
-			} else if diff == 0 && self.reputation > 0 {
-				diff = 1;
-			}
-
-			self.reputation = self.reputation.saturating_sub(diff);
-
-			if self.reputation == 0 {
-				break;
-			}
-		}
+		self.reputation = decay_reputation(self.reputation, seconds_passed);
 	}
 
 	fn bump_last_updated(&mut self) {
@@ -234,6 +244,8 @@ struct PeerStoreInner {
 	peers: HashMap<PeerId, PeerInfo>,
 	protocols: Vec<Arc<dyn ProtocolHandle>>,
 	metrics: Option<PeerStoreMetrics>,
+	/// Whether a peer got banned since the reputations were last persisted.
+	new_ban: bool,
 }
 
 impl PeerStoreInner {
@@ -291,6 +303,7 @@ impl PeerStoreInner {
 
 		// The peer is banned for the first time.
 		if !was_banned {
+			self.new_ban = true;
 			log::warn!(
 				target: LOG_TARGET,
 				"Report {}: {:+} to {}. Reason: {}. Banned, disconnecting.",
@@ -379,6 +392,23 @@ impl PeerStoreInner {
 		}
 	}
 
+	fn load_reputations(&mut self, reputations: Vec<(sc_network_types::PeerId, i32)>) {
+		for (peer_id, reputation) in reputations {
+			self.peers.entry(peer_id.into()).or_default().reputation = reputation;
+		}
+	}
+
+	fn take_new_ban(&mut self) -> bool {
+		std::mem::take(&mut self.new_ban)
+	}
+
+	fn reputations(&self) -> Vec<(sc_network_types::PeerId, i32, Instant)> {
+		self.peers
+			.iter()
+			.map(|(peer_id, info)| (peer_id.into(), info.reputation, info.last_updated))
+			.collect()
+	}
+
 	fn add_known_peer(&mut self, peer_id: PeerId) {
 		match self.peers.entry(peer_id) {
 			Entry::Occupied(mut e) => {
@@ -400,6 +430,7 @@ impl PeerStoreInner {
 #[derive(Debug)]
 pub struct PeerStore {
 	inner: Arc<Mutex<PeerStoreInner>>,
+	database: Option<PeerDatabase>,
 }
 
 impl PeerStore {
@@ -424,19 +455,35 @@ impl PeerStore {
 					.collect(),
 				protocols: Vec::new(),
 				metrics,
+				new_ban: false,
 			})),
+			database: None,
 		}
 	}
 
+	/// Load the reputations persisted in `database` and keep it updated while running.
+	pub fn set_database(&mut self, database: PeerDatabase) {
+		self.inner.lock().load_reputations(database.reputations());
+		self.database = Some(database);
+	}
+
 	/// Get `PeerStoreHandle`.
 	pub fn handle(&self) -> PeerStoreHandle {
 		PeerStoreHandle { inner: self.inner.clone() }
 	}
 
 	/// Drive the `PeerStore`, decaying reputation values over time and removing expired entries.
+	///
+	/// If a [`PeerDatabase`] was set, the reputations are persisted to it periodically, when a peer
+	/// gets banned and when the `PeerStore` stops.
 	pub async fn run(self) {
 		let started = Instant::now();
 		let mut latest_time_update = started;
+		let mut latest_persist = started;
+		let _persist_on_drop = self.database.clone().map(|database| {
+			let inner = self.inner.clone();
+			PersistOnDrop::new(database, move || inner.lock().reputations())
+		});
 
 		loop {
 			let now = Instant::now();
@@ -450,6 +497,17 @@ impl PeerStore {
 			};
 
 			self.inner.lock().progress_time(seconds_passed);
+
+			if let Some(database) = &self.database {
+				let new_ban = self.inner.lock().take_new_ban();
+				if new_ban || now - latest_persist >= PERSIST_INTERVAL {
+					latest_persist = now;
+
+					let reputations = self.inner.lock().reputations();
+					database.persist_in_background(reputations);
+				}
+			}
+
 			let _ = Delay::new(Duration::from_secs(1)).await;
 		}
 	}
@@ -461,6 +519,10 @@ impl PeerStoreT for PeerStore {
 		Arc::new(self.handle())
 	}
 
+	fn set_database(&mut self, database: PeerDatabase) {
+		self.set_database(database);
+	}
+
 	async fn run(self) {
 		self.run().await;
 	}
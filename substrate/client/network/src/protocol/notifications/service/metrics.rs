@@ -16,7 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{service::metrics::NotificationMetrics, types::ProtocolName};
+use crate::{
+	bandwidth::TrafficDirection, service::metrics::NotificationMetrics, types::ProtocolName,
+};
+
+use std::sync::Arc;
 
 /// Register opened substream to Prometheus.
 pub fn register_substream_opened(metrics: &Option<NotificationMetrics>, protocol: &ProtocolName) {
@@ -34,7 +38,7 @@ pub fn register_substream_closed(metrics: &Option<NotificationMetrics>, protocol
 
 /// Register sent notification to Prometheus.
 pub fn register_notification_sent(
-	metrics: &Option<std::sync::Arc<NotificationMetrics>>,
+	metrics: &Option<Arc<NotificationMetrics>>,
 	protocol: &ProtocolName,
 	size: usize,
 ) {
@@ -53,3 +57,41 @@ pub fn register_notification_received(
 		metrics.register_notification_received(protocol, size);
 	}
 }
+
+/// Charge a notification about to be sent to the bandwidth budget of `protocol`.
+///
+/// Returns `false` if the budget is exhausted and the notification must be dropped.
+pub fn try_consume_sent(
+	metrics: &Option<Arc<NotificationMetrics>>,
+	protocol: &ProtocolName,
+	size: usize,
+) -> bool {
+	metrics.as_ref().map_or(true, |metrics| {
+		metrics.bandwidth().try_consume(protocol, TrafficDirection::Outbound, size)
+	})
+}
+
+/// Charge a notification about to be sent to the bandwidth budget of `protocol`, waiting until
+/// the budget allows it.
+pub async fn consume_sent(
+	metrics: &Option<Arc<NotificationMetrics>>,
+	protocol: &ProtocolName,
+	size: usize,
+) {
+	if let Some(metrics) = metrics {
+		metrics.bandwidth().consume(protocol, TrafficDirection::Outbound, size).await;
+	}
+}
+
+/// Charge a received notification to the bandwidth budget of `protocol`.
+///
+/// Returns `false` if the budget is exhausted and the notification must be dropped.
+pub fn try_consume_received(
+	metrics: &Option<NotificationMetrics>,
+	protocol: &ProtocolName,
+	size: usize,
+) -> bool {
+	metrics.as_ref().map_or(true, |metrics| {
+		metrics.bandwidth().try_consume(protocol, TrafficDirection::Inbound, size)
+	})
+}
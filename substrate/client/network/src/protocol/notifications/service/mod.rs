@@ -69,6 +69,10 @@ impl MessageSink for NotificationSink {
 	fn send_sync_notification(&self, notification: Vec<u8>) {
 		let sink = self.lock();
 
+		if !metrics::try_consume_sent(sink.0.metrics(), &sink.1, notification.len()) {
+			return;
+		}
+
 		metrics::register_notification_sent(sink.0.metrics(), &sink.1, notification.len());
 		sink.0.send_sync_notification(notification);
 	}
@@ -83,6 +87,9 @@ impl MessageSink for NotificationSink {
 		// method is also used by `NetworkService` when sending notifications.
 		let notification_len = notification.len();
 		let sink = self.lock().clone();
+
+		metrics::consume_sent(sink.0.metrics(), &sink.1, notification_len).await;
+
 		let permit = sink
 			.0
 			.reserve_notification()
@@ -234,6 +241,10 @@ impl NotificationService for NotificationHandle {
 	/// Send synchronous `notification` to `peer`.
 	fn send_sync_notification(&mut self, peer: &sc_network_types::PeerId, notification: Vec<u8>) {
 		if let Some(info) = self.peers.get(&((*peer).into())) {
+			if !metrics::try_consume_sent(info.sink.metrics(), &self.protocol, notification.len()) {
+				return;
+			}
+
 			metrics::register_notification_sent(
 				info.sink.metrics(),
 				&self.protocol,
@@ -257,6 +268,8 @@ impl NotificationService for NotificationHandle {
 			.ok_or_else(|| error::Error::PeerDoesntExist((*peer).into()))?
 			.sink;
 
+		metrics::consume_sent(sink.metrics(), &self.protocol, notification_len).await;
+
 		sink.reserve_notification()
 			.await
 			.map_err(|_| error::Error::ConnectionClosed)?
@@ -582,6 +595,15 @@ impl ProtocolHandle {
 	) -> Result<(), ()> {
 		metrics::register_notification_received(&self.metrics, &self.protocol, notification.len());
 
+		if !metrics::try_consume_received(&self.metrics, &self.protocol, notification.len()) {
+			log::debug!(
+				target: LOG_TARGET,
+				"{}: dropping notification from {peer:?}, bandwidth budget exhausted",
+				self.protocol,
+			);
+			return Ok(());
+		}
+
 		let mut subscribers = self.subscribers.lock();
 		log::trace!(target: LOG_TARGET, "{}: notification received from {peer:?}", self.protocol);
 
@@ -35,6 +35,7 @@
 //! is used to handle incoming requests.
 
 use crate::{
+	bandwidth::{BandwidthLimiter, TrafficDirection},
 	peer_store::{PeerStoreProvider, BANNED_THRESHOLD},
 	service::traits::RequestResponseConfig as RequestResponseConfigT,
 	types::ProtocolName,
@@ -53,8 +54,8 @@ use libp2p::{
 };
 
 use std::{
-	collections::{hash_map::Entry, HashMap},
-	io, iter,
+	collections::{hash_map::Entry, HashMap, VecDeque},
+	io, iter, mem,
 	ops::Deref,
 	pin::Pin,
 	sync::Arc,
@@ -385,6 +386,17 @@ pub struct RequestResponsesBehaviour {
 	/// Primarily used to get a reputation of a node.
 	peer_store: Arc<dyn PeerStoreProvider>,
 
+	/// Bandwidth budgets of the protocols.
+	bandwidth: BandwidthLimiter,
+
+	/// Outbound requests waiting for the bandwidth budget of their protocol, in the order they
+	/// were issued.
+	throttled_requests: VecDeque<ThrottledRequest>,
+
+	/// Fires when the next throttled request may be sent, set if `throttled_requests` is not
+	/// empty.
+	throttled_requests_delay: Option<futures_timer::Delay>,
+
 	/// Interval to check that the requests are not taking too long.
 	///
 	/// We had issues in the past where libp2p did not produce a timeout event in due time.
@@ -394,6 +406,16 @@ pub struct RequestResponsesBehaviour {
 	periodic_request_check: tokio::time::Interval,
 }
 
+/// Outbound request waiting for the bandwidth budget of its protocol.
+struct ThrottledRequest {
+	target: PeerId,
+	protocol_name: ProtocolName,
+	request: Vec<u8>,
+	fallback_request: Option<(Vec<u8>, ProtocolName)>,
+	pending_response: oneshot::Sender<Result<(Vec<u8>, ProtocolName), RequestFailure>>,
+	connect: IfDisconnected,
+}
+
 /// Generated by the response builder and waiting to be processed.
 struct RequestProcessingOutcome {
 	peer: PeerId,
@@ -409,6 +431,7 @@ impl RequestResponsesBehaviour {
 	pub fn new(
 		list: impl Iterator<Item = ProtocolConfig>,
 		peer_store: Arc<dyn PeerStoreProvider>,
+		bandwidth: BandwidthLimiter,
 	) -> Result<Self, RegisterError> {
 		let mut protocols = HashMap::new();
 		for protocol in list {
@@ -450,6 +473,9 @@ impl RequestResponsesBehaviour {
 			pending_responses_arrival_time: Default::default(),
 			send_feedback: Default::default(),
 			peer_store,
+			bandwidth,
+			throttled_requests: Default::default(),
+			throttled_requests_delay: None,
 			periodic_request_check: tokio::time::interval(PERIODIC_REQUEST_CHECK),
 		})
 	}
@@ -460,6 +486,9 @@ impl RequestResponsesBehaviour {
 	/// choice of `connect`.
 	///
 	/// An error is returned if the protocol doesn't match one that has been registered.
+	///
+	/// If the bandwidth budget of the protocol is exhausted, the request is queued and sent once
+	/// the budget allows it.
 	pub fn send_request(
 		&mut self,
 		target: &PeerId,
@@ -474,6 +503,42 @@ impl RequestResponsesBehaviour {
 		if let Some(ProtocolDetails { behaviour, .. }) =
 			self.protocols.get_mut(protocol_name.deref())
 		{
+			// requests queued earlier for the same protocol are sent first, and the response is
+			// charged to the inbound budget once received
+			let throttled = self
+				.throttled_requests
+				.iter()
+				.any(|throttled| throttled.protocol_name == protocol_name) ||
+				!self.bandwidth.has_budget(&protocol_name, TrafficDirection::Inbound) ||
+				!self.bandwidth.try_consume(
+					&protocol_name,
+					TrafficDirection::Outbound,
+					request.len(),
+				);
+
+			if throttled {
+				log::trace!(
+					target: LOG_TARGET,
+					"Bandwidth budget of {protocol_name:?} exhausted, delaying request to {target}",
+				);
+
+				if self.throttled_requests_delay.is_none() {
+					let delay =
+						self.bandwidth.request_wait_time(&protocol_name).unwrap_or_default();
+					self.throttled_requests_delay = Some(futures_timer::Delay::new(delay));
+				}
+
+				self.throttled_requests.push_back(ThrottledRequest {
+					target: *target,
+					protocol_name,
+					request,
+					fallback_request,
+					pending_response,
+					connect,
+				});
+				return;
+			}
+
 			Self::send_request_inner(
 				behaviour,
 				&mut self.pending_requests,
@@ -494,6 +559,65 @@ impl RequestResponsesBehaviour {
 		}
 	}
 
+	/// Send the throttled requests the bandwidth budget allows again.
+	///
+	/// Returns how long to wait until the remaining requests may be sent, or `None` if no request
+	/// is left.
+	fn send_throttled_requests(&mut self) -> Option<Duration> {
+		let mut blocked = HashMap::<ProtocolName, Duration>::new();
+		let mut remaining = VecDeque::new();
+
+		for throttled in mem::take(&mut self.throttled_requests) {
+			// the local node is no longer interested in the result
+			if throttled.pending_response.is_canceled() {
+				continue;
+			}
+
+			if blocked.contains_key(&throttled.protocol_name) {
+				remaining.push_back(throttled);
+				continue;
+			}
+
+			if let Some(delay) = self.bandwidth.request_wait_time(&throttled.protocol_name) {
+				blocked.insert(throttled.protocol_name.clone(), delay);
+				remaining.push_back(throttled);
+				continue;
+			}
+
+			let ThrottledRequest {
+				target,
+				protocol_name,
+				request,
+				fallback_request,
+				pending_response,
+				connect,
+			} = throttled;
+
+			// requests are only queued for registered protocols
+			let Some(ProtocolDetails { behaviour, .. }) = self.protocols.get_mut(&protocol_name)
+			else {
+				continue
+			};
+
+			self.bandwidth
+				.force_consume(&protocol_name, TrafficDirection::Outbound, request.len());
+
+			Self::send_request_inner(
+				behaviour,
+				&mut self.pending_requests,
+				&target,
+				protocol_name,
+				request,
+				fallback_request,
+				pending_response,
+				connect,
+			);
+		}
+
+		self.throttled_requests = remaining;
+		blocked.into_values().min()
+	}
+
 	fn send_request_inner(
 		behaviour: &mut Behaviour<GenericCodec>,
 		pending_requests: &mut HashMap<ProtocolRequestId<OutboundRequestId>, PendingRequest>,
@@ -632,6 +756,16 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 
 	fn poll(&mut self, cx: &mut Context) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
 		'poll_all: loop {
+			// Send the throttled requests once the bandwidth budget allows them.
+			while let Some(delay) = self.throttled_requests_delay.as_mut() {
+				if delay.poll_unpin(cx).is_pending() {
+					break
+				}
+
+				self.throttled_requests_delay =
+					self.send_throttled_requests().map(futures_timer::Delay::new);
+			}
+
 			// Poll the periodic request check.
 			if self.periodic_request_check.poll_tick(cx).is_ready() {
 				self.pending_requests.retain(|id, req| {
@@ -698,6 +832,13 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 					{
 						log::trace!(target: LOG_TARGET, "send response to {peer} ({protocol_name:?}), {} bytes", payload.len());
 
+						// the budget was checked before accepting the request
+						self.bandwidth.force_consume(
+							&protocol_name,
+							TrafficDirection::Outbound,
+							payload.len(),
+						);
+
 						if behaviour.send_response(inner_channel, Ok(payload)).is_err() {
 							// Note: Failure is handled further below when receiving
 							// `InboundFailure` event from request-response [`Behaviour`].
@@ -778,6 +919,22 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 								continue 'poll_protocol;
 							}
 
+							// the response is charged to the outbound budget once built
+							if !self.bandwidth.has_budget(protocol, TrafficDirection::Outbound) ||
+								!self.bandwidth.try_consume(
+									protocol,
+									TrafficDirection::Inbound,
+									request.len(),
+								) {
+								log::debug!(
+									target: LOG_TARGET,
+									"Dropping request from {} on {:?}, bandwidth budget exhausted",
+									peer,
+									protocol,
+								);
+								continue 'poll_protocol;
+							}
+
 							let (tx, rx) = oneshot::channel();
 
 							// Submit the request to the "response builder" passed by the user at
@@ -841,6 +998,15 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 										response.as_ref().map_or(0usize, |response| response.len()),
 									);
 
+									// the budget was checked before sending the request
+									if let Ok(response) = &response {
+										self.bandwidth.force_consume(
+											protocol,
+											TrafficDirection::Inbound,
+											response.len(),
+										);
+									}
+
 									let delivered = response_tx
 										.send(
 											response
@@ -1197,7 +1363,9 @@ mod tests {
 			.multiplex(libp2p::yamux::Config::default())
 			.boxed();
 
-		let behaviour = RequestResponsesBehaviour::new(list, Arc::new(MockPeerStore {})).unwrap();
+		let behaviour =
+			RequestResponsesBehaviour::new(list, Arc::new(MockPeerStore {}), Default::default())
+				.unwrap();
 
 		let mut swarm = Swarm::new(
 			transport,
@@ -40,6 +40,7 @@ use crate::{
 	network_state::{
 		NetworkState, NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
 	},
+	peer_database::PeerDatabase,
 	peer_store::{PeerStore, PeerStoreProvider},
 	protocol::{self, Protocol, Ready},
 	protocol_controller::{self, ProtoSetConfig, ProtocolController, SetId},
@@ -261,6 +262,7 @@ where
 	/// `worker.service()`. The `NetworkService` can be shared through the codebase.
 	pub fn new(params: Params<B, H, Self>) -> Result<Self, Error> {
 		let peer_store_handle = params.network_config.peer_store_handle();
+		let peer_database = params.network_config.peer_database();
 		let FullNetworkConfiguration {
 			notification_protocols,
 			request_response_protocols,
@@ -395,7 +397,7 @@ where
 				.collect();
 
 		let known_addresses = {
-			// Collect all reserved nodes and bootnodes addresses.
+			// Collect all reserved nodes, bootnodes and persisted peer addresses.
 			let mut addresses: Vec<_> = network_config
 				.default_peers_set
 				.reserved_nodes
@@ -414,6 +416,7 @@ where
 						.iter()
 						.map(|bootnode| (bootnode.peer_id, bootnode.multiaddr.clone())),
 				)
+				.chain(peer_database.iter().flat_map(|database| database.addresses()))
 				.collect();
 
 			// Remove possible duplicates.
@@ -456,6 +459,11 @@ where
 		let num_connected = Arc::new(AtomicUsize::new(0));
 		let external_addresses = Arc::new(Mutex::new(HashSet::new()));
 
+		// the same limiter is shared by notification protocols, which received it through
+		// `NotificationMetrics` when they were created, and request-response protocols
+		let bandwidth_limiter = params.notification_metrics.bandwidth().clone();
+		bandwidth_limiter.configure(&network_config.bandwidth);
+
 		let (protocol, notif_protocol_handles) = Protocol::new(
 			From::from(&params.role),
 			params.notification_metrics,
@@ -518,6 +526,7 @@ where
 					discovery_config,
 					request_response_protocols,
 					Arc::clone(&peer_store_handle),
+					bandwidth_limiter.clone(),
 					external_addresses.clone(),
 					network_config.public_addresses.iter().cloned().map(Into::into).collect(),
 					ConnectionLimits::default()
@@ -570,6 +579,10 @@ where
 			None => None,
 		};
 
+		if let Some(metrics) = &metrics {
+			bandwidth_limiter.set_metrics(metrics);
+		}
+
 		// Listen on multiaddresses.
 		for addr in &network_config.listen_addresses {
 			if let Err(err) = Swarm::<Behaviour<B>>::listen_on(&mut swarm, addr.clone().into()) {
@@ -611,6 +624,7 @@ where
 			boot_node_ids,
 			reported_invalid_boot_nodes: Default::default(),
 			peer_store_handle: Arc::clone(&peer_store_handle),
+			peer_database,
 			notif_protocol_handles,
 			_marker: Default::default(),
 			_block: Default::default(),
@@ -1370,6 +1384,8 @@ where
 	reported_invalid_boot_nodes: HashSet<PeerId>,
 	/// Peer reputation store handle.
 	peer_store_handle: Arc<dyn PeerStoreProvider>,
+	/// Database recording the addresses of dialed peers, if persisting peers is enabled.
+	peer_database: Option<PeerDatabase>,
 	/// Notification protocol handles.
 	notif_protocol_handles: Vec<protocol::ProtocolHandle>,
 	/// Marker to pin the `H` generic. Serves no purpose except to not break backwards
@@ -1726,6 +1742,12 @@ where
 					debug!(target: LOG_TARGET, "Libp2p => Connected({:?})", peer_id);
 				}
 
+				if let (Some(database), ConnectedPoint::Dialer { address, .. }) =
+					(&self.peer_database, &endpoint)
+				{
+					database.add_address(peer_id.into(), address.clone().into());
+				}
+
 				if let Some(metrics) = self.metrics.as_ref() {
 					let direction = match endpoint {
 						ConnectedPoint::Dialer { .. } => "out",
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{service::traits::BandwidthSink, ProtocolName};
+use crate::{bandwidth::BandwidthLimiter, service::traits::BandwidthSink, ProtocolName};
 
 use prometheus_endpoint::{
 	self as prometheus, Counter, CounterVec, Gauge, GaugeVec, HistogramOpts, MetricSource, Opts,
@@ -66,6 +66,7 @@ impl MetricSources {
 #[derive(Clone)]
 pub struct Metrics {
 	// This list is ordered alphabetically
+	pub bandwidth_throttled_total: CounterVec<U64>,
 	pub connections_closed_total: CounterVec<U64>,
 	pub connections_opened_total: CounterVec<U64>,
 	pub distinct_peers_connections_closed_total: Counter<U64>,
@@ -92,6 +93,14 @@ impl Metrics {
 	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
 		Ok(Self {
 			// This list is ordered alphabetically
+			bandwidth_throttled_total: prometheus::register(CounterVec::new(
+				Opts::new(
+					"substrate_sub_libp2p_bandwidth_throttled_total",
+					"Total number of messages delayed or dropped because the bandwidth budget \
+					of the protocol was exhausted"
+				),
+				&["direction", "protocol"]
+			)?, registry)?,
 			connections_closed_total: prometheus::register(CounterVec::new(
 				Opts::new(
 					"substrate_sub_libp2p_connections_closed_total",
@@ -312,10 +321,16 @@ impl MetricSource for NumConnectedGauge {
 /// Notification metrics.
 ///
 /// Wrapper over `Option<InnerNotificationMetrics>` to make metrics reporting code cleaner.
+///
+/// Also carries the [`BandwidthLimiter`] of the notification protocols since the same instance
+/// is handed to every protocol and to the networking backend, which configures it on startup.
 #[derive(Debug, Clone)]
 pub struct NotificationMetrics {
 	/// Metrics, if enabled.
 	metrics: Option<InnerNotificationMetrics>,
+
+	/// Bandwidth budgets of the notification protocols.
+	bandwidth: BandwidthLimiter,
 }
 
 impl NotificationMetrics {
@@ -326,7 +341,12 @@ impl NotificationMetrics {
 			None => None,
 		};
 
-		Self { metrics }
+		Self { metrics, bandwidth: BandwidthLimiter::default() }
+	}
+
+	/// Get the bandwidth limiter shared by the notification protocols.
+	pub fn bandwidth(&self) -> &BandwidthLimiter {
+		&self.bandwidth
 	}
 
 	/// Register opened substream to Prometheus.
@@ -25,6 +25,7 @@ use crate::{
 	error::{self, Error},
 	event::Event,
 	network_state::NetworkState,
+	peer_database::PeerDatabase,
 	request_responses::{IfDisconnected, RequestFailure},
 	service::{metrics::NotificationMetrics, signature::Signature, PeerStoreProvider},
 	types::ProtocolName,
@@ -104,6 +105,9 @@ pub trait PeerStore {
 	/// Get handle to `PeerStore`.
 	fn handle(&self) -> Arc<dyn PeerStoreProvider>;
 
+	/// Load persisted reputations from `database` and persist them to it while running.
+	fn set_database(&mut self, database: PeerDatabase);
+
 	/// Start running `PeerStore` event loop.
 	async fn run(self);
 }
@@ -51,6 +51,9 @@ pub enum Error {
 	/// `SessionKeys` runtime api missing.
 	#[error("`SessionKeys` runtime api not present in the runtime")]
 	MissingSessionKeysApi,
+	/// The sender of the requested extrinsics is empty.
+	#[error("The sender must not be empty")]
+	EmptySender,
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
@@ -20,7 +20,7 @@
 
 use error::Error;
 use jsonrpsee::proc_macros::rpc;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{TransactionReport, TransactionStatus};
 use sp_core::Bytes;
 
 pub mod error;
@@ -82,6 +82,30 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_pendingExtrinsics")]
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>, Error>;
 
+	/// Returns the report explaining the state of the extrinsic with given hash in the pool.
+	///
+	/// The report lists every view of the pool which knows the extrinsic, whether it is ready or
+	/// future there (with the required tags still missing), its priority and longevity. For
+	/// extrinsics recently removed from the pool the reason of removal is reported.
+	///
+	/// Returns `None` if the extrinsic is unknown to the pool.
+	#[method(name = "author_extrinsicReport")]
+	async fn extrinsic_report(
+		&self,
+		hash: Hash,
+	) -> Result<Option<TransactionReport<Hash, BlockHash>>, Error>;
+
+	/// Returns the reports explaining the state of the extrinsics sent by `sender` in the pool.
+	///
+	/// `sender` is the SCALE encoded account id of the sender. An extrinsic is considered to be
+	/// sent by `sender` if any of the tags it requires or provides starts with `sender`, which is
+	/// the case for the nonce tags of FRAME based runtimes. The `sender` must not be empty.
+	#[method(name = "author_extrinsicReportsBySender", with_extensions)]
+	async fn extrinsic_reports_by_sender(
+		&self,
+		sender: Bytes,
+	) -> Result<Vec<TransactionReport<Hash, BlockHash>>, Error>;
+
 	/// Remove given extrinsic from the pool and temporarily ban it to prevent reimporting.
 	#[method(name = "author_removeExtrinsic", with_extensions)]
 	async fn remove_extrinsic(
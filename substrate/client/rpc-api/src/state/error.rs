@@ -47,6 +47,9 @@ pub enum Error {
 		/// Maximum allowed value
 		max: u32,
 	},
+	/// Calling a view function failed.
+	#[error("View function call failed: {}", .0)]
+	ViewFunction(String),
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
@@ -64,6 +67,7 @@ impl From<Error> for ErrorObjectOwned {
 			Error::InvalidCount { .. } => {
 				ErrorObject::owned(BASE_ERROR + 2, e.to_string(), None::<()>)
 			},
+			Error::ViewFunction(_) => ErrorObject::owned(BASE_ERROR + 4, e.to_string(), None::<()>),
 			e => ErrorObject::owned(BASE_ERROR + 3, e.to_string(), None::<()>),
 		}
 	}
@@ -30,3 +30,28 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Arguments of a view function call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ViewFunctionArgs {
+	/// The SCALE encoded arguments, as expected by the runtime.
+	Scale(Bytes),
+	/// One JSON value per argument, encoded according to the argument types found in the
+	/// runtime metadata.
+	///
+	/// Composites are given as JSON objects or arrays, enum variants as
+	/// `{ "name": <variant>, "values": <fields> }` and byte sequences as `0x`-prefixed hex
+	/// strings.
+	Json(Vec<serde_json::Value>),
+}
+
+/// Output of a view function call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewFunctionOutput {
+	/// The SCALE encoded output, as returned by the runtime.
+	pub scale: Bytes,
+	/// The output decoded according to the output type found in the runtime metadata.
+	pub json: serde_json::Value,
+}
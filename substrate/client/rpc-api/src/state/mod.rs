@@ -28,7 +28,7 @@ use sp_version::RuntimeVersion;
 pub mod error;
 pub mod helpers;
 
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, ViewFunctionArgs, ViewFunctionOutput};
 pub use error::Error;
 
 /// Substrate state API
@@ -38,6 +38,19 @@ pub trait StateApi<Hash> {
 	#[method(name = "state_call", aliases = ["state_callAt"], blocking)]
 	fn call(&self, name: String, bytes: Bytes, hash: Option<Hash>) -> Result<Bytes, Error>;
 
+	/// Call a view function of a pallet at a block's state.
+	///
+	/// The arguments and the output are encoded and decoded according to the types found in the
+	/// runtime metadata.
+	#[method(name = "state_callViewFunction", blocking)]
+	fn call_view_function(
+		&self,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+		hash: Option<Hash>,
+	) -> Result<ViewFunctionOutput, Error>;
+
 	/// Returns the keys with prefix, leave empty to get all the keys.
 	#[method(name = "state_getKeys", blocking)]
 	#[deprecated(since = "2.0.0", note = "Please use `getKeysPaged` with proper paging support")]
@@ -43,7 +43,7 @@ pub use jsonrpsee::{
 	server::{middleware::rpc::RpcServiceBuilder, BatchRequestConfig},
 };
 pub use middleware::{Metrics, MiddlewareLayer, NodeHealthProxyLayer, RpcMetrics};
-pub use utils::{RpcEndpoint, RpcMethods};
+pub use utils::{ConnectionClosed, RpcEndpoint, RpcMethods};
 
 const MEGABYTE: u32 = 1024 * 1024;
 
@@ -265,7 +265,8 @@ where
 
 						async move {
 							if is_websocket {
-								let on_disconnect = svc.on_session_closed();
+								let on_disconnect = ConnectionClosed::new(svc.on_session_closed());
+								req.extensions_mut().insert(on_disconnect.clone());
 
 								// Spawn a task to handle when the connection is closed.
 								tokio_handle.spawn(async move {
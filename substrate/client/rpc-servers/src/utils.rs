@@ -21,12 +21,16 @@
 use crate::BatchRequestConfig;
 use std::{
 	error::Error as StdError,
+	future::Future,
 	net::{IpAddr, SocketAddr},
 	num::NonZeroU32,
+	pin::Pin,
 	str::FromStr,
+	task::{Context, Poll},
 };
 
 use forwarded_header_value::ForwardedHeaderValue;
+use futures::future::{BoxFuture, FutureExt, Shared};
 use http::header::{HeaderName, HeaderValue};
 use ip_network::IpNetwork;
 use jsonrpsee::{server::middleware::http::HostFilterLayer, RpcModule};
@@ -48,6 +52,28 @@ impl std::fmt::Display for ListenAddrError {
 	}
 }
 
+/// Resolves when the websocket connection a JSON-RPC call was made over is closed.
+///
+/// It is inserted in the extensions of the calls made over websocket connections. Calls made over
+/// HTTP don't have it, as each of them gets its own connection.
+#[derive(Clone)]
+pub struct ConnectionClosed(Shared<BoxFuture<'static, ()>>);
+
+impl ConnectionClosed {
+	/// Create a new [`ConnectionClosed`] resolving with the given future.
+	pub fn new(closed: impl Future<Output = ()> + Send + 'static) -> Self {
+		Self(closed.boxed().shared())
+	}
+}
+
+impl Future for ConnectionClosed {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		self.0.poll_unpin(cx)
+	}
+}
+
 /// Available RPC methods.
 #[derive(Debug, Copy, Clone)]
 pub enum RpcMethods {
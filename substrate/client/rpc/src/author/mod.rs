@@ -31,7 +31,7 @@ use jsonrpsee::{core::async_trait, types::ErrorObject, Extensions, PendingSubscr
 use sc_rpc_api::check_if_safe;
 use sc_transaction_pool_api::{
 	error::IntoPoolError, BlockHash, InPoolTransaction, TransactionFor, TransactionPool,
-	TransactionSource, TxHash, TxInvalidityReportMap,
+	TransactionReport, TransactionSource, TxHash, TxInvalidityReportMap,
 };
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -192,6 +192,28 @@ where
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
 
+	async fn extrinsic_report(
+		&self,
+		hash: TxHash<P>,
+	) -> Result<Option<TransactionReport<TxHash<P>, BlockHash<P>>>> {
+		Ok(self.pool.transaction_report(hash).await)
+	}
+
+	async fn extrinsic_reports_by_sender(
+		&self,
+		ext: &Extensions,
+		sender: Bytes,
+	) -> Result<Vec<TransactionReport<TxHash<P>, BlockHash<P>>>> {
+		check_if_safe(ext)?;
+
+		// An empty prefix would match the tags of every extrinsic in the pool.
+		if sender.is_empty() {
+			return Err(Error::EmptySender);
+		}
+
+		Ok(self.pool.transaction_reports_by_sender(sender.0).await)
+	}
+
 	async fn remove_extrinsic(
 		&self,
 		ext: &Extensions,
@@ -218,6 +218,26 @@ async fn author_should_remove_extrinsics() {
 	assert_eq!(removed, vec![xt1_hash, xt2_hash, xt3_hash]);
 }
 
+#[tokio::test]
+async fn author_extrinsic_reports_by_sender_checks_sender() {
+	const METHOD: &'static str = "author_extrinsicReportsBySender";
+	let setup = TestSetup::default();
+	let api = setup.to_rpc();
+
+	assert_matches!(
+		api.call::<_, Vec<serde_json::Value>>(METHOD, [Bytes(vec![])]).await,
+		Err(RpcError::JsonRpc(err)) if err.message() == "The sender must not be empty"
+	);
+
+	let mut api = setup.to_rpc();
+	api.extensions_mut().insert(DenyUnsafe::Yes);
+	let sender = Bytes(Sr25519Keyring::Alice.to_raw_public_vec());
+	assert_matches!(
+		api.call::<_, Vec<serde_json::Value>>(METHOD, [sender]).await,
+		Err(RpcError::JsonRpc(err)) if err.message() == "RPC call is unsafe to be called externally"
+	);
+}
+
 #[tokio::test]
 async fn author_should_insert_key() {
 	let setup = TestSetup::default();
@@ -36,7 +36,7 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ExecutorProvider, ProofProvider,
 	StorageProvider,
 };
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, ViewFunctionArgs, ViewFunctionOutput};
 use sc_tracing::block::TracingExecuteBlock;
 use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
 use sp_blockchain::{
@@ -67,6 +67,7 @@ pub struct FullState<BE, Block: BlockT, Client> {
 	client: Arc<Client>,
 	executor: SubscriptionTaskExecutor,
 	execute_block: Option<Arc<dyn TracingExecuteBlock<Block>>>,
+	view_function_metadata: super::view_functions::MetadataCache,
 	_phantom: PhantomData<BE>,
 }
 
@@ -85,7 +86,13 @@ where
 		executor: SubscriptionTaskExecutor,
 		execute_block: Option<Arc<dyn TracingExecuteBlock<Block>>>,
 	) -> Self {
-		Self { client, executor, execute_block, _phantom: PhantomData }
+		Self {
+			client,
+			executor,
+			execute_block,
+			view_function_metadata: Default::default(),
+			_phantom: PhantomData,
+		}
 	}
 
 	/// Returns given block hash or best block hash if None is passed.
@@ -210,6 +217,24 @@ where
 			.map_err(client_err)
 	}
 
+	fn call_view_function(
+		&self,
+		block: Option<Block::Hash>,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> std::result::Result<ViewFunctionOutput, Error> {
+		let block = self.block_or_best(block).map_err(client_err)?;
+		super::view_functions::call_view_function(
+			&*self.client,
+			&self.view_function_metadata,
+			block,
+			&pallet,
+			&function,
+			args,
+		)
+	}
+
 	// TODO: This is horribly broken; either remove it, or make it streaming.
 	fn storage_keys(
 		&self,
@@ -207,6 +207,96 @@ async fn should_call_contract() {
 	)
 }
 
+#[tokio::test]
+async fn should_reject_unknown_view_function() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let (client, _child) = new_full(client, test_executor(), None);
+
+	assert_matches!(
+		client.call_view_function(
+			"Unknown".into(),
+			"get_value".into(),
+			ViewFunctionArgs::Json(vec![]),
+			Some(genesis_hash).into()
+		),
+		Err(Error::ViewFunction(_))
+	)
+}
+
+#[test]
+fn should_convert_view_function_args_and_output() {
+	use frame_metadata::v16::{
+		FunctionParamMetadata, ItemDeprecationInfo, PalletViewFunctionMetadata,
+	};
+	use scale_info::{form::PortableForm, meta_type, PortableRegistry, Registry, TypeInfo};
+	use serde_json::json;
+
+	#[derive(codec::Encode, TypeInfo)]
+	enum Query {
+		Balance { who: [u8; 4] },
+		Total,
+	}
+
+	let mut registry = Registry::new();
+	let view_function = PalletViewFunctionMetadata::<PortableForm> {
+		id: [0; 32],
+		name: "query".into(),
+		inputs: vec![
+			FunctionParamMetadata {
+				name: "key".into(),
+				ty: registry.register_type(&meta_type::<u128>()),
+			},
+			FunctionParamMetadata {
+				name: "query".into(),
+				ty: registry.register_type(&meta_type::<Query>()),
+			},
+		],
+		output: registry.register_type(&meta_type::<Option<(u64, Vec<u8>)>>()),
+		docs: vec![],
+		deprecation_info: ItemDeprecationInfo::NotDeprecated,
+	};
+	let types = PortableRegistry::from(registry);
+	let encode_args = |args| view_functions::encode_args(&view_function, &types, args);
+
+	assert_eq!(
+		encode_args(vec![
+			json!(7),
+			json!({ "name": "Balance", "values": { "who": "0x01020304" } })
+		])
+		.unwrap(),
+		codec::Encode::encode(&(7u128, Query::Balance { who: [1, 2, 3, 4] })),
+	);
+	assert_eq!(
+		encode_args(vec![json!(7), json!({ "name": "Total", "values": [] })]).unwrap(),
+		codec::Encode::encode(&(7u128, Query::Total)),
+	);
+	// Integers can be given as decimal strings, even above `u64::MAX`.
+	assert_eq!(
+		encode_args(vec![json!(u128::MAX.to_string()), json!({ "name": "Total", "values": [] })])
+			.unwrap(),
+		codec::Encode::encode(&(u128::MAX, Query::Total)),
+	);
+	// Wrong number of arguments.
+	assert_matches!(encode_args(vec![json!(7)]), Err(Error::ViewFunction(_)));
+	// Wrong argument type.
+	assert_matches!(
+		encode_args(vec![json!("seven"), json!({ "name": "Total", "values": [] })]),
+		Err(Error::ViewFunction(_))
+	);
+
+	let output = codec::Encode::encode(&Some((5u64, vec![1u8, 2])));
+	assert_eq!(
+		view_functions::decode_output(&view_function, &types, &output).unwrap(),
+		json!({ "name": "Some", "values": [[5, [1, 2]]] }),
+	);
+	// Trailing bytes are not allowed.
+	assert_matches!(
+		view_functions::decode_output(&view_function, &types, &[output, vec![0]].concat()),
+		Err(Error::ViewFunction(_))
+	);
+}
+
 #[tokio::test]
 async fn should_notify_about_storage_changes() {
 	let mut sub = {
@@ -0,0 +1,306 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Calling FRAME view functions.
+//!
+//! View functions are looked up by pallet and function name in the runtime metadata, which
+//! also provides the types used to encode the arguments and to decode the output. The metadata
+//! of the most recently used runtimes is kept in a [`MetadataCache`].
+
+use super::{client_err, error::Error};
+use codec::{Decode, Encode};
+use frame_metadata::{
+	v16::{PalletViewFunctionMetadata, RuntimeMetadataV16},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use parking_lot::Mutex;
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use sc_rpc_api::state::{ViewFunctionArgs, ViewFunctionOutput};
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef, TypeDefPrimitive};
+use scale_value::{Composite, Value, ValueDef};
+use sp_core::{hexdisplay::HexDisplay, traits::CallContext, OpaqueMetadata};
+use sp_runtime::traits::Block as BlockT;
+use sp_version::RuntimeVersion;
+use std::{collections::VecDeque, str::FromStr, sync::Arc};
+
+/// The first metadata version exposing the view functions.
+const VIEW_FUNCTIONS_METADATA_VERSION: u32 = 16;
+
+/// Runtime API method used to execute the view functions.
+const EXECUTE_VIEW_FUNCTION: &str = "RuntimeViewFunction_execute_view_function";
+
+/// Number of runtimes whose metadata is kept by a [`MetadataCache`].
+const METADATA_CACHE_SIZE: usize = 4;
+
+/// Cache of the decoded metadata of the most recently used runtimes, by runtime version.
+#[derive(Debug, Default)]
+pub struct MetadataCache(Mutex<VecDeque<(RuntimeVersion, Arc<RuntimeMetadataV16>)>>);
+
+impl MetadataCache {
+	/// Get the metadata of the runtime with the given `version`, calling `fetch` if it isn't
+	/// cached.
+	fn get_or_fetch(
+		&self,
+		version: RuntimeVersion,
+		fetch: impl FnOnce() -> Result<RuntimeMetadataV16, Error>,
+	) -> Result<Arc<RuntimeMetadataV16>, Error> {
+		{
+			let mut entries = self.0.lock();
+			if let Some(position) = entries.iter().position(|(cached, _)| *cached == version) {
+				let entry = entries.remove(position).expect("position was just found; qed");
+				let metadata = entry.1.clone();
+				entries.push_front(entry);
+				return Ok(metadata);
+			}
+		}
+
+		// Fetched without holding the lock, as it executes the runtime.
+		let metadata = Arc::new(fetch()?);
+		let mut entries = self.0.lock();
+		entries.retain(|(cached, _)| *cached != version);
+		entries.push_front((version, metadata.clone()));
+		entries.truncate(METADATA_CACHE_SIZE);
+		Ok(metadata)
+	}
+}
+
+/// Mirror of `frame_support::view_functions::ViewFunctionDispatchError`.
+#[derive(Debug, Decode)]
+enum DispatchError {
+	NotImplemented,
+	NotFound([u8; 32]),
+	Codec,
+}
+
+fn view_function_err(msg: impl Into<String>) -> Error {
+	Error::ViewFunction(msg.into())
+}
+
+/// Call the view function `function` of `pallet` at the given block.
+///
+/// The metadata of the runtime is taken from `metadata_cache`, or added to it.
+pub fn call_view_function<Block, Client>(
+	client: &Client,
+	metadata_cache: &MetadataCache,
+	at: Block::Hash,
+	pallet: &str,
+	function: &str,
+	args: ViewFunctionArgs,
+) -> Result<ViewFunctionOutput, Error>
+where
+	Block: BlockT,
+	Client: ExecutorProvider<Block>,
+{
+	let call = |method: &str, call_data: &[u8]| {
+		client
+			.executor()
+			.call(at, method, call_data, CallContext::Offchain)
+			.map_err(client_err)
+	};
+
+	let version = client.executor().runtime_version(at).map_err(client_err)?;
+	let metadata = metadata_cache.get_or_fetch(version, || {
+		let metadata =
+			call("Metadata_metadata_at_version", &VIEW_FUNCTIONS_METADATA_VERSION.encode())?;
+		let metadata = Option::<OpaqueMetadata>::decode(&mut &metadata[..])
+			.map_err(|e| view_function_err(format!("Failed to decode metadata: {e}")))?
+			.ok_or_else(|| {
+				view_function_err(format!(
+					"Runtime doesn't support metadata V{VIEW_FUNCTIONS_METADATA_VERSION}"
+				))
+			})?;
+		match RuntimeMetadataPrefixed::decode(&mut &metadata[..]) {
+			Ok(RuntimeMetadataPrefixed(_, RuntimeMetadata::V16(metadata))) => Ok(metadata),
+			Ok(RuntimeMetadataPrefixed(_, other)) => {
+				Err(view_function_err(format!("Unexpected metadata version: V{}", other.version())))
+			},
+			Err(e) => Err(view_function_err(format!("Failed to decode metadata: {e}"))),
+		}
+	})?;
+
+	let view_function = find_view_function(&metadata, pallet, function)?;
+	let input = match args {
+		ViewFunctionArgs::Scale(input) => input.0,
+		ViewFunctionArgs::Json(args) => encode_args(view_function, &metadata.types, args)?,
+	};
+
+	let result = call(EXECUTE_VIEW_FUNCTION, &(view_function.id, input).encode())?;
+	let output = match Result::<Vec<u8>, DispatchError>::decode(&mut &result[..]) {
+		Ok(Ok(output)) => output,
+		Ok(Err(DispatchError::NotImplemented)) => {
+			return Err(view_function_err("View functions are not implemented by the runtime"))
+		},
+		Ok(Err(DispatchError::NotFound(id))) => {
+			return Err(view_function_err(format!(
+				"View function 0x{} not found by the runtime",
+				HexDisplay::from(&id)
+			)))
+		},
+		Ok(Err(DispatchError::Codec)) => {
+			return Err(view_function_err("Failed to decode the view function arguments"))
+		},
+		Err(e) => return Err(view_function_err(format!("Failed to decode the result: {e}"))),
+	};
+
+	let json = decode_output(view_function, &metadata.types, &output)?;
+	Ok(ViewFunctionOutput { scale: output.into(), json })
+}
+
+/// Find the metadata of a view function by pallet and function name.
+fn find_view_function<'a>(
+	metadata: &'a RuntimeMetadataV16,
+	pallet: &str,
+	function: &str,
+) -> Result<&'a PalletViewFunctionMetadata<PortableForm>, Error> {
+	metadata
+		.pallets
+		.iter()
+		.find(|p| p.name == pallet)
+		.ok_or_else(|| view_function_err(format!("Pallet `{pallet}` not found")))?
+		.view_functions
+		.iter()
+		.find(|f| f.name == function)
+		.ok_or_else(|| {
+			view_function_err(format!("View function `{function}` not found in `{pallet}`"))
+		})
+}
+
+/// SCALE encode the JSON arguments of a view function.
+pub(super) fn encode_args(
+	view_function: &PalletViewFunctionMetadata<PortableForm>,
+	types: &PortableRegistry,
+	args: Vec<serde_json::Value>,
+) -> Result<Vec<u8>, Error> {
+	if args.len() != view_function.inputs.len() {
+		return Err(view_function_err(format!(
+			"`{}` expects {} arguments, got {}",
+			view_function.name,
+			view_function.inputs.len(),
+			args.len()
+		)));
+	}
+
+	let mut input = Vec::new();
+	for (arg, param) in args.into_iter().zip(&view_function.inputs) {
+		let value = match integer_type(param.ty.id, types) {
+			Some(signed) => json_to_integer(arg, signed),
+			None => json_to_value(arg),
+		};
+		scale_value::scale::encode_as_type(&value, param.ty.id, types, &mut input)
+			.map_err(|e| view_function_err(format!("Invalid argument `{}`: {e}", param.name)))?;
+	}
+	Ok(input)
+}
+
+/// Decode the SCALE encoded output of a view function into JSON.
+pub(super) fn decode_output(
+	view_function: &PalletViewFunctionMetadata<PortableForm>,
+	types: &PortableRegistry,
+	output: &[u8],
+) -> Result<serde_json::Value, Error> {
+	let mut cursor = output;
+	let value = scale_value::scale::decode_as_type(&mut cursor, view_function.output.id, types)
+		.map_err(|e| view_function_err(format!("Failed to decode the output: {e}")))?;
+	if !cursor.is_empty() {
+		return Err(view_function_err("Output has trailing bytes"));
+	}
+	serde_json::to_value(value.remove_context())
+		.map_err(|e| view_function_err(format!("Failed to serialize the output: {e}")))
+}
+
+/// Returns whether the type `id` is a signed integer, if it is an integer, possibly compact or
+/// wrapped in a single field composite.
+fn integer_type(id: u32, types: &PortableRegistry) -> Option<bool> {
+	match &types.resolve(id)?.type_def {
+		TypeDef::Primitive(
+			TypeDefPrimitive::U8 |
+			TypeDefPrimitive::U16 |
+			TypeDefPrimitive::U32 |
+			TypeDefPrimitive::U64 |
+			TypeDefPrimitive::U128,
+		) => Some(false),
+		TypeDef::Primitive(
+			TypeDefPrimitive::I8 |
+			TypeDefPrimitive::I16 |
+			TypeDefPrimitive::I32 |
+			TypeDefPrimitive::I64 |
+			TypeDefPrimitive::I128,
+		) => Some(true),
+		TypeDef::Compact(compact) => integer_type(compact.type_param.id, types),
+		TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+			integer_type(composite.fields[0].ty.id, types)
+		},
+		_ => None,
+	}
+}
+
+/// Convert a JSON value into an integer value, if it is a number or a decimal string.
+///
+/// Integers above `u64::MAX` don't fit JSON numbers without a loss of precision, so they can be
+/// given as decimal strings.
+fn json_to_integer(json: serde_json::Value, signed: bool) -> Value {
+	let digits = match &json {
+		serde_json::Value::Number(n) => n.to_string(),
+		serde_json::Value::String(s) => s.clone(),
+		_ => return json_to_value(json),
+	};
+	let value = if signed {
+		i128::from_str(&digits).ok().map(Value::i128)
+	} else {
+		u128::from_str(&digits).ok().map(Value::u128)
+	};
+	// Let the encoder complain about anything else.
+	value.unwrap_or_else(|| json_to_value(json))
+}
+
+/// Convert a JSON value into a value which can be encoded according to some type.
+///
+/// Objects of the form `{ "name": .., "values": .. }` are converted to enum variants, matching
+/// how variants are serialized, and `0x`-prefixed hex strings to byte sequences.
+fn json_to_value(json: serde_json::Value) -> Value {
+	use serde_json::Value as Json;
+
+	match json {
+		Json::Null => Value::unnamed_composite([]),
+		Json::Bool(b) => Value::bool(b),
+		Json::Number(n) => match (n.as_u64(), n.as_i64()) {
+			(Some(n), _) => Value::u128(n.into()),
+			(None, Some(n)) => Value::i128(n.into()),
+			// Floats can't be SCALE encoded, let the encoder complain about the type.
+			(None, None) => Value::string(n.to_string()),
+		},
+		Json::String(s) if s.starts_with("0x") => match sp_core::bytes::from_hex(&s) {
+			Ok(bytes) => Value::from_bytes(bytes),
+			Err(_) => Value::string(s),
+		},
+		Json::String(s) => Value::string(s),
+		Json::Array(values) => Value::unnamed_composite(values.into_iter().map(json_to_value)),
+		Json::Object(fields) => {
+			if let (2, Some(Json::String(name)), Some(values)) =
+				(fields.len(), fields.get("name"), fields.get("values"))
+			{
+				let values = match json_to_value(values.clone()).value {
+					ValueDef::Composite(values) => values,
+					value => Composite::unnamed([Value::without_context(value)]),
+				};
+				return Value::variant(name.clone(), values);
+			}
+			Value::named_composite(fields.into_iter().map(|(k, v)| (k, json_to_value(v))))
+		},
+	}
+}
@@ -46,9 +46,9 @@ mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Encode};
 use log::trace;
-use noncanonical::NonCanonicalOverlay;
+use noncanonical::{NonCanonicalOverlay, LAST_CANONICAL};
 use parking_lot::RwLock;
 use pruning::{HaveBlock, RefWindow};
 use std::{
@@ -676,7 +676,10 @@ pub enum IsPruned {
 	MaybePruned,
 }
 
-fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, Error<D::Error>> {
+/// Read the pruning mode stored in the database, if any.
+pub fn fetch_stored_pruning_mode<D: MetaDb>(
+	db: &D,
+) -> Result<Option<PruningMode>, Error<D::Error>> {
 	let meta_key_mode = to_meta_key(PRUNING_MODE, &());
 	if let Some(stored_mode) = db.get_meta(&meta_key_mode).map_err(Error::Db)? {
 		if let Some(mode) = PruningMode::from_id(&stored_mode) {
@@ -693,6 +696,71 @@ fn fetch_stored_pruning_mode<D: MetaDb>(db: &D) -> Result<Option<PruningMode>, E
 	}
 }
 
+/// Change the pruning mode stored in an existing database.
+///
+/// `canonical` is the last canonicalized block, or the last finalized block when the stored mode
+/// is [`PruningMode::ArchiveAll`]. In the latter case `non_canonical` lists the blocks built on
+/// top of it, parents first, which are added to the non-canonical overlay with empty change sets
+/// since their state is already in the database.
+///
+/// When switching from an archive mode to [`PruningMode::Constrained`], the pruning window starts
+/// at `canonical`. Removing the state of the older blocks from the database is left to the
+/// caller. Changing the constraints of [`PruningMode::Constrained`] doesn't need any migration.
+pub fn migrate_pruning_mode<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	requested: PruningMode,
+	canonical: (BlockHash, u64),
+	non_canonical: &[(BlockHash, u64, BlockHash)],
+) -> Result<CommitSet<Key>, Error<D::Error>> {
+	let stored = fetch_stored_pruning_mode(db)?.ok_or_else(|| {
+		StateDbError::Metadata("The database does not have PRUNING_MODE stored".into())
+	})?;
+	let mut commit = CommitSet::default();
+
+	match (&stored, &requested) {
+		(PruningMode::ArchiveAll, PruningMode::ArchiveAll) |
+		(PruningMode::ArchiveCanonical, PruningMode::ArchiveCanonical) |
+		(PruningMode::Constrained(_), PruningMode::Constrained(_)) => return Ok(commit),
+		(PruningMode::ArchiveAll, PruningMode::ArchiveCanonical | PruningMode::Constrained(_)) => {
+			let mut overlay = NonCanonicalOverlay::<BlockHash, Key>::new(db)?;
+			match non_canonical.first() {
+				// The overlay assumes the parent of its first block is canonicalized.
+				Some((_, number, parent_hash))
+					if *parent_hash != canonical.0 || *number != canonical.1 + 1 =>
+				{
+					return Err(StateDbError::InvalidParent.into())
+				},
+				Some(_) => (),
+				None => commit
+					.meta
+					.inserted
+					.push((to_meta_key(LAST_CANONICAL, &()), canonical.encode())),
+			}
+			for (hash, number, parent_hash) in non_canonical {
+				let block_commit =
+					overlay.insert(hash, *number, parent_hash, Default::default())?;
+				commit.meta.inserted.extend(block_commit.meta.inserted);
+			}
+		},
+		(PruningMode::ArchiveCanonical, PruningMode::Constrained(_)) => {
+			let overlay = NonCanonicalOverlay::<BlockHash, Key>::new(db)?;
+			if overlay.last_canonicalized_block_number() != Some(canonical.1) {
+				return Err(StateDbError::InvalidBlock.into());
+			}
+		},
+		_ => return Err(StateDbError::IncompatiblePruningModes { stored, requested }.into()),
+	}
+
+	if let PruningMode::Constrained(_) = requested {
+		pruning::init_window(&canonical.0, canonical.1, &mut commit);
+	}
+	commit
+		.meta
+		.inserted
+		.push((to_meta_key(PRUNING_MODE, &()), requested.id().to_owned()));
+	Ok(commit)
+}
+
 fn choose_pruning_mode(
 	stored: PruningMode,
 	requested: PruningMode,
@@ -712,8 +780,9 @@ fn choose_pruning_mode(
 #[cfg(test)]
 mod tests {
 	use crate::{
+		migrate_pruning_mode,
 		test::{make_changeset, make_db, TestDb},
-		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
+		Constraints, Error, IsPruned, LastCanonicalized, PruningMode, StateDb, StateDbError,
 	};
 	use sp_core::H256;
 
@@ -953,4 +1022,69 @@ mod tests {
 			check_stored_and_requested_mode_compatibility(created, reopened, expected);
 		}
 	}
+
+	#[test]
+	fn migrate_archive_to_constrained() {
+		let mut db = make_db(&[]);
+		let (init, state_db) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::ArchiveAll),
+			false,
+			true,
+		)
+		.unwrap();
+		db.commit(&init);
+		for number in 1..=3 {
+			db.commit(
+				&state_db
+					.insert_block(
+						&H256::from_low_u64_be(number),
+						number,
+						&H256::from_low_u64_be(number - 1),
+						make_changeset(&[number], &[]),
+					)
+					.unwrap(),
+			);
+		}
+		drop(state_db);
+
+		// Block 2 is finalized, block 3 is not.
+		let mode = PruningMode::blocks_pruning(1);
+		let commit = migrate_pruning_mode::<H256, H256, _>(
+			&db,
+			mode.clone(),
+			(H256::from_low_u64_be(2), 2),
+			&[(H256::from_low_u64_be(3), 3, H256::from_low_u64_be(2))],
+		)
+		.unwrap();
+		db.commit(&commit);
+
+		let (init, state_db) =
+			StateDb::<H256, H256, TestDb>::open(db.clone(), Some(mode.clone()), false, false)
+				.unwrap();
+		assert!(init.meta.inserted.is_empty());
+		assert_eq!(state_db.pruning_mode(), mode);
+		assert_eq!(state_db.last_canonicalized(), LastCanonicalized::Block(2));
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::NotPruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+
+		db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(3)).unwrap());
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(2), 2), IsPruned::Pruned);
+		assert!(db.data_eq(&make_db(&[1, 2, 3])));
+	}
+
+	#[test]
+	fn migrate_rejects_incompatible_modes() {
+		let (db, _) = make_test_db(PruningMode::blocks_pruning(2));
+		assert!(matches!(
+			migrate_pruning_mode::<H256, H256, _>(
+				&db,
+				PruningMode::ArchiveAll,
+				(H256::from_low_u64_be(1), 1),
+				&[],
+			),
+			Err(Error::StateDb(StateDbError::IncompatiblePruningModes { .. }))
+		));
+	}
 }
@@ -279,6 +279,25 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+/// Start the pruning window of an existing database at the canonicalized block `number`.
+///
+/// The state of the block is already in the database, so its journal record is empty.
+pub(crate) fn init_window<BlockHash: Hash, Key: Hash>(
+	hash: &BlockHash,
+	number: u64,
+	commit: &mut CommitSet<Key>,
+) {
+	if number > 0 {
+		commit
+			.meta
+			.inserted
+			.push((to_meta_key(LAST_PRUNED, &()), (number - 1).encode()));
+	}
+	let journal_record =
+		JournalRecord::<BlockHash, Key> { hash: hash.clone(), inserted: vec![], deleted: vec![] };
+	commit.meta.inserted.push((to_journal_key(number), journal_record.encode()));
+}
+
 /// The result return by `RefWindow::have_block`
 #[derive(Debug, PartialEq, Eq)]
 pub enum HaveBlock {
@@ -25,7 +25,7 @@ use async_trait::async_trait;
 use codec::Codec;
 use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use sp_core::offchain::TransactionPoolExt;
+use sp_core::{offchain::TransactionPoolExt, Bytes};
 use sp_runtime::traits::{Block as BlockT, Member};
 use std::{collections::HashMap, hash::Hash, marker::PhantomData, pin::Pin, sync::Arc};
 
@@ -189,6 +189,65 @@ impl<Hash, BlockHash> TransactionStatus<Hash, BlockHash> {
 	}
 }
 
+/// State of a transaction in a single view of the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionViewState {
+	/// All the tags required by the transaction are provided, the transaction can be included in
+	/// the block built on top of the view.
+	Ready,
+	/// The transaction waits for other transactions to provide the listed tags.
+	#[serde(rename_all = "camelCase")]
+	Future {
+		/// Required tags which are not provided by any transaction in the view.
+		missing_tags: Vec<Bytes>,
+	},
+}
+
+/// Introspection data of a transaction in a single view of the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionViewReport<BlockHash> {
+	/// Hash of the block the view is associated with.
+	pub block_hash: BlockHash,
+	/// State of the transaction in the view.
+	pub state: TransactionViewState,
+	/// Priority of the transaction, as validated at the view's block.
+	pub priority: TransactionPriority,
+	/// Number of blocks after the view's block the transaction remains valid for.
+	pub longevity: TransactionLongevity,
+	/// Tags required by the transaction.
+	pub requires: Vec<Bytes>,
+	/// Tags provided by the transaction.
+	pub provides: Vec<Bytes>,
+}
+
+/// Reason of a transaction being removed from the pool without being included in a block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionDropReason<Hash> {
+	/// The transaction was replaced by the given transaction providing the same tags.
+	Usurped(Hash),
+	/// The transaction was dropped because of the pool limits.
+	LimitsEnforced,
+	/// The transaction became invalid in every view.
+	Invalid,
+}
+
+/// Introspection data explaining the state of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReport<Hash, BlockHash> {
+	/// Hash of the transaction.
+	pub hash: Hash,
+	/// True if the transaction is kept in the pool, even if no view currently references it.
+	pub in_pool: bool,
+	/// State of the transaction in every view which knows it.
+	pub views: Vec<TransactionViewReport<BlockHash>>,
+	/// Why the transaction was recently removed from the pool, if it was.
+	pub dropped: Option<TransactionDropReason<Hash>>,
+}
+
 /// The stream of transaction events.
 pub type TransactionStatusStream<Hash, BlockHash> =
 	dyn Stream<Item = TransactionStatus<Hash, BlockHash>> + Send;
@@ -347,6 +406,31 @@ pub trait TransactionPool: Send + Sync {
 		at: <Self::Block as BlockT>::Hash,
 		timeout: std::time::Duration,
 	) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>;
+
+	// *** RPC introspection
+	/// Returns the report explaining the state of the transaction with the given hash.
+	///
+	/// Returns `None` if the transaction is unknown, or if the implementation does not support
+	/// introspection.
+	async fn transaction_report(
+		&self,
+		_hash: TxHash<Self>,
+	) -> Option<TransactionReport<TxHash<Self>, BlockHash<Self>>> {
+		None
+	}
+
+	/// Returns the reports explaining the state of the transactions sent by `sender`.
+	///
+	/// The pool has no notion of a sender, a transaction is considered as sent by `sender` if any
+	/// of the tags it requires or provides starts with the given bytes. For FRAME based runtimes
+	/// these are the tags of `CheckNonce`, i.e. SCALE encoded `(sender, nonce)` pairs, so the
+	/// SCALE encoded account id shall be given.
+	async fn transaction_reports_by_sender(
+		&self,
+		_sender: Vec<u8>,
+	) -> Vec<TransactionReport<TxHash<Self>, BlockHash<Self>>> {
+		Vec::new()
+	}
 }
 
 /// An iterator of ready transactions.
@@ -604,4 +688,31 @@ mod tests {
 		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, TransactionStatus::Finalized((1, 0)));
 	}
+
+	#[test]
+	fn tx_report_serialization() {
+		let report: TransactionReport<u8, u8> = TransactionReport {
+			hash: 1,
+			in_pool: true,
+			views: vec![TransactionViewReport {
+				block_hash: 2,
+				state: TransactionViewState::Future { missing_tags: vec![vec![0xab].into()] },
+				priority: 3,
+				longevity: 4,
+				requires: vec![vec![0xab].into()],
+				provides: vec![vec![0xcd].into()],
+			}],
+			dropped: None,
+		};
+		let ser = serde_json::to_string(&report).unwrap();
+
+		let exp = r#"{"hash":1,"inPool":true,"views":[{"blockHash":2,"state":{"future":{"missingTags":["0xab"]}},"priority":3,"longevity":4,"requires":["0xab"],"provides":["0xcd"]}],"dropped":null}"#;
+		assert_eq!(ser, exp);
+
+		let report_dec: TransactionReport<u8, u8> = serde_json::from_str(exp).unwrap();
+		assert_eq!(report_dec, report);
+
+		let dropped: TransactionDropReason<u8> = TransactionDropReason::Usurped(5);
+		assert_eq!(serde_json::to_string(&dropped).unwrap(), r#"{"usurped":5}"#);
+	}
 }
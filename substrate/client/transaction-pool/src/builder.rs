@@ -20,7 +20,7 @@
 
 use crate::{
 	common::api::FullChainApi,
-	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
+	fork_aware_txpool::{ForkAwareTxPool as ForkAwareFullPool, MempoolJournalOptions},
 	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
 	single_state_txpool::BasicPool as SingleStateFullPool,
 	TransactionPoolWrapper, LOG_TARGET,
@@ -45,11 +45,16 @@ pub enum TransactionPoolType {
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	mempool_journal: Option<MempoolJournalOptions>,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			mempool_journal: None,
+		}
 	}
 }
 
@@ -81,7 +86,15 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, mempool_journal: None }
+	}
+
+	/// Enables the on-disk journal of the pending transactions.
+	///
+	/// Only supported by the fork-aware transaction pool.
+	pub fn with_mempool_journal(mut self, journal: MempoolJournalOptions) -> Self {
+		self.mempool_journal = Some(journal);
+		self
 	}
 
 	/// Creates predefined options for benchmarking
@@ -100,6 +113,7 @@ impl TransactionPoolOptions {
 				ban_time: Duration::from_secs(30 * 60),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			mempool_journal: None,
 		}
 	}
 }
@@ -231,6 +245,15 @@ where
 			future = ?self.options.options.future,
 			"Creating transaction pool"
 		);
+		if let (TransactionPoolType::SingleState, Some(journal)) =
+			(&self.options.txpool_type, &self.options.mempool_journal)
+		{
+			tracing::warn!(
+				target: LOG_TARGET,
+				path = ?journal.path,
+				"The transaction pool journal is not supported by the single-state pool, ignoring"
+			);
+		}
 		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
 			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
 				self.options.options,
@@ -245,6 +268,7 @@ where
 				self.prometheus,
 				self.spawner,
 				self.client,
+				self.options.mempool_journal,
 			)),
 		})
 	}
@@ -28,13 +28,13 @@ use crate::{
 	LOG_TARGET,
 };
 use futures::stream::StreamExt;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{TransactionDropReason, TransactionStatus};
 use sc_utils::mpsc;
 use sp_runtime::traits::Block as BlockT;
 use std::{
 	collections::{
 		hash_map::{Entry, OccupiedEntry},
-		HashMap, HashSet,
+		HashMap, HashSet, VecDeque,
 	},
 	fmt::{self, Debug, Formatter},
 	pin::Pin,
@@ -70,7 +70,7 @@ impl<Hash> DroppedTransaction<Hash> {
 }
 
 /// Provides reason of why transactions was dropped.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DroppedReason<Hash> {
 	/// Transaction was replaced by other transaction (e.g. because of higher priority).
 	Usurped(Hash),
@@ -80,6 +80,56 @@ pub enum DroppedReason<Hash> {
 	Invalid,
 }
 
+impl<Hash> From<DroppedReason<Hash>> for TransactionDropReason<Hash> {
+	fn from(reason: DroppedReason<Hash>) -> Self {
+		match reason {
+			DroppedReason::Usurped(by) => TransactionDropReason::Usurped(by),
+			DroppedReason::LimitsEnforced => TransactionDropReason::LimitsEnforced,
+			DroppedReason::Invalid => TransactionDropReason::Invalid,
+		}
+	}
+}
+
+/// The maximum number of entries kept in [`RecentlyDroppedTransactions`].
+const RECENTLY_DROPPED_CAPACITY: usize = 8192;
+
+/// Bounded record of the reasons why the most recent transactions were dropped.
+///
+/// Dropped transactions are removed from the mempool and all the views, so this is the only place
+/// allowing to explain their fate to the user (e.g. via RPC).
+pub(crate) struct RecentlyDroppedTransactions<Hash> {
+	/// The reasons of dropped transactions, by transaction hash.
+	reasons: HashMap<Hash, DroppedReason<Hash>>,
+	/// Hashes of the dropped transactions, in the order they were dropped.
+	order: VecDeque<Hash>,
+}
+
+impl<Hash> Default for RecentlyDroppedTransactions<Hash> {
+	fn default() -> Self {
+		Self { reasons: Default::default(), order: Default::default() }
+	}
+}
+
+impl<Hash: std::hash::Hash + Eq + Clone> RecentlyDroppedTransactions<Hash> {
+	/// Records the dropped transaction, evicting the oldest record if the capacity is exceeded.
+	pub(crate) fn insert(&mut self, dropped: &DroppedTransaction<Hash>) {
+		if self.reasons.insert(dropped.tx_hash.clone(), dropped.reason.clone()).is_none() {
+			self.order.push_back(dropped.tx_hash.clone());
+		}
+
+		if self.order.len() > RECENTLY_DROPPED_CAPACITY {
+			if let Some(oldest) = self.order.pop_front() {
+				self.reasons.remove(&oldest);
+			}
+		}
+	}
+
+	/// Returns the reason why the transaction was dropped, if it is recorded.
+	pub(crate) fn get(&self, tx_hash: &Hash) -> Option<DroppedReason<Hash>> {
+		self.reasons.get(tx_hash).cloned()
+	}
+}
+
 /// Dropped-logic related event from the single view.
 pub type ViewStreamEvent<C> =
 	crate::fork_aware_txpool::view::TransactionStatusEvent<ExtrinsicHash<C>, BlockHash<C>>;
@@ -23,7 +23,7 @@ use super::{
 	import_notification_sink::MultiViewImportNotificationSink,
 	metrics::{EventsMetricsCollector, MetricsLink as PrometheusMetrics},
 	multi_view_listener::MultiViewListener,
-	tx_mem_pool::{InsertionInfo, TxMemPool},
+	tx_mem_pool::{InsertionInfo, MempoolJournal, MempoolJournalOptions, TxMemPool},
 	view::View,
 	view_store::ViewStore,
 };
@@ -36,7 +36,7 @@ use crate::{
 	},
 	enactment_state::{EnactmentAction, EnactmentState},
 	fork_aware_txpool::{
-		dropped_watcher::{DroppedReason, DroppedTransaction},
+		dropped_watcher::{DroppedReason, DroppedTransaction, RecentlyDroppedTransactions},
 		revalidation_worker,
 	},
 	graph::{
@@ -58,8 +58,8 @@ use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
 	error::Error as TxPoolApiError, ChainEvent, ImportNotificationStream,
-	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionSource,
-	TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionReport,
+	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
@@ -92,6 +92,29 @@ const MEMPOOL_TO_VIEW_BATCH_SIZE: usize = 7_000;
 /// Fork aware transaction pool task, that needs to be polled.
 pub type ForkAwareTxPoolTask = Pin<Box<dyn Future<Output = ()> + Send>>;
 
+/// Saves the memory pool to the journal when dropped.
+///
+/// Owned by the journal task, which is dropped by the task manager at the shutdown of the node.
+struct JournalFlushGuard<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	mempool: Arc<TxMemPool<ChainApi, Block>>,
+	journal: Arc<MempoolJournal>,
+}
+
+impl<ChainApi, Block> Drop for JournalFlushGuard<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	fn drop(&mut self) {
+		// The journal task is a blocking task, so waiting for the mempool lock is fine here.
+		futures::executor::block_on(self.mempool.save_journal(&self.journal));
+	}
+}
+
 /// A structure that maintains a collection of pollers associated with specific block hashes
 /// (views).
 struct ReadyPoll<T, Block>
@@ -200,6 +223,9 @@ where
 
 	/// Stats for submit_and_watch call durations
 	submit_and_watch_stats: DurationSlidingStats,
+
+	/// Reasons why the most recent transactions were dropped, used for introspection.
+	recently_dropped: Arc<Mutex<RecentlyDroppedTransactions<ExtrinsicHash<ChainApi>>>>,
 }
 
 impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
@@ -280,11 +306,13 @@ where
 			import_notification_sink.clone(),
 		));
 
+		let recently_dropped = Arc::new(Mutex::new(RecentlyDroppedTransactions::default()));
 		let dropped_monitor_task = Self::dropped_monitor_task(
 			dropped_stream,
 			mempool.clone(),
 			view_store.clone(),
 			import_notification_sink.clone(),
+			recently_dropped.clone(),
 		);
 
 		let combined_tasks = async move {
@@ -321,6 +349,7 @@ where
 				submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 					STAT_SLIDING_WINDOW,
 				)),
+				recently_dropped,
 			}
 			.inject_initial_view(best_block_hash),
 			[combined_tasks, mempool_task],
@@ -333,7 +362,7 @@ where
 	/// This asynchronous task continuously listens for dropped transaction notifications provided
 	/// within `dropped_stream` and ensures that these transactions are removed from the `mempool`
 	/// and `import_notification_sink` instances. For Usurped events, the transaction is also
-	/// removed from the view_store.
+	/// removed from the view_store. The reason of the drop is recorded in `recently_dropped`.
 	async fn dropped_monitor_task(
 		mut dropped_stream: StreamOfDropped<ChainApi>,
 		mempool: Arc<TxMemPool<ChainApi, Block>>,
@@ -342,6 +371,7 @@ where
 			Block::Hash,
 			ExtrinsicHash<ChainApi>,
 		>,
+		recently_dropped: Arc<Mutex<RecentlyDroppedTransactions<ExtrinsicHash<ChainApi>>>>,
 	) {
 		let dropped_stats = DurationSlidingStats::new(Duration::from_secs(STAT_SLIDING_WINDOW));
 		loop {
@@ -376,6 +406,7 @@ where
 
 			mempool.remove_transactions(&[tx_hash]).await;
 			import_notification_sink.clean_notified_items(&[tx_hash]);
+			recently_dropped.lock().insert(&dropped);
 			view_store.listener.transaction_dropped(dropped);
 			insert_and_log_throttled!(
 				Level::DEBUG,
@@ -387,10 +418,48 @@ where
 		}
 	}
 
+	/// Restores and maintains the on-disk journal of the mempool.
+	///
+	/// The journaled transactions are re-submitted to the mempool first, and the mempool is sent
+	/// to the revalidation queue, which removes the restored transactions that are no longer valid
+	/// at the `best` block. The valid ones are submitted to the views when they are updated with
+	/// the mempool, as any other unwatched transaction. Afterwards the mempool is periodically
+	/// saved to the journal, unless the interval of the journal is zero.
+	///
+	/// The journal is also saved when the task is stopped at the shutdown of the node.
+	async fn journal_task(
+		mempool: Arc<TxMemPool<ChainApi, Block>>,
+		view_store: Arc<ViewStore<ChainApi, Block>>,
+		revalidation_queue: Arc<revalidation_worker::RevalidationQueue<ChainApi, Block>>,
+		journal: Arc<MempoolJournal>,
+		best: Option<HashAndNumber<Block>>,
+	) {
+		let _flush_on_shutdown =
+			JournalFlushGuard { mempool: mempool.clone(), journal: journal.clone() };
+
+		let restored = mempool.restore_journal(&journal).await;
+		if let (true, Some(best)) = (restored > 0, best) {
+			revalidation_queue.revalidate_mempool(mempool.clone(), view_store, best).await;
+		}
+
+		let interval = journal.options().interval;
+		if interval.is_zero() {
+			// the journal is only saved at shutdown
+			return futures::future::pending().await;
+		}
+		loop {
+			futures_timer::Delay::new(interval).await;
+			mempool.save_journal(&journal).await;
+		}
+	}
+
 	/// Creates new fork aware transaction pool with the background revalidation worker.
 	///
 	/// The txpool essential tasks (including a revalidation worker) are spawned using provided
 	/// spawner.
+	///
+	/// If the `journal` options are provided, the journal task restoring and maintaining the
+	/// on-disk journal of the mempool is also spawned. Refer to [`Self::journal_task`].
 	pub fn new_with_background_worker(
 		options: Options,
 		is_validator: IsValidator,
@@ -399,6 +468,7 @@ where
 		spawner: impl SpawnEssentialNamed,
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
+		journal: Option<MempoolJournalOptions>,
 	) -> Self {
 		let metrics = PrometheusMetrics::new(prometheus);
 		let (events_metrics_collector, event_metrics_task) =
@@ -433,11 +503,13 @@ where
 			import_notification_sink.clone(),
 		));
 
+		let recently_dropped = Arc::new(Mutex::new(RecentlyDroppedTransactions::default()));
 		let dropped_monitor_task = Self::dropped_monitor_task(
 			dropped_stream,
 			mempool.clone(),
 			view_store.clone(),
 			import_notification_sink.clone(),
+			recently_dropped.clone(),
 		);
 
 		let combined_tasks = async move {
@@ -457,7 +529,7 @@ where
 			blocking_mempool_task,
 		);
 
-		Self {
+		let pool = Self {
 			mempool,
 			api: pool_api,
 			view_store,
@@ -478,8 +550,32 @@ where
 			submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 				STAT_SLIDING_WINDOW,
 			)),
+			recently_dropped,
 		}
-		.inject_initial_view(best_block_hash)
+		.inject_initial_view(best_block_hash);
+
+		if let Some(options) = journal {
+			let best = pool
+				.api
+				.block_id_to_number(&BlockId::Hash(best_block_hash))
+				.ok()
+				.flatten()
+				.map(|number| HashAndNumber { hash: best_block_hash, number });
+			spawner.spawn_essential_blocking(
+				"txpool-journal",
+				Some("transaction-pool"),
+				Self::journal_task(
+					pool.mempool.clone(),
+					pool.view_store.clone(),
+					pool.revalidation_queue.clone(),
+					Arc::new(MempoolJournal::new(options)),
+					best,
+				)
+				.boxed(),
+			);
+		}
+
+		pool
 	}
 
 	/// Get access to the underlying api
@@ -1146,6 +1242,48 @@ where
 	) -> ReadyIteratorFor<ChainApi> {
 		self.ready_at_with_timeout_internal(at, timeout).await
 	}
+
+	/// Returns the report explaining the state of the transaction with the given hash.
+	///
+	/// The state of the transaction is collected from all the active views. If the transaction is
+	/// no longer in the pool, the recorded reason of it being dropped is reported.
+	async fn transaction_report(
+		&self,
+		hash: TxHash<Self>,
+	) -> Option<TransactionReport<TxHash<Self>, Block::Hash>> {
+		let in_pool = self.mempool.get_by_hash(hash).await.is_some();
+		let views = self.view_store.transaction_view_reports(&hash);
+		let dropped = (!in_pool && views.is_empty())
+			.then(|| self.recently_dropped.lock().get(&hash))
+			.flatten()
+			.map(Into::into);
+
+		(in_pool || !views.is_empty() || dropped.is_some()).then_some(TransactionReport {
+			hash,
+			in_pool,
+			views,
+			dropped,
+		})
+	}
+
+	/// Returns the reports explaining the state of the transactions sent by `sender`.
+	///
+	/// Only the transactions known to the active views are reported, as the transactions' tags
+	/// are known only after validation.
+	async fn transaction_reports_by_sender(
+		&self,
+		sender: Vec<u8>,
+	) -> Vec<TransactionReport<TxHash<Self>, Block::Hash>> {
+		let hashes = self.view_store.transactions_with_tag_prefix(&sender);
+
+		let mut reports = Vec::with_capacity(hashes.len());
+		for hash in hashes {
+			if let Some(report) = self.transaction_report(hash).await {
+				reports.push(report);
+			}
+		}
+		reports
+	}
 }
 
 impl<ChainApi, Block> sc_transaction_pool_api::LocalTransactionPool
@@ -2089,6 +2227,7 @@ where
 		prometheus: Option<&PrometheusRegistry>,
 		spawner: impl SpawnEssentialNamed,
 		client: Arc<Client>,
+		journal: Option<MempoolJournalOptions>,
 	) -> Self {
 		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
 		let pool = Self::new_with_background_worker(
@@ -2099,6 +2238,7 @@ where
 			spawner,
 			client.usage_info().chain.best_hash,
 			client.usage_info().chain.finalized_hash,
+			journal,
 		);
 
 		pool
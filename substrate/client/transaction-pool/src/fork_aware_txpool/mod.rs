@@ -119,6 +119,11 @@
 //! are also periodically verified at every finalized block and removed from the *mempool* if no
 //! longer valid. This is process is called [*mempool* revalidation](#mempool-pruningrevalidation).
 //!
+//! Optionally, the *mempool* transactions can be persisted in an on-disk journal (see
+//! [`MempoolJournalOptions`]). The journal is saved periodically and when the node shuts down. At
+//! startup the journaled transactions are re-submitted to the *mempool* as unwatched transactions
+//! and the *mempool* is sent to the revalidation worker at the finalized block.
+//!
 //! ## Flows
 //!
 //! The transaction pool internally is executing numerous tasks. This includes handling submitted
@@ -377,6 +382,7 @@ mod view;
 mod view_store;
 
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use tx_mem_pool::{MempoolJournalOptions, MempoolJournalSources};
 
 mod stream_map_util {
 	use futures::Stream;
@@ -31,6 +31,7 @@
 //! See <https://github.com/paritytech/polkadot-sdk/issues/8912> for some more information. The implementation of the
 //! bridging is based on passing messages from sync context to tokio thread.
 
+use codec::{Decode, Encode};
 use futures::{future::join_all, FutureExt};
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -41,7 +42,7 @@ use sp_runtime::{
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
 };
 use std::{
-	collections::HashSet,
+	collections::{HashMap, HashSet},
 	future::Future,
 	pin::Pin,
 	sync::{
@@ -54,7 +55,7 @@ use std::{
 	},
 	time::Instant,
 };
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 use crate::{
 	common::tracing_log_xt::log_xt_trace,
@@ -67,8 +68,13 @@ use super::{
 	view_store::ViewStore,
 };
 
+mod journal;
 mod tx_mem_pool_map;
 
+use journal::JournalEntry;
+pub(crate) use journal::MempoolJournal;
+pub use journal::{MempoolJournalOptions, MempoolJournalSources};
+
 /// The minimum interval between single transaction revalidations. Given in blocks.
 pub(crate) const TXMEMPOOL_REVALIDATION_PERIOD: u64 = 10;
 
@@ -815,6 +821,73 @@ where
 	}
 }
 
+impl<ChainApi, Block> TxMemPool<ChainApi, Block>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	/// Collects the journal entries of the given transactions.
+	///
+	/// Transactions are ordered by descending priority, older first for equal priorities, so the
+	/// best transactions are kept when the journal limits are reached.
+	fn journal_entries<'a>(
+		journal: &MempoolJournal,
+		transactions: impl Iterator<Item = &'a Arc<TxInMemPool<ChainApi, Block>>>,
+	) -> Vec<JournalEntry> {
+		let entries = transactions
+			.sorted_by_key(|tx| {
+				(std::cmp::Reverse(tx.priority().unwrap_or_default()), tx.source.timestamp)
+			})
+			.map(|tx| JournalEntry { source: tx.source.source, data: tx.tx.encode() });
+		journal.select(entries)
+	}
+
+	/// Decodes the transactions of the journal, grouped by source.
+	///
+	/// Transactions that cannot be decoded are skipped.
+	fn journaled_transactions(
+		&self,
+		journal: &MempoolJournal,
+	) -> HashMap<TransactionSource, Vec<ExtrinsicFor<ChainApi>>> {
+		let mut by_source = HashMap::<TransactionSource, Vec<ExtrinsicFor<ChainApi>>>::new();
+		for entry in journal.load() {
+			match Block::Extrinsic::decode(&mut &entry.data[..]) {
+				Ok(xt) => by_source.entry(entry.source).or_default().push(Arc::from(xt)),
+				Err(error) => {
+					debug!(
+						target: LOG_TARGET,
+						?error,
+						"mempool::journal skipping undecodable transaction"
+					);
+				},
+			}
+		}
+		by_source
+	}
+
+	/// Writes the given entries to the journal, logging the failure.
+	fn write_journal(journal: &MempoolJournal, entries: &[JournalEntry]) {
+		if let Err(error) = journal.save(entries) {
+			warn!(
+				target: LOG_TARGET,
+				?error,
+				path = ?journal.options().path,
+				"mempool::journal cannot be saved"
+			);
+		}
+	}
+
+	/// Writes the transactions of the memory pool to the journal.
+	pub(super) async fn save_journal(&self, journal: &MempoolJournal) {
+		let entries = self
+			.transactions
+			.read()
+			.await
+			.with_items(|iter| Self::journal_entries(journal, iter.map(|(_, tx)| tx)));
+		Self::write_journal(journal, &entries);
+	}
+}
+
 /// Convenient return type of extend_unwatched
 type ExtendUnwatchedResult<ChainApi> =
 	Vec<Result<InsertionInfo<ExtrinsicHash<ChainApi>>, sc_transaction_pool_api::error::Error>>;
@@ -965,6 +1038,22 @@ where
 		}
 	}
 
+	/// Re-submits the transactions of the journal to the memory pool.
+	///
+	/// Transactions are inserted as unwatched transactions which were never validated, so they
+	/// are picked by the next mempool revalidation.
+	///
+	/// Returns the number of restored transactions.
+	pub(super) async fn restore_journal(&self, journal: &MempoolJournal) -> usize {
+		let mut restored = 0;
+		for (source, xts) in self.journaled_transactions(journal) {
+			let results = self.extend_unwatched(source, 0, &xts).await;
+			restored += results.iter().filter(|result| result.is_ok()).count();
+		}
+		debug!(target: LOG_TARGET, restored, "mempool::journal restored transactions");
+		restored
+	}
+
 	pub(super) fn try_insert_with_replacement_sync(
 		self: Arc<Self>,
 		new_tx: ExtrinsicFor<ChainApi>,
@@ -1393,4 +1482,34 @@ mod tx_mem_pool_tests {
 			sc_transaction_pool_api::error::Error::ImmediatelyDropped
 		));
 	}
+
+	#[tokio::test]
+	async fn journal_save_and_restore_works() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut options = MempoolJournalOptions::new(dir.path().join("journal"));
+		options.sources = MempoolJournalSources::Local;
+		let journal = MempoolJournal::new(options);
+
+		let api = Arc::from(TestApi::default());
+		let mempool = TxMemPool::new_test(api.clone(), usize::MAX, usize::MAX);
+
+		let xts = (0..4).map(|x| Arc::from(uxt(x as _))).collect::<Vec<_>>();
+		let results = mempool.extend_unwatched(TransactionSource::Local, 10, &xts[..3]).await;
+		assert!(results.iter().all(Result::is_ok));
+		let results = mempool.extend_unwatched(TransactionSource::External, 10, &xts[3..]).await;
+		assert!(results.iter().all(Result::is_ok));
+		mempool.save_journal(&journal).await;
+
+		// Only local transactions are journaled.
+		let restored = TxMemPool::new_test(api.clone(), usize::MAX, usize::MAX);
+		assert_eq!(restored.restore_journal(&journal).await, 3);
+		assert_eq!(restored.len(), 3);
+
+		for xt in &xts[..3] {
+			let tx = restored.get_by_hash(api.hash_and_length(xt).0).await.unwrap();
+			assert_eq!(tx.source().source, TransactionSource::Local);
+			assert!(!tx.is_watched());
+			assert_eq!(tx.validated_at.load(atomic::Ordering::Relaxed), 0);
+		}
+	}
 }
@@ -0,0 +1,298 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of the [`TxMemPool`](super::TxMemPool) transactions.
+//!
+//! The journal allows to keep the pending transactions across node restarts. It is written
+//! periodically by a background task and once more when the pool is dropped. At startup the
+//! journaled transactions are re-submitted to the mempool as unwatched transactions, and the
+//! mempool is sent to the revalidation worker.
+//!
+//! The journal is a single SCALE encoded file, replaced atomically on every save.
+
+use codec::{Decode, Encode};
+use sc_transaction_pool_api::TransactionSource;
+use std::{
+	fs,
+	io::{self, Write},
+	path::PathBuf,
+	time::Duration,
+};
+use tracing::{debug, warn};
+
+use crate::LOG_TARGET;
+
+/// The version of the journal file format.
+const JOURNAL_VERSION: u8 = 1;
+
+/// The default interval between two saves of the journal.
+const DEFAULT_JOURNAL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The default maximum number of journaled transactions.
+const DEFAULT_JOURNAL_MAX_COUNT: usize = 8192;
+
+/// The default maximum size of the encodings of all journaled transactions.
+const DEFAULT_JOURNAL_MAX_BYTES: usize = 20 * 1024 * 1024;
+
+/// The sources of the transactions kept in the journal.
+///
+/// Transactions included in blocks are never journaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MempoolJournalSources {
+	/// Only the transactions submitted locally.
+	Local,
+	/// Only the transactions received from the network or the RPC.
+	External,
+	/// Both local and external transactions.
+	All,
+}
+
+impl MempoolJournalSources {
+	/// Returns true if the transactions from the given source shall be journaled.
+	pub(crate) fn includes(&self, source: TransactionSource) -> bool {
+		match (self, source) {
+			(_, TransactionSource::InBlock) => false,
+			(Self::All, _) |
+			(Self::Local, TransactionSource::Local) |
+			(Self::External, TransactionSource::External) => true,
+			_ => false,
+		}
+	}
+}
+
+/// The options of the mempool journal.
+#[derive(Debug, Clone)]
+pub struct MempoolJournalOptions {
+	/// The path of the journal file.
+	pub path: PathBuf,
+	/// The interval between two periodic saves of the journal.
+	///
+	/// A zero interval disables the periodic saves, the journal is then only saved at shutdown.
+	pub interval: Duration,
+	/// The maximum number of transactions kept in the journal.
+	pub max_count: usize,
+	/// The maximum size of the encodings of all transactions kept in the journal.
+	pub max_bytes: usize,
+	/// The sources of the transactions kept in the journal.
+	pub sources: MempoolJournalSources,
+}
+
+impl MempoolJournalOptions {
+	/// Creates the options of a journal stored at given path, with default limits.
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self {
+			path: path.into(),
+			interval: DEFAULT_JOURNAL_INTERVAL,
+			max_count: DEFAULT_JOURNAL_MAX_COUNT,
+			max_bytes: DEFAULT_JOURNAL_MAX_BYTES,
+			sources: MempoolJournalSources::All,
+		}
+	}
+}
+
+/// A single journaled transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub(crate) struct JournalEntry {
+	/// The source the transaction was submitted from.
+	pub(crate) source: TransactionSource,
+	/// The encoded extrinsic.
+	pub(crate) data: Vec<u8>,
+}
+
+/// The on-disk journal of the mempool.
+#[derive(Debug)]
+pub(crate) struct MempoolJournal {
+	options: MempoolJournalOptions,
+}
+
+impl MempoolJournal {
+	/// Creates a new journal with given options.
+	pub(crate) fn new(options: MempoolJournalOptions) -> Self {
+		Self { options }
+	}
+
+	/// The options of the journal.
+	pub(crate) fn options(&self) -> &MempoolJournalOptions {
+		&self.options
+	}
+
+	/// Selects the entries to be journaled, obeying the source policy and the size limits.
+	///
+	/// Entries are expected to be provided best first: once a limit is reached, the remaining
+	/// entries are skipped.
+	pub(crate) fn select(&self, entries: impl Iterator<Item = JournalEntry>) -> Vec<JournalEntry> {
+		let mut total_bytes = 0usize;
+		entries
+			.filter(|entry| self.options.sources.includes(entry.source))
+			.take_while(|entry| {
+				total_bytes = total_bytes.saturating_add(entry.data.len());
+				total_bytes <= self.options.max_bytes
+			})
+			.take(self.options.max_count)
+			.collect()
+	}
+
+	/// Writes the given entries to the journal, replacing its previous content.
+	///
+	/// The entries are written to a temporary file first, which is then renamed, so a crash
+	/// while saving does not corrupt the journal.
+	pub(crate) fn save(&self, entries: &[JournalEntry]) -> io::Result<()> {
+		if let Some(dir) = self.options.path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+
+		let tmp_path = self.options.path.with_extension("tmp");
+		{
+			let mut file = fs::File::create(&tmp_path)?;
+			file.write_all(&(JOURNAL_VERSION, entries).encode())?;
+			file.sync_all()?;
+		}
+		fs::rename(&tmp_path, &self.options.path)?;
+
+		debug!(
+			target: LOG_TARGET,
+			count = entries.len(),
+			path = ?self.options.path,
+			"mempool::journal saved"
+		);
+		Ok(())
+	}
+
+	/// Reads the entries of the journal.
+	///
+	/// A missing, unreadable or corrupted journal is treated as empty.
+	pub(crate) fn load(&self) -> Vec<JournalEntry> {
+		let data = match fs::read(&self.options.path) {
+			Ok(data) => data,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => return Vec::new(),
+			Err(error) => {
+				warn!(
+					target: LOG_TARGET,
+					?error,
+					path = ?self.options.path,
+					"mempool::journal cannot be read"
+				);
+				return Vec::new();
+			},
+		};
+
+		match <(u8, Vec<JournalEntry>)>::decode(&mut &data[..]) {
+			Ok((JOURNAL_VERSION, entries)) => {
+				debug!(
+					target: LOG_TARGET,
+					count = entries.len(),
+					path = ?self.options.path,
+					"mempool::journal loaded"
+				);
+				entries
+			},
+			Ok((version, _)) => {
+				warn!(
+					target: LOG_TARGET,
+					version,
+					path = ?self.options.path,
+					"mempool::journal has unsupported version"
+				);
+				Vec::new()
+			},
+			Err(error) => {
+				warn!(
+					target: LOG_TARGET,
+					?error,
+					path = ?self.options.path,
+					"mempool::journal cannot be decoded"
+				);
+				Vec::new()
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entry(source: TransactionSource, len: usize) -> JournalEntry {
+		JournalEntry { source, data: vec![len as u8; len] }
+	}
+
+	fn journal(dir: &tempfile::TempDir) -> MempoolJournal {
+		MempoolJournal::new(MempoolJournalOptions::new(dir.path().join("txpool").join("journal")))
+	}
+
+	#[test]
+	fn save_and_load_roundtrip() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = journal(&dir);
+		assert!(journal.load().is_empty());
+
+		let entries =
+			vec![entry(TransactionSource::Local, 3), entry(TransactionSource::External, 5)];
+		journal.save(&entries).unwrap();
+		assert_eq!(journal.load(), entries);
+
+		journal.save(&entries[1..]).unwrap();
+		assert_eq!(journal.load(), entries[1..]);
+	}
+
+	#[test]
+	fn corrupted_journal_is_ignored() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = journal(&dir);
+		fs::create_dir_all(journal.options().path.parent().unwrap()).unwrap();
+
+		fs::write(&journal.options().path, [JOURNAL_VERSION, 0xff]).unwrap();
+		assert!(journal.load().is_empty());
+
+		fs::write(
+			&journal.options().path,
+			(JOURNAL_VERSION + 1, Vec::<JournalEntry>::new()).encode(),
+		)
+		.unwrap();
+		assert!(journal.load().is_empty());
+	}
+
+	#[test]
+	fn select_obeys_sources_and_limits() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut journal = journal(&dir);
+		let entries = vec![
+			entry(TransactionSource::Local, 10),
+			entry(TransactionSource::InBlock, 10),
+			entry(TransactionSource::External, 10),
+			entry(TransactionSource::Local, 10),
+		];
+
+		assert_eq!(journal.select(entries.clone().into_iter()).len(), 3);
+
+		journal.options.sources = MempoolJournalSources::Local;
+		let selected = journal.select(entries.clone().into_iter());
+		assert_eq!(selected, vec![entries[0].clone(), entries[3].clone()]);
+
+		journal.options.sources = MempoolJournalSources::External;
+		assert_eq!(journal.select(entries.clone().into_iter()), vec![entries[2].clone()]);
+
+		journal.options.sources = MempoolJournalSources::All;
+		journal.options.max_count = 2;
+		assert_eq!(journal.select(entries.clone().into_iter()).len(), 2);
+
+		journal.options.max_count = usize::MAX;
+		journal.options.max_bytes = 25;
+		assert_eq!(journal.select(entries.into_iter()).len(), 2);
+	}
+}
@@ -35,7 +35,10 @@ use crate::{
 };
 use indexmap::IndexMap;
 use parking_lot::Mutex;
-use sc_transaction_pool_api::{error::Error as TxPoolError, PoolStatus, TransactionStatus};
+use sc_transaction_pool_api::{
+	error::Error as TxPoolError, PoolStatus, TransactionStatus, TransactionViewReport,
+	TransactionViewState,
+};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_blockchain::HashAndNumber;
 use sp_runtime::{
@@ -668,6 +671,32 @@ where
 		self.pool.validated_pool().check_is_known(tx_hash, IGNORE_BANNED).is_err()
 	}
 
+	/// Returns the introspection report of the transaction with given hash, if the view knows it.
+	pub(super) fn transaction_report(
+		&self,
+		tx_hash: &ExtrinsicHash<ChainApi>,
+	) -> Option<TransactionViewReport<BlockHash<ChainApi>>> {
+		let validated_pool = self.pool.validated_pool();
+		let (tx, state) = match validated_pool.ready_by_hash(tx_hash) {
+			Some(tx) => (tx, TransactionViewState::Ready),
+			None => {
+				let (tx, missing_tags) = validated_pool.future_by_hash(tx_hash)?;
+				let missing_tags = missing_tags.into_iter().map(Into::into).collect();
+				(tx, TransactionViewState::Future { missing_tags })
+			},
+		};
+
+		Some(TransactionViewReport {
+			block_hash: self.at.hash,
+			state,
+			priority: tx.priority,
+			// `valid_till` is the block number the transaction is valid until.
+			longevity: tx.valid_till.saturating_sub(self.at.number.saturated_into::<u64>()),
+			requires: tx.requires.iter().cloned().map(Into::into).collect(),
+			provides: tx.provides.iter().cloned().map(Into::into).collect(),
+		})
+	}
+
 	/// Removes the whole transaction subtree from the inner pool.
 	///
 	/// Refer to [`crate::graph::ValidatedPool::remove_subtree`] for more details.
@@ -36,7 +36,8 @@ use crate::{
 use itertools::Itertools;
 use parking_lot::RwLock;
 use sc_transaction_pool_api::{
-	error::Error as PoolError, PoolStatus, TransactionTag as Tag, TxInvalidityReportMap,
+	error::Error as PoolError, PoolStatus, TransactionTag as Tag, TransactionViewReport,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_runtime::{
@@ -520,6 +521,36 @@ where
 			.and_then(|v| v.pool.validated_pool().ready_by_hash(tx_hash))
 	}
 
+	/// Returns the introspection reports of the transaction with given hash from every active view
+	/// which knows it.
+	pub(super) fn transaction_view_reports(
+		&self,
+		tx_hash: &ExtrinsicHash<ChainApi>,
+	) -> Vec<TransactionViewReport<Block::Hash>> {
+		self.active_views
+			.read()
+			.values()
+			.filter_map(|view| view.transaction_report(tx_hash))
+			.collect()
+	}
+
+	/// Returns hashes of the transactions known to any active view which require or provide a tag
+	/// starting with `prefix`.
+	pub(super) fn transactions_with_tag_prefix(
+		&self,
+		prefix: &[u8],
+	) -> HashSet<ExtrinsicHash<ChainApi>> {
+		self.active_views
+			.read()
+			.values()
+			.flat_map(|view| {
+				view.pool.validated_pool().hashes_matching(|tx| {
+					tx.requires.iter().chain(tx.provides.iter()).any(|tag| tag.starts_with(prefix))
+				})
+			})
+			.collect()
+	}
+
 	/// Inserts new view into the view store.
 	///
 	/// Refer to [`Self::insert_new_view_sync`] more details.
@@ -454,6 +454,27 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.ready.by_hash(hash)
 	}
 
+	/// Returns future transaction by hash together with the required tags it is still missing.
+	pub fn future_by_hash(&self, hash: &Hash) -> Option<(Arc<Transaction<Hash, Ex>>, Vec<Tag>)> {
+		self.future.waiting_by_hash(hash).map(|waiting| {
+			(waiting.transaction.clone(), waiting.missing_tags.iter().cloned().collect())
+		})
+	}
+
+	/// Returns hashes of all transactions (both ready and future) matching the given predicate.
+	pub fn hashes_matching(&self, predicate: impl Fn(&Transaction<Hash, Ex>) -> bool) -> Vec<Hash> {
+		self.ready
+			.fold(Vec::new(), |mut hashes, tx| {
+				if predicate(&tx.transaction.transaction) {
+					hashes.push(tx.transaction.transaction.hash.clone());
+				}
+				hashes
+			})
+			.into_iter()
+			.chain(self.future.all().filter(|tx| predicate(tx)).map(|tx| tx.hash.clone()))
+			.collect()
+	}
+
 	/// Makes sure that the transactions in the queues stay within provided limits.
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
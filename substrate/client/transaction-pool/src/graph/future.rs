@@ -160,6 +160,11 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 		self.waiting.contains_key(hash)
 	}
 
+	/// Returns the waiting transaction with the given hash, if it is part of the queue.
+	pub fn waiting_by_hash(&self, hash: &Hash) -> Option<&WaitingTransaction<Hash, Ex>> {
+		self.waiting.get(hash)
+	}
+
 	/// Returns a list of known transactions
 	pub fn by_hashes(&self, hashes: &[Hash]) -> Vec<Option<Arc<Transaction<Hash, Ex>>>> {
 		hashes
@@ -628,6 +628,19 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		self.pool.read().ready_by_hash(hash)
 	}
 
+	/// Get future transaction by hash together with the required tags it is still missing.
+	pub fn future_by_hash(&self, hash: &ExtrinsicHash<B>) -> Option<(TransactionFor<B>, Vec<Tag>)> {
+		self.pool.read().future_by_hash(hash)
+	}
+
+	/// Returns hashes of all transactions in the pool matching the given predicate.
+	pub fn hashes_matching(
+		&self,
+		predicate: impl Fn(&base::Transaction<ExtrinsicHash<B>, ExtrinsicFor<B>>) -> bool,
+	) -> Vec<ExtrinsicHash<B>> {
+		self.pool.read().hashes_matching(predicate)
+	}
+
 	/// Prunes ready transactions that provide given list of tags.
 	pub fn prune_tags(
 		&self,
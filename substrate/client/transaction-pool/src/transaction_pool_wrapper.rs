@@ -28,7 +28,8 @@ use async_trait::async_trait;
 use sc_transaction_pool_api::{
 	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
 	MaintainedTransactionPool, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	TransactionReport, TransactionSource, TransactionStatusStreamFor, TxHash,
+	TxInvalidityReportMap,
 };
 use sp_runtime::traits::Block as BlockT;
 use std::{collections::HashMap, pin::Pin, sync::Arc};
@@ -146,6 +147,20 @@ where
 	) -> ReadyIteratorFor<FullChainApi<Client, Block>> {
 		self.0.ready_at_with_timeout(at, timeout).await
 	}
+
+	async fn transaction_report(
+		&self,
+		hash: TxHash<Self>,
+	) -> Option<TransactionReport<TxHash<Self>, Block::Hash>> {
+		self.0.transaction_report(hash).await
+	}
+
+	async fn transaction_reports_by_sender(
+		&self,
+		sender: Vec<u8>,
+	) -> Vec<TransactionReport<TxHash<Self>, Block::Hash>> {
+		self.0.transaction_reports_by_sender(sender).await
+	}
 }
 
 #[async_trait]
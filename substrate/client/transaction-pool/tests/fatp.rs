@@ -26,7 +26,7 @@ use futures::{executor::block_on, task::Poll, FutureExt, StreamExt};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{
 	error::Error as TxPoolError, ChainEvent, MaintainedTransactionPool, TransactionPool,
-	TransactionStatus,
+	TransactionStatus, TransactionViewState,
 };
 use sp_runtime::transaction_validity::InvalidTransaction;
 use std::{sync::Arc, time::Duration};
@@ -2731,3 +2731,55 @@ fn fatp_watcher_ready_event_after_instant_finalization() {
 		"First event should be Ready, delivered from the finalized view"
 	);
 }
+
+#[test]
+fn fatp_transaction_report_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+	api.set_nonce(api.genesis_hash(), Bob.into(), 600);
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+	let xt2 = uxt(Bob, 600);
+	let xt0_hash = api.hash_and_length(&xt0).0;
+	let xt1_hash = api.hash_and_length(&xt1).0;
+
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt1.clone())).unwrap();
+	block_on(pool.submit_one(invalid_hash(), SOURCE, xt2.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 2, 1);
+
+	let report = block_on(pool.transaction_report(xt0_hash)).unwrap();
+	assert!(report.in_pool);
+	assert!(report.dropped.is_none());
+	assert_eq!(report.views.len(), 1);
+	assert_eq!(report.views[0].block_hash, header01.hash());
+	assert_eq!(report.views[0].state, TransactionViewState::Ready);
+	// The test api validates transactions with a longevity of 64 blocks.
+	assert_eq!(report.views[0].longevity, 64);
+
+	// xt1 waits for the transaction with nonce 201, which provides the `[Alice, 201]` tag.
+	let report = block_on(pool.transaction_report(xt1_hash)).unwrap();
+	assert_eq!(
+		report.views[0].state,
+		TransactionViewState::Future { missing_tags: vec![vec![0, 201].into()] }
+	);
+	assert_eq!(report.views[0].provides, vec![vec![0, 202].into()]);
+
+	assert!(block_on(pool.transaction_report(api.hash_and_length(&uxt(Alice, 203)).0)).is_none());
+
+	// The tags of the test runtime start with the index of the sender in the keyring.
+	let mut reports = block_on(pool.transaction_reports_by_sender(vec![0]))
+		.into_iter()
+		.map(|report| report.hash)
+		.collect::<Vec<_>>();
+	reports.sort();
+	let mut expected = vec![xt0_hash, xt1_hash];
+	expected.sort();
+	assert_eq!(reports, expected);
+}
@@ -26,7 +26,8 @@ use fatp_common::{
 use futures::{executor::block_on, FutureExt};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolError, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+	error::Error as TxPoolError, MaintainedTransactionPool, TransactionDropReason, TransactionPool,
+	TransactionStatus,
 };
 use std::thread::sleep;
 use substrate_test_runtime_client::Sr25519Keyring::*;
@@ -830,3 +831,45 @@ fn fatp_limits_watcher_future_transactions_are_droped_when_view_is_dropped() {
 		assert_eq!(x_status, vec![TransactionStatus::Future]);
 	}
 }
+
+#[test]
+fn fatp_limits_dropped_transaction_is_reported() {
+	sp_tracing::try_init_simple();
+
+	let builder = TestPoolBuilder::new();
+	let (pool, api, _) = builder.with_mempool_count_limit(3).with_ready_count(2).build();
+	api.set_nonce(api.genesis_hash(), Charlie.into(), 500);
+
+	let header01 = api.push_block(1, vec![], true);
+
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Charlie, 500);
+	let xt1 = uxt(Alice, 200);
+	let xt2 = uxt(Alice, 201);
+	let xt0_hash = api.hash_and_length(&xt0).0;
+
+	let submissions = vec![
+		pool.submit_one(header01.hash(), SOURCE, xt0.clone()),
+		pool.submit_one(header01.hash(), SOURCE, xt1.clone()),
+		pool.submit_one(header01.hash(), SOURCE, xt2.clone()),
+	];
+
+	let results = block_on(futures::future::join_all(submissions));
+	assert!(results.iter().all(Result::is_ok));
+	// todo: can we do better? We don't have API to check if event was processed internally.
+	let mut counter = 0;
+	while block_on(pool.mempool_len()).0 == 3 {
+		sleep(std::time::Duration::from_millis(1));
+		counter = counter + 1;
+		if counter > 20 {
+			assert!(false, "timeout");
+		}
+	}
+
+	let report = block_on(pool.transaction_report(xt0_hash)).unwrap();
+	assert!(!report.in_pool);
+	assert!(report.views.is_empty());
+	assert_eq!(report.dropped, Some(TransactionDropReason::LimitsEnforced));
+}
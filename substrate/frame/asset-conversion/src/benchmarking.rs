@@ -27,6 +27,7 @@ use frame_support::{
 	traits::{
 		fungible::NativeOrWithId,
 		fungibles::{Create, Inspect, Mutate, Refund},
+		EnsureOrigin,
 	},
 };
 use frame_system::RawOrigin as SystemOrigin;
@@ -166,6 +167,85 @@ where
 	(lp_token, liquidity1, liquidity2)
 }
 
+/// Creates an empty StableSwap pool of the given asset pair with the given `amplification`
+/// coefficient.
+fn setup_stable_pool<T: Config>(
+	caller: &T::AccountId,
+	asset1: &T::AssetKind,
+	asset2: &T::AssetKind,
+	amplification: u32,
+) where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	create_asset::<T>(caller, asset1, T::Assets::minimum_balance(asset1.clone()), true);
+	create_asset::<T>(caller, asset2, T::Assets::minimum_balance(asset2.clone()), true);
+	let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+	create_fee_asset::<T>(caller);
+	mint_setup_fee_asset::<T>(caller, asset1, asset2, &lp_token);
+
+	assert_ok!(AssetConversion::<T>::create_stable_pool(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Box::new(asset1.clone()),
+		Box::new(asset2.clone()),
+		amplification,
+	));
+}
+
+/// Creates `n - 1` StableSwap pools with liquidity, chained into a swap path of length `n`.
+///
+/// The pools have the max amplification coefficient and imbalanced reserves, so that Newton's
+/// method takes more iterations to find their invariants. Returns the path along with the least
+/// amount of liquidity provided for the first asset of every pool.
+fn create_stable_swap_path<T: Config>(
+	caller: &T::AccountId,
+	n: u32,
+) -> (Vec<Box<T::AssetKind>>, T::Balance)
+where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	let mut path = vec![];
+	let mut liquidity = T::Balance::zero();
+	for n in 1..n {
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(n - 1, n);
+		if path.len() == 0 {
+			path = vec![Box::new(asset1.clone()), Box::new(asset2.clone())];
+		} else {
+			path.push(Box::new(asset2.clone()));
+		}
+
+		// enough liquidity for the swapped amounts to leave the reserves above the existential
+		// deposits, with ten times more of the second asset
+		let (liquidity1, liquidity2) = valid_liquidity_amount::<T>(
+			T::Assets::minimum_balance(asset1.clone()),
+			T::Assets::minimum_balance(asset2.clone()),
+		);
+		let (liquidity1, liquidity2) = (liquidity1 * 10u32.into(), liquidity2 * 100u32.into());
+		create_asset::<T>(caller, &asset1, liquidity1, true);
+		create_asset::<T>(caller, &asset2, liquidity2, true);
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		mint_setup_fee_asset::<T>(caller, &asset1, &asset2, &lp_token);
+
+		assert_ok!(AssetConversion::<T>::create_stable_pool(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			MAX_AMPLIFICATION,
+		));
+		assert_ok!(AssetConversion::<T>::add_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			liquidity1,
+			liquidity2,
+			T::Balance::one(),
+			T::Balance::zero(),
+			caller.clone(),
+		));
+		liquidity = if liquidity.is_zero() { liquidity1 } else { liquidity.min(liquidity1) };
+	}
+	(path, liquidity)
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -368,6 +448,58 @@ mod benchmarks {
 		assert_eq!(actual_balance, init_caller_balance + T::Balance::one());
 	}
 
+	#[benchmark]
+	fn swap_exact_tokens_for_tokens_stable(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		create_fee_asset::<T>(&caller);
+		let (path, liquidity) = create_stable_swap_path::<T>(&caller, n);
+		let swap_amount = liquidity / 10u32.into();
+
+		let asset_in = *path.first().unwrap().clone();
+		assert_ok!(T::Assets::mint_into(asset_in.clone(), &caller, swap_amount));
+		let init_caller_balance = T::Assets::balance(asset_in.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_exact_tokens_for_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			path,
+			swap_amount,
+			T::Balance::one(),
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset_in, &caller);
+		assert_eq!(actual_balance, init_caller_balance - swap_amount);
+	}
+
+	#[benchmark]
+	fn swap_tokens_for_exact_tokens_stable(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		create_fee_asset::<T>(&caller);
+		let (path, liquidity) = create_stable_swap_path::<T>(&caller, n);
+		let max_swap_amount = liquidity / 2u32.into();
+		let amount_out = liquidity / 10u32.into();
+
+		let asset_in = *path.first().unwrap().clone();
+		let asset_out = *path.last().unwrap().clone();
+		assert_ok!(T::Assets::mint_into(asset_in, &caller, max_swap_amount));
+		let init_caller_balance = T::Assets::balance(asset_out.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_tokens_for_exact_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			path,
+			amount_out,
+			max_swap_amount,
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset_out, &caller);
+		assert_eq!(actual_balance, init_caller_balance + amount_out);
+	}
+
 	#[benchmark]
 	fn touch(n: Linear<0, 3>) {
 		let caller: T::AccountId = whitelisted_caller();
@@ -410,5 +542,94 @@ mod benchmarks {
 		assert_last_event::<T>(Event::Touched { pool_id, who: caller }.into());
 	}
 
+	#[benchmark]
+	fn create_stable_pool() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_asset::<T>(&caller, &asset1, T::Assets::minimum_balance(asset1.clone()), true);
+		create_asset::<T>(&caller, &asset2, T::Assets::minimum_balance(asset2.clone()), true);
+
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		create_fee_asset::<T>(&caller);
+		mint_setup_fee_asset::<T>(&caller, &asset1, &asset2, &lp_token);
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			100,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(Event::StablePoolCreated { pool_id, amplification: 100 }.into());
+	}
+
+	#[benchmark]
+	fn ramp_amplification() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		setup_stable_pool::<T>(&caller, &asset1, &asset2, 100);
+
+		let origin = T::AmplificationOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		// the call is otherwise moved to block 1, after `future_block` is computed
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		let now = frame_system::Pallet::<T>::block_number();
+		let future_block = now + T::MinAmplificationRampDuration::get();
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			200,
+			future_block,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(
+			Event::AmplificationRampStarted {
+				pool_id,
+				initial: 100,
+				future: 200,
+				initial_block: now,
+				future_block,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn stop_amplification_ramp() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		setup_stable_pool::<T>(&caller, &asset1, &asset2, 100);
+
+		let origin = T::AmplificationOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let duration = T::MinAmplificationRampDuration::get();
+		assert_ok!(AssetConversion::<T>::ramp_amplification(
+			origin.clone(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			200,
+			now + duration,
+		));
+		frame_system::Pallet::<T>::set_block_number(now + duration / 2u32.into());
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		let amplification = StablePools::<T>::get(&pool_id)
+			.unwrap()
+			.at(frame_system::Pallet::<T>::block_number());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(asset1.clone()), Box::new(asset2.clone()));
+
+		assert_last_event::<T>(Event::AmplificationRampStopped { pool_id, amplification }.into());
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
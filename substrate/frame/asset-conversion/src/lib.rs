@@ -24,6 +24,8 @@
 //! This pallet allows you to:
 //!
 //!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a StableSwap liquidity pool](`Pallet::create_stable_pool()`) for 2 assets of like
+//!    value
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -34,6 +36,10 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [find the best swap path](`AssetConversionApi::find_best_route`) between two assets via a
+//!    runtime api endpoint.
+//!  - [query the time weighted average price](`AssetConversionApi::get_twap`) of a pool between two
+//!    blocks via a runtime api endpoint, or from other pallets via the [`TwapOracle`] trait.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -51,6 +57,34 @@
 //! http://localhost:9933/
 //! ```
 //! (This can be run against the kitchen sync node in the `node` folder of this repo.)
+//!
+//! ## Price oracle
+//!
+//! Like in Uniswap V2, every pool keeps cumulative prices of its assets, i.e. the sum of the pool
+//! prices weighted by the number of blocks they were in effect. The accumulators are updated after
+//! every swap and liquidity change, using the reserves the pool had before the change. A price
+//! therefore only contributes to the accumulators once a block has passed, which makes the time
+//! weighted average price (TWAP) derived from them costly to manipulate. The cumulative prices at
+//! the first change of the pool within a block are kept in a ring buffer of
+//! [`Config::MaxPriceObservations`] entries, bounding how far back the TWAP can be queried.
+//! Each observation records the amplification coefficient of a StableSwap pool the prices are
+//! accumulated with until the next one, so later ramps don't change the past prices.
+//!
+//! ## StableSwap pools
+//!
+//! Pools price swaps with the constant product curve, `x * y = k`, unless created with
+//! [`Pallet::create_stable_pool`]. Such pools follow the
+//! [Curve StableSwap](https://docs.curve.fi/assets/pdf/stableswap-paper.pdf) invariant instead,
+//! which stays close to a constant sum curve around the balanced state of the pool and causes
+//! much less slippage for assets of like value, e.g. two stablecoins. How flat the curve is, is
+//! set by the amplification coefficient of the pool, which [`Config::AmplificationOrigin`] can
+//! [ramp](`Pallet::ramp_amplification()`) to a new value over at least
+//! [`Config::MinAmplificationRampDuration`] blocks. The assets of a StableSwap pool are expected
+//! to have the same number of decimals.
+//!
+//! Liquidity is added and removed in proportion to the reserves for both curves. The swap
+//! functions, the [`Swap`], [`SwapCredit`] and [`QuotePrice`] implementations and the price
+//! oracle transparently follow the curve of each pool.
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -59,6 +93,9 @@ mod benchmarking;
 mod liquidity;
 #[cfg(test)]
 mod mock;
+mod oracle;
+mod route;
+mod stable_swap;
 mod swap;
 #[cfg(test)]
 mod tests;
@@ -67,7 +104,9 @@ pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 pub use benchmarking::{BenchmarkHelper, NativeOrWithIdFactory};
 pub use liquidity::*;
+pub use oracle::*;
 pub use pallet::*;
+pub use stable_swap::*;
 pub use swap::*;
 pub use types::*;
 pub use weights::WeightInfo;
@@ -89,13 +128,13 @@ use frame_support::{
 	},
 	PalletId,
 };
-use sp_core::Get;
+use sp_core::{Get, U256};
 use sp_runtime::{
 	traits::{
 		CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, IntegerSquareRoot, MaybeDisplay,
-		One, TrailingZeroInput, Zero,
+		NumberFor, One, TrailingZeroInput, UniqueSaturatedInto, Zero,
 	},
-	DispatchError, Saturating, TokenError, TransactionOutcome,
+	DispatchError, FixedPointNumber, FixedU128, Saturating, TokenError, TransactionOutcome,
 };
 
 #[frame_support::pallet]
@@ -184,6 +223,22 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// The max number of price observations kept per pool.
+		///
+		/// An observation is recorded at most once per block, for blocks in which the pool
+		/// reserves change. This bounds how far back the time weighted average price of a pool
+		/// can be queried.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
+		/// The origin allowed to ramp the amplification coefficient of StableSwap pools.
+		type AmplificationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The min number of blocks a ramp of the amplification coefficient of a StableSwap pool
+		/// can span.
+		#[pallet::constant]
+		type MinAmplificationRampDuration: Get<BlockNumberFor<Self>>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -207,6 +262,35 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, OptionQuery>;
 
+	/// Map from `PoolId` to the amplification coefficient of the pool, for the pools following
+	/// the StableSwap curve. Pools absent from this map follow the constant product curve.
+	#[pallet::storage]
+	pub type StablePools<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, Amplification<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Map from `PoolId` to the price accumulator of the pool.
+	#[pallet::storage]
+	pub type PriceAccumulators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		PriceAccumulator<T::Balance, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Ring buffers of the [`Config::MaxPriceObservations`] latest price observations of the
+	/// pools, indexed by `PoolId` and the position in the buffer.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Twox64Concat,
+		u32,
+		PriceObservation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	// Pallet's events.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -294,6 +378,34 @@ pub mod pallet {
 			/// The account initiating the touch.
 			who: T::AccountId,
 		},
+		/// A successful call of the `CreateStablePool` extrinsic will create this event, next to
+		/// the `PoolCreated` one.
+		StablePoolCreated {
+			/// The pool id associated with the pool.
+			pool_id: T::PoolId,
+			/// The amplification coefficient of the pool.
+			amplification: u32,
+		},
+		/// The amplification coefficient of a StableSwap pool started ramping.
+		AmplificationRampStarted {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The amplification coefficient at the start of the ramp.
+			initial: u32,
+			/// The amplification coefficient at the end of the ramp.
+			future: u32,
+			/// The block the ramp starts at.
+			initial_block: BlockNumberFor<T>,
+			/// The block the ramp ends at.
+			future_block: BlockNumberFor<T>,
+		},
+		/// The ramp of the amplification coefficient of a StableSwap pool has been stopped.
+		AmplificationRampStopped {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The amplification coefficient the pool is left with.
+			amplification: u32,
+		},
 	}
 
 	#[pallet::error]
@@ -347,6 +459,18 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The amplification coefficient must be greater than zero and at most
+		/// [`MAX_AMPLIFICATION`].
+		InvalidAmplification,
+		/// The pool doesn't follow the StableSwap curve.
+		NotStablePool,
+		/// The ramp of the amplification coefficient ends too soon.
+		AmplificationRampTooShort,
+		/// The amplification coefficient can't change by more than a factor of
+		/// [`MAX_AMPLIFICATION_CHANGE`] in a single ramp.
+		AmplificationChangeTooLarge,
+		/// The StableSwap curve math didn't converge.
+		StableSwapDidNotConverge,
 	}
 
 	#[pallet::hooks]
@@ -356,6 +480,10 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(
+				T::MaxPriceObservations::get() > 0,
+				"the `MaxPriceObservations` should be greater than 0",
+			);
 		}
 	}
 
@@ -452,7 +580,10 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32)
+				.max(T::WeightInfo::swap_exact_tokens_for_tokens_stable(path.len() as u32))
+		)]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -480,7 +611,10 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32)
+				.max(T::WeightInfo::swap_tokens_for_exact_tokens_stable(path.len() as u32))
+		)]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -543,6 +677,117 @@ pub mod pallet {
 			Self::deposit_event(Event::Touched { pool_id, who });
 			Ok(Some(T::WeightInfo::touch(refunds_number)).into())
 		}
+
+		/// Creates an empty liquidity pool following the StableSwap curve with the given
+		/// `amplification` coefficient, and an associated new `lp_token` asset (the id of which is
+		/// returned in the `Event::PoolCreated` event).
+		///
+		/// The assets of the pool are expected to be of like value and to have the same number of
+		/// decimals.
+		///
+		/// Once a pool is created, someone may [`Pallet::add_liquidity`] to it.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_stable_pool())]
+		pub fn create_stable_pool(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			amplification: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				amplification > 0 && amplification <= MAX_AMPLIFICATION,
+				Error::<T>::InvalidAmplification
+			);
+			let pool_id = Self::do_create_pool(&sender, *asset1, *asset2)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			StablePools::<T>::insert(&pool_id, Amplification::constant(amplification, now));
+			Self::deposit_event(Event::StablePoolCreated { pool_id, amplification });
+			Ok(())
+		}
+
+		/// Linearly ramps the amplification coefficient of the StableSwap pool of `asset1` and
+		/// `asset2`, from its current value to `future_amplification` at `future_block`.
+		///
+		/// The origin must be [`Config::AmplificationOrigin`]. The ramp must span at least
+		/// [`Config::MinAmplificationRampDuration`] blocks and can't change the coefficient by
+		/// more than a factor of [`MAX_AMPLIFICATION_CHANGE`].
+		///
+		/// Emits `AmplificationRampStarted` event when successful.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::ramp_amplification())]
+		pub fn ramp_amplification(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			future_amplification: u32,
+			future_block: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::AmplificationOrigin::ensure_origin(origin)?;
+			ensure!(
+				future_amplification > 0 && future_amplification <= MAX_AMPLIFICATION,
+				Error::<T>::InvalidAmplification
+			);
+			let (pool_id, amplification) = Self::stable_pool(&asset1, &asset2)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				future_block >= now.saturating_add(T::MinAmplificationRampDuration::get()),
+				Error::<T>::AmplificationRampTooShort
+			);
+			let initial = amplification.at(now);
+			ensure!(
+				future_amplification <= initial.saturating_mul(MAX_AMPLIFICATION_CHANGE) &&
+					future_amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE) >= initial,
+				Error::<T>::AmplificationChangeTooLarge
+			);
+
+			// accumulate the prices with the coefficient in effect so far
+			Self::update_price_accumulator(&asset1, &asset2);
+			StablePools::<T>::insert(
+				&pool_id,
+				Amplification {
+					initial,
+					future: future_amplification,
+					initial_block: now,
+					future_block,
+				},
+			);
+			Self::deposit_event(Event::AmplificationRampStarted {
+				pool_id,
+				initial,
+				future: future_amplification,
+				initial_block: now,
+				future_block,
+			});
+			Ok(())
+		}
+
+		/// Stops the ramp of the amplification coefficient of the StableSwap pool of `asset1` and
+		/// `asset2`, keeping its current value.
+		///
+		/// The origin must be [`Config::AmplificationOrigin`].
+		///
+		/// Emits `AmplificationRampStopped` event when successful.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::stop_amplification_ramp())]
+		pub fn stop_amplification_ramp(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+		) -> DispatchResult {
+			T::AmplificationOrigin::ensure_origin(origin)?;
+			let (pool_id, amplification) = Self::stable_pool(&asset1, &asset2)?;
+			// accumulate the prices with the coefficient in effect so far
+			Self::update_price_accumulator(&asset1, &asset2);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let amplification = amplification.at(now);
+			StablePools::<T>::insert(&pool_id, Amplification::constant(amplification, now));
+			Self::deposit_event(Event::AmplificationRampStopped { pool_id, amplification });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -666,8 +911,8 @@ pub mod pallet {
 				Error::<T>::AmountTwoLessThanMinimal
 			);
 
-			T::Assets::transfer(asset1, who, &pool_account, amount1, Preserve)?;
-			T::Assets::transfer(asset2, who, &pool_account, amount2, Preserve)?;
+			T::Assets::transfer(asset1.clone(), who, &pool_account, amount1, Preserve)?;
+			T::Assets::transfer(asset2.clone(), who, &pool_account, amount2, Preserve)?;
 
 			let total_supply = T::PoolAssets::total_issuance(pool.lp_token.clone());
 
@@ -692,6 +937,8 @@ pub mod pallet {
 
 			T::PoolAssets::mint_into(pool.lp_token.clone(), mint_to, lp_token_amount)?;
 
+			Self::update_price_accumulator(&asset1, &asset2);
+
 			Self::deposit_event(Event::LiquidityAdded {
 				who: who.clone(),
 				mint_to: mint_to.clone(),
@@ -763,8 +1010,10 @@ pub mod pallet {
 				Polite,
 			)?;
 
-			T::Assets::transfer(asset1, &pool_account, withdraw_to, amount1, Expendable)?;
-			T::Assets::transfer(asset2, &pool_account, withdraw_to, amount2, Expendable)?;
+			T::Assets::transfer(asset1.clone(), &pool_account, withdraw_to, amount1, Expendable)?;
+			T::Assets::transfer(asset2.clone(), &pool_account, withdraw_to, amount2, Expendable)?;
+
+			Self::update_price_accumulator(&asset1, &asset2);
 
 			Self::deposit_event(Event::LiquidityRemoved {
 				who: who.clone(),
@@ -1054,6 +1303,12 @@ pub mod pallet {
 			T::Assets::resolve(&pool_to, credit_in)
 				.map_err(|c| (c, Error::<T>::BelowMinimum.into()))?;
 
+			for pair in path.windows(2) {
+				if let [(asset1, _), (asset2, _)] = pair {
+					Self::update_price_accumulator(asset1, asset2);
+				}
+			}
+
 			Ok(credit_out)
 		}
 
@@ -1101,8 +1356,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::pool_curve(asset1, &asset2);
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				amount_in =
+					Self::get_curve_amount_in(curve, &amount_in, &reserve_in, &reserve_out)?;
 			}
 			balance_path.reverse();
 
@@ -1127,8 +1384,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::pool_curve(&asset1, asset2);
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				amount_out =
+					Self::get_curve_amount_out(curve, &amount_out, &reserve_in, &reserve_out)?;
 			}
 			Ok(balance_path)
 		}
@@ -1251,6 +1510,103 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
+		/// Returns the curve the pool of `asset1` and `asset2` currently prices swaps with.
+		pub(crate) fn pool_curve(asset1: &T::AssetKind, asset2: &T::AssetKind) -> PoolCurve {
+			T::PoolLocator::pool_id(asset1, asset2)
+				.map_or(PoolCurve::ConstantProduct, |pool_id| Self::pool_id_curve(&pool_id))
+		}
+
+		/// Returns the curve the pool `pool_id` currently prices swaps with.
+		pub(crate) fn pool_id_curve(pool_id: &T::PoolId) -> PoolCurve {
+			match StablePools::<T>::get(pool_id) {
+				Some(amplification) => PoolCurve::StableSwap {
+					amplification: amplification.at(frame_system::Pallet::<T>::block_number()),
+				},
+				None => PoolCurve::ConstantProduct,
+			}
+		}
+
+		/// Returns the StableSwap pool of `asset1` and `asset2` along with its amplification
+		/// coefficient.
+		fn stable_pool(
+			asset1: &T::AssetKind,
+			asset2: &T::AssetKind,
+		) -> Result<(T::PoolId, Amplification<BlockNumberFor<T>>), Error<T>> {
+			let pool_id = T::PoolLocator::pool_id(asset1, asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+			let amplification = StablePools::<T>::get(&pool_id).ok_or(Error::<T>::NotStablePool)?;
+			Ok((pool_id, amplification))
+		}
+
+		/// Calculates amount out following the `curve` of a pool, see [`Pallet::get_amount_out`]
+		/// and [`Pallet::get_stable_amount_out`].
+		pub fn get_curve_amount_out(
+			curve: PoolCurve,
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			match curve {
+				PoolCurve::ConstantProduct => {
+					Self::get_amount_out(amount_in, reserve_in, reserve_out)
+				},
+				PoolCurve::StableSwap { amplification } => Self::get_stable_amount_out(
+					amount_in,
+					reserve_in,
+					reserve_out,
+					amplification,
+					T::LPFee::get(),
+				),
+			}
+		}
+
+		/// Calculates amount in following the `curve` of a pool, see [`Pallet::get_amount_in`]
+		/// and [`Pallet::get_stable_amount_in`].
+		pub fn get_curve_amount_in(
+			curve: PoolCurve,
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			match curve {
+				PoolCurve::ConstantProduct => {
+					Self::get_amount_in(amount_out, reserve_in, reserve_out)
+				},
+				PoolCurve::StableSwap { amplification } => Self::get_stable_amount_in(
+					amount_out,
+					reserve_in,
+					reserve_out,
+					amplification,
+					T::LPFee::get(),
+				),
+			}
+		}
+
+		/// Returns the marginal price of the asset with `reserve1` denominated in the asset with
+		/// `reserve2`, in a pool following `curve`.
+		///
+		/// Returns `None` for an empty pool.
+		pub(crate) fn spot_price(
+			curve: PoolCurve,
+			reserve1: &T::Balance,
+			reserve2: &T::Balance,
+		) -> Option<FixedU128> {
+			if reserve1.is_zero() || reserve2.is_zero() {
+				return None;
+			}
+			match curve {
+				PoolCurve::ConstantProduct => {
+					let reserve1: u128 = (*reserve1).unique_saturated_into();
+					let reserve2: u128 = (*reserve2).unique_saturated_into();
+					Some(FixedU128::saturating_from_rational(reserve2, reserve1))
+				},
+				PoolCurve::StableSwap { amplification } => {
+					Self::stable_swap_spot_price(reserve1, reserve2, amplification)
+				},
+			}
+		}
+
 		/// Ensure that a path is valid.
 		fn validate_swap_path(path: &Vec<T::AssetKind>) -> Result<(), DispatchError> {
 			ensure!(path.len() >= 2, Error::<T>::InvalidPath);
@@ -1272,6 +1628,172 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Returns true if `asset1` and `asset2` are in the order the price accumulator of their
+		/// pool is kept in, i.e. `asset1` has the lesser encoding.
+		pub(crate) fn is_price_order(asset1: &T::AssetKind, asset2: &T::AssetKind) -> bool {
+			asset1.using_encoded(|a| asset2.using_encoded(|b| a <= b))
+		}
+
+		/// Accumulates the prices of the pool of `asset1` and `asset2` since its previous update
+		/// and records its current reserves.
+		///
+		/// Must be called after every change of the pool reserves. Only the first call within a
+		/// block accumulates prices and records an observation, the following ones merely update
+		/// the reserves.
+		fn update_price_accumulator(asset1: &T::AssetKind, asset2: &T::AssetKind) {
+			let Ok(pool_id) = T::PoolLocator::pool_id(asset1, asset2) else { return };
+			let Ok(pool_account) = T::PoolLocator::address(&pool_id) else { return };
+			let (asset1, asset2) = if Self::is_price_order(asset1, asset2) {
+				(asset1, asset2)
+			} else {
+				(asset2, asset1)
+			};
+			let reserves = (
+				Self::get_balance(&pool_account, asset1.clone()),
+				Self::get_balance(&pool_account, asset2.clone()),
+			);
+			let now = frame_system::Pallet::<T>::block_number();
+			let amplification = Self::pool_id_curve(&pool_id).amplification();
+
+			let Some(mut accumulator) = PriceAccumulators::<T>::get(&pool_id) else {
+				let latest = PriceObservation {
+					block: now,
+					price1_cumulative: U256::zero(),
+					price2_cumulative: U256::zero(),
+					amplification,
+				};
+				PriceObservations::<T>::insert(&pool_id, 0, latest);
+				PriceAccumulators::<T>::insert(
+					&pool_id,
+					PriceAccumulator {
+						reserves,
+						latest,
+						observation_index: 0,
+						observation_count: 1,
+					},
+				);
+				return;
+			};
+
+			if accumulator.latest.block < now {
+				let max_observations = T::MaxPriceObservations::get();
+				accumulator.latest =
+					Self::extrapolate_price_observation(&accumulator, now, amplification);
+				accumulator.observation_index =
+					accumulator.observation_index.saturating_add(1) % max_observations;
+				accumulator.observation_count =
+					accumulator.observation_count.saturating_add(1).min(max_observations);
+				PriceObservations::<T>::insert(
+					&pool_id,
+					accumulator.observation_index,
+					accumulator.latest,
+				);
+			} else if accumulator.latest.amplification != amplification {
+				accumulator.latest.amplification = amplification;
+				PriceObservations::<T>::insert(
+					&pool_id,
+					accumulator.observation_index,
+					accumulator.latest,
+				);
+			}
+			accumulator.reserves = reserves;
+			PriceAccumulators::<T>::insert(&pool_id, accumulator);
+		}
+
+		/// Extends the latest cumulative prices of `accumulator` up to `block`, the reserves of
+		/// the pool being unchanged since its last update, and records `amplification` in the
+		/// returned observation.
+		///
+		/// Prices follow the curve of the amplification coefficient recorded in the latest
+		/// observation, so that the accumulated prices don't depend on later changes of the
+		/// coefficient.
+		fn extrapolate_price_observation(
+			accumulator: &PriceAccumulator<T::Balance, BlockNumberFor<T>>,
+			block: BlockNumberFor<T>,
+			amplification: Option<u32>,
+		) -> PriceObservation<BlockNumberFor<T>> {
+			let latest = accumulator.latest;
+			let elapsed: u128 = block.saturating_sub(latest.block).unique_saturated_into();
+			let (reserve1, reserve2) = &accumulator.reserves;
+			let curve = PoolCurve::from_amplification(latest.amplification);
+
+			let mut observation = PriceObservation { block, amplification, ..latest };
+			// the price is undefined for an empty pool, nothing is accumulated
+			let (Some(price1), Some(price2)) = (
+				Self::spot_price(curve, reserve1, reserve2),
+				Self::spot_price(curve, reserve2, reserve1),
+			) else {
+				return observation;
+			};
+			let weighted_price = |price: FixedU128| {
+				U256::from(price.into_inner()).saturating_mul(U256::from(elapsed))
+			};
+			observation.price1_cumulative =
+				latest.price1_cumulative.saturating_add(weighted_price(price1));
+			observation.price2_cumulative =
+				latest.price2_cumulative.saturating_add(weighted_price(price2));
+			observation
+		}
+
+		/// Returns the cumulative prices of the pool `pool_id` as of `block`, which must not be in
+		/// the future.
+		///
+		/// Returns `None` if `block` precedes the oldest price observation kept for the pool.
+		fn price_observation_at(
+			pool_id: &T::PoolId,
+			accumulator: &PriceAccumulator<T::Balance, BlockNumberFor<T>>,
+			block: BlockNumberFor<T>,
+		) -> Option<PriceObservation<BlockNumberFor<T>>> {
+			if block >= accumulator.latest.block {
+				return Some(Self::extrapolate_price_observation(
+					accumulator,
+					block,
+					accumulator.latest.amplification,
+				));
+			}
+
+			let max_observations = T::MaxPriceObservations::get() as u64;
+			let count = (accumulator.observation_count as u64).min(max_observations);
+			// position `0` is the oldest observation, `count - 1` the latest one
+			let observation = |position: u64| {
+				let index =
+					(accumulator.observation_index as u64 + max_observations + 1 + position -
+						count) % max_observations;
+				PriceObservations::<T>::get(pool_id, index as u32)
+			};
+			if block < observation(0)?.block {
+				return None;
+			}
+
+			// the latest observation is past `block`, find the pair of observations around it
+			let (mut low, mut high) = (0, count - 1);
+			while high - low > 1 {
+				let middle = low + (high - low) / 2;
+				if observation(middle)?.block <= block {
+					low = middle;
+				} else {
+					high = middle;
+				}
+			}
+			let (before, after) = (observation(low)?, observation(high)?);
+
+			// the price was constant in between the two observations
+			let elapsed: u128 = block.saturating_sub(before.block).unique_saturated_into();
+			let span: u128 = after.block.saturating_sub(before.block).unique_saturated_into();
+			let interpolate = |before: U256, after: U256| {
+				before.saturating_add(
+					after.saturating_sub(before).saturating_mul(U256::from(elapsed)) /
+						U256::from(span),
+				)
+			};
+			Some(PriceObservation {
+				block,
+				price1_cumulative: interpolate(before.price1_cumulative, after.price1_cumulative),
+				price2_cumulative: interpolate(before.price2_cumulative, after.price2_cumulative),
+				amplification: before.amplification,
+			})
+		}
+
 		/// Returns the next pool asset id for benchmark purposes only.
 		#[cfg(any(test, feature = "runtime-benchmarks"))]
 		pub fn get_next_pool_asset_id() -> T::PoolAssetId {
@@ -1316,14 +1838,22 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::pool_curve(&asset1, &asset2);
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
+				match (include_fee, curve) {
+					(true, curve) => {
+						Self::get_curve_amount_out(curve, &amount, &balance1, &balance2).ok()
+					},
+					(false, PoolCurve::ConstantProduct) => {
+						Self::quote(&amount, &balance1, &balance2).ok()
+					},
+					(false, PoolCurve::StableSwap { amplification }) => {
+						Self::get_stable_amount_out(&amount, &balance1, &balance2, amplification, 0)
+							.ok()
+					},
 				}
 			} else {
 				None
@@ -1344,19 +1874,57 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::pool_curve(&asset1, &asset2);
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
+				match (include_fee, curve) {
+					(true, curve) => {
+						Self::get_curve_amount_in(curve, &amount, &balance1, &balance2).ok()
+					},
+					(false, PoolCurve::ConstantProduct) => {
+						Self::quote(&amount, &balance2, &balance1).ok()
+					},
+					(false, PoolCurve::StableSwap { amplification }) => {
+						Self::get_stable_amount_in(&amount, &balance1, &balance2, amplification, 0)
+							.ok()
+					},
 				}
 			} else {
 				None
 			}
 		}
+
+		/// Returns the time weighted average price of `asset1` denominated in `asset2`, i.e. the
+		/// amount of `asset2` per unit of `asset1`, between the blocks `from` and `to`.
+		///
+		/// Returns `None` if the pool does not exist, `from` is not before `to`, `to` is in the
+		/// future, or the price observations kept for the pool do not reach back to `from`.
+		pub fn get_twap(
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			from: BlockNumberFor<T>,
+			to: BlockNumberFor<T>,
+		) -> Option<FixedU128> {
+			if from >= to || to > frame_system::Pallet::<T>::block_number() {
+				return None;
+			}
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).ok()?;
+			let accumulator = PriceAccumulators::<T>::get(&pool_id)?;
+
+			let start = Self::price_observation_at(&pool_id, &accumulator, from)?;
+			let end = Self::price_observation_at(&pool_id, &accumulator, to)?;
+			let (start, end) = if Self::is_price_order(&asset1, &asset2) {
+				(start.price1_cumulative, end.price1_cumulative)
+			} else {
+				(start.price2_cumulative, end.price2_cumulative)
+			};
+
+			let blocks: u128 = to.saturating_sub(from).unique_saturated_into();
+			let average = end.saturating_sub(start) / U256::from(blocks);
+			Some(FixedU128::from_inner(u128::try_from(average).unwrap_or(u128::MAX)))
+		}
 	}
 }
 
@@ -1392,6 +1960,29 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Returns the time weighted average price of `asset1` denominated in `asset2` between
+		/// the blocks `from` and `to`.
+		///
+		/// See [`Pallet::get_twap`].
+		#[api_version(2)]
+		fn get_twap(
+			asset1: AssetId,
+			asset2: AssetId,
+			from: NumberFor<Block>,
+			to: NumberFor<Block>,
+		) -> Option<FixedU128>;
+
+		/// Returns the swap path giving the most of `asset_out` for `amount_in` of `asset_in`,
+		/// along with the expected amount out and the price impact of the swap.
+		///
+		/// See [`Pallet::find_best_route`].
+		#[api_version(3)]
+		fn find_best_route(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount_in: Balance,
+		) -> Option<SwapRoute<AssetId, Balance>>;
 	}
 }
 
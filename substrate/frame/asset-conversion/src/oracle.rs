@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits and implementations for the time weighted average prices of the liquidity pools.
+
+use super::*;
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// Trait providing the time weighted average prices (TWAP) of the liquidity pools.
+pub trait TwapOracle<AssetKind, BlockNumber> {
+	/// Returns the time weighted average price of `asset1` denominated in `asset2`, i.e. the
+	/// amount of `asset2` per unit of `asset1`, over the `window` blocks preceding the current
+	/// one.
+	///
+	/// Returns `None` if the pool does not exist or its price history does not cover the window.
+	fn twap(asset1: &AssetKind, asset2: &AssetKind, window: BlockNumber) -> Option<FixedU128>;
+}
+
+impl<T: Config> TwapOracle<T::AssetKind, BlockNumberFor<T>> for Pallet<T> {
+	fn twap(
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Self::get_twap(asset1.clone(), asset2.clone(), now.checked_sub(&window)?, now)
+	}
+}
@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Discovery of the best swap route between two assets.
+
+use super::*;
+use alloc::collections::btree_map::BTreeMap;
+use codec::Encode;
+use sp_runtime::Permill;
+
+/// Maximum number of pools whose reserves are read while searching for the best route.
+///
+/// Bounds the cost of [`Pallet::find_best_route`] when many pools are reachable from the asset
+/// swapped in.
+const MAX_ROUTING_POOLS: usize = 256;
+
+/// A pool considered for routing, with its reserves and curve.
+struct RoutingPool<AssetKind, Balance> {
+	assets: (AssetKind, AssetKind),
+	reserves: (Balance, Balance),
+	curve: PoolCurve,
+}
+
+impl<AssetKind: PartialEq + Clone, Balance: Copy> RoutingPool<AssetKind, Balance> {
+	/// Returns the other asset of the pool and the reserves in the swap direction, if `asset` is
+	/// one of the assets of the pool.
+	fn swap_from(&self, asset: &AssetKind) -> Option<(AssetKind, Balance, Balance)> {
+		let (asset1, asset2) = &self.assets;
+		let (reserve1, reserve2) = self.reserves;
+		if asset1 == asset {
+			Some((asset2.clone(), reserve1, reserve2))
+		} else if asset2 == asset {
+			Some((asset1.clone(), reserve2, reserve1))
+		} else {
+			None
+		}
+	}
+}
+
+/// Indices of the items of each asset, keyed by the encoded asset.
+type AssetIndex = BTreeMap<Vec<u8>, Vec<usize>>;
+
+/// Indexes `pairs` by both of their assets.
+fn index_by_asset<AssetKind: Encode>(
+	pairs: impl Iterator<Item = (AssetKind, AssetKind)>,
+) -> AssetIndex {
+	let mut adjacent = AssetIndex::new();
+	for (index, (asset1, asset2)) in pairs.enumerate() {
+		adjacent.entry(asset1.encode()).or_default().push(index);
+		adjacent.entry(asset2.encode()).or_default().push(index);
+	}
+	adjacent
+}
+
+/// The pools considered for routing, indexed by the assets they can swap from.
+struct RoutingGraph<AssetKind, Balance> {
+	pools: Vec<RoutingPool<AssetKind, Balance>>,
+	adjacent: AssetIndex,
+}
+
+impl<AssetKind: Encode + PartialEq + Clone, Balance: Copy> RoutingGraph<AssetKind, Balance> {
+	fn new(pools: Vec<RoutingPool<AssetKind, Balance>>) -> Self {
+		let adjacent = index_by_asset(pools.iter().map(|pool| pool.assets.clone()));
+		Self { pools, adjacent }
+	}
+
+	/// Returns the pools `asset` can be swapped from.
+	fn pools_from<'a>(
+		&'a self,
+		asset: &AssetKind,
+	) -> impl Iterator<Item = &'a RoutingPool<AssetKind, Balance>> + 'a {
+		self.adjacent
+			.get(&asset.encode())
+			.into_iter()
+			.flatten()
+			.map(move |index| &self.pools[*index])
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	T::PoolId: Into<(T::AssetKind, T::AssetKind)>,
+{
+	/// Finds the swap path giving the most of `asset_out` for `amount_in` of `asset_in`.
+	///
+	/// The paths of at most [`Config::MaxSwapPathLength`] assets are searched breadth first,
+	/// keeping for every number of swaps the path giving the most of each asset reached. Only the
+	/// [`MAX_ROUTING_POOLS`] pools with liquidity closest to `asset_in` are considered, so the
+	/// search is best-effort when more pools are reachable. Amounts are computed as by
+	/// [`Pallet::swap_exact_tokens_for_tokens`], so that swapping along the returned path gives
+	/// the returned amount out, provided the pools don't change in between.
+	///
+	/// Returns `None` if no path leads from `asset_in` to `asset_out`.
+	pub fn find_best_route(
+		asset_in: T::AssetKind,
+		asset_out: T::AssetKind,
+		amount_in: T::Balance,
+	) -> Option<SwapRoute<T::AssetKind, T::Balance>> {
+		if asset_in == asset_out || amount_in.is_zero() {
+			return None;
+		}
+		let graph = Self::routing_graph(&asset_in);
+
+		// the path reaching each asset with the most of it, among the paths of the last round
+		let mut frontier = BTreeMap::<Vec<u8>, (Vec<T::AssetKind>, T::Balance)>::new();
+		frontier.insert(asset_in.encode(), (alloc::vec![asset_in.clone()], amount_in));
+		// the most of each asset reached by any path so far
+		let mut reached = BTreeMap::<Vec<u8>, T::Balance>::new();
+		reached.insert(asset_in.encode(), amount_in);
+		let mut best: Option<(Vec<T::AssetKind>, T::Balance)> = None;
+
+		for _ in 1..T::MaxSwapPathLength::get() {
+			let mut next_frontier = BTreeMap::<Vec<u8>, (Vec<T::AssetKind>, T::Balance)>::new();
+			for (path, amount) in frontier.values() {
+				let Some(asset) = path.last() else { continue };
+				for pool in graph.pools_from(asset) {
+					let Some((next, reserve_in, reserve_out)) = pool.swap_from(asset) else {
+						continue;
+					};
+					if path.contains(&next) {
+						continue;
+					}
+					let Ok(amount_out) =
+						Self::get_curve_amount_out(pool.curve, amount, &reserve_in, &reserve_out)
+					else {
+						continue;
+					};
+					if amount_out.is_zero() {
+						continue;
+					}
+					if next == asset_out {
+						// a path found in an earlier round is shorter and wins a tie
+						if best.as_ref().map_or(true, |(_, best_amount)| amount_out > *best_amount)
+						{
+							let mut path = path.clone();
+							path.push(next);
+							best = Some((path, amount_out));
+						}
+						continue;
+					}
+					let key = next.encode();
+					if reached.get(&key).map_or(false, |most| amount_out <= *most) {
+						continue;
+					}
+					reached.insert(key.clone(), amount_out);
+					let mut path = path.clone();
+					path.push(next);
+					next_frontier.insert(key, (path, amount_out));
+				}
+			}
+			if next_frontier.is_empty() {
+				break;
+			}
+			frontier = next_frontier;
+		}
+		let (path, amount_out) = best?;
+
+		// the output at the marginal prices of the pools along the path
+		let mut marginal_amount_out: u128 = amount_in.unique_saturated_into();
+		for pair in path.windows(2) {
+			let (curve, reserve_in, reserve_out) = graph.pools_from(&pair[0]).find_map(|pool| {
+				let (asset, reserve_in, reserve_out) = pool.swap_from(&pair[0])?;
+				(asset == pair[1]).then_some((pool.curve, reserve_in, reserve_out))
+			})?;
+			let price = Self::spot_price(curve, &reserve_in, &reserve_out)?;
+			marginal_amount_out = price.saturating_mul_int(marginal_amount_out);
+		}
+		let shortfall = marginal_amount_out.saturating_sub(amount_out.unique_saturated_into());
+		let price_impact = Permill::from_rational(shortfall, marginal_amount_out.max(1));
+
+		Some(SwapRoute { path, amount_out, price_impact })
+	}
+
+	/// Returns the pools with liquidity reachable from `asset_in` within a path of
+	/// [`Config::MaxSwapPathLength`] assets, closest first and at most [`MAX_ROUTING_POOLS`].
+	///
+	/// Only the keys of [`Pools`] are iterated, the reserves are read for the returned pools only.
+	fn routing_graph(asset_in: &T::AssetKind) -> RoutingGraph<T::AssetKind, T::Balance> {
+		let pool_ids: Vec<T::PoolId> = Pools::<T>::iter_keys().collect();
+		let pool_assets: Vec<(T::AssetKind, T::AssetKind)> =
+			pool_ids.iter().map(|pool_id| pool_id.clone().into()).collect();
+		let adjacent = index_by_asset(pool_assets.iter().cloned());
+
+		let mut visited = alloc::vec![false; pool_ids.len()];
+		let mut pools = Vec::new();
+		let mut assets = alloc::vec![asset_in.clone()];
+		'rounds: for _ in 1..T::MaxSwapPathLength::get() {
+			let mut next_assets = Vec::new();
+			for asset in &assets {
+				for &index in adjacent.get(&asset.encode()).into_iter().flatten() {
+					if visited[index] {
+						continue;
+					}
+					visited[index] = true;
+					let pool_id = &pool_ids[index];
+					let Ok(pool_account) = T::PoolLocator::address(pool_id) else { continue };
+					let (asset1, asset2) = pool_assets[index].clone();
+					let reserves = (
+						Self::get_balance(&pool_account, asset1.clone()),
+						Self::get_balance(&pool_account, asset2.clone()),
+					);
+					if reserves.0.is_zero() || reserves.1.is_zero() {
+						continue;
+					}
+					let next = if &asset1 == asset { asset2.clone() } else { asset1.clone() };
+					next_assets.push(next);
+					let curve = Self::pool_id_curve(pool_id);
+					pools.push(RoutingPool { assets: (asset1, asset2), reserves, curve });
+					if pools.len() >= MAX_ROUTING_POOLS {
+						break 'rounds;
+					}
+				}
+			}
+			assets = next_assets;
+		}
+		RoutingGraph::new(pools)
+	}
+}
@@ -0,0 +1,261 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Math of the two assets [StableSwap](https://docs.curve.fi/assets/pdf/stableswap-paper.pdf)
+//! invariant.
+//!
+//! The invariant `D` of a pool with the reserves `x` and `y` and the amplification coefficient
+//! `A` is given by:
+//!
+//! `A * n^n * (x + y) + D = A * n^n * D + D^(n + 1) / (n^n * x * y)`, with `n = 2`.
+//!
+//! Both the invariant and the reserves keeping it are found with Newton's method, as done by the
+//! Curve contracts. The assets of a StableSwap pool are expected to have the same number of
+//! decimals.
+
+use super::*;
+
+/// The max amplification coefficient of a StableSwap pool.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The max factor the amplification coefficient of a StableSwap pool can be changed by within a
+/// single ramp.
+pub const MAX_AMPLIFICATION_CHANGE: u32 = 10;
+
+/// Number of assets in a pool.
+const N_COINS: u32 = 2;
+
+/// The max number of iterations of Newton's method.
+const MAX_ITERATIONS: u32 = 255;
+
+impl<T: Config> Pallet<T> {
+	/// Calculates amount out of a StableSwap pool.
+	///
+	/// Given an input amount of an asset, pair reserves and the amplification coefficient of the
+	/// pool, returns the maximum output amount of the other asset. The liquidity provider fee
+	/// `lp_fee`, in 10ths of a percent, is taken from the input amount.
+	pub fn get_stable_amount_out(
+		amount_in: &T::Balance,
+		reserve_in: &T::Balance,
+		reserve_out: &T::Balance,
+		amplification: u32,
+		lp_fee: u32,
+	) -> Result<T::Balance, Error<T>> {
+		let amount_in = Self::to_u256(amount_in)?;
+		let reserve_in = Self::to_u256(reserve_in)?;
+		let reserve_out = Self::to_u256(reserve_out)?;
+
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			return Err(Error::<T>::ZeroLiquidity);
+		}
+
+		let amount_in_with_fee = amount_in
+			.checked_mul(U256::from(1000u32.saturating_sub(lp_fee)))
+			.ok_or(Error::<T>::Overflow)? /
+			1000u32;
+
+		let invariant = Self::stable_swap_invariant(reserve_in, reserve_out, amplification)?;
+		let new_reserve_in =
+			reserve_in.checked_add(amount_in_with_fee).ok_or(Error::<T>::Overflow)?;
+		let new_reserve_out = Self::stable_swap_reserve(new_reserve_in, invariant, amplification)?;
+
+		// rounded down by one unit, in favour of the pool
+		let result = reserve_out.saturating_sub(new_reserve_out).saturating_sub(U256::one());
+
+		Self::from_u256(result)
+	}
+
+	/// Calculates amount in of a StableSwap pool.
+	///
+	/// Given an output amount of an asset, pair reserves and the amplification coefficient of the
+	/// pool, returns a required input amount of the other asset. The liquidity provider fee
+	/// `lp_fee`, in 10ths of a percent, is added to the input amount.
+	pub fn get_stable_amount_in(
+		amount_out: &T::Balance,
+		reserve_in: &T::Balance,
+		reserve_out: &T::Balance,
+		amplification: u32,
+		lp_fee: u32,
+	) -> Result<T::Balance, Error<T>> {
+		let amount_out = Self::to_u256(amount_out)?;
+		let reserve_in = Self::to_u256(reserve_in)?;
+		let reserve_out = Self::to_u256(reserve_out)?;
+
+		if reserve_in.is_zero() || reserve_out.is_zero() {
+			Err(Error::<T>::ZeroLiquidity)?
+		}
+
+		if amount_out >= reserve_out {
+			Err(Error::<T>::AmountOutTooHigh)?
+		}
+
+		let invariant = Self::stable_swap_invariant(reserve_in, reserve_out, amplification)?;
+		let new_reserve_in =
+			Self::stable_swap_reserve(reserve_out - amount_out, invariant, amplification)?;
+
+		// rounded up by one unit, in favour of the pool
+		let amount_in_with_fee = new_reserve_in
+			.checked_sub(reserve_in)
+			.ok_or(Error::<T>::Overflow)?
+			.saturating_add(U256::one());
+
+		let result = amount_in_with_fee
+			.checked_mul(U256::from(1000u32))
+			.ok_or(Error::<T>::Overflow)? /
+			U256::from(1000u32.saturating_sub(lp_fee)).max(U256::one()) +
+			U256::one();
+
+		Self::from_u256(result)
+	}
+
+	/// Returns the marginal price of the asset with `reserve1` denominated in the asset with
+	/// `reserve2`, in a StableSwap pool with the given amplification coefficient.
+	///
+	/// Returns `None` for an empty pool.
+	pub(crate) fn stable_swap_spot_price(
+		reserve1: &T::Balance,
+		reserve2: &T::Balance,
+		amplification: u32,
+	) -> Option<FixedU128> {
+		let reserve1 = Self::to_u256(reserve1).ok()?;
+		let reserve2 = Self::to_u256(reserve2).ok()?;
+		let invariant = Self::stable_swap_invariant(reserve1, reserve2, amplification).ok()?;
+		let ratio = |reserve: U256| {
+			FixedU128::checked_from_rational(
+				u128::try_from(invariant).ok()?,
+				u128::try_from(reserve).ok()?,
+			)
+		};
+		let (ratio1, ratio2) = (ratio(reserve1)?, ratio(reserve2)?);
+
+		// the price is the ratio of the partial derivatives of the invariant equation, i.e.
+		// `(A * n^n + D^3 / (4 * x^2 * y)) / (A * n^n + D^3 / (4 * x * y^2))`
+		let four = FixedU128::saturating_from_integer(4u32);
+		let term1 = ratio1.saturating_mul(ratio1).saturating_mul(ratio2) / four;
+		let term2 = ratio1.saturating_mul(ratio2).saturating_mul(ratio2) / four;
+		let ann =
+			FixedU128::saturating_from_integer(amplification.saturating_mul(N_COINS * N_COINS));
+
+		ann.saturating_add(term1).checked_div(&ann.saturating_add(term2))
+	}
+
+	/// Calculates the StableSwap invariant `D` of the pool with the given reserves.
+	fn stable_swap_invariant(
+		reserve1: U256,
+		reserve2: U256,
+		amplification: u32,
+	) -> Result<U256, Error<T>> {
+		if reserve1.is_zero() || reserve2.is_zero() {
+			return Err(Error::<T>::ZeroLiquidity);
+		}
+		let n = U256::from(N_COINS);
+		let ann = U256::from(amplification) * n * n;
+		let sum = reserve1.checked_add(reserve2).ok_or(Error::<T>::Overflow)?;
+		let ann_sum = ann.checked_mul(sum).ok_or(Error::<T>::Overflow)?;
+
+		let mut invariant = sum;
+		for _ in 0..MAX_ITERATIONS {
+			// D^(n + 1) / (n^n * x * y)
+			let invariant_product =
+				invariant.checked_mul(invariant).ok_or(Error::<T>::Overflow)? / (reserve1 * n);
+			let invariant_product =
+				invariant_product.checked_mul(invariant).ok_or(Error::<T>::Overflow)? /
+					(reserve2 * n);
+
+			let previous = invariant;
+			// (Ann * S + D_P * n) * D / ((Ann - 1) * D + (n + 1) * D_P)
+			let numerator = invariant_product
+				.checked_mul(n)
+				.and_then(|p| p.checked_add(ann_sum))
+				.and_then(|p| p.checked_mul(invariant))
+				.ok_or(Error::<T>::Overflow)?;
+			let denominator = ann
+				.saturating_sub(U256::one())
+				.checked_mul(invariant)
+				.and_then(|p| p.checked_add(invariant_product.checked_mul(n + 1)?))
+				.filter(|d| !d.is_zero())
+				.ok_or(Error::<T>::Overflow)?;
+			invariant = numerator / denominator;
+
+			if Self::converged(invariant, previous) {
+				return Ok(invariant);
+			}
+		}
+		Err(Error::<T>::StableSwapDidNotConverge)
+	}
+
+	/// Calculates the reserve of an asset keeping the StableSwap `invariant`, given the reserve
+	/// of the other asset of the pool.
+	fn stable_swap_reserve(
+		other_reserve: U256,
+		invariant: U256,
+		amplification: u32,
+	) -> Result<U256, Error<T>> {
+		let n = U256::from(N_COINS);
+		let ann = U256::from(amplification) * n * n;
+		if other_reserve.is_zero() || ann.is_zero() {
+			return Err(Error::<T>::ZeroLiquidity);
+		}
+
+		// c = D^(n + 1) / (n^n * x * Ann)
+		let c = invariant.checked_mul(invariant).ok_or(Error::<T>::Overflow)? / (other_reserve * n);
+		let c = c.checked_mul(invariant).ok_or(Error::<T>::Overflow)? / (ann * n);
+		// b = x + D / Ann
+		let b = other_reserve.checked_add(invariant / ann).ok_or(Error::<T>::Overflow)?;
+
+		let mut reserve = invariant;
+		for _ in 0..MAX_ITERATIONS {
+			let previous = reserve;
+			// (y^2 + c) / (2 * y + b - D)
+			let numerator = reserve
+				.checked_mul(reserve)
+				.and_then(|p| p.checked_add(c))
+				.ok_or(Error::<T>::Overflow)?;
+			let denominator = reserve
+				.checked_mul(n)
+				.and_then(|p| p.checked_add(b))
+				.and_then(|p| p.checked_sub(invariant))
+				.filter(|d| !d.is_zero())
+				.ok_or(Error::<T>::Overflow)?;
+			reserve = numerator / denominator;
+
+			if Self::converged(reserve, previous) {
+				return Ok(reserve);
+			}
+		}
+		Err(Error::<T>::StableSwapDidNotConverge)
+	}
+
+	/// Returns true if two consecutive approximations differ by at most one unit.
+	fn converged(value: U256, previous: U256) -> bool {
+		value.abs_diff(previous) <= U256::one()
+	}
+
+	/// Converts a balance for the StableSwap math.
+	fn to_u256(balance: &T::Balance) -> Result<U256, Error<T>> {
+		let balance: u128 = (*balance).try_into().map_err(|_| Error::<T>::Overflow)?;
+		Ok(U256::from(balance))
+	}
+
+	/// Converts a result of the StableSwap math back to a balance.
+	fn from_u256(value: U256) -> Result<T::Balance, Error<T>> {
+		u128::try_from(value)
+			.ok()
+			.and_then(|value| value.try_into().ok())
+			.ok_or(Error::<T>::Overflow)
+	}
+}
@@ -2491,3 +2491,701 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+/// Creates the assets of the given `ids` and funds `user` with `amount` of each, as well as of the
+/// native asset.
+fn fund_user(user: u128, ids: &[u32], amount: u128) {
+	create_tokens(user, ids.iter().map(|id| NativeOrWithId::WithId(*id)).collect());
+	assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, amount));
+	for id in ids {
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), *id, user, amount));
+	}
+}
+
+/// Creates a pool of `token_1` and `token_2`, a StableSwap one if an `amplification` coefficient
+/// is given, and adds the `liquidity` of `user` to it.
+fn setup_pool(
+	user: u128,
+	token_1: &NativeOrWithId<u32>,
+	token_2: &NativeOrWithId<u32>,
+	amplification: Option<u32>,
+	liquidity: (u128, u128),
+) {
+	let (asset1, asset2) = (Box::new(token_1.clone()), Box::new(token_2.clone()));
+	match amplification {
+		Some(amplification) => assert_ok!(AssetConversion::create_stable_pool(
+			RuntimeOrigin::signed(user),
+			asset1.clone(),
+			asset2.clone(),
+			amplification,
+		)),
+		None => assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			asset1.clone(),
+			asset2.clone()
+		)),
+	}
+	assert_ok!(AssetConversion::add_liquidity(
+		RuntimeOrigin::signed(user),
+		asset1,
+		asset2,
+		liquidity.0,
+		liquidity.1,
+		1,
+		1,
+		user,
+	));
+}
+
+fn swap_for_twap(user: u128, token_in: &NativeOrWithId<u32>, token_out: &NativeOrWithId<u32>) {
+	assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+		RuntimeOrigin::signed(user),
+		bvec![token_in.clone(), token_out.clone()],
+		10_000,
+		1,
+		user,
+		false,
+	));
+}
+
+fn spot_price(
+	token_1: &NativeOrWithId<u32>,
+	token_2: &NativeOrWithId<u32>,
+) -> sp_runtime::FixedU128 {
+	let (reserve1, reserve2) =
+		AssetConversion::get_reserves(token_1.clone(), token_2.clone()).unwrap();
+	// rounded down, as the accumulated prices
+	<sp_runtime::FixedU128 as sp_runtime::FixedPointNumber>::saturating_from_rational(
+		reserve2, reserve1,
+	)
+}
+
+#[test]
+fn twap_follows_pool_price() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::FixedU128;
+
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		fund_user(user, &[2], 1_000_000);
+		setup_pool(user, &token_1, &token_2, None, (100_000, 10_000));
+		let price_1 = spot_price(&token_1, &token_2);
+		assert_eq!(price_1, FixedU128::from_rational(1, 10));
+
+		System::set_block_number(11);
+		swap_for_twap(user, &token_1, &token_2);
+		let price_2 = spot_price(&token_1, &token_2);
+		assert!(price_2 < price_1);
+		System::set_block_number(21);
+
+		// constant price in between the observations
+		assert_eq!(
+			AssetConversion::get_twap(token_1.clone(), token_2.clone(), 1, 11),
+			Some(price_1)
+		);
+		assert_eq!(
+			AssetConversion::get_twap(token_1.clone(), token_2.clone(), 3, 8),
+			Some(price_1)
+		);
+		assert_eq!(
+			AssetConversion::get_twap(token_2.clone(), token_1.clone(), 1, 11),
+			Some(FixedU128::from_u32(10))
+		);
+		// the price since the latest observation is taken from the recorded reserves
+		assert_eq!(
+			AssetConversion::get_twap(token_1.clone(), token_2.clone(), 11, 21),
+			Some(price_2)
+		);
+		assert_eq!(
+			AssetConversion::get_twap(token_1.clone(), token_2.clone(), 5, 15),
+			Some(FixedU128::from_inner((price_1.into_inner() * 6 + price_2.into_inner() * 4) / 10))
+		);
+		assert_eq!(
+			<AssetConversion as TwapOracle<_, _>>::twap(&token_1, &token_2, 20),
+			Some(FixedU128::from_inner((price_1.into_inner() + price_2.into_inner()) / 2))
+		);
+
+		// invalid ranges
+		assert_eq!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 11, 11), None);
+		assert_eq!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 11, 22), None);
+		// before the pool got liquidity
+		assert_eq!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 0, 11), None);
+		// unknown pool
+		assert_eq!(
+			AssetConversion::get_twap(token_1.clone(), NativeOrWithId::WithId(3), 1, 11),
+			None
+		);
+	});
+}
+
+#[test]
+fn twap_ignores_price_changes_within_the_current_block() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		fund_user(user, &[2], 1_000_000);
+		setup_pool(user, &token_1, &token_2, None, (100_000, 10_000));
+		let price = spot_price(&token_1, &token_2);
+
+		System::set_block_number(11);
+		swap_for_twap(user, &token_1, &token_2);
+		swap_for_twap(user, &token_1, &token_2);
+		assert!(spot_price(&token_1, &token_2) < price);
+
+		assert_eq!(
+			<AssetConversion as TwapOracle<_, _>>::twap(&token_1, &token_2, 10),
+			Some(price)
+		);
+		// the manipulated price is accounted for from the next block on
+		System::set_block_number(12);
+		assert!(
+			<AssetConversion as TwapOracle<_, _>>::twap(&token_1, &token_2, 11).unwrap() < price
+		);
+	});
+}
+
+#[test]
+fn price_observations_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		fund_user(user, &[2], 1_000_000);
+		setup_pool(user, &token_1, &token_2, None, (100_000, 10_000));
+
+		for block in 2..=7 {
+			System::set_block_number(block);
+			swap_for_twap(user, &token_1, &token_2);
+			swap_for_twap(user, &token_2, &token_1);
+		}
+
+		let max_observations: u32 = <Test as Config>::MaxPriceObservations::get();
+		let accumulator = PriceAccumulators::<Test>::get(&pool_id).unwrap();
+		assert_eq!(accumulator.latest.block, 7);
+		assert_eq!(accumulator.observation_count, max_observations);
+		assert_eq!(PriceObservations::<Test>::iter_prefix(&pool_id).count(), 4);
+
+		// observations are kept for the blocks 4 to 7
+		assert_eq!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 3, 7), None);
+		assert!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 4, 7).is_some());
+	});
+}
+
+#[test]
+fn create_stable_pool_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+		for amplification in [0, MAX_AMPLIFICATION + 1] {
+			assert_noop!(
+				AssetConversion::create_stable_pool(
+					RuntimeOrigin::signed(user),
+					Box::new(token_1.clone()),
+					Box::new(token_2.clone()),
+					amplification,
+				),
+				Error::<Test>::InvalidAmplification
+			);
+		}
+
+		assert_ok!(AssetConversion::create_stable_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			100,
+		));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::StablePoolCreated {
+				pool_id: pool_id.clone(),
+				amplification: 100
+			})
+		);
+		assert_eq!(pools(), vec![pool_id.clone()]);
+		assert_eq!(StablePools::<Test>::get(&pool_id).unwrap().at(100), 100);
+		assert_eq!(
+			AssetConversion::pool_curve(&token_2, &token_1),
+			PoolCurve::StableSwap { amplification: 100 }
+		);
+	});
+}
+
+#[test]
+fn stable_swap_has_less_slippage_than_constant_product() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		fund_user(user, &[2], 10_000_000);
+		setup_pool(user, &token_1, &token_2, Some(100), (1_000_000, 1_000_000));
+		create_tokens(user, vec![token_3.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone())
+		));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 10_000_000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone()),
+			1_000_000,
+			1_000_000,
+			1,
+			1,
+			user,
+		));
+
+		let amount_in = 100_000;
+		let stable_quote = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_in,
+			true,
+		)
+		.unwrap();
+		let constant_product_quote = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_3.clone(),
+			amount_in,
+			true,
+		)
+		.unwrap();
+		assert_eq!(constant_product_quote, 90_661);
+		assert!(stable_quote > constant_product_quote);
+		// close to the input minus the 0.3% fee
+		assert!(stable_quote < amount_in * 997 / 1000);
+		assert!(stable_quote > amount_in * 990 / 1000);
+
+		// without the fee
+		let stable_quote_without_fee = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_in,
+			false,
+		)
+		.unwrap();
+		assert!(stable_quote_without_fee > stable_quote);
+		assert!(stable_quote_without_fee < amount_in);
+
+		// the quote matches the execution
+		let balance_before = balance(user, token_2.clone());
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			amount_in,
+			stable_quote,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_2.clone()), balance_before + stable_quote);
+		assert_eq!(
+			AssetConversion::get_reserves(token_1.clone(), token_2.clone()).unwrap(),
+			(1_000_000 + amount_in, 1_000_000 - stable_quote)
+		);
+	});
+}
+
+#[test]
+fn stable_swap_exact_out_quote_matches_execution() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		fund_user(user, &[2], 10_000_000);
+		setup_pool(user, &token_1, &token_2, Some(100), (1_000_000, 1_000_000));
+
+		let amount_out = 100_000;
+		let quote = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_2.clone(),
+			token_1.clone(),
+			amount_out,
+			true,
+		)
+		.unwrap();
+		assert!(quote > amount_out * 1003 / 1000);
+		assert!(quote < amount_out * 1010 / 1000);
+		// swapping the quoted amount in gives at least the exact amount out
+		assert!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				quote,
+				true,
+			)
+			.unwrap() >= amount_out
+		);
+
+		let balance_before = balance(user, token_2.clone());
+		assert_noop!(
+			AssetConversion::swap_tokens_for_exact_tokens(
+				RuntimeOrigin::signed(user),
+				bvec![token_2.clone(), token_1.clone()],
+				amount_out,
+				quote - 1,
+				user,
+				false,
+			),
+			Error::<Test>::ProvidedMaximumNotSufficientForSwap
+		);
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			amount_out,
+			quote,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_2.clone()), balance_before - quote);
+	});
+}
+
+#[test]
+fn swap_credit_routes_through_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		fund_user(user, &[2], 10_000_000);
+		setup_pool(user, &token_1, &token_2, Some(100), (1_000_000, 1_000_000));
+
+		let amount_out = 1_000;
+		let amount_in = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_out,
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			amount_in,
+			AssetConversion::get_stable_amount_in(
+				&amount_out,
+				&1_000_000,
+				&1_000_000,
+				100,
+				<Test as Config>::LPFee::get(),
+			)
+			.unwrap()
+		);
+
+		let credit_in = NativeAndAssets::issue(token_1.clone(), amount_in + 10);
+		let (credit_out, change) =
+			<AssetConversion as SwapCredit<_>>::swap_tokens_for_exact_tokens(
+				vec![token_1.clone(), token_2.clone()],
+				credit_in,
+				amount_out,
+			)
+			.unwrap();
+		assert_eq!(credit_out.peek(), amount_out);
+		assert_eq!(change.peek(), 10);
+	});
+}
+
+#[test]
+fn ramp_amplification_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		fund_user(user, &[2], 10_000_000);
+		setup_pool(user, &token_1, &token_2, Some(100), (1_000_000, 1_000_000));
+		let ramp = |origin: RuntimeOrigin, future_amplification: u32, future_block: u64| {
+			AssetConversion::ramp_amplification(
+				origin,
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				future_amplification,
+				future_block,
+			)
+		};
+
+		assert_noop!(ramp(RuntimeOrigin::signed(user), 200, 21), DispatchError::BadOrigin);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), 200, 10),
+			Error::<Test>::AmplificationRampTooShort
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), 1001, 21),
+			Error::<Test>::AmplificationChangeTooLarge
+		);
+		assert_noop!(
+			ramp(RuntimeOrigin::root(), 9, 21),
+			Error::<Test>::AmplificationChangeTooLarge
+		);
+		assert_noop!(ramp(RuntimeOrigin::root(), 0, 21), Error::<Test>::InvalidAmplification);
+
+		let quote = || {
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				100_000,
+				true,
+			)
+			.unwrap()
+		};
+		let quote_before = quote();
+
+		assert_ok!(ramp(RuntimeOrigin::root(), 200, 21));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::AmplificationRampStarted {
+				pool_id: pool_id.clone(),
+				initial: 100,
+				future: 200,
+				initial_block: 1,
+				future_block: 21,
+			})
+		);
+
+		System::set_block_number(11);
+		assert_eq!(
+			AssetConversion::pool_curve(&token_1, &token_2),
+			PoolCurve::StableSwap { amplification: 150 }
+		);
+		// a flatter curve gives a better rate
+		assert!(quote() > quote_before);
+
+		assert_noop!(
+			AssetConversion::stop_amplification_ramp(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(AssetConversion::stop_amplification_ramp(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+		));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::AmplificationRampStopped {
+				pool_id: pool_id.clone(),
+				amplification: 150
+			})
+		);
+
+		System::set_block_number(21);
+		assert_eq!(
+			AssetConversion::pool_curve(&token_1, &token_2),
+			PoolCurve::StableSwap { amplification: 150 }
+		);
+	});
+}
+
+#[test]
+fn ramp_amplification_requires_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		fund_user(user, &[2], 1_000_000);
+		setup_pool(user, &token_1, &token_2, None, (100_000, 10_000));
+
+		assert_noop!(
+			AssetConversion::ramp_amplification(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				200,
+				21,
+			),
+			Error::<Test>::NotStablePool
+		);
+		assert_noop!(
+			AssetConversion::stop_amplification_ramp(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_3.clone()),
+			),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn twap_follows_stable_pool_price() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		fund_user(user, &[2], 10_000_000);
+		setup_pool(user, &token_1, &token_2, Some(100), (1_000_000, 1_000_000));
+
+		System::set_block_number(11);
+		let twap = AssetConversion::get_twap(token_1.clone(), token_2.clone(), 1, 11).unwrap();
+		// a balanced StableSwap pool has a price of one
+		assert_eq!(twap, sp_runtime::FixedU128::from_u32(1));
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			500_000,
+			1,
+			user,
+			false,
+		));
+		System::set_block_number(21);
+		let twap = AssetConversion::get_twap(token_1.clone(), token_2.clone(), 11, 21).unwrap();
+		let constant_product_price = spot_price(&token_1, &token_2);
+		// the native asset is in excess, still priced closer to one than by the reserves ratio
+		assert!(twap < sp_runtime::FixedU128::from_u32(1));
+		assert!(twap > constant_product_price);
+	});
+}
+
+#[test]
+fn twap_keeps_past_prices_across_amplification_ramps() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		fund_user(user, &[2], 10_000_000);
+		setup_pool(user, &token_1, &token_2, Some(100), (1_000_000, 1_000_000));
+		// unbalance the pool, so that its price depends on the amplification coefficient
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			500_000,
+			1,
+			user,
+			false,
+		));
+
+		System::set_block_number(11);
+		let twap = AssetConversion::get_twap(token_1.clone(), token_2.clone(), 1, 11).unwrap();
+		assert_ok!(AssetConversion::ramp_amplification(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			1000,
+			31,
+		));
+		assert_eq!(
+			PriceAccumulators::<Test>::get(&pool_id).unwrap().latest.amplification,
+			Some(100)
+		);
+
+		System::set_block_number(21);
+		assert_eq!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 1, 11), Some(twap));
+
+		// the next change of the pool records the current coefficient
+		swap_for_twap(user, &token_2, &token_1);
+		assert_eq!(
+			PriceAccumulators::<Test>::get(&pool_id).unwrap().latest.amplification,
+			Some(550)
+		);
+		assert_eq!(AssetConversion::get_twap(token_1.clone(), token_2.clone(), 1, 11), Some(twap));
+	});
+}
+
+fn setup_routing_pools(user: u128) {
+	let token_1 = NativeOrWithId::Native;
+	let token_2 = NativeOrWithId::WithId(2);
+	let token_3 = NativeOrWithId::WithId(3);
+	let token_4 = NativeOrWithId::WithId(4);
+	let token_5 = NativeOrWithId::WithId(5);
+	fund_user(user, &[2, 3, 4, 5], 10_000_000);
+
+	// a shallow direct pool and a deep two hops route between the native asset and asset 2
+	setup_pool(user, &token_1, &token_2, None, (10_000, 10_000));
+	setup_pool(user, &token_1, &token_3, None, (1_000_000, 1_000_000));
+	setup_pool(user, &token_2, &token_3, None, (1_000_000, 1_000_000));
+	// pools not connected to the others
+	setup_pool(user, &token_4, &token_5, None, (1_000_000, 1_000_000));
+}
+
+#[test]
+fn find_best_route_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		setup_routing_pools(user);
+
+		// small amounts go through the direct pool, paying a single fee
+		let route = AssetConversion::find_best_route(token_2.clone(), token_1.clone(), 10).unwrap();
+		assert_eq!(route.path, vec![token_2.clone(), token_1.clone()]);
+		assert_eq!(route.amount_out, 9);
+
+		// large amounts go through the deeper pools
+		let amount_in = 5_000;
+		let route =
+			AssetConversion::find_best_route(token_2.clone(), token_1.clone(), amount_in).unwrap();
+		assert_eq!(route.path, vec![token_2.clone(), token_3.clone(), token_1.clone()]);
+		let direct_amount_out = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_2.clone(),
+			token_1.clone(),
+			amount_in,
+			true,
+		)
+		.unwrap();
+		assert!(route.amount_out > direct_amount_out);
+
+		// the route matches the execution
+		let balance_before = balance(user, token_1.clone());
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			route.path.into_iter().map(Box::new).collect(),
+			amount_in,
+			route.amount_out,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_1.clone()), balance_before + route.amount_out);
+	});
+}
+
+#[test]
+fn find_best_route_reports_price_impact() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_3 = NativeOrWithId::WithId(3);
+		setup_routing_pools(user);
+
+		let small =
+			AssetConversion::find_best_route(token_3.clone(), token_1.clone(), 10_000).unwrap();
+		let large =
+			AssetConversion::find_best_route(token_3.clone(), token_1.clone(), 100_000).unwrap();
+		assert_eq!(small.path, vec![token_3.clone(), token_1.clone()]);
+		assert_eq!(large.path, vec![token_3.clone(), token_1.clone()]);
+		// the fee and the slippage
+		assert_eq!(small.price_impact, Permill::from_parts(12_900));
+		assert!(large.price_impact > small.price_impact);
+		assert!(large.price_impact > Permill::from_percent(9));
+	});
+}
+
+#[test]
+fn find_best_route_needs_connected_pools() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_4 = NativeOrWithId::WithId(4);
+		let token_6 = NativeOrWithId::WithId(6);
+		setup_routing_pools(user);
+
+		assert_eq!(AssetConversion::find_best_route(token_1.clone(), token_4.clone(), 1_000), None);
+		assert_eq!(AssetConversion::find_best_route(token_1.clone(), token_6.clone(), 1_000), None);
+		assert_eq!(AssetConversion::find_best_route(token_1.clone(), token_1.clone(), 1_000), None);
+		assert_eq!(AssetConversion::find_best_route(token_1.clone(), token_2.clone(), 0), None);
+	});
+}
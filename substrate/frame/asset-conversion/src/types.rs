@@ -19,7 +19,11 @@ use super::*;
 use codec::{Decode, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
 use scale_info::TypeInfo;
-use sp_runtime::traits::TryConvert;
+use sp_core::U256;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, TryConvert},
+	Permill,
+};
 
 /// Represents a swap path with associated asset amounts indicating how much of the asset needs to
 /// be deposited to get the following asset's amount withdrawn (this is inclusive of fees).
@@ -41,6 +45,128 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// Cumulative prices of a pool as of a given block.
+///
+/// Each cumulative price is the sum of the pool prices weighted by the number of blocks they were
+/// in effect, see [`PriceAccumulator`].
+#[derive(Decode, Encode, Clone, Copy, Default, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block the cumulative prices are observed at.
+	pub block: BlockNumber,
+	/// Cumulative price of the first asset of the pair denominated in the second one.
+	pub price1_cumulative: U256,
+	/// Cumulative price of the second asset of the pair denominated in the first one.
+	pub price2_cumulative: U256,
+	/// Amplification coefficient the prices are accumulated with from `block` on, `None` for a
+	/// constant product pool.
+	pub amplification: Option<u32>,
+}
+
+/// Uniswap V2 like price accumulator of a pool.
+///
+/// The assets of the pool are ordered by their encoding, the first asset of the pair being the
+/// one with the lesser encoding. Prices are [`FixedU128`](sp_runtime::FixedU128) numbers,
+/// accumulated as their inner value.
+#[derive(Decode, Encode, Clone, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct PriceAccumulator<Balance, BlockNumber> {
+	/// Reserves of the pool as of the last swap or liquidity change.
+	pub reserves: (Balance, Balance),
+	/// Cumulative prices as of the last swap or liquidity change.
+	pub latest: PriceObservation<BlockNumber>,
+	/// Index of the latest observation in the [`Config::MaxPriceObservations`] long ring buffer.
+	pub observation_index: u32,
+	/// Number of observations in the ring buffer.
+	pub observation_count: u32,
+}
+
+/// Amplification coefficient of a StableSwap pool, linearly ramping from `initial` at
+/// `initial_block` to `future` at `future_block`.
+#[derive(Decode, Encode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct Amplification<BlockNumber> {
+	/// Amplification coefficient at the start of the ramp.
+	pub initial: u32,
+	/// Amplification coefficient at the end of the ramp.
+	pub future: u32,
+	/// Block the ramp starts at.
+	pub initial_block: BlockNumber,
+	/// Block the ramp ends at.
+	pub future_block: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> Amplification<BlockNumber> {
+	/// Amplification coefficient constant from `block` on.
+	pub fn constant(amplification: u32, block: BlockNumber) -> Self {
+		Self {
+			initial: amplification,
+			future: amplification,
+			initial_block: block,
+			future_block: block,
+		}
+	}
+
+	/// Returns the amplification coefficient at `block`.
+	pub fn at(&self, block: BlockNumber) -> u32 {
+		if block >= self.future_block {
+			return self.future;
+		}
+		if block <= self.initial_block {
+			return self.initial;
+		}
+		let elapsed: u128 = (block - self.initial_block).unique_saturated_into();
+		let duration: u128 = (self.future_block - self.initial_block).unique_saturated_into();
+		let (initial, future) = (self.initial as u128, self.future as u128);
+		let amplification = if future > initial {
+			initial + (future - initial) * elapsed / duration
+		} else {
+			initial - (initial - future) * elapsed / duration
+		};
+		amplification as u32
+	}
+}
+
+/// Invariant curve a pool prices swaps with.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PoolCurve {
+	/// Uniswap V2 like constant product curve, `x * y = k`.
+	ConstantProduct,
+	/// Curve like StableSwap curve with the given amplification coefficient.
+	StableSwap {
+		/// Amplification coefficient of the pool.
+		amplification: u32,
+	},
+}
+
+impl PoolCurve {
+	/// Curve of a pool with the given amplification coefficient, `None` for a constant product
+	/// pool.
+	pub fn from_amplification(amplification: Option<u32>) -> Self {
+		match amplification {
+			Some(amplification) => Self::StableSwap { amplification },
+			None => Self::ConstantProduct,
+		}
+	}
+
+	/// Amplification coefficient of the curve, `None` for the constant product curve.
+	pub fn amplification(&self) -> Option<u32> {
+		match self {
+			Self::StableSwap { amplification } => Some(*amplification),
+			Self::ConstantProduct => None,
+		}
+	}
+}
+
+/// Swap route between two assets, see [`Pallet::find_best_route`].
+#[derive(Decode, Encode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct SwapRoute<AssetKind, Balance> {
+	/// The assets to swap through, from the input asset to the output one.
+	pub path: Vec<AssetKind>,
+	/// The expected amount of the output asset.
+	pub amount_out: Balance,
+	/// The relative shortfall of `amount_out` against the output at the current marginal prices
+	/// of the pools along the path. It includes the liquidity provider fees.
+	pub price_impact: Permill,
+}
+
 /// Provides means to resolve the `PoolId` and `AccountId` from a pair of assets.
 ///
 /// Resulting `PoolId` remains consistent whether the asset pair is presented as (asset1, asset2)
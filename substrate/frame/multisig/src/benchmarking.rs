@@ -45,6 +45,23 @@ fn setup_multi<T: Config>(
 	Ok((signatories, Box::new(call)))
 }
 
+fn setup_stored_multi<T: Config>(
+	s: u32,
+	z: u32,
+) -> Result<(T::AccountId, Vec<T::AccountId>, Box<<T as Config>::RuntimeCall>), &'static str> {
+	let (signatories, call) = setup_multi::<T>(s, z)?;
+	let creator = signatories[0].clone();
+	let multisig = Multisig::<T>::stored_multi_account_id(&creator, Multisig::<T>::timepoint(), 0);
+	Multisig::<T>::create_multisig(
+		RawOrigin::Signed(creator).into(),
+		signatories.clone(),
+		s as u16,
+		0,
+	)
+	.map_err(|_| "stored multisig should be created")?;
+	Ok((multisig, signatories, call))
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -359,5 +376,155 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// `s`: Signatories
+	#[benchmark]
+	fn create_multisig(s: Linear<1, { T::MaxSignatories::get() }>) -> Result<(), BenchmarkError> {
+		let (signatories, _) = setup_multi::<T>(s, 0)?;
+		// The account of the caller is not whitelisted, as the deposit is reserved from it.
+		let caller = signatories[0].clone();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), signatories, s as u16, 0);
+
+		assert_eq!(MultisigAccounts::<T>::iter_keys().count(), 1);
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 2 people (so we don't dispatch the call)
+	#[benchmark]
+	fn propose(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, signatories, call) = setup_stored_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[0].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multisig.clone(), call);
+
+		assert!(Proposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 3 people (so we don't dispatch the call)
+	#[benchmark]
+	fn approve_proposal(
+		s: Linear<3, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, signatories, call) = setup_stored_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let call_len = call.encoded_size() as u32;
+		Multisig::<T>::propose(
+			RawOrigin::Signed(signatories[0].clone()).into(),
+			multisig.clone(),
+			call,
+		)?;
+		let caller = signatories[1].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		approve(RawOrigin::Signed(caller), multisig.clone(), call_hash, Weight::zero(), call_len);
+
+		let proposal = Proposals::<T>::get(multisig, call_hash).ok_or("proposal not stored")?;
+		assert_eq!(proposal.approvals.len(), 2);
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn execute_proposal(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (multisig, mut signatories, call) = setup_stored_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let call_len = call.encoded_size() as u32;
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let mut signatories = signatories.into_iter();
+		let proposer = signatories.next().ok_or("signatories should have len 2 or more")?;
+		Multisig::<T>::propose(RawOrigin::Signed(proposer).into(), multisig.clone(), call)?;
+		for signatory in signatories {
+			Multisig::<T>::approve(
+				RawOrigin::Signed(signatory).into(),
+				multisig.clone(),
+				call_hash,
+				Weight::zero(),
+				call_len,
+			)?;
+		}
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		approve(RawOrigin::Signed(caller), multisig.clone(), call_hash, Weight::MAX, call_len);
+
+		assert!(!Proposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn cancel_proposal(s: Linear<2, { T::MaxSignatories::get() }>) -> Result<(), BenchmarkError> {
+		// The call is not an argument, so just use any:
+		let call_len = 10_000;
+		let (multisig, signatories, call) = setup_stored_multi::<T>(s, call_len)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[0].clone();
+		Multisig::<T>::propose(RawOrigin::Signed(caller.clone()).into(), multisig.clone(), call)?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multisig.clone(), call_hash);
+
+		assert!(!Proposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: New signatories
+	#[benchmark]
+	fn update_multisig(s: Linear<1, { T::MaxSignatories::get() }>) -> Result<(), BenchmarkError> {
+		let (multisig, signatories, _) = setup_stored_multi::<T>(T::MaxSignatories::get(), 0)?;
+		T::Currency::make_free_balance_be(&multisig, BalanceOf::<T>::max_value());
+		let new_signatories = signatories[..s as usize].to_vec();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), new_signatories, s as u16);
+
+		let account = MultisigAccounts::<T>::get(multisig).ok_or("multisig not stored")?;
+		assert_eq!(account.signatories.len(), s as usize);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn destroy_multisig() -> Result<(), BenchmarkError> {
+		let (multisig, ..) = setup_stored_multi::<T>(T::MaxSignatories::get(), 0)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()));
+
+		assert!(!MultisigAccounts::<T>::contains_key(multisig));
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
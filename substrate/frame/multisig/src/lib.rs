@@ -39,6 +39,22 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `create_multisig` - Create a stored multisig account with an on-chain set of signatories and
+//!   threshold.
+//! * `propose` - Store a call to be dispatched from a stored multisig account.
+//! * `approve` - Approve a stored call, dispatching it once the threshold is reached.
+//! * `cancel_proposal` - Cancel a stored call.
+//! * `update_multisig` - Change the signatories and threshold of a stored multisig account.
+//! * `destroy_multisig` - Remove a stored multisig account.
+//!
+//! ### Stored multisig accounts
+//!
+//! The account of an `as_multi` operation is derived from its signatories and threshold, so
+//! changing either of them means moving to a new account. Stored multisig accounts instead keep
+//! their signatories and threshold on-chain, under an account derived from its creator. The
+//! account can change its own signatories and threshold by dispatching `update_multisig` through
+//! one of its proposals. Proposals keep the call on-chain, so only the proposer has to submit the
+//! call data. Updating the signatories invalidates all proposals made under the previous set.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -50,7 +66,9 @@ pub mod weights;
 
 extern crate alloc;
 use alloc::{boxed::Box, vec, vec::Vec};
+use codec::DecodeLimit;
 use frame::{
+	deps::frame_support::MAX_EXTRINSIC_DEPTH,
 	prelude::*,
 	traits::{Currency, ReservableCurrency},
 };
@@ -131,8 +149,76 @@ where
 	pub approvals: BoundedVec<AccountId, MaxApprovals>,
 }
 
+/// A multisig account whose signatories and threshold are kept on-chain.
+#[derive(
+	Clone,
+	Eq,
+	PartialEq,
+	Encode,
+	Decode,
+	Default,
+	Debug,
+	TypeInfo,
+	MaxEncodedLen,
+	DecodeWithMemTracking,
+)]
+#[scale_info(skip_type_params(MaxSignatories))]
+pub struct MultisigAccount<Balance, AccountId, MaxSignatories>
+where
+	MaxSignatories: Get<u32>,
+{
+	/// The accounts which can propose and approve calls. Always sorted.
+	pub signatories: BoundedVec<AccountId, MaxSignatories>,
+	/// The number of approvals needed to dispatch a call.
+	pub threshold: u16,
+	/// The amount held in reserve of the `depositor`, to be returned once the account is
+	/// destroyed.
+	pub deposit: Balance,
+	/// The account which holds the deposit.
+	pub depositor: AccountId,
+	/// Incremented every time the signatories or threshold change. Proposals made under an
+	/// earlier version can no longer be approved.
+	pub version: u32,
+}
+
+/// A call stored for dispatch from a [`MultisigAccount`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxCallLen, MaxApprovals))]
+pub struct Proposal<BlockNumber, Balance, AccountId, MaxCallLen, MaxApprovals>
+where
+	MaxCallLen: Get<u32>,
+	MaxApprovals: Get<u32>,
+{
+	/// The extrinsic when the proposal was made.
+	pub when: Timepoint<BlockNumber>,
+	/// The encoded call to be dispatched.
+	pub call: BoundedVec<u8, MaxCallLen>,
+	/// The amount held in reserve of the `depositor`, to be returned once the proposal ends.
+	pub deposit: Balance,
+	/// The account who made the proposal.
+	pub depositor: AccountId,
+	/// The approvals achieved so far, including the depositor. Always sorted.
+	pub approvals: BoundedVec<AccountId, MaxApprovals>,
+	/// The [`MultisigAccount::version`] under which the proposal was made.
+	pub version: u32,
+}
+
 type CallHash = [u8; 32];
 
+type MultisigAccountOf<T> = MultisigAccount<
+	BalanceOf<T>,
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::MaxSignatories,
+>;
+
+type ProposalOf<T> = Proposal<
+	BlockNumberFor<T>,
+	BalanceOf<T>,
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::MaxCallLen,
+	<T as Config>::MaxSignatories,
+>;
+
 enum CallOrHash<T: Config> {
 	Call(<T as Config>::RuntimeCall),
 	Hash([u8; 32]),
@@ -176,6 +262,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSignatories: Get<u32>;
 
+		/// The maximum encoded length of a call proposed for a stored multisig account.
+		#[pallet::constant]
+		type MaxCallLen: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: weights::WeightInfo;
 
@@ -222,9 +312,19 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The stored multisig accounts.
+	#[pallet::storage]
+	pub type MultisigAccounts<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, MultisigAccountOf<T>>;
+
+	/// The open proposals of stored multisig accounts.
+	#[pallet::storage]
+	pub type Proposals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Blake2_128Concat, CallHash, ProposalOf<T>>;
+
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Threshold must be 2 or greater.
+		/// Threshold must be 2 or greater, or 1 or greater for a stored multisig account.
 		MinimumThreshold,
 		/// Call is already approved by this signatory.
 		AlreadyApproved,
@@ -253,6 +353,20 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The threshold is greater than the number of signatories.
+		ThresholdTooHigh,
+		/// The stored multisig account was not found.
+		MultisigNotFound,
+		/// The sender is not a signatory of the stored multisig account.
+		NotSignatory,
+		/// The proposal was made under signatories that have since been updated.
+		StaleProposal,
+		/// The length bound given is lower than the length of the stored call.
+		WrongProposalLength,
+		/// The proposed call is longer than `Config::MaxCallLen`.
+		ProposalTooLong,
+		/// The stored call can no longer be decoded, e.g. after a runtime upgrade.
+		UndecodableProposal,
 	}
 
 	#[pallet::event]
@@ -289,6 +403,12 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// A stored multisig account has been created.
+		MultisigCreated { creator: T::AccountId, multisig: T::AccountId, threshold: u16 },
+		/// The signatories or threshold of a stored multisig account have been updated.
+		MultisigUpdated { multisig: T::AccountId, threshold: u16 },
+		/// A stored multisig account has been destroyed.
+		MultisigDestroyed { multisig: T::AccountId },
 	}
 
 	#[pallet::hooks]
@@ -634,6 +754,335 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Create a stored multisig account with the given `signatories` and `threshold`.
+		///
+		/// The account is derived from the sender, the current timepoint and `index`, so it does
+		/// not change when the signatories or threshold are updated.
+		///
+		/// Payment: `DepositBase` plus `DepositFactor` for each signatory is reserved from the
+		/// sender. It is returned once the account is updated or destroyed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `signatories`: The accounts which can propose and approve calls. Must be sorted and
+		///   may include the sender.
+		/// - `threshold`: The number of approvals needed to dispatch a call.
+		/// - `index`: A disambiguation index, in case the sender creates several accounts within
+		///   the same transaction.
+		///
+		/// Emits `MultisigCreated`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_multisig(signatories.len() as u32))]
+		pub fn create_multisig(
+			origin: OriginFor<T>,
+			signatories: Vec<T::AccountId>,
+			threshold: u16,
+			index: u16,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let signatories = Self::ensure_valid_signatories(signatories, threshold)?;
+
+			let multisig = Self::stored_multi_account_id(&who, Self::timepoint(), index);
+			ensure!(!MultisigAccounts::<T>::contains_key(&multisig), Error::<T>::AlreadyStored);
+
+			let deposit = Self::account_deposit(signatories.len() as u32);
+			T::Currency::reserve(&who, deposit)?;
+
+			MultisigAccounts::<T>::insert(
+				&multisig,
+				MultisigAccount {
+					signatories,
+					threshold,
+					deposit,
+					depositor: who.clone(),
+					version: 0,
+				},
+			);
+			Self::deposit_event(Event::MultisigCreated { creator: who, multisig, threshold });
+			Ok(())
+		}
+
+		/// Propose a call to be dispatched from the stored multisig account `multisig`, counting
+		/// as the sender's approval.
+		///
+		/// **If the threshold of `multisig` is `1`, this will immediately dispatch the call.**
+		/// Otherwise the call is stored until enough signatories `approve` it.
+		///
+		/// Payment: `DepositBase` plus `DepositFactor` for each unit of threshold and for each 32
+		/// bytes of the call is reserved from the sender. It is returned once the call is
+		/// dispatched or cancelled.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The stored multisig account to dispatch the call from.
+		/// - `call`: The call to be dispatched. Unless it is dispatched immediately, its encoding
+		///   must not be longer than `MaxCallLen`.
+		///
+		/// Emits `NewMultisig`, or `MultisigExecuted` if the call is dispatched.
+		#[pallet::call_index(6)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				T::WeightInfo::propose(
+					T::MaxSignatories::get(),
+					call.using_encoded(|c| c.len() as u32),
+				)
+				.saturating_add(dispatch_info.call_weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn propose(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let account =
+				MultisigAccounts::<T>::get(&multisig).ok_or(Error::<T>::MultisigNotFound)?;
+			let pos =
+				account.signatories.binary_search(&who).map_err(|_| Error::<T>::NotSignatory)?;
+			let s = account.signatories.len() as u32;
+
+			let (call_hash, call_len) = call.using_encoded(|d| (blake2_256(d), d.len() as u32));
+			let weight = T::WeightInfo::propose(s, call_len);
+			let timepoint = Self::timepoint();
+
+			if account.threshold <= 1 {
+				let result = call.dispatch(RawOrigin::Signed(multisig.clone()).into());
+				Self::deposit_event(Event::MultisigExecuted {
+					approving: who,
+					timepoint,
+					multisig,
+					call_hash,
+					result: result.map(|_| ()).map_err(|e| e.error),
+				});
+				return Ok(get_result_weight(result)
+					.map(|actual_weight| weight.saturating_add(actual_weight))
+					.into());
+			}
+
+			ensure!(!Proposals::<T>::contains_key(&multisig, call_hash), Error::<T>::AlreadyStored);
+			let call = call.encode().try_into().map_err(|_| Error::<T>::ProposalTooLong)?;
+			let deposit = Self::proposal_deposit(account.threshold, call_len);
+			T::Currency::reserve(&who, deposit)?;
+
+			let approvals = account.signatories[pos..=pos]
+				.to_vec()
+				.try_into()
+				.map_err(|_| Error::<T>::TooManySignatories)?;
+			Proposals::<T>::insert(
+				&multisig,
+				call_hash,
+				Proposal {
+					when: timepoint,
+					call,
+					deposit,
+					depositor: who.clone(),
+					approvals,
+					version: account.version,
+				},
+			);
+			Self::deposit_event(Event::NewMultisig { approving: who, multisig, call_hash });
+
+			// Call is not made, so the actual weight does not include call
+			Ok(Some(weight).into())
+		}
+
+		/// Approve a call proposed for the stored multisig account `multisig`.
+		///
+		/// **If the threshold is met (including the sender's approval), this will immediately
+		/// dispatch the call.**
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The stored multisig account the call was proposed for.
+		/// - `call_hash`: The hash of the proposed call.
+		/// - `max_weight`: The maximum weight of the proposed call.
+		/// - `length_bound`: An upper bound on the encoded length of the proposed call.
+		///
+		/// Emits `MultisigApproval`, or `MultisigExecuted` if the call is dispatched.
+		#[pallet::call_index(7)]
+		#[pallet::weight({
+			let s = T::MaxSignatories::get();
+
+			T::WeightInfo::approve_proposal(s, *length_bound)
+				.max(T::WeightInfo::execute_proposal(s, *length_bound))
+				.saturating_add(*max_weight)
+		})]
+		pub fn approve(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			max_weight: Weight,
+			#[pallet::compact] length_bound: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let account =
+				MultisigAccounts::<T>::get(&multisig).ok_or(Error::<T>::MultisigNotFound)?;
+			ensure!(account.signatories.binary_search(&who).is_ok(), Error::<T>::NotSignatory);
+			let s = account.signatories.len() as u32;
+
+			let mut proposal =
+				Proposals::<T>::get(&multisig, call_hash).ok_or(Error::<T>::NotFound)?;
+			ensure!(proposal.version == account.version, Error::<T>::StaleProposal);
+			let call_len = proposal.call.len() as u32;
+			ensure!(call_len <= length_bound, Error::<T>::WrongProposalLength);
+
+			let pos = proposal
+				.approvals
+				.binary_search(&who)
+				.err()
+				.ok_or(Error::<T>::AlreadyApproved)?;
+			proposal
+				.approvals
+				.try_insert(pos, who.clone())
+				.map_err(|_| Error::<T>::TooManySignatories)?;
+
+			if (proposal.approvals.len() as u16) < account.threshold {
+				let timepoint = proposal.when;
+				Proposals::<T>::insert(&multisig, call_hash, proposal);
+				Self::deposit_event(Event::MultisigApproval {
+					approving: who,
+					timepoint,
+					multisig,
+					call_hash,
+				});
+				return Ok(Some(T::WeightInfo::approve_proposal(s, call_len)).into());
+			}
+
+			let call = <T as Config>::RuntimeCall::decode_all_with_depth_limit(
+				MAX_EXTRINSIC_DEPTH,
+				&mut &proposal.call[..],
+			)
+			.map_err(|_| Error::<T>::UndecodableProposal)?;
+			ensure!(
+				call.get_dispatch_info().call_weight.all_lte(max_weight),
+				Error::<T>::MaxWeightTooLow
+			);
+
+			// Clean up storage before executing call to avoid an possibility of reentrancy
+			// attack.
+			Proposals::<T>::remove(&multisig, call_hash);
+			T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+
+			let result = call.dispatch(RawOrigin::Signed(multisig.clone()).into());
+			Self::deposit_event(Event::MultisigExecuted {
+				approving: who,
+				timepoint: proposal.when,
+				multisig,
+				call_hash,
+				result: result.map(|_| ()).map_err(|e| e.error),
+			});
+			Ok(get_result_weight(result)
+				.map(|actual_weight| {
+					T::WeightInfo::execute_proposal(s, call_len).saturating_add(actual_weight)
+				})
+				.into())
+		}
+
+		/// Cancel a call proposed for the stored multisig account `multisig`, returning its
+		/// deposit to the proposer.
+		///
+		/// The dispatch origin for this call must be _Signed_. It must be the proposer, unless the
+		/// proposal is stale or `multisig` was destroyed, in which case anyone may remove it.
+		///
+		/// - `multisig`: The stored multisig account the call was proposed for.
+		/// - `call_hash`: The hash of the proposed call.
+		///
+		/// Emits `MultisigCancelled`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::cancel_proposal(T::MaxSignatories::get()))]
+		pub fn cancel_proposal(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal = Proposals::<T>::get(&multisig, call_hash).ok_or(Error::<T>::NotFound)?;
+			let is_live = MultisigAccounts::<T>::get(&multisig)
+				.is_some_and(|account| account.version == proposal.version);
+			ensure!(!is_live || proposal.depositor == who, Error::<T>::NotOwner);
+
+			let err_amount = T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+			debug_assert!(err_amount.is_zero());
+			Proposals::<T>::remove(&multisig, call_hash);
+
+			Self::deposit_event(Event::MultisigCancelled {
+				cancelling: who,
+				timepoint: proposal.when,
+				multisig,
+				call_hash,
+			});
+			Ok(())
+		}
+
+		/// Change the signatories and threshold of a stored multisig account.
+		///
+		/// All open proposals of the account become stale and can be removed by anyone with
+		/// `cancel_proposal`.
+		///
+		/// Payment: the deposit of the account is returned to its depositor and a new deposit for
+		/// the new signatories is reserved from the account itself.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stored multisig account, i.e.
+		/// it must be dispatched through one of its proposals.
+		///
+		/// - `signatories`: The new signatories. Must be sorted.
+		/// - `threshold`: The new number of approvals needed to dispatch a call.
+		///
+		/// Emits `MultisigUpdated`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::update_multisig(signatories.len() as u32))]
+		pub fn update_multisig(
+			origin: OriginFor<T>,
+			signatories: Vec<T::AccountId>,
+			threshold: u16,
+		) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let signatories = Self::ensure_valid_signatories(signatories, threshold)?;
+
+			MultisigAccounts::<T>::try_mutate(&multisig, |maybe_account| -> DispatchResult {
+				let account = maybe_account.as_mut().ok_or(Error::<T>::MultisigNotFound)?;
+
+				let err_amount = T::Currency::unreserve(&account.depositor, account.deposit);
+				debug_assert!(err_amount.is_zero());
+				let deposit = Self::account_deposit(signatories.len() as u32);
+				T::Currency::reserve(&multisig, deposit)?;
+
+				account.signatories = signatories;
+				account.threshold = threshold;
+				account.deposit = deposit;
+				account.depositor = multisig.clone();
+				account.version.saturating_inc();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MultisigUpdated { multisig, threshold });
+			Ok(())
+		}
+
+		/// Remove a stored multisig account, returning its deposit to the depositor.
+		///
+		/// Open proposals of the account can then be removed by anyone with `cancel_proposal`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stored multisig account, i.e.
+		/// it must be dispatched through one of its proposals.
+		///
+		/// Emits `MultisigDestroyed`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::destroy_multisig())]
+		pub fn destroy_multisig(origin: OriginFor<T>) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let account =
+				MultisigAccounts::<T>::take(&multisig).ok_or(Error::<T>::MultisigNotFound)?;
+
+			let err_amount = T::Currency::unreserve(&account.depositor, account.deposit);
+			debug_assert!(err_amount.is_zero());
+
+			Self::deposit_event(Event::MultisigDestroyed { multisig });
+			Ok(())
+		}
 	}
 }
 
@@ -813,6 +1262,52 @@ impl<T: Config> Pallet<T> {
 	pub fn deposit(threshold: u16) -> BalanceOf<T> {
 		T::DepositBase::get() + T::DepositFactor::get() * threshold.into()
 	}
+
+	/// Derive the account ID of a stored multisig account from its creator, the timepoint of
+	/// its creation and a disambiguation `index`.
+	pub fn stored_multi_account_id(
+		who: &T::AccountId,
+		when: Timepoint<BlockNumberFor<T>>,
+		index: u16,
+	) -> T::AccountId {
+		let entropy =
+			(b"modlpy/utilistor", who, when.height, when.index, index).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// Calculate the deposit for a stored multisig account.
+	///
+	/// The deposit is calculated as `DepositBase + DepositFactor * signatories`.
+	pub fn account_deposit(signatories: u32) -> BalanceOf<T> {
+		T::DepositBase::get() + T::DepositFactor::get() * signatories.into()
+	}
+
+	/// Calculate the deposit for a proposal of a stored multisig account.
+	///
+	/// The deposit is calculated as `DepositBase + DepositFactor * (threshold + words)`, where
+	/// `words` is the number of 32 byte words needed to store the call.
+	pub fn proposal_deposit(threshold: u16, call_len: u32) -> BalanceOf<T> {
+		let words = call_len.div_ceil(32);
+		T::DepositBase::get() +
+			T::DepositFactor::get() * u32::from(threshold).saturating_add(words).into()
+	}
+
+	/// Check that `signatories` is sorted and fits `MaxSignatories`, and that `threshold` is
+	/// reachable by them.
+	fn ensure_valid_signatories(
+		signatories: Vec<T::AccountId>,
+		threshold: u16,
+	) -> Result<BoundedVec<T::AccountId, T::MaxSignatories>, DispatchError> {
+		ensure!(threshold >= 1, Error::<T>::MinimumThreshold);
+		ensure!(!signatories.is_empty(), Error::<T>::TooFewSignatories);
+		ensure!(usize::from(threshold) <= signatories.len(), Error::<T>::ThresholdTooHigh);
+		ensure!(
+			signatories.windows(2).all(|pair| pair[0] < pair[1]),
+			Error::<T>::SignatoriesOutOfOrder
+		);
+		Ok(signatories.try_into().map_err(|_| Error::<T>::TooManySignatories)?)
+	}
 }
 
 /// Return the weight of a dispatch call result as an `Option`.
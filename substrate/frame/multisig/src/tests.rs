@@ -52,6 +52,8 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 	fn contains(c: &RuntimeCall) -> bool {
 		match *c {
 			RuntimeCall::Balances(_) => true,
+			RuntimeCall::Multisig(Call::update_multisig { .. }) => true,
+			RuntimeCall::Multisig(Call::destroy_multisig { .. }) => true,
 			// Needed for benchmarking
 			RuntimeCall::System(frame_system::Call::remark { .. }) => true,
 			_ => false,
@@ -71,6 +73,7 @@ impl Config for Test {
 	type DepositBase = MultisigDepositBase;
 	type DepositFactor = MultisigDepositFactor;
 	type MaxSignatories = ConstU32<3>;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
 }
@@ -1426,3 +1429,299 @@ fn poke_deposit_handles_insufficient_balance() {
 		);
 	});
 }
+
+fn create_stored_multisig(signatories: Vec<u64>, threshold: u16) -> u64 {
+	let multisig = Multisig::stored_multi_account_id(&1, now(), 0);
+	assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(1), signatories, threshold, 0));
+	multisig
+}
+
+fn call_update(signatories: Vec<u64>, threshold: u16) -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::Multisig(Call::update_multisig { signatories, threshold }))
+}
+
+#[test]
+fn stored_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![1, 2, 3], 2);
+		System::assert_last_event(
+			Event::MultisigCreated { creator: 1, multisig: multi, threshold: 2 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), Multisig::account_deposit(3));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 10);
+		let call_hash = blake2_256(&call.encode());
+		let call_len = call.encoded_size() as u32;
+		let call_weight = call.get_dispatch_info().call_weight;
+		let deposit = Multisig::proposal_deposit(2, call_len);
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(2), multi, call));
+		assert_eq!(Balances::reserved_balance(2), deposit);
+		assert_eq!(Balances::free_balance(6), 0);
+
+		assert_noop!(
+			Multisig::approve(RuntimeOrigin::signed(3), multi, call_hash, Weight::zero(), call_len),
+			Error::<Test>::MaxWeightTooLow,
+		);
+		assert_ok!(Multisig::approve(
+			RuntimeOrigin::signed(3),
+			multi,
+			call_hash,
+			call_weight,
+			call_len
+		));
+		assert_eq!(Balances::free_balance(6), 10);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!Proposals::<Test>::contains_key(multi, call_hash));
+		System::assert_last_event(
+			Event::MultisigExecuted {
+				approving: 3,
+				timepoint: now(),
+				multisig: multi,
+				call_hash,
+				result: Ok(()),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn stored_multisig_with_threshold_1_dispatches_immediately() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![2, 3], 1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(3), multi, call_transfer(6, 5)));
+		assert_eq!(Balances::free_balance(6), 5);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Proposals::<Test>::iter_prefix(multi).count(), 0);
+	});
+}
+
+#[test]
+fn create_multisig_checks_signatories() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![1, 2], 0, 0),
+			Error::<Test>::MinimumThreshold,
+		);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![], 1, 0),
+			Error::<Test>::TooFewSignatories,
+		);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![1, 2], 3, 0),
+			Error::<Test>::ThresholdTooHigh,
+		);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![2, 1], 2, 0),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![2, 2], 2, 0),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![1, 2, 3, 4], 2, 0),
+			Error::<Test>::TooManySignatories,
+		);
+
+		create_stored_multisig(vec![1, 2], 2);
+		assert_noop!(
+			Multisig::create_multisig(RuntimeOrigin::signed(1), vec![1, 2], 2, 0),
+			Error::<Test>::AlreadyStored,
+		);
+		assert_ok!(Multisig::create_multisig(RuntimeOrigin::signed(1), vec![1, 2], 2, 1));
+	});
+}
+
+#[test]
+fn propose_and_approve_check_signatories() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![1, 2, 3], 3);
+		let call = call_transfer(6, 10);
+		let call_hash = blake2_256(&call.encode());
+		let call_len = call.encoded_size() as u32;
+
+		assert_noop!(
+			Multisig::propose(RuntimeOrigin::signed(4), multi, call.clone()),
+			Error::<Test>::NotSignatory,
+		);
+		assert_noop!(
+			Multisig::propose(RuntimeOrigin::signed(1), 4, call.clone()),
+			Error::<Test>::MultisigNotFound,
+		);
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(1), multi, call.clone()));
+		assert_noop!(
+			Multisig::propose(RuntimeOrigin::signed(2), multi, call),
+			Error::<Test>::AlreadyStored,
+		);
+
+		assert_noop!(
+			Multisig::approve(RuntimeOrigin::signed(4), multi, call_hash, Weight::zero(), call_len),
+			Error::<Test>::NotSignatory,
+		);
+		assert_noop!(
+			Multisig::approve(RuntimeOrigin::signed(1), multi, call_hash, Weight::zero(), call_len),
+			Error::<Test>::AlreadyApproved,
+		);
+		assert_noop!(
+			Multisig::approve(RuntimeOrigin::signed(2), multi, [0; 32], Weight::zero(), call_len),
+			Error::<Test>::NotFound,
+		);
+		assert_noop!(
+			Multisig::approve(
+				RuntimeOrigin::signed(2),
+				multi,
+				call_hash,
+				Weight::zero(),
+				call_len - 1
+			),
+			Error::<Test>::WrongProposalLength,
+		);
+
+		assert_ok!(Multisig::approve(
+			RuntimeOrigin::signed(2),
+			multi,
+			call_hash,
+			Weight::zero(),
+			call_len
+		));
+		System::assert_last_event(
+			Event::MultisigApproval { approving: 2, timepoint: now(), multisig: multi, call_hash }
+				.into(),
+		);
+		assert_eq!(Proposals::<Test>::get(multi, call_hash).unwrap().approvals, vec![1, 2]);
+	});
+}
+
+#[test]
+fn propose_checks_call_length() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![1, 2], 2);
+		let remark = |len| {
+			Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![0; len] }))
+		};
+
+		assert_noop!(
+			Multisig::propose(RuntimeOrigin::signed(1), multi, remark(16 * 1024)),
+			Error::<Test>::ProposalTooLong,
+		);
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(1), multi, remark(16)));
+	});
+}
+
+#[test]
+fn cancel_proposal_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![1, 2, 3], 2);
+		let call = call_transfer(6, 10);
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(2), multi, call));
+		assert!(!Balances::reserved_balance(2).is_zero());
+
+		assert_noop!(
+			Multisig::cancel_proposal(RuntimeOrigin::signed(3), multi, call_hash),
+			Error::<Test>::NotOwner,
+		);
+		assert_ok!(Multisig::cancel_proposal(RuntimeOrigin::signed(2), multi, call_hash));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!Proposals::<Test>::contains_key(multi, call_hash));
+		assert_noop!(
+			Multisig::cancel_proposal(RuntimeOrigin::signed(2), multi, call_hash),
+			Error::<Test>::NotFound,
+		);
+	});
+}
+
+#[test]
+fn update_multisig_through_proposal_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![1, 2], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 10));
+
+		// Only the stored multisig account itself can update it.
+		assert_noop!(
+			Multisig::update_multisig(RuntimeOrigin::signed(1), vec![2, 3], 1),
+			Error::<Test>::MultisigNotFound,
+		);
+
+		let pending = call_transfer(6, 5);
+		let pending_hash = blake2_256(&pending.encode());
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(2), multi, pending));
+
+		let update = call_update(vec![2, 3], 1);
+		let update_hash = blake2_256(&update.encode());
+		let update_len = update.encoded_size() as u32;
+		let update_weight = update.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(1), multi, update));
+		assert_ok!(Multisig::approve(
+			RuntimeOrigin::signed(2),
+			multi,
+			update_hash,
+			update_weight,
+			update_len
+		));
+		System::assert_has_event(Event::MultisigUpdated { multisig: multi, threshold: 1 }.into());
+
+		let account = MultisigAccounts::<Test>::get(multi).unwrap();
+		assert_eq!(account.signatories, vec![2, 3]);
+		assert_eq!(account.threshold, 1);
+		assert_eq!(account.version, 1);
+		// The deposit moved from the creator to the multisig account.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(multi), Multisig::account_deposit(2));
+
+		// Proposals made under the previous signatories are stale, and anyone can remove them.
+		let pending_len = call_transfer(6, 5).encoded_size() as u32;
+		assert_noop!(
+			Multisig::approve(
+				RuntimeOrigin::signed(3),
+				multi,
+				pending_hash,
+				Weight::MAX,
+				pending_len
+			),
+			Error::<Test>::StaleProposal,
+		);
+		assert_ok!(Multisig::cancel_proposal(RuntimeOrigin::signed(4), multi, pending_hash));
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// The new signatories control the account, the old ones do not.
+		assert_noop!(
+			Multisig::propose(RuntimeOrigin::signed(1), multi, call_transfer(6, 5)),
+			Error::<Test>::NotSignatory,
+		);
+		assert_ok!(Multisig::propose(RuntimeOrigin::signed(3), multi, call_transfer(6, 5)));
+		assert_eq!(Balances::free_balance(6), 5);
+	});
+}
+
+#[test]
+fn destroy_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored_multisig(vec![1, 2], 1);
+		let call = call_transfer(6, 5);
+		let call_hash = blake2_256(&call.encode());
+
+		assert_ok!(Multisig::propose(
+			RuntimeOrigin::signed(2),
+			multi,
+			Box::new(RuntimeCall::Multisig(Call::destroy_multisig {}))
+		));
+		System::assert_has_event(Event::MultisigDestroyed { multisig: multi }.into());
+		assert!(!MultisigAccounts::<Test>::contains_key(multi));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		assert_noop!(
+			Multisig::propose(RuntimeOrigin::signed(2), multi, call),
+			Error::<Test>::MultisigNotFound,
+		);
+		assert_noop!(
+			Multisig::approve(RuntimeOrigin::signed(2), multi, call_hash, Weight::zero(), 0),
+			Error::<Test>::MultisigNotFound,
+		);
+	});
+}
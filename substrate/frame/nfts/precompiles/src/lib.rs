@@ -0,0 +1,558 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use ethereum_standards::{
+	IERC721Receiver, IERC721,
+	IERC721::{IERC721Calls, IERC721Events},
+};
+use frame_support::{sp_runtime::traits::BlockNumberProvider, traits::Get};
+use pallet_nfts::{weights::WeightInfo, Account, Config, Item, ItemDetailsFor, ItemMetadataOf};
+use pallet_revive::precompiles::{
+	alloy::{
+		self,
+		primitives::IntoLogData,
+		sol_types::{Revert, SolCall},
+	},
+	AddressMapper, AddressMatcher, CallResources, Error, Ext, Precompile, ReentrancyProtection,
+	RuntimeCosts, H160, H256, U256,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Mean of extracting the collection id from the precompile address.
+pub trait CollectionIdExtractor {
+	type CollectionId;
+	/// Extracts the collection id from the address.
+	fn collection_id_from_address(address: &[u8; 20]) -> Result<Self::CollectionId, Error>;
+}
+
+/// Mean of converting an ERC-721 token id into an item id.
+pub trait ItemIdConverter {
+	type ItemId;
+	/// Converts the token id into an item id.
+	fn item_id_from_token_id(token_id: alloy::primitives::U256) -> Result<Self::ItemId, Error>;
+}
+
+/// The configuration of a pallet-nfts precompile.
+pub trait NftsPrecompileConfig {
+	/// The Address matcher used by the precompile.
+	const MATCHER: AddressMatcher;
+
+	/// The [`CollectionIdExtractor`] used by the precompile.
+	type CollectionIdExtractor: CollectionIdExtractor;
+
+	/// The [`ItemIdConverter`] used by the precompile.
+	type ItemIdConverter: ItemIdConverter;
+}
+
+/// A `CollectionIdExtractor` that stores the collection id directly inside the address.
+pub struct InlineCollectionIdExtractor;
+
+impl CollectionIdExtractor for InlineCollectionIdExtractor {
+	type CollectionId = u32;
+	fn collection_id_from_address(addr: &[u8; 20]) -> Result<Self::CollectionId, Error> {
+		let bytes: [u8; 4] = addr[0..4].try_into().expect("slice is 4 bytes; qed");
+		Ok(u32::from_be_bytes(bytes))
+	}
+}
+
+/// An `ItemIdConverter` for `u32` item ids, rejecting token ids that do not fit.
+pub struct U32ItemIdConverter;
+
+impl ItemIdConverter for U32ItemIdConverter {
+	type ItemId = u32;
+	fn item_id_from_token_id(token_id: alloy::primitives::U256) -> Result<Self::ItemId, Error> {
+		token_id
+			.try_into()
+			.map_err(|_| Error::Revert(Revert { reason: ERR_INVALID_TOKEN_ID.into() }))
+	}
+}
+
+/// A precompile configuration that uses a prefix [`AddressMatcher`].
+pub struct InlineIdConfig<const PREFIX: u16>;
+
+impl<const P: u16> NftsPrecompileConfig for InlineIdConfig<P> {
+	const MATCHER: AddressMatcher = AddressMatcher::Prefix(core::num::NonZero::new(P).unwrap());
+	type CollectionIdExtractor = InlineCollectionIdExtractor;
+	type ItemIdConverter = U32ItemIdConverter;
+}
+
+/// An ERC721 precompile.
+pub struct ERC721<Runtime, PrecompileConfig, Instance = ()> {
+	_phantom: PhantomData<(Runtime, PrecompileConfig, Instance)>,
+}
+
+impl<Runtime, PrecompileConfig, Instance: 'static> Precompile
+	for ERC721<Runtime, PrecompileConfig, Instance>
+where
+	PrecompileConfig: NftsPrecompileConfig,
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	<<PrecompileConfig as NftsPrecompileConfig>::CollectionIdExtractor as CollectionIdExtractor>::CollectionId:
+		Into<<Runtime as Config<Instance>>::CollectionId>,
+	<<PrecompileConfig as NftsPrecompileConfig>::ItemIdConverter as ItemIdConverter>::ItemId:
+		Into<<Runtime as Config<Instance>>::ItemId>,
+{
+	type T = Runtime;
+	type Interface = IERC721::IERC721Calls;
+	const MATCHER: AddressMatcher = PrecompileConfig::MATCHER;
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn call(
+		address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let collection =
+			PrecompileConfig::CollectionIdExtractor::collection_id_from_address(address)?.into();
+
+		match input {
+			IERC721Calls::safeTransferFrom_0(_) |
+			IERC721Calls::safeTransferFrom_1(_) |
+			IERC721Calls::transferFrom(_) |
+			IERC721Calls::approve(_) |
+			IERC721Calls::setApprovalForAll(_)
+				if env.is_read_only() =>
+			{
+				Err(Error::Error(pallet_revive::Error::<Self::T>::StateChangeDenied.into()))
+			},
+
+			IERC721Calls::balanceOf(call) => Self::balance_of(collection, call, env),
+			IERC721Calls::ownerOf(call) => Self::owner_of(collection, call, env),
+			IERC721Calls::safeTransferFrom_0(call) => Self::safe_transfer_from(
+				collection,
+				call.from,
+				call.to,
+				call.tokenId,
+				call.data.to_vec(),
+				env,
+			),
+			IERC721Calls::safeTransferFrom_1(call) => Self::safe_transfer_from(
+				collection,
+				call.from,
+				call.to,
+				call.tokenId,
+				Vec::new(),
+				env,
+			),
+			IERC721Calls::transferFrom(call) => {
+				Self::transfer_from(collection, call.from, call.to, call.tokenId, env)?;
+				Ok(IERC721::transferFromCall::abi_encode_returns(&IERC721::transferFromReturn {}))
+			},
+			IERC721Calls::approve(call) => Self::approve(collection, call, env),
+			IERC721Calls::setApprovalForAll(call) =>
+				Self::set_approval_for_all(collection, call, env),
+			IERC721Calls::getApproved(call) => Self::get_approved(collection, call, env),
+			IERC721Calls::isApprovedForAll(call) =>
+				Self::is_approved_for_all(collection, call, env),
+			IERC721Calls::supportsInterface(call) => Self::supports_interface(call),
+			IERC721Calls::tokenURI(call) => Self::token_uri(collection, call, env),
+		}
+	}
+}
+
+const ERR_INVALID_CALLER: &str = "Invalid caller";
+const ERR_INVALID_TOKEN_ID: &str = "ERC721: invalid token ID";
+const ERR_INVALID_OWNER: &str = "ERC721: address zero is not a valid owner";
+const ERR_INVALID_RECEIVER: &str = "ERC721: transfer to the zero address";
+const ERR_INCORRECT_OWNER: &str = "ERC721: transfer from incorrect owner";
+const ERR_NOT_AUTHORIZED: &str = "ERC721: caller is not token owner or approved";
+const ERR_NON_RECEIVER: &str = "ERC721: transfer to non ERC721Receiver implementer";
+const ERR_APPROVE_TO_CALLER: &str = "ERC721: approve to caller";
+
+/// The ERC-165 interface id of ERC-165 itself.
+const INTERFACE_ID_ERC165: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+/// The ERC-165 interface id of ERC-721.
+const INTERFACE_ID_ERC721: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+
+fn revert(reason: &str) -> Error {
+	Error::Revert(Revert { reason: reason.into() })
+}
+
+impl<Runtime, PrecompileConfig, Instance: 'static> ERC721<Runtime, PrecompileConfig, Instance>
+where
+	PrecompileConfig: NftsPrecompileConfig,
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	<<PrecompileConfig as NftsPrecompileConfig>::CollectionIdExtractor as CollectionIdExtractor>::CollectionId:
+		Into<<Runtime as Config<Instance>>::CollectionId>,
+	<<PrecompileConfig as NftsPrecompileConfig>::ItemIdConverter as ItemIdConverter>::ItemId:
+		Into<<Runtime as Config<Instance>>::ItemId>,
+{
+	/// Get the caller as an `H160` address.
+	fn caller(env: &mut impl Ext<T = Runtime>) -> Result<H160, Error> {
+		env.caller()
+			.account_id()
+			.map(<Runtime as pallet_revive::Config>::AddressMapper::to_address)
+			.map_err(|_| revert(ERR_INVALID_CALLER))
+	}
+
+	/// Convert an address to the account id of the runtime.
+	fn to_account_id(address: &alloy::primitives::Address) -> Runtime::AccountId {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(
+			&address.into_array().into(),
+		)
+	}
+
+	/// Convert an account id of the runtime to an address.
+	fn to_address(account: &Runtime::AccountId) -> alloy::primitives::Address {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_address(account).0.into()
+	}
+
+	/// Charge the weight of reading `n` storage items.
+	fn charge_reads(env: &mut impl Ext<T = Runtime>, n: u64) -> Result<(), Error> {
+		env.charge(<Runtime as frame_system::Config>::DbWeight::get().reads(n))?;
+		Ok(())
+	}
+
+	/// Get the details of the item of the given token id, reverting if it does not exist.
+	fn item(
+		collection: &<Runtime as Config<Instance>>::CollectionId,
+		token_id: alloy::primitives::U256,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<(<Runtime as Config<Instance>>::ItemId, ItemDetailsFor<Runtime, Instance>), Error>
+	{
+		Self::charge_reads(env, 1)?;
+		let item = PrecompileConfig::ItemIdConverter::item_id_from_token_id(token_id)?.into();
+		let details = Item::<Runtime, Instance>::get(collection, &item)
+			.ok_or_else(|| revert(ERR_INVALID_TOKEN_ID))?;
+		Ok((item, details))
+	}
+
+	/// Whether `operator` is approved to manage all the items of `owner` in the collection.
+	fn is_operator(
+		collection: &<Runtime as Config<Instance>>::CollectionId,
+		owner: &Runtime::AccountId,
+		operator: &Runtime::AccountId,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<bool, Error> {
+		Self::charge_reads(env, 1)?;
+		Ok(pallet_nfts::Pallet::<Runtime, Instance>::is_operator(collection, owner, operator))
+	}
+
+	/// Whether `account` holds a non expired approval of the item.
+	fn is_approved(details: &ItemDetailsFor<Runtime, Instance>, account: &Runtime::AccountId) -> bool {
+		match details.approvals.get(account) {
+			Some(Some(deadline)) => {
+				let now =
+					<Runtime as Config<Instance>>::BlockNumberProvider::current_block_number();
+				now <= *deadline
+			},
+			Some(None) => true,
+			None => false,
+		}
+	}
+
+	/// Deposit an event to the runtime.
+	fn deposit_event(env: &mut impl Ext<T = Runtime>, event: IERC721Events) -> Result<(), Error> {
+		let (topics, data) = event.into_log_data().split();
+		let topics = topics.into_iter().map(|v| H256(v.0)).collect::<Vec<_>>();
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::DepositEvent {
+			num_topic: topics.len() as u32,
+			len: topics.len() as u32,
+		})?;
+		env.deposit_event(topics, data.to_vec());
+		Ok(())
+	}
+
+	/// Execute the balance_of call.
+	fn balance_of(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::balanceOfCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		if call.owner.is_zero() {
+			return Err(revert(ERR_INVALID_OWNER));
+		}
+
+		let owner = Self::to_account_id(&call.owner);
+		let mut balance = 0u64;
+		for _ in Account::<Runtime, Instance>::iter_key_prefix((owner, collection)) {
+			Self::charge_reads(env, 1)?;
+			balance = balance.saturating_add(1);
+		}
+
+		Ok(IERC721::balanceOfCall::abi_encode_returns(&alloy::primitives::U256::from(balance)))
+	}
+
+	/// Execute the owner_of call.
+	fn owner_of(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::ownerOfCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let (_, details) = Self::item(&collection, call.tokenId, env)?;
+		Ok(IERC721::ownerOfCall::abi_encode_returns(&Self::to_address(&details.owner)))
+	}
+
+	/// Transfer the item from `from` to `to` on behalf of the caller.
+	fn transfer_from(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		from: alloy::primitives::Address,
+		to: alloy::primitives::Address,
+		token_id: alloy::primitives::U256,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<(), Error> {
+		env.charge(<Runtime as Config<Instance>>::WeightInfo::transfer())?;
+		if to.is_zero() {
+			return Err(revert(ERR_INVALID_RECEIVER));
+		}
+
+		let caller = Self::caller(env)?;
+		let (item, details) = Self::item(&collection, token_id, env)?;
+		if Self::to_address(&details.owner) != from {
+			return Err(revert(ERR_INCORRECT_OWNER));
+		}
+
+		let caller_account = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&caller);
+		let authorized = caller_account == details.owner ||
+			Self::is_approved(&details, &caller_account) ||
+			Self::is_operator(&collection, &details.owner, &caller_account, env)?;
+		if !authorized {
+			return Err(revert(ERR_NOT_AUTHORIZED));
+		}
+
+		pallet_nfts::Pallet::<Runtime, Instance>::do_transfer(
+			collection,
+			item,
+			Self::to_account_id(&to),
+			|_, _| Ok(()),
+		)?;
+
+		Self::deposit_event(
+			env,
+			IERC721Events::Transfer(IERC721::Transfer { from, to, tokenId: token_id }),
+		)
+	}
+
+	/// Execute the safe_transfer_from calls.
+	///
+	/// Transfers the item, then checks that a contract receiver accepts it.
+	fn safe_transfer_from(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		from: alloy::primitives::Address,
+		to: alloy::primitives::Address,
+		token_id: alloy::primitives::U256,
+		data: Vec<u8>,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		Self::transfer_from(collection, from, to, token_id, env)?;
+
+		let receiver = H160::from(to.into_array());
+		if env.code_size(&receiver) > 0 {
+			let operator = Self::caller(env)?;
+			let input = IERC721Receiver::onERC721ReceivedCall {
+				operator: operator.0.into(),
+				from,
+				tokenId: token_id,
+				data: data.into(),
+			}
+			.abi_encode();
+
+			env.call(
+				&CallResources::NoLimits,
+				&receiver,
+				U256::zero(),
+				input,
+				ReentrancyProtection::AllowReentry,
+				false,
+			)
+			.map_err(|_| revert(ERR_NON_RECEIVER))?;
+
+			let output = env.last_frame_output();
+			let accepted = !output.did_revert() &&
+				output.data.get(..4) ==
+					Some(&IERC721Receiver::onERC721ReceivedCall::SELECTOR[..]);
+			if !accepted {
+				return Err(revert(ERR_NON_RECEIVER));
+			}
+		}
+
+		Ok(IERC721::safeTransferFrom_0Call::abi_encode_returns(
+			&IERC721::safeTransferFrom_0Return {},
+		))
+	}
+
+	/// Execute the approve call.
+	///
+	/// Replaces all the approvals of the item by a single approval of `call.to`, or clears them
+	/// if it is the zero address.
+	fn approve(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::approveCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		env.charge(<Runtime as Config<Instance>>::WeightInfo::clear_all_transfer_approvals())?;
+		env.charge(<Runtime as Config<Instance>>::WeightInfo::approve_transfer())?;
+
+		let caller = Self::caller(env)?;
+		let (item, details) = Self::item(&collection, call.tokenId, env)?;
+		let owner = Self::to_address(&details.owner);
+		if owner == call.to {
+			return Err(revert(ERR_APPROVE_TO_CALLER));
+		}
+
+		let caller_account = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&caller);
+		let authorized = caller_account == details.owner ||
+			Self::is_operator(&collection, &details.owner, &caller_account, env)?;
+		if !authorized {
+			return Err(revert(ERR_NOT_AUTHORIZED));
+		}
+
+		let delegate = (!call.to.is_zero()).then(|| Self::to_account_id(&call.to));
+		pallet_nfts::Pallet::<Runtime, Instance>::do_replace_transfer_approval(
+			caller_account,
+			collection,
+			item,
+			delegate,
+		)?;
+
+		Self::deposit_event(
+			env,
+			IERC721Events::Approval(IERC721::Approval {
+				owner,
+				approved: call.to,
+				tokenId: call.tokenId,
+			}),
+		)?;
+
+		Ok(IERC721::approveCall::abi_encode_returns(&IERC721::approveReturn {}))
+	}
+
+	/// Execute the set_approval_for_all call.
+	///
+	/// Approving an operator reserves a deposit from the caller, which is returned once the
+	/// approval is revoked. The approval covers the items the caller holds at any time.
+	fn set_approval_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::setApprovalForAllCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		// the operator and the operator count are read, the approval and the reserve of the
+		// deposit are written
+		env.charge(<Runtime as frame_system::Config>::DbWeight::get().reads_writes(
+			2 + u64::from(<Runtime as Config<Instance>>::ApprovalsLimit::get()),
+			2,
+		))?;
+		let owner = Self::caller(env)?;
+		let operator = H160::from(call.operator.into_array());
+		if owner == operator {
+			return Err(revert(ERR_APPROVE_TO_CALLER));
+		}
+
+		let owner_account = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&owner);
+		let operator_account = Self::to_account_id(&call.operator);
+		let is_operator = pallet_nfts::Pallet::<Runtime, Instance>::is_operator(
+			&collection,
+			&owner_account,
+			&operator_account,
+		);
+		match (call.approved, is_operator) {
+			(true, false) => pallet_nfts::Pallet::<Runtime, Instance>::do_approve_operator(
+				owner_account,
+				collection,
+				operator_account,
+			)?,
+			(false, true) => pallet_nfts::Pallet::<Runtime, Instance>::do_cancel_operator(
+				owner_account,
+				collection,
+				operator_account,
+			)?,
+			_ => {},
+		}
+
+		Self::deposit_event(
+			env,
+			IERC721Events::ApprovalForAll(IERC721::ApprovalForAll {
+				owner: owner.0.into(),
+				operator: call.operator,
+				approved: call.approved,
+			}),
+		)?;
+
+		Ok(IERC721::setApprovalForAllCall::abi_encode_returns(
+			&IERC721::setApprovalForAllReturn {},
+		))
+	}
+
+	/// Execute the get_approved call.
+	///
+	/// Returns the first non expired approval of the item, or the zero address.
+	fn get_approved(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::getApprovedCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let (_, details) = Self::item(&collection, call.tokenId, env)?;
+		let approved = details
+			.approvals
+			.keys()
+			.find(|account| Self::is_approved(&details, account))
+			.map(Self::to_address)
+			.unwrap_or_default();
+		Ok(IERC721::getApprovedCall::abi_encode_returns(&approved))
+	}
+
+	/// Execute the is_approved_for_all call.
+	fn is_approved_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::isApprovedForAllCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let approved = Self::is_operator(
+			&collection,
+			&Self::to_account_id(&call.owner),
+			&Self::to_account_id(&call.operator),
+			env,
+		)?;
+		Ok(IERC721::isApprovedForAllCall::abi_encode_returns(&approved))
+	}
+
+	/// Execute the supports_interface call.
+	fn supports_interface(call: &IERC721::supportsInterfaceCall) -> Result<Vec<u8>, Error> {
+		let supported = [INTERFACE_ID_ERC165, INTERFACE_ID_ERC721].contains(&call.interfaceId.0);
+		Ok(IERC721::supportsInterfaceCall::abi_encode_returns(&supported))
+	}
+
+	/// Execute the token_uri call.
+	///
+	/// Returns the metadata of the item, or an empty string if the item has no metadata.
+	fn token_uri(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::tokenURICall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let (item, _) = Self::item(&collection, call.tokenId, env)?;
+		Self::charge_reads(env, 1)?;
+
+		let uri = ItemMetadataOf::<Runtime, Instance>::get(&collection, &item)
+			.map(|metadata| metadata.data.into_inner())
+			.unwrap_or_default();
+		let uri = alloc::string::String::from_utf8(uri)
+			.map_err(|_| revert("Invalid UTF-8 in token URI"))?;
+
+		Ok(IERC721::tokenURICall::abi_encode_returns(&uri))
+	}
+}
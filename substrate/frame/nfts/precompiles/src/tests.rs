@@ -0,0 +1,318 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+	alloy::hex,
+	mock::{new_test_ext, Balances, Nfts, RuntimeEvent, RuntimeOrigin, System, Test},
+};
+use alloy::primitives::{FixedBytes, U256};
+use frame_support::{assert_ok, traits::Currency};
+use pallet_nfts::{CollectionConfig, CollectionSettings, ItemConfig, ItemSettings, MintSettings};
+use pallet_revive::{precompiles::TransactionLimits, ExecConfig, ExecReturnValue};
+use sp_core::H160;
+use sp_runtime::{DispatchError, Weight};
+
+const OWNER: u64 = 123456789;
+const ALICE: u64 = 987654321;
+const BOB: u64 = 555555555;
+
+fn collection_addr() -> H160 {
+	H160::from(hex::const_decode_to_array(b"0000000000000000000000000000000001210000").unwrap())
+}
+
+fn address_of(account: u64) -> H160 {
+	<Test as pallet_revive::Config>::AddressMapper::to_address(&account)
+}
+
+fn assert_contract_event(contract: H160, event: IERC721Events) {
+	let (topics, data) = event.into_log_data().split();
+	let topics = topics.into_iter().map(|v| H256(v.0)).collect::<Vec<_>>();
+	System::assert_has_event(RuntimeEvent::Revive(pallet_revive::Event::ContractEmitted {
+		contract,
+		data: data.to_vec(),
+		topics,
+	}));
+}
+
+/// Call the collection precompile as `origin`.
+fn try_call(origin: u64, data: Vec<u8>) -> Result<ExecReturnValue, DispatchError> {
+	pallet_revive::Pallet::<Test>::bare_call(
+		RuntimeOrigin::signed(origin),
+		collection_addr(),
+		0u32.into(),
+		TransactionLimits::WeightAndDeposit { weight_limit: Weight::MAX, deposit_limit: u64::MAX },
+		data,
+		&ExecConfig::new_substrate_tx(),
+	)
+	.result
+}
+
+/// Call the collection precompile as `origin`, expecting the call to return.
+fn call(origin: u64, data: Vec<u8>) -> ExecReturnValue {
+	try_call(origin, data).unwrap()
+}
+
+/// Create collection `0` owned by `OWNER` and mint the given items to `OWNER`.
+fn create_collection(items: &[u32]) {
+	for account in [OWNER, ALICE, BOB] {
+		Balances::make_free_balance_be(&account, 1000);
+	}
+	assert_ok!(Nfts::force_create(
+		RuntimeOrigin::root(),
+		OWNER,
+		CollectionConfig {
+			settings: CollectionSettings::all_enabled(),
+			max_supply: None,
+			mint_settings: MintSettings::default(),
+		},
+	));
+	for item in items {
+		assert_ok!(Nfts::force_mint(
+			RuntimeOrigin::signed(OWNER),
+			0,
+			*item,
+			OWNER,
+			ItemConfig { settings: ItemSettings::all_enabled() },
+		));
+	}
+}
+
+#[test]
+fn collection_id_extractor_works() {
+	let address: [u8; 20] =
+		hex::const_decode_to_array(b"0000053900000000000000000000000001210000").unwrap();
+	assert!(InlineIdConfig::<0x0121>::MATCHER.matches(&address));
+	assert_eq!(
+		<InlineIdConfig<0x0121> as NftsPrecompileConfig>::CollectionIdExtractor::collection_id_from_address(
+			&address
+		)
+		.unwrap(),
+		1337u32
+	);
+}
+
+#[test]
+fn balance_of_and_owner_of_work() {
+	new_test_ext().execute_with(|| {
+		create_collection(&[0, 1]);
+
+		let data =
+			call(OWNER, IERC721::balanceOfCall { owner: address_of(OWNER).0.into() }.abi_encode())
+				.data;
+		assert_eq!(IERC721::balanceOfCall::abi_decode_returns(&data).unwrap(), U256::from(2));
+
+		let data =
+			call(ALICE, IERC721::balanceOfCall { owner: address_of(ALICE).0.into() }.abi_encode())
+				.data;
+		assert_eq!(IERC721::balanceOfCall::abi_decode_returns(&data).unwrap(), U256::ZERO);
+
+		let data = call(ALICE, IERC721::ownerOfCall { tokenId: U256::from(1) }.abi_encode()).data;
+		assert_eq!(
+			IERC721::ownerOfCall::abi_decode_returns(&data).unwrap(),
+			alloy::primitives::Address::from(address_of(OWNER).0)
+		);
+
+		// Unknown items revert.
+		assert!(
+			call(ALICE, IERC721::ownerOfCall { tokenId: U256::from(2) }.abi_encode()).did_revert()
+		);
+	});
+}
+
+#[test]
+fn transfer_from_works() {
+	new_test_ext().execute_with(|| {
+		create_collection(&[0]);
+		let from = address_of(OWNER).0.into();
+		let to = address_of(ALICE).0.into();
+
+		let ret =
+			call(OWNER, IERC721::transferFromCall { from, to, tokenId: U256::ZERO }.abi_encode());
+		assert!(!ret.did_revert());
+
+		assert_contract_event(
+			collection_addr(),
+			IERC721Events::Transfer(IERC721::Transfer { from, to, tokenId: U256::ZERO }),
+		);
+		assert_eq!(pallet_nfts::Pallet::<Test>::owner(0, 0), Some(ALICE));
+	});
+}
+
+#[test]
+fn transfer_from_requires_approval() {
+	new_test_ext().execute_with(|| {
+		create_collection(&[0]);
+		let from = address_of(OWNER).0.into();
+		let bob = address_of(BOB).0.into();
+		let transfer =
+			IERC721::transferFromCall { from, to: bob, tokenId: U256::ZERO }.abi_encode();
+
+		// Bob is neither the owner nor approved.
+		assert!(call(BOB, transfer.clone()).did_revert());
+
+		let ret = call(OWNER, IERC721::approveCall { to: bob, tokenId: U256::ZERO }.abi_encode());
+		assert!(!ret.did_revert());
+		assert_contract_event(
+			collection_addr(),
+			IERC721Events::Approval(IERC721::Approval {
+				owner: from,
+				approved: bob,
+				tokenId: U256::ZERO,
+			}),
+		);
+
+		let data = call(BOB, IERC721::getApprovedCall { tokenId: U256::ZERO }.abi_encode()).data;
+		assert_eq!(IERC721::getApprovedCall::abi_decode_returns(&data).unwrap(), bob);
+
+		assert!(!call(BOB, transfer).did_revert());
+		assert_eq!(pallet_nfts::Pallet::<Test>::owner(0, 0), Some(BOB));
+
+		// The approval is cleared by the transfer.
+		let data = call(BOB, IERC721::getApprovedCall { tokenId: U256::ZERO }.abi_encode()).data;
+		assert!(IERC721::getApprovedCall::abi_decode_returns(&data).unwrap().is_zero());
+	});
+}
+
+#[test]
+fn set_approval_for_all_works() {
+	new_test_ext().execute_with(|| {
+		create_collection(&[0, 1, 2]);
+		let owner = address_of(OWNER).0.into();
+		let operator = address_of(ALICE).0.into();
+		let bob = address_of(BOB).0.into();
+		let reserved = Balances::reserved_balance(&OWNER);
+
+		let ret =
+			call(OWNER, IERC721::setApprovalForAllCall { operator, approved: true }.abi_encode());
+		assert!(!ret.did_revert());
+		assert_contract_event(
+			collection_addr(),
+			IERC721Events::ApprovalForAll(IERC721::ApprovalForAll {
+				owner,
+				operator,
+				approved: true,
+			}),
+		);
+		// The approval is stored by pallet-nfts against a deposit.
+		assert!(Nfts::is_operator(&0, &OWNER, &ALICE));
+		assert_eq!(Balances::reserved_balance(&OWNER), reserved + 1);
+
+		let data = call(BOB, IERC721::isApprovedForAllCall { owner, operator }.abi_encode()).data;
+		assert!(IERC721::isApprovedForAllCall::abi_decode_returns(&data).unwrap());
+
+		// The operator can transfer any item of the owner.
+		for token in [0u32, 1] {
+			let transfer =
+				IERC721::transferFromCall { from: owner, to: bob, tokenId: U256::from(token) };
+			assert!(!call(ALICE, transfer.abi_encode()).did_revert());
+			assert_eq!(pallet_nfts::Pallet::<Test>::owner(0, token), Some(BOB));
+		}
+
+		// The operator can approve any item of the owner, but not the items of others.
+		let approve = |token: u32| IERC721::approveCall { to: bob, tokenId: U256::from(token) };
+		assert!(!call(ALICE, approve(2).abi_encode()).did_revert());
+		assert_contract_event(
+			collection_addr(),
+			IERC721Events::Approval(IERC721::Approval {
+				owner,
+				approved: bob,
+				tokenId: U256::from(2),
+			}),
+		);
+		assert!(call(ALICE, approve(0).abi_encode()).did_revert());
+
+		assert!(!call(
+			OWNER,
+			IERC721::setApprovalForAllCall { operator, approved: false }.abi_encode()
+		)
+		.did_revert());
+		let data = call(BOB, IERC721::isApprovedForAllCall { owner, operator }.abi_encode()).data;
+		assert!(!IERC721::isApprovedForAllCall::abi_decode_returns(&data).unwrap());
+		assert_eq!(Balances::reserved_balance(&OWNER), reserved);
+	});
+}
+
+#[test]
+fn operator_approvals_are_kept_when_the_owner_holds_no_items() {
+	new_test_ext().execute_with(|| {
+		create_collection(&[0, 1]);
+		let owner = address_of(OWNER).0.into();
+		let operator = address_of(ALICE).0.into();
+		let bob = address_of(BOB).0.into();
+
+		// Bob holds no item of the collection and can approve operators.
+		let ret =
+			call(BOB, IERC721::setApprovalForAllCall { operator, approved: true }.abi_encode());
+		assert!(!ret.did_revert());
+		assert!(Nfts::is_operator(&0, &BOB, &ALICE));
+
+		let ret =
+			call(OWNER, IERC721::setApprovalForAllCall { operator, approved: true }.abi_encode());
+		assert!(!ret.did_revert());
+		let reserved = Balances::reserved_balance(&OWNER);
+
+		// The approval outlives the items of the owner.
+		let transfer = IERC721::transferFromCall { from: owner, to: bob, tokenId: U256::ZERO };
+		assert!(!call(OWNER, transfer.abi_encode()).did_revert());
+		let item_deposit = Item::<Test>::get(0, 1).unwrap().deposit.amount;
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(OWNER), 0, 1));
+		let data = call(BOB, IERC721::isApprovedForAllCall { owner, operator }.abi_encode()).data;
+		assert!(IERC721::isApprovedForAllCall::abi_decode_returns(&data).unwrap());
+		assert_eq!(Balances::reserved_balance(&OWNER), reserved - item_deposit);
+
+		// It applies to the items the owner acquires later.
+		let transfer = IERC721::transferFromCall { from: bob, to: owner, tokenId: U256::ZERO };
+		assert!(!call(BOB, transfer.abi_encode()).did_revert());
+		let transfer = IERC721::transferFromCall { from: owner, to: bob, tokenId: U256::ZERO };
+		assert!(!call(ALICE, transfer.abi_encode()).did_revert());
+		assert_eq!(Nfts::owner(0, 0), Some(BOB));
+	});
+}
+
+#[test]
+fn supports_interface_and_token_uri_work() {
+	new_test_ext().execute_with(|| {
+		create_collection(&[0]);
+
+		for (interface, supported) in
+			[([0x01, 0xff, 0xc9, 0xa7], true), ([0x80, 0xac, 0x58, 0xcd], true), ([0xff; 4], false)]
+		{
+			let data = call(
+				ALICE,
+				IERC721::supportsInterfaceCall { interfaceId: FixedBytes(interface) }.abi_encode(),
+			)
+			.data;
+			assert_eq!(
+				IERC721::supportsInterfaceCall::abi_decode_returns(&data).unwrap(),
+				supported
+			);
+		}
+
+		let data = call(ALICE, IERC721::tokenURICall { tokenId: U256::ZERO }.abi_encode()).data;
+		assert_eq!(IERC721::tokenURICall::abi_decode_returns(&data).unwrap(), "");
+
+		assert_ok!(Nfts::set_metadata(
+			RuntimeOrigin::signed(OWNER),
+			0,
+			0,
+			b"ipfs://token".to_vec().try_into().unwrap(),
+		));
+		let data = call(ALICE, IERC721::tokenURICall { tokenId: U256::ZERO }.abi_encode()).data;
+		assert_eq!(IERC721::tokenURICall::abi_decode_returns(&data).unwrap(), "ipfs://token");
+	});
+}
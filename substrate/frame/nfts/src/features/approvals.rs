@@ -172,4 +172,99 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		Ok(())
 	}
+
+	/// Replaces all the transfer approvals of an item by a single approval of `maybe_delegate`, or
+	/// clears them if `maybe_delegate` is `None`.
+	///
+	/// The `caller` must be the owner of the item or an operator approved by the owner for the
+	/// collection. The approval of `maybe_delegate` has no deadline.
+	///
+	/// - `caller`: The account replacing the approvals.
+	/// - `collection`: The collection ID containing the item.
+	/// - `item`: The item ID whose approvals are replaced.
+	/// - `maybe_delegate`: The account, if any, that will be allowed to take control of the item.
+	pub fn do_replace_transfer_approval(
+		caller: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		maybe_delegate: Option<T::AccountId>,
+	) -> DispatchResult {
+		let owner = Self::owner(collection, item).ok_or(Error::<T, I>::UnknownItem)?;
+		ensure!(
+			caller == owner || Self::is_operator(&collection, &owner, &caller),
+			Error::<T, I>::NoPermission
+		);
+
+		Self::do_clear_all_transfer_approvals(None, collection, item)?;
+		if let Some(delegate) = maybe_delegate {
+			Self::do_approve_transfer(None, collection, item, delegate, None)?;
+		}
+		Ok(())
+	}
+
+	/// Returns whether `operator` is approved to manage all the items `owner` holds in the
+	/// `collection`.
+	pub fn is_operator(
+		collection: &T::CollectionId,
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+	) -> bool {
+		CollectionOperatorOf::<T, I>::contains_key((collection, owner, operator))
+	}
+
+	/// Approves `operator` to transfer and approve all the items `owner` holds in the
+	/// `collection`.
+	///
+	/// The approval also applies to the items the `owner` acquires later, and is kept until it is
+	/// cancelled. A deposit of [`Config::OperatorDeposit`] is reserved from the `owner` for the
+	/// approval and returned once the approval is cancelled. Approving an already approved
+	/// operator does nothing. Emits the `OperatorApproved` event.
+	///
+	/// - `owner`: The account approving the operator.
+	/// - `collection`: The collection ID the approval applies to.
+	/// - `operator`: The account that will be allowed to manage the items of `owner`.
+	pub fn do_approve_operator(
+		owner: T::AccountId,
+		collection: T::CollectionId,
+		operator: T::AccountId,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Approvals),
+			Error::<T, I>::MethodDisabled
+		);
+		if Self::is_operator(&collection, &owner, &operator) {
+			return Ok(());
+		}
+		let operators =
+			CollectionOperatorOf::<T, I>::iter_key_prefix((&collection, &owner)).count();
+		ensure!(operators < T::ApprovalsLimit::get() as usize, Error::<T, I>::ReachedApprovalLimit);
+
+		let deposit = T::OperatorDeposit::get();
+		T::Currency::reserve(&owner, deposit)?;
+		CollectionOperatorOf::<T, I>::insert((&collection, &owner, &operator), deposit);
+
+		Self::deposit_event(Event::OperatorApproved { collection, owner, operator });
+		Ok(())
+	}
+
+	/// Cancels the approval of `operator` to manage all the items `owner` holds in the
+	/// `collection`, returning the deposit of the approval.
+	///
+	/// Emits the `OperatorApprovalCancelled` event.
+	///
+	/// - `owner`: The account that approved the operator.
+	/// - `collection`: The collection ID the approval applies to.
+	/// - `operator`: The account whose approval is cancelled.
+	pub fn do_cancel_operator(
+		owner: T::AccountId,
+		collection: T::CollectionId,
+		operator: T::AccountId,
+	) -> DispatchResult {
+		let deposit = CollectionOperatorOf::<T, I>::take((&collection, &owner, &operator))
+			.ok_or(Error::<T, I>::NotOperator)?;
+		T::Currency::unreserve(&owner, deposit);
+
+		Self::deposit_event(Event::OperatorApprovalCancelled { collection, owner, operator });
+		Ok(())
+	}
 }
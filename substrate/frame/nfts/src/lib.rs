@@ -185,6 +185,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type DepositPerByte: Get<DepositBalanceOf<Self, I>>;
 
+		/// The amount of funds that must be reserved when approving an operator for all the items
+		/// of an account in a collection.
+		#[pallet::constant]
+		type OperatorDeposit: Get<DepositBalanceOf<Self, I>>;
+
 		/// The maximum length of data stored on-chain.
 		#[pallet::constant]
 		type StringLimit: Get<u32>;
@@ -414,6 +419,23 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The operators approved to transfer and approve any item an account holds in a collection,
+	/// along with the deposit reserved from the account for the approval.
+	///
+	/// An account can approve at most [`Config::ApprovalsLimit`] operators per collection. The
+	/// approvals are kept until the account cancels them, whether it holds items or not.
+	#[pallet::storage]
+	pub type CollectionOperatorOf<T: Config<I>, I: 'static = ()> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::CollectionId>,
+			NMapKey<Blake2_128Concat, T::AccountId>, // owner
+			NMapKey<Blake2_128Concat, T::AccountId>, // operator
+		),
+		DepositBalanceOf<T, I>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -475,6 +497,19 @@ pub mod pallet {
 		},
 		/// All approvals of an item got cancelled.
 		AllApprovalsCancelled { collection: T::CollectionId, item: T::ItemId, owner: T::AccountId },
+		/// An `operator` was approved to manage all the items of `owner` in the `collection`.
+		OperatorApproved {
+			collection: T::CollectionId,
+			owner: T::AccountId,
+			operator: T::AccountId,
+		},
+		/// The approval of an `operator` to manage all the items of `owner` in the `collection`
+		/// was cancelled.
+		OperatorApprovalCancelled {
+			collection: T::CollectionId,
+			owner: T::AccountId,
+			operator: T::AccountId,
+		},
 		/// A `collection` has had its config changed by the `Force` origin.
 		CollectionConfigChanged { collection: T::CollectionId },
 		/// New metadata has been set for a `collection`.
@@ -688,6 +723,8 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// The account is not an approved operator.
+		NotOperator,
 	}
 
 	#[pallet::call]
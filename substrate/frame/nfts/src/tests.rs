@@ -1827,6 +1827,71 @@ fn approval_lifecycle_works() {
 	});
 }
 
+#[test]
+fn operator_approval_lifecycle_works() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&account(2), 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			account(1),
+			default_collection_config()
+		));
+		for item in [42, 43] {
+			assert_ok!(Nfts::force_mint(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				item,
+				account(2),
+				default_item_config()
+			));
+		}
+
+		// accounts holding no item of the collection can approve operators too
+		Balances::make_free_balance_be(&account(3), 100);
+		assert_ok!(Nfts::do_approve_operator(account(3), 0, account(4)));
+		assert!(Nfts::is_operator(&0, &account(3), &account(4)));
+
+		assert_ok!(Nfts::do_approve_operator(account(2), 0, account(3)));
+		assert!(Nfts::is_operator(&0, &account(2), &account(3)));
+		assert_eq!(Balances::reserved_balance(&account(2)), 1);
+		assert!(events().contains(&Event::<Test>::OperatorApproved {
+			collection: 0,
+			owner: account(2),
+			operator: account(3),
+		}));
+
+		// the operator can approve the items of the owner, other accounts can't
+		assert_noop!(
+			Nfts::do_replace_transfer_approval(account(4), 0, 42, Some(account(4))),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nfts::do_replace_transfer_approval(account(3), 0, 42, Some(account(4))));
+		assert_eq!(approvals(0, 42), vec![(account(4), None)]);
+		assert_ok!(Nfts::do_replace_transfer_approval(account(3), 0, 42, None));
+		assert!(approvals(0, 42).is_empty());
+
+		assert_ok!(Nfts::do_cancel_operator(account(2), 0, account(3)));
+		assert!(!Nfts::is_operator(&0, &account(2), &account(3)));
+		assert_eq!(Balances::reserved_balance(&account(2)), 0);
+		assert_noop!(
+			Nfts::do_cancel_operator(account(2), 0, account(3)),
+			Error::<Test>::NotOperator
+		);
+
+		// the approvals are kept once the owner holds no more items of the collection
+		assert_ok!(Nfts::do_approve_operator(account(2), 0, account(3)));
+		assert_ok!(Nfts::transfer(RuntimeOrigin::signed(account(2)), 0, 42, account(4)));
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(account(2)), 0, 43));
+		assert!(Nfts::is_operator(&0, &account(2), &account(3)));
+		assert_eq!(Balances::reserved_balance(&account(2)), 1);
+
+		// and apply to the items the owner acquires later
+		assert_ok!(Nfts::transfer(RuntimeOrigin::signed(account(4)), 0, 42, account(2)));
+		assert_ok!(Nfts::do_replace_transfer_approval(account(3), 0, 42, Some(account(4))));
+		assert_eq!(approvals(0, 42), vec![(account(4), None)]);
+	});
+}
+
 #[test]
 fn cancel_approval_works() {
 	new_test_ext().execute_with(|| {
@@ -52,6 +52,41 @@ fn add_proxies<T: Config>(n: u32, maybe_who: Option<T::AccountId>) -> Result<(),
 	Ok(())
 }
 
+/// A spending limit which does not restrict the benchmarked calls.
+fn spending_limit<T: Config>() -> SpendingLimitOf<T> {
+	SpendingLimit {
+		asset: T::SpendingInspector::benchmark_asset_kind(),
+		amount: BalanceOf::<T>::max_value(),
+		period: 10u32.into(),
+	}
+}
+
+/// Adds `n` proxies of the whitelisted caller, all constrained by a spending limit: the worst case
+/// for finding and updating the constraints of one of them.
+fn add_constrained_proxies<T: Config>(n: u32) -> Result<(), &'static str> {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	for i in 0..n {
+		Proxy::<T>::add_constrained_proxy(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Lookup::unlookup(account("target", i, SEED)),
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+			None,
+			Some(spending_limit::<T>()),
+		)?;
+	}
+	Ok(())
+}
+
+/// The result of dispatching `call` through a proxy with a spending limit.
+fn spending_limited_result<T: Config>(call: &<T as Config>::RuntimeCall) -> DispatchResult {
+	match T::SpendingInspector::spending(call) {
+		Spending::Nothing => Ok(()),
+		_ => Err(Error::<T>::SpendingLimitExceeded.into()),
+	}
+}
+
 fn add_announcements<T: Config>(
 	n: u32,
 	maybe_who: Option<T::AccountId>,
@@ -90,7 +125,7 @@ mod benchmarks {
 
 	#[benchmark]
 	fn proxy(p: Linear<1, { T::MaxProxies::get() - 1 }>) -> Result<(), BenchmarkError> {
-		add_proxies::<T>(p, None)?;
+		add_constrained_proxies::<T>(p)?;
 		// In this case the caller is the "target" proxy
 		let caller: T::AccountId = account("target", p - 1, SEED);
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
@@ -99,11 +134,12 @@ mod benchmarks {
 		let real_lookup = T::Lookup::unlookup(real);
 		let call: <T as Config>::RuntimeCall =
 			frame_system::Call::<T>::remark { remark: vec![] }.into();
+		let result = spending_limited_result::<T>(&call);
 
 		#[extrinsic_call]
 		_(RawOrigin::Signed(caller), real_lookup, Some(T::ProxyType::default()), Box::new(call));
 
-		assert_last_event::<T>(Event::ProxyExecuted { result: Ok(()) }.into());
+		assert_last_event::<T>(Event::ProxyExecuted { result }.into());
 
 		Ok(())
 	}
@@ -113,7 +149,7 @@ mod benchmarks {
 		a: Linear<0, { T::MaxPending::get() - 1 }>,
 		p: Linear<1, { T::MaxProxies::get() - 1 }>,
 	) -> Result<(), BenchmarkError> {
-		add_proxies::<T>(p, None)?;
+		add_constrained_proxies::<T>(p)?;
 		// In this case the caller is the "target" proxy
 		let caller: T::AccountId = account("pure", 0, SEED);
 		let delegate: T::AccountId = account("target", p - 1, SEED);
@@ -130,6 +166,7 @@ mod benchmarks {
 			T::CallHasher::hash_of(&call),
 		)?;
 		add_announcements::<T>(a, Some(delegate.clone()), None)?;
+		let result = spending_limited_result::<T>(&call);
 
 		#[extrinsic_call]
 		_(
@@ -140,7 +177,7 @@ mod benchmarks {
 			Box::new(call),
 		);
 
-		assert_last_event::<T>(Event::ProxyExecuted { result: Ok(()) }.into());
+		assert_last_event::<T>(Event::ProxyExecuted { result }.into());
 
 		Ok(())
 	}
@@ -459,5 +496,66 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn add_constrained_proxy(
+		p: Linear<1, { T::MaxProxies::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		add_constrained_proxies::<T>(p)?;
+		let caller: T::AccountId = whitelisted_caller();
+		let real = T::Lookup::unlookup(account("target", T::MaxProxies::get(), SEED));
+		let expiry = T::BlockNumberProvider::current_block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			real,
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+			Some(expiry),
+			Some(spending_limit::<T>()),
+		);
+
+		let (proxies, _) = Proxies::<T>::get(&caller);
+		assert_eq!(proxies.len() as u32, p + 1);
+		assert_eq!(Constraints::<T>::get(&caller).len() as u32, p + 1);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_expired_proxy(
+		p: Linear<1, { T::MaxProxies::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		add_constrained_proxies::<T>(p)?;
+		let real: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("target", T::MaxProxies::get(), SEED);
+		let expiry = T::BlockNumberProvider::current_block_number() + 1u32.into();
+		Proxy::<T>::add_constrained_proxy(
+			RawOrigin::Signed(real.clone()).into(),
+			T::Lookup::unlookup(delegate.clone()),
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+			Some(expiry),
+			None,
+		)?;
+		T::BlockNumberProvider::set_block_number(expiry);
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			T::Lookup::unlookup(real.clone()),
+			T::Lookup::unlookup(delegate),
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+		);
+
+		let (proxies, _) = Proxies::<T>::get(&real);
+		assert_eq!(proxies.len() as u32, p);
+		assert_eq!(Constraints::<T>::get(&real).len() as u32, p);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Proxy, crate::tests::new_test_ext(), crate::tests::Test);
 }
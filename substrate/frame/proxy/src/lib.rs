@@ -23,6 +23,14 @@
 //! wish to execute some duration prior to execution happens. In this case, the target account may
 //! reject the announcement and in doing so, veto the execution.
 //!
+//! Proxies added with `add_constrained_proxy` can further be bounded by an expiry block, after
+//! which they can no longer be used and anyone may remove them, and by a spending limit per
+//! period in one of the assets of the runtime. The runtime tells which calls move funds out of the
+//! proxied account, and how much of which asset, through [`Config::SpendingInspector`]. Every
+//! call dispatched by a spending-limited proxy, including the ones nested in batches, is checked
+//! against it: calls moving the limited asset are counted against the allowance, calls not moving
+//! any funds are allowed, and any other call is rejected.
+//!
 //! - [`Config`]
 //! - [`Call`]
 
@@ -34,8 +42,11 @@ mod tests;
 pub mod weights;
 
 extern crate alloc;
-use alloc::{boxed::Box, vec};
+use alloc::{boxed::Box, rc::Rc, vec};
+use codec::Codec;
+use core::cell::Cell;
 use frame::{
+	deps::sp_api,
 	prelude::*,
 	traits::{Currency, InstanceFilter, ReservableCurrency},
 };
@@ -100,6 +111,132 @@ pub struct Announcement<AccountId, Hash, BlockNumber> {
 	height: BlockNumber,
 }
 
+/// The funds a call moves out of the account dispatching it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Spending<AssetKind, Balance> {
+	/// The call does not move any funds out of the account.
+	Nothing,
+	/// The call moves at most the given amount of the given asset out of the account.
+	Amount(AssetKind, Balance),
+	/// The funds moved by the call are not known.
+	Unknown,
+}
+
+/// Inspects the calls dispatched by proxies with a spending limit.
+pub trait InspectSpending<Call, Balance> {
+	/// The kind of asset a spending limit applies to.
+	type AssetKind: Parameter + MaxEncodedLen;
+
+	/// The funds `call` moves out of the account dispatching it.
+	///
+	/// Calls dispatching other calls with the same origin, like `utility::batch`, should report
+	/// [`Spending::Nothing`]: the nested calls are inspected on their own.
+	fn spending(call: &Call) -> Spending<Self::AssetKind, Balance>;
+
+	/// An asset to set the spending limit of proxies in benchmarks to.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_asset_kind() -> Self::AssetKind;
+}
+
+/// Treats every call as moving unknown funds: a proxy with a spending limit cannot dispatch any
+/// call.
+impl<Call, Balance> InspectSpending<Call, Balance> for () {
+	type AssetKind = ();
+
+	fn spending(_: &Call) -> Spending<(), Balance> {
+		Spending::Unknown
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_asset_kind() {}
+}
+
+/// A limit on the funds of one asset a proxy may spend on behalf of the proxied account.
+#[derive(
+	Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, Debug, MaxEncodedLen, TypeInfo,
+)]
+pub struct SpendingLimit<AssetKind, Balance, BlockNumber> {
+	/// The asset the limit applies to.
+	pub asset: AssetKind,
+	/// The amount that may be spent within a single period.
+	pub amount: Balance,
+	/// The length of a period, in blocks.
+	pub period: BlockNumber,
+}
+
+/// The constraints a proxy relationship was added with, together with the spending recorded
+/// against them.
+#[derive(
+	Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, Debug, MaxEncodedLen, TypeInfo,
+)]
+pub struct ProxyConstraints<AssetKind, Balance, BlockNumber> {
+	/// The block from which on the proxy can no longer be used.
+	pub expiry: Option<BlockNumber>,
+	/// The funds the proxy may spend per period.
+	pub spending_limit: Option<SpendingLimit<AssetKind, Balance, BlockNumber>>,
+	/// The funds spent within the current period.
+	pub spent: Balance,
+	/// The block at which the current period started.
+	pub period_start: BlockNumber,
+	/// The amount held on deposit for storing the constraints.
+	pub deposit: Balance,
+}
+
+impl<AssetKind, Balance: AtLeast32BitUnsigned + Copy, BlockNumber: AtLeast32BitUnsigned + Copy>
+	ProxyConstraints<AssetKind, Balance, BlockNumber>
+{
+	/// Whether the proxy can no longer be used at `now`.
+	pub fn is_expired(&self, now: BlockNumber) -> bool {
+		self.expiry.is_some_and(|expiry| now >= expiry)
+	}
+
+	/// The funds the proxy may still spend within the period containing `now`, or `None` if it
+	/// has no spending limit.
+	pub fn remaining(&self, now: BlockNumber) -> Option<Balance> {
+		let limit = self.spending_limit.as_ref()?;
+		let spent = if self.is_new_period(now) { Zero::zero() } else { self.spent };
+		Some(limit.amount.saturating_sub(spent))
+	}
+
+	/// Record `amount` as spent within the period containing `now`.
+	fn record_spending(&mut self, amount: Balance, now: BlockNumber) {
+		self.roll_period(now);
+		self.spent = self.spent.saturating_add(amount);
+	}
+
+	/// Whether `now` is past the current period.
+	fn is_new_period(&self, now: BlockNumber) -> bool {
+		self.spending_limit.as_ref().is_some_and(|limit| {
+			!limit.period.is_zero() && now.saturating_sub(self.period_start) >= limit.period
+		})
+	}
+
+	/// Move to the period containing `now`, resetting the spent funds if it is a new one.
+	fn roll_period(&mut self, now: BlockNumber) {
+		let Some(period) = self.spending_limit.as_ref().map(|limit| limit.period) else { return };
+		if self.is_new_period(now) {
+			let elapsed = now.saturating_sub(self.period_start);
+			self.period_start = now - elapsed % period;
+			self.spent = Zero::zero();
+		}
+	}
+}
+
+type SpendingAssetOf<T> = <<T as Config>::SpendingInspector as InspectSpending<
+	<T as Config>::RuntimeCall,
+	BalanceOf<T>,
+>>::AssetKind;
+
+type ProxyConstraintsOf<T> = ProxyConstraints<SpendingAssetOf<T>, BalanceOf<T>, BlockNumberFor<T>>;
+
+type SpendingLimitOf<T> = SpendingLimit<SpendingAssetOf<T>, BalanceOf<T>, BlockNumberFor<T>>;
+
+type ProxyDefinitionOf<T> = ProxyDefinition<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::ProxyType,
+	BlockNumberFor<T>,
+>;
+
 /// The type of deposit
 #[derive(
 	Encode,
@@ -223,6 +360,13 @@ pub mod pallet {
 		///     providers can be used. Relay provider can be a bit better in cases where the
 		///     parachain is lagging its block production to avoid clock skew.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// Reports the funds moved by the calls dispatched through proxies with a spending
+		/// limit.
+		///
+		/// Only the calls it reports as moving funds of the limited asset, or as moving no funds at
+		/// all, may be dispatched by such proxies. Use `()` to not support spending limits.
+		type SpendingInspector: InspectSpending<<Self as Config>::RuntimeCall, BalanceOf<Self>>;
 	}
 
 	#[pallet::call]
@@ -253,10 +397,10 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let real = T::Lookup::lookup(real)?;
-			let def = Self::find_proxy(&real, &who, force_proxy_type)?;
+			let (def, constraints) = Self::find_constrained_proxy(&real, &who, force_proxy_type)?;
 			ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
 
-			Self::do_proxy(def, real, *call);
+			Self::do_proxy(def, constraints, real, *call);
 
 			Ok(())
 		}
@@ -404,6 +548,7 @@ pub mod pallet {
 			ensure!(proxy == who, Error::<T>::NoPermission);
 
 			let (_, deposit) = Proxies::<T>::take(&who);
+			Self::clear_constraints(&who);
 			T::Currency::unreserve(&spawner, deposit);
 
 			Self::deposit_event(Event::PureKilled {
@@ -440,11 +585,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let real = T::Lookup::lookup(real)?;
-			Proxies::<T>::get(&real)
-				.0
-				.into_iter()
-				.find(|x| x.delegate == who)
-				.ok_or(Error::<T>::NotProxy)?;
+			Self::find_proxy(&real, &who, None)?;
 
 			let announcement = Announcement {
 				real: real.clone(),
@@ -557,7 +698,8 @@ pub mod pallet {
 			ensure_signed(origin)?;
 			let delegate = T::Lookup::lookup(delegate)?;
 			let real = T::Lookup::lookup(real)?;
-			let def = Self::find_proxy(&real, &delegate, force_proxy_type)?;
+			let (def, constraints) =
+				Self::find_constrained_proxy(&real, &delegate, force_proxy_type)?;
 
 			let call_hash = T::CallHasher::hash_of(&call);
 			let now = T::BlockNumberProvider::current_block_number();
@@ -568,7 +710,7 @@ pub mod pallet {
 			})
 			.map_err(|_| Error::<T>::Unannounced)?;
 
-			Self::do_proxy(def, real, *call);
+			Self::do_proxy(def, constraints, real, *call);
 
 			Ok(())
 		}
@@ -671,6 +813,112 @@ pub mod pallet {
 
 			Ok(if deposit_updated { Pays::No.into() } else { Pays::Yes.into() })
 		}
+
+		/// Register a proxy account for the sender that is able to make calls on its behalf,
+		/// bounded in time and in the funds it may spend.
+		///
+		/// A proxy added this way may not add further proxies or spawn pure accounts on behalf of
+		/// the sender, since those would not carry its constraints.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that the `caller` would like to make a proxy.
+		/// - `proxy_type`: The permissions allowed for this proxy account.
+		/// - `delay`: The announcement period required of the initial proxy. Will generally be
+		/// zero.
+		/// - `expiry`: The block from which on the proxy can no longer be used. Once reached,
+		/// anyone may remove the proxy with `remove_expired_proxy`.
+		/// - `spending_limit`: The funds of one asset the proxy may spend per period. Every call
+		/// the proxy dispatches must be reported by the `SpendingInspector` as moving either
+		/// funds of this asset, which are counted against the limit, or no funds at all.
+		/// Funds received by the `caller` do not restore the allowance.
+		///
+		/// Storing the constraints requires a deposit of `ProxyDepositBase + ProxyDepositFactor`
+		/// on top of the deposit for the proxy itself.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::add_constrained_proxy(T::MaxProxies::get()))]
+		pub fn add_constrained_proxy(
+			origin: OriginFor<T>,
+			delegate: AccountIdLookupOf<T>,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+			expiry: Option<BlockNumberFor<T>>,
+			spending_limit: Option<SpendingLimitOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			let now = T::BlockNumberProvider::current_block_number();
+			ensure!(expiry.is_some() || spending_limit.is_some(), Error::<T>::InvalidConstraints);
+			ensure!(expiry.map_or(true, |expiry| expiry > now), Error::<T>::InvalidConstraints);
+			ensure!(
+				spending_limit.as_ref().map_or(true, |limit| !limit.period.is_zero()),
+				Error::<T>::InvalidConstraints
+			);
+
+			Self::add_proxy_delegate(&who, delegate.clone(), proxy_type.clone(), delay)?;
+			let deposit = T::ProxyDepositBase::get() + T::ProxyDepositFactor::get();
+			T::Currency::reserve(&who, deposit)?;
+			let proxy_def = ProxyDefinition {
+				delegate: delegate.clone(),
+				proxy_type: proxy_type.clone(),
+				delay,
+			};
+			let constraints = ProxyConstraints {
+				expiry,
+				spending_limit: spending_limit.clone(),
+				spent: Zero::zero(),
+				period_start: now,
+				deposit,
+			};
+			Constraints::<T>::try_mutate(&who, |all| {
+				all.try_push((proxy_def, constraints)).map_err(|_| Error::<T>::TooMany)
+			})?;
+			Self::deposit_event(Event::ProxyConstrained {
+				delegator: who,
+				delegatee: delegate,
+				proxy_type,
+				delay,
+				expiry,
+				spending_limit,
+			});
+
+			Ok(())
+		}
+
+		/// Unregister an expired proxy account of `delegator`, returning its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_. It may be any account.
+		///
+		/// Parameters:
+		/// - `delegator`: The account that added the proxy.
+		/// - `delegate`: The expired proxy account.
+		/// - `proxy_type`: The permissions of the expired proxy account.
+		/// - `delay`: The announcement period of the expired proxy account.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::remove_expired_proxy(T::MaxProxies::get()))]
+		pub fn remove_expired_proxy(
+			origin: OriginFor<T>,
+			delegator: AccountIdLookupOf<T>,
+			delegate: AccountIdLookupOf<T>,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let delegator = T::Lookup::lookup(delegator)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			let proxy_def = ProxyDefinition {
+				delegate: delegate.clone(),
+				proxy_type: proxy_type.clone(),
+				delay,
+			};
+			let constraints =
+				Self::constraints_of(&delegator, &proxy_def).ok_or(Error::<T>::NotFound)?;
+			let now = T::BlockNumberProvider::current_block_number();
+			ensure!(constraints.is_expired(now), Error::<T>::NotExpired);
+
+			Self::remove_proxy_delegate(&delegator, delegate, proxy_type, delay)
+		}
 	}
 
 	#[pallet::event]
@@ -722,6 +970,15 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// A proxy was added with an expiry or a spending limit.
+		ProxyConstrained {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+			expiry: Option<BlockNumberFor<T>>,
+			spending_limit: Option<SpendingLimitOf<T>>,
+		},
 	}
 
 	#[pallet::error]
@@ -742,6 +999,15 @@ pub mod pallet {
 		Unannounced,
 		/// Cannot add self as proxy.
 		NoSelfProxy,
+		/// The proxy has expired.
+		Expired,
+		/// The proxy has not expired yet.
+		NotExpired,
+		/// No constraint was given, the expiry is not in the future or the spending period is
+		/// zero.
+		InvalidConstraints,
+		/// The call would spend more than the proxy is allowed to within the current period.
+		SpendingLimitExceeded,
 	}
 
 	/// The set of account proxies. Maps the account which has delegated to the accounts
@@ -774,6 +1040,17 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The constraints of the proxies added with `add_constrained_proxy`, keyed by the account
+	/// which has delegated.
+	#[pallet::storage]
+	pub type Constraints<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<(ProxyDefinitionOf<T>, ProxyConstraintsOf<T>), T::MaxProxies>,
+		ValueQuery,
+	>;
+
 	#[pallet::view_functions]
 	impl<T: Config> Pallet<T> {
 		/// Check if a `RuntimeCall` is allowed for a given `ProxyType`.
@@ -802,6 +1079,26 @@ impl<T: Config> Pallet<T> {
 		Proxies::<T>::get(account)
 	}
 
+	/// The funds a proxy may still spend on behalf of `real` within the current period.
+	///
+	/// Returns `None` if the proxy has no spending limit, and zero if it has expired.
+	pub fn remaining_allowance(
+		real: T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: BlockNumberFor<T>,
+	) -> Option<BalanceOf<T>> {
+		let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
+		let now = T::BlockNumberProvider::current_block_number();
+		Self::constraints_of(&real, &proxy_def).and_then(|constraints| {
+			if constraints.is_expired(now) {
+				Some(Zero::zero())
+			} else {
+				constraints.remaining(now)
+			}
+		})
+	}
+
 	/// Public function to announcements storage.
 	pub fn announcements(
 		account: T::AccountId,
@@ -905,6 +1202,16 @@ impl<T: Config> Pallet<T> {
 			};
 			let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
 			proxies.remove(i);
+			Constraints::<T>::mutate_exists(delegator, |maybe_all| {
+				let Some(all) = maybe_all else { return };
+				if let Some(i) = all.iter().position(|(def, _)| def == &proxy_def) {
+					let (_, constraints) = all.remove(i);
+					T::Currency::unreserve(delegator, constraints.deposit);
+				}
+				if all.is_empty() {
+					*maybe_all = None;
+				}
+			});
 			let new_deposit = Self::deposit(proxies.len() as u32);
 			if new_deposit > old_deposit {
 				T::Currency::reserve(delegator, new_deposit - old_deposit)?;
@@ -984,28 +1291,87 @@ impl<T: Config> Pallet<T> {
 		delegate: &T::AccountId,
 		force_proxy_type: Option<T::ProxyType>,
 	) -> Result<ProxyDefinition<T::AccountId, T::ProxyType, BlockNumberFor<T>>, DispatchError> {
+		Self::find_constrained_proxy(real, delegate, force_proxy_type).map(|(def, _)| def)
+	}
+
+	/// Find a usable proxy of `delegate` for `real`, together with its constraints. Expired
+	/// proxies are skipped.
+	fn find_constrained_proxy(
+		real: &T::AccountId,
+		delegate: &T::AccountId,
+		force_proxy_type: Option<T::ProxyType>,
+	) -> Result<(ProxyDefinitionOf<T>, Option<ProxyConstraintsOf<T>>), DispatchError> {
 		let f = |x: &ProxyDefinition<T::AccountId, T::ProxyType, BlockNumberFor<T>>| -> bool {
 			&x.delegate == delegate &&
 				force_proxy_type.as_ref().map_or(true, |y| &x.proxy_type == y)
 		};
-		Ok(Proxies::<T>::get(real).0.into_iter().find(f).ok_or(Error::<T>::NotProxy)?)
+		let now = T::BlockNumberProvider::current_block_number();
+		let all_constraints = Constraints::<T>::get(real);
+		let mut found_expired = false;
+		for proxy_def in Proxies::<T>::get(real).0.into_iter().filter(f) {
+			let constraints = all_constraints
+				.iter()
+				.find(|(def, _)| def == &proxy_def)
+				.map(|(_, constraints)| constraints.clone());
+			match constraints {
+				Some(constraints) if constraints.is_expired(now) => found_expired = true,
+				constraints => return Ok((proxy_def, constraints)),
+			}
+		}
+		Err(if found_expired { Error::<T>::Expired } else { Error::<T>::NotProxy }.into())
+	}
+
+	/// The constraints of the proxy `proxy_def` of `delegator`, if it has any.
+	fn constraints_of(
+		delegator: &T::AccountId,
+		proxy_def: &ProxyDefinitionOf<T>,
+	) -> Option<ProxyConstraintsOf<T>> {
+		Constraints::<T>::get(delegator)
+			.into_iter()
+			.find(|(def, _)| def == proxy_def)
+			.map(|(_, constraints)| constraints)
 	}
 
 	fn do_proxy(
 		def: ProxyDefinition<T::AccountId, T::ProxyType, BlockNumberFor<T>>,
+		constraints: Option<ProxyConstraintsOf<T>>,
 		real: T::AccountId,
 		call: <T as Config>::RuntimeCall,
 	) {
 		use frame::traits::{InstanceFilter as _, OriginTrait as _};
+		let now = T::BlockNumberProvider::current_block_number();
+		let is_constrained = constraints.is_some();
+		// The asset and remaining allowance of a spending-limited proxy.
+		let allowance = constraints.as_ref().and_then(|constraints| {
+			let limit = constraints.spending_limit.as_ref()?;
+			Some((limit.asset.clone(), constraints.remaining(now)?))
+		});
+		// The funds moved by the calls admitted so far, and whether a call was rejected for
+		// exceeding the allowance.
+		let spent = Rc::new(Cell::new(BalanceOf::<T>::zero()));
+		let exceeded = Rc::new(Cell::new(false));
+		let (filter_spent, filter_exceeded) = (spent.clone(), exceeded.clone());
+		let filter_def = def.clone();
 		// This is a freshly authenticated new account, the origin restrictions doesn't apply.
-		let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(real).into();
+		let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(real.clone()).into();
 		origin.add_filter(move |c: &<T as frame_system::Config>::RuntimeCall| {
+			let def = &filter_def;
 			let c = <T as Config>::RuntimeCall::from_ref(c);
 			// We make sure the proxy call does access this pallet to change modify proxies.
-			match c.is_sub_type() {
+			let allowed = match c.is_sub_type() {
+				// A constrained proxy cannot create proxies, as they would escape its
+				// constraints.
+				Some(Call::add_proxy { .. }) |
+				Some(Call::add_constrained_proxy { .. }) |
+				Some(Call::create_pure { .. })
+					if is_constrained =>
+				{
+					false
+				},
 				// Proxy call cannot add or remove a proxy with more permissions than it already
 				// has.
 				Some(Call::add_proxy { ref proxy_type, .. }) |
+				Some(Call::add_constrained_proxy { ref proxy_type, .. }) |
 				Some(Call::remove_proxy { ref proxy_type, .. })
 					if !def.proxy_type.is_superset(proxy_type) =>
 				{
@@ -1019,10 +1385,51 @@ impl<T: Config> Pallet<T> {
 					false
 				},
 				_ => def.proxy_type.filter(c),
+			};
+			let Some((asset, remaining)) = allowance.as_ref().filter(|_| allowed) else {
+				return allowed;
+			};
+			// Each call is counted on its own, funds received by other calls do not offset it.
+			let within_limit = match T::SpendingInspector::spending(c) {
+				Spending::Nothing => true,
+				Spending::Amount(spent_asset, amount) if &spent_asset == asset => {
+					let total = filter_spent.get().saturating_add(amount);
+					let within_limit = total <= *remaining;
+					if within_limit {
+						filter_spent.set(total);
+					}
+					within_limit
+				},
+				Spending::Amount(..) | Spending::Unknown => false,
+			};
+			if !within_limit {
+				filter_exceeded.set(true);
 			}
+			within_limit
 		});
-		let e = call.dispatch(origin);
-		Self::deposit_event(Event::ProxyExecuted { result: e.map(|_| ()).map_err(|e| e.error) });
+		let mut result = call.dispatch(origin).map(|_| ()).map_err(|e| e.error);
+		if let Some(mut constraints) = constraints.filter(|c| c.spending_limit.is_some()) {
+			if result.is_err() && exceeded.get() {
+				result = Err(Error::<T>::SpendingLimitExceeded.into());
+			}
+			// A failed call is reverted, along with the funds it moved.
+			let amount = if result.is_ok() { spent.get() } else { Zero::zero() };
+			// Written back even if nothing was spent, as the period may have rolled over.
+			constraints.record_spending(amount, now);
+			Constraints::<T>::mutate(&real, |all| {
+				if let Some(entry) = all.iter_mut().find(|(proxy_def, _)| proxy_def == &def) {
+					entry.1 = constraints;
+				}
+			});
+		}
+		Self::deposit_event(Event::ProxyExecuted { result });
+	}
+
+	/// Removes the constraints of all the proxies of `delegator`, returning their deposits.
+	fn clear_constraints(delegator: &T::AccountId) {
+		for (_, constraints) in Constraints::<T>::take(delegator) {
+			T::Currency::unreserve(delegator, constraints.deposit);
+		}
 	}
 
 	/// Removes all proxy delegates for a given delegator.
@@ -1031,6 +1438,7 @@ impl<T: Config> Pallet<T> {
 	/// - `delegator`: The delegator account.
 	pub fn remove_all_proxy_delegates(delegator: &T::AccountId) {
 		let (proxies, old_deposit) = Proxies::<T>::take(delegator);
+		Self::clear_constraints(delegator);
 		T::Currency::unreserve(delegator, old_deposit);
 		proxies.into_iter().for_each(|proxy_def| {
 			Self::deposit_event(Event::<T>::ProxyRemoved {
@@ -1042,3 +1450,25 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 }
+
+sp_api::decl_runtime_apis! {
+	/// The runtime API for the proxy pallet.
+	pub trait ProxyApi<AccountId, ProxyType, BlockNumber, Balance>
+	where
+		AccountId: Codec,
+		ProxyType: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// The funds `delegate` may still spend on behalf of `real` within the current period,
+		/// through the proxy of the given type and delay.
+		///
+		/// Returns `None` if the proxy has no spending limit, and zero if it has expired.
+		fn remaining_allowance(
+			real: AccountId,
+			delegate: AccountId,
+			proxy_type: ProxyType,
+			delay: BlockNumber,
+		) -> Option<Balance>;
+	}
+}
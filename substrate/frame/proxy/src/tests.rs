@@ -115,6 +115,32 @@ parameter_types! {
 	pub static AnnouncementDepositFactor: u64 = 1;
 }
 
+/// Reports balance transfers as spending asset `0`, and remarks and batches as spending nothing.
+pub struct TestSpending;
+impl InspectSpending<RuntimeCall, u64> for TestSpending {
+	type AssetKind = u32;
+
+	fn spending(call: &RuntimeCall) -> Spending<u32, u64> {
+		match call {
+			RuntimeCall::Balances(BalancesCall::transfer_allow_death { value, .. }) => {
+				Spending::Amount(0, *value)
+			},
+			RuntimeCall::System(SystemCall::remark { .. }) |
+			RuntimeCall::Utility(
+				UtilityCall::batch { .. } |
+				UtilityCall::batch_all { .. } |
+				UtilityCall::force_batch { .. },
+			) => Spending::Nothing,
+			_ => Spending::Unknown,
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn benchmark_asset_kind() -> u32 {
+		0
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -129,6 +155,7 @@ impl Config for Test {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type SpendingInspector = TestSpending;
 }
 
 use super::{Call as ProxyCall, Event as ProxyEvent};
@@ -866,3 +893,354 @@ fn poke_deposit_fails_for_unsigned_origin() {
 		assert_noop!(Proxy::poke_deposit(RuntimeOrigin::none()), DispatchError::BadOrigin,);
 	});
 }
+
+fn spending_limit(amount: u64, period: u64) -> Option<SpendingLimit<u32, u64, u64>> {
+	Some(SpendingLimit { asset: 0, amount, period })
+}
+
+#[test]
+fn add_constrained_proxy_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			Some(10),
+			spending_limit(5, 10),
+		));
+		System::assert_last_event(
+			ProxyEvent::ProxyConstrained {
+				delegator: 1,
+				delegatee: 3,
+				proxy_type: ProxyType::Any,
+				delay: 0,
+				expiry: Some(10),
+				spending_limit: spending_limit(5, 10),
+			}
+			.into(),
+		);
+		let proxy_def = ProxyDefinition { delegate: 3, proxy_type: ProxyType::Any, delay: 0 };
+		assert_eq!(Proxies::<Test>::get(1).0, vec![proxy_def]);
+		// The deposit of the proxy and the one of its constraints.
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_eq!(
+			Constraints::<Test>::get(1).into_inner(),
+			vec![(
+				proxy_def,
+				ProxyConstraints {
+					expiry: Some(10),
+					spending_limit: spending_limit(5, 10),
+					spent: 0,
+					period_start: 1,
+					deposit: 2,
+				}
+			)]
+		);
+
+		// Removing the proxy removes its constraints too.
+		assert_ok!(Proxy::remove_proxy(RuntimeOrigin::signed(1), 3, ProxyType::Any, 0));
+		assert!(!Constraints::<Test>::contains_key(1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			Some(10),
+			None,
+		));
+		assert_ok!(Proxy::remove_proxies(RuntimeOrigin::signed(1)));
+		assert!(!Constraints::<Test>::contains_key(1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn add_constrained_proxy_checks_constraints() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(
+			Proxy::add_constrained_proxy(
+				RuntimeOrigin::signed(1),
+				3,
+				ProxyType::Any,
+				0,
+				None,
+				None
+			),
+			Error::<Test>::InvalidConstraints
+		);
+		assert_noop!(
+			Proxy::add_constrained_proxy(
+				RuntimeOrigin::signed(1),
+				3,
+				ProxyType::Any,
+				0,
+				Some(5),
+				None
+			),
+			Error::<Test>::InvalidConstraints
+		);
+		assert_noop!(
+			Proxy::add_constrained_proxy(
+				RuntimeOrigin::signed(1),
+				3,
+				ProxyType::Any,
+				0,
+				None,
+				spending_limit(5, 0)
+			),
+			Error::<Test>::InvalidConstraints
+		);
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(1), 3, ProxyType::Any, 0));
+		assert_noop!(
+			Proxy::add_constrained_proxy(
+				RuntimeOrigin::signed(1),
+				3,
+				ProxyType::Any,
+				0,
+				Some(6),
+				None
+			),
+			Error::<Test>::Duplicate
+		);
+	});
+}
+
+#[test]
+fn expired_proxy_is_unusable_and_removable() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			Some(10),
+			None,
+		));
+		let call = Box::new(call_transfer(6, 1));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call.clone()));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert_noop!(
+			Proxy::remove_expired_proxy(RuntimeOrigin::signed(4), 1, 3, ProxyType::Any, 0),
+			Error::<Test>::NotExpired
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call.clone()),
+			Error::<Test>::Expired
+		);
+		assert_noop!(
+			Proxy::announce(RuntimeOrigin::signed(3), 1, [1; 32].into()),
+			Error::<Test>::Expired
+		);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(0),);
+
+		// An unconstrained proxy of the same delegate remains usable.
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(1), 3, ProxyType::JustTransfer, 0));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+
+		// Anyone can clean up the expired proxy.
+		assert_ok!(Proxy::remove_expired_proxy(RuntimeOrigin::signed(4), 1, 3, ProxyType::Any, 0));
+		System::assert_last_event(
+			ProxyEvent::ProxyRemoved {
+				delegator: 1,
+				delegatee: 3,
+				proxy_type: ProxyType::Any,
+				delay: 0,
+			}
+			.into(),
+		);
+		assert_eq!(Proxies::<Test>::get(1).0.len(), 1);
+		assert_eq!(Balances::reserved_balance(1), 2);
+		assert!(!Constraints::<Test>::contains_key(1));
+		assert_noop!(
+			Proxy::remove_expired_proxy(RuntimeOrigin::signed(4), 1, 3, ProxyType::Any, 0),
+			Error::<Test>::NotFound
+		);
+	});
+}
+
+#[test]
+fn spending_limit_is_enforced() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 1000);
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			None,
+			spending_limit(5, 10),
+		));
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(5));
+		assert_eq!(Proxy::remaining_allowance(1, 4, ProxyType::Any, 0), None);
+
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 3))));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(2));
+
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 3))));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(Error::<Test>::SpendingLimitExceeded.into()) }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(2));
+
+		// Calls which do not move funds are not affected.
+		let remark = Box::new(RuntimeCall::System(SystemCall::remark { remark: vec![] }));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, remark));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+
+		// The allowance is restored in the next period.
+		System::set_block_number(11);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(5));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 5))));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert_eq!(Balances::free_balance(6), 8);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(0));
+		assert_eq!(Constraints::<Test>::get(1)[0].1.period_start, 11);
+	});
+}
+
+#[test]
+fn spending_limit_applies_to_batched_calls() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 1000);
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			None,
+			spending_limit(5, 10),
+		));
+		let call = Box::new(RuntimeCall::Utility(UtilityCall::batch_all {
+			calls: vec![call_transfer(6, 3), call_transfer(6, 3)],
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(Error::<Test>::SpendingLimitExceeded.into()) }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 0);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(5));
+
+		// A batch stops at the first call exceeding the allowance, the calls before it are kept.
+		let call = Box::new(RuntimeCall::Utility(UtilityCall::batch {
+			calls: vec![call_transfer(6, 2), call_transfer(6, 2), call_transfer(6, 2)],
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert_eq!(Balances::free_balance(6), 4);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(1));
+	});
+}
+
+#[test]
+fn spending_limit_counts_outflows_only() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 1000);
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			None,
+			spending_limit(5, 10),
+		));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 3))));
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(2));
+
+		// Funds received by the proxied account do not restore the allowance.
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), 1, 5));
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(2));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 3))));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(Error::<Test>::SpendingLimitExceeded.into()) }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 3);
+	});
+}
+
+#[test]
+fn spending_limit_rejects_unknown_calls_and_other_assets() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 1000);
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			None,
+			spending_limit(5, 10),
+		));
+		// Calls moving an unknown amount are rejected.
+		let call = Box::new(RuntimeCall::Balances(BalancesCall::transfer_all {
+			dest: 6,
+			keep_alive: true,
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(Error::<Test>::SpendingLimitExceeded.into()) }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 0);
+
+		// So are the calls moving funds of another asset than the limited one.
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			4,
+			ProxyType::Any,
+			0,
+			None,
+			Some(SpendingLimit { asset: 1, amount: 5, period: 10 }),
+		));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(4), 1, None, Box::new(call_transfer(6, 1))));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(Error::<Test>::SpendingLimitExceeded.into()) }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 0);
+		assert_eq!(Proxy::remaining_allowance(1, 4, ProxyType::Any, 0), Some(5));
+	});
+}
+
+#[test]
+fn constrained_proxy_cannot_add_proxies() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_constrained_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			Some(10),
+			None,
+		));
+		let call = Box::new(RuntimeCall::Proxy(ProxyCall::add_proxy {
+			delegate: 3,
+			proxy_type: ProxyType::Any,
+			delay: 1,
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(SystemError::CallFiltered.into()) }.into(),
+		);
+		let call = Box::new(RuntimeCall::Proxy(ProxyCall::create_pure {
+			proxy_type: ProxyType::Any,
+			delay: 0,
+			index: 0,
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(SystemError::CallFiltered.into()) }.into(),
+		);
+		assert_eq!(Proxies::<Test>::get(1).0.len(), 1);
+	});
+}
@@ -20,8 +20,20 @@ pub use debug_apis::*;
 mod execution_apis;
 pub use execution_apis::*;
 
+mod filter_apis;
+pub use filter_apis::*;
+
 mod health_api;
 pub use health_api::*;
 
 mod polkadot_api;
 pub use polkadot_api::*;
+
+mod pubsub_apis;
+pub use pubsub_apis::*;
+
+mod trace_apis;
+pub use trace_apis::*;
+
+mod txpool_apis;
+pub use txpool_apis::*;
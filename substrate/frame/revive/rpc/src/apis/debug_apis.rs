@@ -32,6 +32,18 @@ pub trait DebugRpc {
 		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Vec<TransactionTrace>>;
 
+	/// Returns the tracing of the execution of a specific block using its hash.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debugtraceblockbyhash>
+	#[method(name = "debug_traceBlockByHash")]
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: Option<TracerConfig>,
+	) -> RpcResult<Vec<TransactionTrace>>;
+
 	/// Returns a transaction's traces by replaying it.
 	///
 	/// ## References
@@ -100,6 +112,15 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		with_timeout(timeout, self.client.trace_block_by_number(block, config)).await
 	}
 
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: Option<TracerConfig>,
+	) -> RpcResult<Vec<TransactionTrace>> {
+		let TracerConfig { config, timeout } = tracer_config.unwrap_or_default();
+		with_timeout(timeout, self.client.trace_block_by_hash(block_hash, config)).await
+	}
+
 	async fn trace_transaction(
 		&self,
 		transaction_hash: H256,
@@ -0,0 +1,94 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum installable filter JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, Extensions};
+
+#[rpc(server, client)]
+pub trait FilterRpc {
+	/// Install a filter on the server, to notify when the state changes (logs).
+	#[method(name = "eth_newFilter", with_extensions)]
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256>;
+
+	/// Install a filter on the server, to notify when a new block arrives.
+	#[method(name = "eth_newBlockFilter", with_extensions)]
+	async fn new_block_filter(&self) -> RpcResult<U256>;
+
+	/// Install a filter on the server, to notify when new pending transactions arrive.
+	#[method(name = "eth_newPendingTransactionFilter", with_extensions)]
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256>;
+
+	/// Polling method for a filter, which returns an array of logs or hashes which occurred since
+	/// last poll.
+	#[method(name = "eth_getFilterChanges", with_extensions)]
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Returns an array of all logs matching the log filter with the given id.
+	#[method(name = "eth_getFilterLogs", with_extensions)]
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults>;
+
+	/// Uninstalls a filter with the given id.
+	#[method(name = "eth_uninstallFilter", with_extensions)]
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool>;
+}
+
+pub struct FilterRpcServerImpl {
+	client: client::Client,
+}
+
+impl FilterRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl FilterRpcServer for FilterRpcServerImpl {
+	async fn new_filter(&self, ext: &Extensions, filter: Filter) -> RpcResult<U256> {
+		Ok(self.client.new_filter(FilterConnection::from_extensions(ext), filter).await?)
+	}
+
+	async fn new_block_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		Ok(self.client.new_block_filter(FilterConnection::from_extensions(ext)).await?)
+	}
+
+	async fn new_pending_transaction_filter(&self, ext: &Extensions) -> RpcResult<U256> {
+		let connection = FilterConnection::from_extensions(ext);
+		Ok(self.client.new_pending_transaction_filter(connection).await?)
+	}
+
+	async fn get_filter_changes(
+		&self,
+		ext: &Extensions,
+		filter_id: U256,
+	) -> RpcResult<FilterResults> {
+		let connection = FilterConnection::from_extensions(ext);
+		Ok(self.client.filter_changes(filter_id, &connection).await?)
+	}
+
+	async fn get_filter_logs(&self, ext: &Extensions, filter_id: U256) -> RpcResult<FilterResults> {
+		let connection = FilterConnection::from_extensions(ext);
+		let logs = self.client.filter_logs(filter_id, &connection).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	async fn uninstall_filter(&self, ext: &Extensions, filter_id: U256) -> RpcResult<bool> {
+		let connection = FilterConnection::from_extensions(ext);
+		Ok(self.client.uninstall_filter(filter_id, &connection).await)
+	}
+}
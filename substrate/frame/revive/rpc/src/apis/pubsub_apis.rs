@@ -0,0 +1,150 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum publish/subscribe JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{
+	core::SubscriptionResult, proc_macros::rpc, PendingSubscriptionSink, SubscriptionMessage,
+	SubscriptionSink,
+};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+#[rpc(server, client)]
+pub trait EthPubSubRpc {
+	/// Subscribe to new heads, logs or pending transactions.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn eth_subscribe(
+		&self,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult;
+}
+
+pub struct EthPubSubRpcServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+/// Forward the items received from `receiver` to the subscription `sink`, until either the
+/// subscriber or the sender goes away.
+async fn pipe_from_receiver<T: Clone>(
+	sink: SubscriptionSink,
+	mut receiver: Receiver<T>,
+	into_items: impl Fn(T) -> Vec<SubscriptionItem>,
+) -> SubscriptionResult {
+	loop {
+		let value = tokio::select! {
+			_ = sink.closed() => return Ok(()),
+			value = receiver.recv() => value,
+		};
+
+		match value {
+			Ok(value) => {
+				for item in into_items(value) {
+					let message = SubscriptionMessage::from_json(&item)?;
+					if sink.send(message).await.is_err() {
+						return Ok(());
+					}
+				}
+			},
+			Err(RecvError::Lagged(skipped)) => {
+				log::debug!(target: LOG_TARGET, "Subscription {:?} lagged, skipped {skipped} notifications", sink.subscription_id());
+			},
+			Err(RecvError::Closed) => return Ok(()),
+		}
+	}
+}
+
+#[async_trait]
+impl EthPubSubRpcServer for EthPubSubRpcServerImpl {
+	async fn eth_subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		if filter.is_some() && kind != SubscriptionKind::Logs {
+			pending
+				.reject(ErrorObjectOwned::owned::<String>(
+					ErrorCode::InvalidParams.code(),
+					format!("{kind:?} subscription does not accept a filter"),
+					None,
+				))
+				.await;
+			return Ok(());
+		}
+
+		match kind {
+			SubscriptionKind::NewHeads => {
+				let receiver = self.client.subscribe_new_heads();
+				let sink = pending.accept().await?;
+				pipe_from_receiver(sink, receiver, |notification| {
+					vec![SubscriptionItem::Header(Box::new(notification.block.clone()))]
+				})
+				.await
+			},
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				if let Err(err) = filter.topic_criteria() {
+					pending
+						.reject(ErrorObjectOwned::owned::<String>(
+							ErrorCode::InvalidParams.code(),
+							err.to_string(),
+							None,
+						))
+						.await;
+					return Ok(());
+				}
+
+				let receiver = self.client.subscribe_new_heads();
+				let sink = pending.accept().await?;
+				pipe_from_receiver(sink, receiver, |notification| {
+					notification
+						.removed_logs
+						.iter()
+						.chain(&notification.logs)
+						.filter(|log| filter.matches_log(log))
+						.cloned()
+						.map(SubscriptionItem::Log)
+						.collect()
+				})
+				.await
+			},
+			SubscriptionKind::NewPendingTransactions => {
+				let receiver = self.client.subscribe_pending_transactions();
+				let sink = pending.accept().await?;
+				pipe_from_receiver(sink, receiver, |hash| {
+					vec![SubscriptionItem::TransactionHash(hash)]
+				})
+				.await
+			},
+		}
+	}
+}
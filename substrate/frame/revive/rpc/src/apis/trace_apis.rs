@@ -0,0 +1,82 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Parity trace JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// Parity `trace_*` JSON-RPC apis, built on top of the call tracer.
+#[rpc(server, client)]
+pub trait TraceRpc {
+	/// Returns the traces of all the transactions of the given block.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_block>
+	#[method(name = "trace_block")]
+	async fn trace_block(&self, block: BlockNumberOrTag) -> RpcResult<Option<Vec<ParityTrace>>>;
+
+	/// Returns the traces matching the given filter.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_filter>
+	#[method(name = "trace_filter")]
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<ParityTrace>>;
+
+	/// Returns the traces of the given transaction.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_transaction>
+	#[method(name = "trace_transaction")]
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<Vec<ParityTrace>>>;
+}
+
+pub struct TraceRpcServerImpl {
+	client: client::Client,
+}
+
+impl TraceRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl TraceRpcServer for TraceRpcServerImpl {
+	async fn trace_block(&self, block: BlockNumberOrTag) -> RpcResult<Option<Vec<ParityTrace>>> {
+		let Some(block) = self.client.block_by_number_or_tag(&block).await? else {
+			return Ok(None);
+		};
+		Ok(Some(self.client.parity_block_traces(&block).await?))
+	}
+
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<ParityTrace>> {
+		Ok(self.client.parity_filter_traces(filter).await?)
+	}
+
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<Vec<ParityTrace>>> {
+		Ok(self.client.parity_transaction_traces(transaction_hash).await?)
+	}
+}
@@ -0,0 +1,79 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Transaction pool JSON-RPC methods.
+
+use crate::*;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// Geth `txpool_*` JSON-RPC apis, exposing the Ethereum transactions of the node transaction pool.
+///
+/// The node only exposes the transactions of its pool that are ready to be included in a block,
+/// so all the transactions are reported as pending and no transaction is ever reported as queued.
+#[rpc(server, client)]
+pub trait TxPoolRpc {
+	/// Returns the number of pending and queued transactions. The number of queued transactions
+	/// is always 0.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool#txpool-status>
+	#[method(name = "txpool_status")]
+	async fn status(&self) -> RpcResult<TxPoolStatus>;
+
+	/// Returns the pending and queued transactions, grouped by sender and nonce. The queued
+	/// transactions are always empty.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool#txpool-content>
+	#[method(name = "txpool_content")]
+	async fn content(&self) -> RpcResult<TxPoolContent>;
+
+	/// Returns a textual summary of the pending and queued transactions, grouped by sender and
+	/// nonce. The queued transactions are always empty.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool#txpool-inspect>
+	#[method(name = "txpool_inspect")]
+	async fn inspect(&self) -> RpcResult<TxPoolInspect>;
+}
+
+pub struct TxPoolRpcServerImpl {
+	client: client::Client,
+}
+
+impl TxPoolRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl TxPoolRpcServer for TxPoolRpcServerImpl {
+	async fn status(&self) -> RpcResult<TxPoolStatus> {
+		Ok(self.client.txpool_content().await?.status())
+	}
+
+	async fn content(&self) -> RpcResult<TxPoolContent> {
+		Ok(self.client.txpool_content().await?)
+	}
+
+	async fn inspect(&self) -> RpcResult<TxPoolInspect> {
+		Ok(self.client.txpool_content().await?.inspect())
+	}
+}
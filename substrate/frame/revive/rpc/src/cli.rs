@@ -17,9 +17,11 @@
 //! The Ethereum JSON-RPC server.
 use crate::{
 	client::{connect, Client, SubscriptionType, SubstrateBlockNumber},
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, PolkadotRpcServer,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubRpcServer, EthPubSubRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, FilterProvider, FilterRpcServer, FilterRpcServerImpl, PolkadotRpcServer,
 	PolkadotRpcServerImpl, ReceiptExtractor, ReceiptProvider, SubxtBlockInfoProvider,
-	SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
+	SystemHealthRpcServer, SystemHealthRpcServerImpl, TraceRpcServer, TraceRpcServerImpl,
+	TxPoolRpcServer, TxPoolRpcServerImpl, LOG_TARGET,
 };
 use clap::Parser;
 use futures::{future::BoxFuture, pin_mut, FutureExt};
@@ -30,6 +32,7 @@ use sc_service::{
 	start_rpc_servers, TaskManager,
 };
 use sqlx::sqlite::SqlitePoolOptions;
+use std::time::Duration;
 
 // Default port if --prometheus-port is not specified
 const DEFAULT_PROMETHEUS_PORT: u16 = 9616;
@@ -77,6 +80,20 @@ pub struct CliCommand {
 	#[clap(flatten)]
 	pub prometheus_params: PrometheusParams,
 
+	/// The number of seconds after which a filter installed with `eth_newFilter`,
+	/// `eth_newBlockFilter` or `eth_newPendingTransactionFilter` is removed if it is not polled.
+	#[clap(long, default_value = "300")]
+	pub filter_idle_timeout: u64,
+
+	/// The maximum number of filters a single connection can install.
+	#[clap(long, default_value = "128")]
+	pub max_filters_per_connection: usize,
+
+	/// The maximum number of filters installed by all connections together, including the
+	/// filters installed over HTTP.
+	#[clap(long, default_value = "4096")]
+	pub max_filters: usize,
+
 	/// By default, the node rejects any transaction that's unprotected (i.e., that doesn't have a
 	/// chain-id). If the user wishes the submit such a transaction then they can use this flag to
 	/// instruct the RPC to ignore this check.
@@ -173,6 +190,9 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		index_last_n_blocks,
 		shared_params,
 		allow_unprotected_txs,
+		filter_idle_timeout,
+		max_filters_per_connection,
+		max_filters,
 		..
 	} = cmd;
 
@@ -209,7 +229,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 	let tokio_handle = tokio_runtime.handle();
 	let mut task_manager = TaskManager::new(tokio_handle.clone(), prometheus_registry)?;
 
-	let client = build_client(
+	let mut client = build_client(
 		tokio_handle,
 		cache_size,
 		earliest_receipt_block,
@@ -219,6 +239,11 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		rpc_config.max_response_size * 1024 * 1024,
 		tokio_runtime.block_on(async { Signals::capture() })?,
 	)?;
+	client.set_filter_provider(FilterProvider::new(
+		Duration::from_secs(filter_idle_timeout),
+		max_filters_per_connection,
+		max_filters,
+	));
 
 	// Prometheus metrics.
 	if let Some(PrometheusConfig { port, registry }) = prometheus_config.clone() {
@@ -243,6 +268,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 			let mut futures: Vec<BoxFuture<'_, Result<(), _>>> = vec![
 				Box::pin(client.subscribe_and_cache_new_blocks(SubscriptionType::BestBlocks)),
 				Box::pin(client.subscribe_and_cache_new_blocks(SubscriptionType::FinalizedBlocks)),
+				Box::pin(client.watch_pending_transactions()),
 			];
 
 			if let Some(index_last_n_blocks) = index_last_n_blocks {
@@ -284,12 +310,20 @@ fn rpc_module(
 
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client.clone()).into_rpc();
+	let pubsub_api = EthPubSubRpcServerImpl::new(client.clone()).into_rpc();
+	let filter_api = FilterRpcServerImpl::new(client.clone()).into_rpc();
+	let trace_api = TraceRpcServerImpl::new(client.clone()).into_rpc();
+	let txpool_api = TxPoolRpcServerImpl::new(client.clone()).into_rpc();
 	let polkadot_api = PolkadotRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(filter_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(trace_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(txpool_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module
 		.merge(polkadot_api)
 		.map_err(|e| sc_service::Error::Application(e.into()))?;
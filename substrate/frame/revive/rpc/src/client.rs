@@ -22,22 +22,32 @@ pub(crate) mod storage_api;
 
 use crate::{
 	subxt_client::{self, revive::calls::types::EthTransact, SrcChainConfig},
-	BlockInfoProvider, BlockTag, FeeHistoryProvider, ReceiptProvider, SubxtBlockInfoProvider,
-	TracerType, TransactionInfo,
+	BlockInfoProvider, BlockTag, FeeHistoryProvider, FilterConnection, FilterPoll, FilterProvider,
+	ReceiptProvider, SubxtBlockInfoProvider, TracerType, TransactionInfo,
 };
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObjectOwned};
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, FeeHistoryResult,
-		Filter, GenericTransaction, HashesOrTransactionInfos, Log, ReceiptInfo, SyncingProgress,
-		SyncingStatus, Trace, TransactionSigned, TransactionTrace, H256,
+		decode_revert_reason, proof, AccessListEntry, AccessListResult, AccountProof, Block,
+		BlockNumberOrTag, BlockNumberOrTagOrHash, Bytes, CallTracerConfig, FeeHistoryResult,
+		Filter, FilterResults, GenericTransaction, HashesOrTransactionInfos, Log, ParityTrace,
+		PrestateTrace, PrestateTracerConfig, ReceiptInfo, StorageProof, SyncingProgress,
+		SyncingStatus, Trace, TraceFilter, TransactionSigned, TransactionTrace, TxPoolContent,
+		TxPoolTransaction, H160, H256, U256,
 	},
 	EthTransactError,
 };
 use runtime_api::RuntimeApi;
+use sc_rpc_api::state::ReadProof;
+use sp_core::keccak_256;
 use sp_runtime::traits::Block as BlockT;
 use sp_weights::Weight;
-use std::{ops::Range, sync::Arc, time::Duration};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	ops::Range,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 use storage_api::StorageApi;
 use subxt::{
 	backend::{
@@ -130,12 +140,50 @@ pub enum ClientError {
 	/// Receipt data length mismatch.
 	#[error("Receipt data length mismatch")]
 	ReceiptDataLengthMismatch,
+	/// The filter was not found, or has expired.
+	#[error("filter not found")]
+	FilterNotFound,
+	/// The connection installed the maximum number of filters.
+	#[error("too many filters installed, the limit per connection is {0}")]
+	FilterLimitReached(usize),
+	/// The maximum number of filters is installed.
+	#[error("too many filters installed, the limit is {0}")]
+	TooManyFilters(usize),
+	/// The requested block range is too large.
+	#[error("block range too large, the maximum is {0} blocks")]
+	BlockRangeTooLarge(SubstrateBlockNumber),
+	/// The execution trace has more steps than a single request may return.
+	#[error("execution trace too long, set a `limit` of at most {0} steps")]
+	ExecutionTraceTooLong(u64),
+	/// The runtime does not implement the runtime API method needed by the request.
+	#[error("{0} is not supported by the runtime")]
+	UnsupportedRuntimeApi(&'static str),
 }
 const LOG_TARGET: &str = "eth-rpc::client";
 
 const REVERT_CODE: i32 = 3;
 
 const NOTIFIER_CAPACITY: usize = 16;
+
+/// The capacity of the channels used to notify `eth_subscribe` subscribers.
+const SUBSCRIPTION_CAPACITY: usize = 256;
+
+/// The number of best blocks kept to report the logs removed by a reorg.
+const MAX_REORG_DEPTH: usize = 64;
+
+/// The interval at which the transaction pool of the node is polled for new pending transactions,
+/// while there are subscribers or filters interested in them.
+const PENDING_TRANSACTIONS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum number of steps of a transaction's execution trace returned by a single request.
+///
+/// Execution traces are collected in a single runtime API call and returned at once, the cap
+/// bounds the memory they use in the runtime and in the RPC server.
+const MAX_EXECUTION_TRACE_STEPS: u64 = 100_000;
+
+/// The maximum number of blocks traced by a single `trace_filter` request.
+const MAX_TRACE_FILTER_BLOCK_RANGE: SubstrateBlockNumber = 1_000;
+
 impl From<ClientError> for ErrorObjectOwned {
 	fn from(err: ClientError) -> Self {
 		match err {
@@ -164,6 +212,25 @@ impl From<ClientError> for ErrorObjectOwned {
 	}
 }
 
+/// A notification sent to `eth_subscribe` subscribers for every new best block.
+#[derive(Debug, Clone)]
+pub struct BlockNotification {
+	/// The Ethereum block.
+	pub block: Block,
+	/// The logs emitted by the Ethereum transactions of the block.
+	pub logs: Vec<Log>,
+	/// The logs of the previous best blocks that were retracted by this block, marked as removed.
+	pub removed_logs: Vec<Log>,
+}
+
+/// A best block, and the logs it emitted.
+#[derive(Debug)]
+struct NotifiedBlock {
+	hash: SubstrateBlockHash,
+	number: SubstrateBlockNumber,
+	logs: Vec<Log>,
+}
+
 /// A client connect to a node and maintains a cache of the last `CACHE_SIZE` blocks.
 #[derive(Clone)]
 pub struct Client {
@@ -173,6 +240,7 @@ pub struct Client {
 	receipt_provider: ReceiptProvider,
 	block_provider: SubxtBlockInfoProvider,
 	fee_history_provider: FeeHistoryProvider,
+	filter_provider: FilterProvider,
 	chain_id: u64,
 	max_block_weight: Weight,
 	/// Whether the node has automine enabled.
@@ -181,6 +249,14 @@ pub struct Client {
 	block_notifier: Option<tokio::sync::broadcast::Sender<H256>>,
 	/// A lock to ensure only one subscription can perform write operations at a time.
 	subscription_lock: Arc<Mutex<()>>,
+	/// A notifier, that informs `eth_subscribe` subscribers of new best blocks.
+	new_heads_notifier: tokio::sync::broadcast::Sender<Arc<BlockNotification>>,
+	/// A notifier, that informs `eth_subscribe` subscribers of submitted transactions.
+	pending_transactions_notifier: tokio::sync::broadcast::Sender<H256>,
+	/// The last best blocks, used to find the logs removed by a reorg.
+	best_blocks: Arc<Mutex<VecDeque<NotifiedBlock>>>,
+	/// The pending transactions that subscribers and filters were notified of, and when.
+	seen_pending_transactions: Arc<Mutex<HashMap<H256, Instant>>>,
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -197,6 +273,36 @@ async fn max_block_weight(api: &OnlineClient<SrcChainConfig>) -> Result<Weight,
 	Ok(max_block.0)
 }
 
+/// Cap the steps captured by the execution tracer to [`MAX_EXECUTION_TRACE_STEPS`].
+///
+/// Unless a lower `limit` is requested, one more step than the cap is captured so that
+/// [`ensure_execution_trace_capped`] can tell whether the trace was cut.
+fn capped_tracer_config(config: TracerType) -> TracerType {
+	let TracerType::ExecutionTracer(config) = config else { return config };
+	let mut config = config.unwrap_or_default();
+
+	if config.limit.map_or(true, |limit| limit > MAX_EXECUTION_TRACE_STEPS) {
+		config.limit = Some(MAX_EXECUTION_TRACE_STEPS + 1);
+	}
+
+	config.into()
+}
+
+/// Refuse execution traces cut by [`capped_tracer_config`].
+fn ensure_execution_trace_capped(trace: &Trace) -> Result<(), ClientError> {
+	match trace {
+		Trace::Execution(trace) if trace.struct_logs.len() as u64 > MAX_EXECUTION_TRACE_STEPS => {
+			Err(ClientError::ExecutionTraceTooLong(MAX_EXECUTION_TRACE_STEPS))
+		},
+		_ => Ok(()),
+	}
+}
+
+/// The tracer configuration used to build the Parity traces.
+fn parity_tracer_config() -> TracerType {
+	CallTracerConfig { with_logs: false, only_top_call: false }.into()
+}
+
 /// Get the automine status from the node.
 async fn get_automine(rpc_client: &RpcClient) -> bool {
 	match rpc_client.request::<bool>("getAutomine", rpc_params![]).await {
@@ -252,12 +358,17 @@ impl Client {
 			receipt_provider,
 			block_provider,
 			fee_history_provider: FeeHistoryProvider::default(),
+			filter_provider: FilterProvider::default(),
 			chain_id,
 			max_block_weight,
 			automine,
 			block_notifier: automine
 				.then(|| tokio::sync::broadcast::channel::<H256>(NOTIFIER_CAPACITY).0),
 			subscription_lock: Arc::new(Mutex::new(())),
+			new_heads_notifier: tokio::sync::broadcast::channel(SUBSCRIPTION_CAPACITY).0,
+			pending_transactions_notifier: tokio::sync::broadcast::channel(SUBSCRIPTION_CAPACITY).0,
+			best_blocks: Default::default(),
+			seen_pending_transactions: Default::default(),
 		};
 
 		Ok(client)
@@ -273,6 +384,11 @@ impl Client {
 		self.block_notifier = notifier;
 	}
 
+	/// Sets the filter provider, used to keep track of installed filters.
+	pub fn set_filter_provider(&mut self, filter_provider: FilterProvider) {
+		self.filter_provider = filter_provider;
+	}
+
 	/// Subscribe to past blocks executing the callback for each block in `range`.
 	async fn subscribe_past_blocks<F, Fut>(
 		&self,
@@ -369,6 +485,8 @@ impl Client {
 		log::info!(target: LOG_TARGET, "🔌 Subscribing to new blocks ({subscription_type:?})");
 		self.subscribe_new_blocks(subscription_type, |block| async {
 			let hash = block.hash();
+			let number = block.number();
+			let parent_hash = block.header().parent_hash;
 			let evm_block = self.runtime_api(hash).eth_block().await?;
 			let (_, receipts): (Vec<_>, Vec<_>) = self
 				.receipt_provider
@@ -380,6 +498,23 @@ impl Client {
 			self.block_provider.update_latest(Arc::new(block), subscription_type).await;
 			self.fee_history_provider.update_fee_history(&evm_block, &receipts).await;
 
+			if subscription_type == SubscriptionType::BestBlocks {
+				self.filter_provider.on_new_block(evm_block.hash).await;
+			}
+
+			if subscription_type == SubscriptionType::BestBlocks {
+				let logs: Vec<_> = receipts.into_iter().flat_map(|receipt| receipt.logs).collect();
+				let removed_logs =
+					self.update_best_blocks(hash, number, parent_hash, logs.clone()).await;
+				if self.new_heads_notifier.receiver_count() > 0 {
+					let _ = self.new_heads_notifier.send(Arc::new(BlockNotification {
+						block: evm_block,
+						logs,
+						removed_logs,
+					}));
+				}
+			}
+
 			// Only broadcast for best blocks to avoid duplicate notifications.
 			match (subscription_type, &self.block_notifier) {
 				(SubscriptionType::BestBlocks, Some(sender)) if sender.receiver_count() > 0 => {
@@ -392,6 +527,63 @@ impl Client {
 		.await
 	}
 
+	/// Record a new best block, and return the logs of the previous best blocks that are not part
+	/// of its chain, marked as removed.
+	async fn update_best_blocks(
+		&self,
+		hash: SubstrateBlockHash,
+		number: SubstrateBlockNumber,
+		parent_hash: SubstrateBlockHash,
+		logs: Vec<Log>,
+	) -> Vec<Log> {
+		let mut best_blocks = self.best_blocks.lock().await;
+		let mut removed_logs = Vec::new();
+
+		if best_blocks.back().is_some_and(|block| block.number < number.saturating_sub(1)) {
+			// Some blocks were missed, we can't tell which of the previous ones were retracted.
+			best_blocks.clear();
+		}
+
+		// Walk back both chains until the common ancestor, the blocks of the previous best chain
+		// that are not ancestors of the new best block are retracted.
+		let (mut ancestor_hash, mut ancestor_number) = (parent_hash, number.saturating_sub(1));
+		while let Some((last_hash, last_number)) =
+			best_blocks.back().map(|block| (block.hash, block.number))
+		{
+			if last_hash == ancestor_hash {
+				break;
+			}
+
+			if last_number >= ancestor_number {
+				if let Some(retracted) = best_blocks.pop_back() {
+					removed_logs.extend(retracted.logs);
+				}
+			}
+
+			if last_number <= ancestor_number {
+				let parent = match self.block_provider.block_by_hash(&ancestor_hash).await {
+					Ok(Some(block)) if ancestor_number > 0 => block.header().parent_hash,
+					_ => {
+						best_blocks.clear();
+						break;
+					},
+				};
+				ancestor_hash = parent;
+				ancestor_number -= 1;
+			}
+		}
+
+		best_blocks.push_back(NotifiedBlock { hash, number, logs });
+		if best_blocks.len() > MAX_REORG_DEPTH {
+			best_blocks.pop_front();
+		}
+
+		for log in &mut removed_logs {
+			log.removed = true;
+		}
+		removed_logs
+	}
+
 	/// Cache old blocks up to the given block number.
 	pub async fn subscribe_and_cache_blocks(
 		&self,
@@ -477,6 +669,89 @@ impl Client {
 		Ok(())
 	}
 
+	/// Get the EVM transactions in the transaction pool of the node.
+	///
+	/// The node only exposes the transactions that are ready to be included in a block, so all
+	/// the transactions are reported as pending and the queued transactions are always empty.
+	pub async fn txpool_content(&self) -> Result<TxPoolContent, ClientError> {
+		let gas_price = self.runtime_api(self.latest_block().await.hash()).gas_price().await?;
+		let pending = self
+			.pool_transactions()
+			.await?
+			.into_iter()
+			.filter_map(|(hash, signed_tx)| {
+				let from = signed_tx.recover_eth_address().ok()?;
+				let transaction = GenericTransaction::from_signed(signed_tx, gas_price, Some(from));
+				Some(TxPoolTransaction { hash, transaction, ..Default::default() })
+			})
+			.collect();
+
+		Ok(TxPoolContent::new(pending, vec![]))
+	}
+
+	/// Get the hashes and payloads of the EVM transactions in the transaction pool of the node.
+	async fn pool_transactions(&self) -> Result<Vec<(H256, TransactionSigned)>, ClientError> {
+		let extrinsics: Vec<Bytes> =
+			self.rpc_client.request("author_pendingExtrinsics", rpc_params![]).await?;
+		let metadata = self.api.metadata();
+
+		Ok(extrinsics
+			.into_iter()
+			.filter_map(|Bytes(extrinsic)| {
+				let extrinsics =
+					subxt::ext::subxt_core::blocks::Extrinsics::<SrcChainConfig>::decode_from(
+						vec![extrinsic],
+						metadata.clone(),
+					)
+					.ok()?;
+				let call = extrinsics.find_first::<EthTransact>().ok()??.value;
+				let hash = H256(keccak_256(&call.payload));
+				let signed_tx = TransactionSigned::decode(&call.payload).ok()?;
+				Some((hash, signed_tx))
+			})
+			.collect())
+	}
+
+	/// Poll the transaction pool of the node, and notify subscribers and filters of the pending
+	/// transactions they were not notified of yet.
+	///
+	/// This catches the transactions that were not submitted through this server.
+	pub async fn watch_pending_transactions(&self) -> Result<(), ClientError> {
+		let mut interval = tokio::time::interval(PENDING_TRANSACTIONS_POLL_INTERVAL);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+		loop {
+			interval.tick().await;
+			if self.pending_transactions_notifier.receiver_count() == 0 &&
+				!self.filter_provider.has_pending_transaction_filters().await
+			{
+				self.seen_pending_transactions.lock().await.clear();
+				continue;
+			}
+
+			let polled_at = Instant::now();
+			let hashes = match self.pool_transactions().await {
+				Ok(transactions) => {
+					transactions.into_iter().map(|(hash, _)| hash).collect::<HashSet<_>>()
+				},
+				Err(err) => {
+					log::debug!(target: LOG_TARGET, "Failed to fetch pending transactions: {err:?}");
+					continue;
+				},
+			};
+
+			// Forget the transactions that left the pool, unless they were submitted after the
+			// pool was polled.
+			self.seen_pending_transactions
+				.lock()
+				.await
+				.retain(|hash, seen_at| hashes.contains(hash) || *seen_at > polled_at);
+
+			for hash in hashes {
+				self.notify_pending_transaction(hash).await;
+			}
+		}
+	}
+
 	/// Get an EVM transaction receipt by hash.
 	pub async fn receipt(&self, tx_hash: &H256) -> Option<ReceiptInfo> {
 		self.receipt_provider.receipt_by_hash(tx_hash).await
@@ -502,6 +777,101 @@ impl Client {
 		Ok(sync_state)
 	}
 
+	/// Get the account and storage proofs of the given address at the given block.
+	///
+	/// The account proof is a storage proof of the [`pallet_revive::AccountInfo`] of the address,
+	/// of its account mapping and of the `frame_system` account it is mapped to, which prove the
+	/// balance and the nonce, and of the root of its storage child trie for contracts. The storage
+	/// proofs are child storage proofs of each requested slot against that root.
+	pub async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccountProof, ClientError> {
+		let hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(hash);
+		let storage_api = self.storage_api(hash);
+
+		let (balance, nonce) =
+			tokio::try_join!(runtime_api.balance(address), runtime_api.nonce(address))?;
+		let contract_info = match storage_api.get_contract_info(&address).await {
+			Ok(info) => Some(info),
+			Err(ClientError::ContractNotFound) => None,
+			Err(err) => return Err(err),
+		};
+		let account_id = storage_api.get_account_id(&address).await?;
+
+		let storage = self.api.storage();
+		let revive = subxt_client::storage().revive();
+		let mut keys = vec![
+			to_hex(storage.address_bytes(&revive.account_info_of(address.0.into()))?),
+			to_hex(storage.address_bytes(&revive.original_account(address.0.into()))?),
+			to_hex(storage.address_bytes(&subxt_client::storage().system().account(account_id))?),
+		];
+		let child_key = contract_info.as_ref().map(|info| proof::storage_root_key(&info.trie_id.0));
+		if let Some(child_key) = &child_key {
+			keys.push(to_hex(child_key));
+		}
+
+		let account_proof: ReadProof<H256> =
+			self.rpc_client.request("state_getReadProof", rpc_params![keys, hash]).await?;
+
+		let (code_hash, storage_hash, storage_proof) = match (contract_info, child_key) {
+			(Some(info), Some(child_key)) => {
+				let storage_hash =
+					storage_api.get_child_storage_root(&child_key).await?.unwrap_or_default();
+				let child_key = to_hex(&child_key);
+
+				let storage_proof =
+					futures::future::try_join_all(storage_keys.into_iter().map(|key| {
+						let runtime_api = &runtime_api;
+						let child_key = &child_key;
+						async move {
+							let slot_key = to_hex(proof::storage_slot_key(key.to_big_endian()));
+							let proof: ReadProof<H256> = self
+								.rpc_client
+								.request(
+									"state_getChildReadProof",
+									rpc_params![child_key, vec![slot_key], hash],
+								)
+								.await?;
+							let value = runtime_api
+								.get_storage(address, key.to_big_endian())
+								.await?
+								.map(|value| {
+									U256::from_big_endian(&value[value.len().saturating_sub(32)..])
+								})
+								.unwrap_or_default();
+							let proof = proof.proof.into_iter().map(|p| Bytes(p.0)).collect();
+
+							Ok::<_, ClientError>(StorageProof { key, value, proof })
+						}
+					}))
+					.await?;
+
+				(H256(info.code_hash.0), storage_hash, storage_proof)
+			},
+			_ => {
+				let storage_proofs = storage_keys
+					.into_iter()
+					.map(|key| StorageProof { key, ..Default::default() })
+					.collect();
+				(H256(keccak_256(&[])), H256::zero(), storage_proofs)
+			},
+		};
+
+		Ok(AccountProof {
+			address,
+			account_proof: account_proof.proof.into_iter().map(|p| Bytes(p.0)).collect(),
+			balance,
+			code_hash,
+			nonce,
+			storage_hash,
+			storage_proof,
+		})
+	}
+
 	/// Get the syncing status of the chain.
 	pub async fn syncing(&self) -> Result<SyncingStatus, ClientError> {
 		let health = self.rpc.system_health().await?;
@@ -532,6 +902,14 @@ impl Client {
 			.await
 	}
 
+	/// Get the receipts of all the EVM transactions of the given block.
+	pub async fn receipts_by_block_hash(
+		&self,
+		block_hash: &SubstrateBlockHash,
+	) -> Result<Option<Vec<ReceiptInfo>>, ClientError> {
+		self.receipt_provider.receipts_by_block_hash(block_hash).await
+	}
+
 	pub async fn signed_tx_by_hash(&self, tx_hash: &H256) -> Option<TransactionSigned> {
 		self.receipt_provider.signed_tx_by_hash(tx_hash).await
 	}
@@ -673,10 +1051,35 @@ impl Client {
 		}
 
 		let block_hash = self.block_hash_for_tag(at.into()).await?;
+		self.trace_block(block_hash, config).await
+	}
+
+	/// Get the transaction traces for the block with the given Ethereum hash.
+	pub async fn trace_block_by_hash(
+		&self,
+		ethereum_hash: H256,
+		config: TracerType,
+	) -> Result<Vec<TransactionTrace>, ClientError> {
+		let block = self
+			.block_by_ethereum_hash(&ethereum_hash)
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		self.trace_block(block.hash(), config).await
+	}
+
+	/// Get the transaction traces for the given substrate block.
+	async fn trace_block(
+		&self,
+		block_hash: H256,
+		config: TracerType,
+	) -> Result<Vec<TransactionTrace>, ClientError> {
 		let block = self.tracing_block(block_hash).await?;
 		let parent_hash = block.header().parent_hash;
 		let runtime_api = RuntimeApi::new(self.api.runtime_api().at(parent_hash));
-		let traces = runtime_api.trace_block(block, config.clone()).await?;
+		let traces = runtime_api.trace_block(block, capped_tracer_config(config)).await?;
+		for (_, trace) in &traces {
+			ensure_execution_trace_capped(trace)?;
+		}
 
 		let mut hashes = self
 			.receipt_provider
@@ -707,7 +1110,11 @@ impl Client {
 		let parent_hash = block.header.parent_hash;
 		let runtime_api = self.runtime_api(parent_hash);
 
-		runtime_api.trace_tx(block, transaction_index as u32, config).await
+		let trace = runtime_api
+			.trace_tx(block, transaction_index as u32, capped_tracer_config(config))
+			.await?;
+		ensure_execution_trace_capped(&trace)?;
+		Ok(trace)
 	}
 
 	/// Get the transaction traces for the given block.
@@ -719,7 +1126,170 @@ impl Client {
 	) -> Result<Trace, ClientError> {
 		let block_hash = self.block_hash_for_tag(block).await?;
 		let runtime_api = self.runtime_api(block_hash);
-		runtime_api.trace_call(transaction, config).await
+		let trace = runtime_api.trace_call(transaction, capped_tracer_config(config)).await?;
+		ensure_execution_trace_capped(&trace)?;
+		Ok(trace)
+	}
+
+	/// Get the flat Parity traces of the transactions of the given block.
+	///
+	/// Fails with [`ClientError::EthereumBlockNotFound`] if the Ethereum hash of the block is not
+	/// known.
+	pub async fn parity_block_traces(
+		&self,
+		block: &SubstrateBlock,
+	) -> Result<Vec<ParityTrace>, ClientError> {
+		let block_hash = block.hash();
+		let ethereum_hash = self
+			.resolve_ethereum_hash(&block_hash)
+			.await
+			.ok_or(ClientError::EthereumBlockNotFound)?;
+		let traces = self.trace_block(block_hash, parity_tracer_config()).await?;
+
+		let mut parity_traces = Vec::new();
+		for (position, TransactionTrace { tx_hash, trace }) in traces.into_iter().enumerate() {
+			let Trace::Call(trace) = trace else { return Err(ClientError::ConversionFailed) };
+			parity_traces.extend(trace.into_parity_traces(
+				ethereum_hash,
+				block.number().into(),
+				tx_hash,
+				position as u64,
+			));
+		}
+
+		Ok(parity_traces)
+	}
+
+	/// Get the flat Parity traces of the given transaction.
+	pub async fn parity_transaction_traces(
+		&self,
+		transaction_hash: H256,
+	) -> Result<Option<Vec<ParityTrace>>, ClientError> {
+		let Some(receipt) = self.receipt(&transaction_hash).await else { return Ok(None) };
+		let Trace::Call(trace) =
+			self.trace_transaction(transaction_hash, parity_tracer_config()).await?
+		else {
+			return Err(ClientError::ConversionFailed);
+		};
+
+		let block_number =
+			receipt.block_number.try_into().map_err(|_| ClientError::ConversionFailed)?;
+		let position = receipt
+			.transaction_index
+			.try_into()
+			.map_err(|_| ClientError::ConversionFailed)?;
+		Ok(Some(trace.into_parity_traces(
+			receipt.block_hash,
+			block_number,
+			transaction_hash,
+			position,
+		)))
+	}
+
+	/// Get the flat Parity traces matching the given filter.
+	pub async fn parity_filter_traces(
+		&self,
+		filter: TraceFilter,
+	) -> Result<Vec<ParityTrace>, ClientError> {
+		let block_number = |tag: &Option<BlockNumberOrTag>| {
+			let tag = tag.clone().unwrap_or_default();
+			async move {
+				self.block_by_number_or_tag(&tag)
+					.await?
+					.map(|block| block.number())
+					.ok_or(ClientError::BlockNotFound)
+			}
+		};
+		let from_block = block_number(&filter.from_block).await?;
+		let to_block = block_number(&filter.to_block).await?;
+
+		let range = to_block.saturating_sub(from_block).saturating_add(1);
+		if range > MAX_TRACE_FILTER_BLOCK_RANGE {
+			return Err(ClientError::BlockRangeTooLarge(MAX_TRACE_FILTER_BLOCK_RANGE));
+		}
+
+		let mut skip = filter.after.unwrap_or_default();
+		let count = filter.count.unwrap_or(u64::MAX);
+		let mut traces = Vec::new();
+		for number in from_block..=to_block {
+			let Some(block) = self.block_by_number(number).await? else { continue };
+			let block_traces = match self.parity_block_traces(&block).await {
+				Ok(block_traces) => block_traces,
+				// Blocks without a known Ethereum hash have no traces to report.
+				Err(ClientError::EthereumBlockNotFound) => {
+					log::debug!(target: LOG_TARGET, "Skip block #{number} without Ethereum hash");
+					continue;
+				},
+				Err(err) => return Err(err),
+			};
+			for trace in block_traces {
+				if !filter.matches(&trace) {
+					continue;
+				}
+				if skip > 0 {
+					skip -= 1;
+					continue;
+				}
+				if traces.len() as u64 >= count {
+					return Ok(traces);
+				}
+				traces.push(trace);
+			}
+		}
+
+		Ok(traces)
+	}
+
+	/// Create an EIP-2930 access list for the given transaction.
+	///
+	/// The accounts and storage slots touched by the transaction are collected by tracing it with
+	/// the prestate tracer. The transaction is then dry-run with the resulting access list to
+	/// compute the gas it uses.
+	pub async fn create_access_list(
+		&self,
+		mut transaction: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccessListResult, ClientError> {
+		let block_hash = self.block_hash_for_tag(block.clone()).await?;
+		let runtime_api = self.runtime_api(block_hash);
+
+		let config = PrestateTracerConfig { disable_code: true, ..Default::default() };
+		let Trace::Prestate(PrestateTrace::Prestate(accounts)) =
+			runtime_api.trace_call(transaction.clone(), config.into()).await?
+		else {
+			return Err(ClientError::ConversionFailed);
+		};
+
+		// The sender and the recipient are always accessed, so they are only included if some of
+		// their storage slots are accessed.
+		let access_list = accounts
+			.into_iter()
+			.filter_map(|(address, info)| {
+				let storage_keys = info
+					.storage
+					.into_keys()
+					.filter(|key| key.0.len() == H256::len_bytes())
+					.map(|key| H256::from_slice(&key.0))
+					.collect::<Vec<_>>();
+				let always_accessed =
+					Some(address) == transaction.from || Some(address) == transaction.to;
+				(!storage_keys.is_empty() || !always_accessed)
+					.then_some(AccessListEntry { address, storage_keys })
+			})
+			.collect::<Vec<_>>();
+
+		transaction.access_list = Some(access_list.clone());
+		match runtime_api.dry_run(transaction, block).await {
+			Ok(dry_run) => {
+				Ok(AccessListResult { access_list, error: None, gas_used: dry_run.eth_gas })
+			},
+			Err(err @ ClientError::TransactError(_)) => Ok(AccessListResult {
+				access_list,
+				error: Some(err.to_string()),
+				gas_used: U256::zero(),
+			}),
+			Err(err) => Err(err),
+		}
 	}
 
 	/// Get the EVM block for the given Substrate block.
@@ -777,6 +1347,35 @@ impl Client {
 		self.block_notifier.clone()
 	}
 
+	/// Subscribe to new best blocks, along with the logs they emitted.
+	pub fn subscribe_new_heads(&self) -> tokio::sync::broadcast::Receiver<Arc<BlockNotification>> {
+		self.new_heads_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of the transactions added to the transaction pool.
+	pub fn subscribe_pending_transactions(&self) -> tokio::sync::broadcast::Receiver<H256> {
+		self.pending_transactions_notifier.subscribe()
+	}
+
+	/// Notify subscribers and filters that a transaction was submitted to the transaction pool,
+	/// unless they were already notified of it.
+	pub async fn notify_pending_transaction(&self, hash: H256) {
+		if self
+			.seen_pending_transactions
+			.lock()
+			.await
+			.insert(hash, Instant::now())
+			.is_some()
+		{
+			return;
+		}
+
+		self.filter_provider.on_pending_transaction(hash).await;
+		if self.pending_transactions_notifier.receiver_count() > 0 {
+			let _ = self.pending_transactions_notifier.send(hash);
+		}
+	}
+
 	/// Get the logs matching the given filter.
 	pub async fn logs(&self, filter: Option<Filter>) -> Result<Vec<Log>, ClientError> {
 		let logs =
@@ -784,6 +1383,62 @@ impl Client {
 		Ok(logs)
 	}
 
+	/// Install a new log filter, and return its id.
+	pub async fn new_filter(
+		&self,
+		connection: FilterConnection,
+		filter: Filter,
+	) -> Result<U256, ClientError> {
+		filter.topic_criteria().map_err(|err| anyhow::anyhow!(err))?;
+		let latest_block = self.block_number().await?;
+		self.filter_provider.new_filter(connection, filter, latest_block).await
+	}
+
+	/// Install a new block filter, and return its id.
+	pub async fn new_block_filter(
+		&self,
+		connection: FilterConnection,
+	) -> Result<U256, ClientError> {
+		self.filter_provider.new_block_filter(connection).await
+	}
+
+	/// Install a new pending transaction filter, and return its id.
+	pub async fn new_pending_transaction_filter(
+		&self,
+		connection: FilterConnection,
+	) -> Result<U256, ClientError> {
+		self.filter_provider.new_pending_transaction_filter(connection).await
+	}
+
+	/// Get the changes of the given filter since it was last polled.
+	pub async fn filter_changes(
+		&self,
+		id: U256,
+		connection: &FilterConnection,
+	) -> Result<FilterResults, ClientError> {
+		let latest_block = self.block_number().await?;
+		match self.filter_provider.poll(&id, connection, latest_block).await? {
+			FilterPoll::Hashes(hashes) => Ok(FilterResults::Hashes(hashes)),
+			FilterPoll::Logs(filter) => Ok(FilterResults::Logs(self.logs(Some(filter)).await?)),
+			FilterPoll::NoLogs => Ok(FilterResults::Logs(vec![])),
+		}
+	}
+
+	/// Get all the logs matching the given log filter.
+	pub async fn filter_logs(
+		&self,
+		id: U256,
+		connection: &FilterConnection,
+	) -> Result<Vec<Log>, ClientError> {
+		let filter = self.filter_provider.log_filter(&id, connection).await?;
+		self.logs(Some(filter)).await
+	}
+
+	/// Uninstall the given filter, returns `true` if the filter was installed.
+	pub async fn uninstall_filter(&self, id: U256, connection: &FilterConnection) -> bool {
+		self.filter_provider.uninstall(&id, connection).await
+	}
+
 	pub async fn fee_history(
 		&self,
 		block_count: u32,
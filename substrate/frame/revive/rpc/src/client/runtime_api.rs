@@ -24,7 +24,7 @@ use futures::TryFutureExt;
 use pallet_revive::{
 	evm::{
 		Block as EthBlock, BlockNumberOrTagOrHash, BlockTag, GenericTransaction, ReceiptGasInfo,
-		Trace, H160, U256,
+		SimulateBlock, SimulatedBlock, StateOverride, Trace, H160, U256,
 	},
 	DryRunConfig, EthTransactInfo,
 };
@@ -34,6 +34,21 @@ use subxt::{error::MetadataError, ext::subxt_rpcs::UserError, Error::Metadata, O
 
 const LOG_TARGET: &str = "eth-rpc::runtime_api";
 
+/// The version of the `ReviveApi` runtime API adding `eth_transact_with_overrides` and
+/// `eth_simulate`.
+const REVIVE_API_DRY_RUN_OVERRIDES_VERSION: u32 = 2;
+
+/// The dry-run config for the given block, the pending block is dry-run at the current time.
+fn dry_run_config(block: &BlockNumberOrTagOrHash) -> DryRunConfig<u64> {
+	let timestamp_override = match block {
+		BlockNumberOrTagOrHash::BlockTag(BlockTag::Pending) => {
+			Some(Timestamp::current().as_millis())
+		},
+		_ => None,
+	};
+	DryRunConfig::new(timestamp_override)
+}
+
 /// A Wrapper around subxt Runtime API
 #[derive(Clone)]
 pub struct RuntimeApi(subxt::runtime_api::RuntimeApi<SrcChainConfig, OnlineClient<SrcChainConfig>>);
@@ -72,19 +87,9 @@ impl RuntimeApi {
 		tx: GenericTransaction,
 		block: BlockNumberOrTagOrHash,
 	) -> Result<EthTransactInfo<Balance>, ClientError> {
-		let timestamp_override = match block {
-			BlockNumberOrTagOrHash::BlockTag(BlockTag::Pending) => {
-				Some(Timestamp::current().as_millis())
-			},
-			_ => None,
-		};
-
 		let payload = subxt_client::apis()
 			.revive_api()
-			.eth_transact_with_config(
-				tx.clone().into(),
-				DryRunConfig::new(timestamp_override).into(),
-			)
+			.eth_transact_with_config(tx.clone().into(), dry_run_config(&block).into())
 			.unvalidated();
 
 		let result = self
@@ -122,6 +127,78 @@ impl RuntimeApi {
 		}
 	}
 
+	/// Get the version of the `ReviveApi` runtime API, if the runtime implements it.
+	async fn revive_api_version(&self) -> Result<Option<u32>, ClientError> {
+		let version: sp_version::RuntimeVersion = self.0.call_raw("Core_version", None).await?;
+		Ok(version.api_version(&sp_crypto_hashing::blake2_64(b"ReviveApi")))
+	}
+
+	/// Fails with [`ClientError::UnsupportedRuntimeApi`] if the `ReviveApi` runtime API of the
+	/// runtime is older than `version`, which added `method`.
+	async fn ensure_revive_api_version(
+		&self,
+		method: &'static str,
+		version: u32,
+	) -> Result<(), ClientError> {
+		match self.revive_api_version().await? {
+			Some(runtime_version) if runtime_version >= version => Ok(()),
+			runtime_version => {
+				log::debug!(target: LOG_TARGET, "{method} needs ReviveApi version {version}, the runtime has {runtime_version:?}");
+				Err(ClientError::UnsupportedRuntimeApi(method))
+			},
+		}
+	}
+
+	/// Dry run a transaction on top of the given state overrides and returns the
+	/// [`EthTransactInfo`] for the transaction.
+	pub async fn dry_run_with_overrides(
+		&self,
+		tx: GenericTransaction,
+		state_override: StateOverride,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<EthTransactInfo<Balance>, ClientError> {
+		self.ensure_revive_api_version(
+			"eth_transact_with_overrides",
+			REVIVE_API_DRY_RUN_OVERRIDES_VERSION,
+		)
+		.await?;
+		let payload = subxt_client::apis().revive_api().eth_transact_with_overrides(
+			tx.into(),
+			state_override.into(),
+			dry_run_config(&block).into(),
+		);
+
+		match self.0.call(payload).await? {
+			Err(err) => {
+				log::debug!(target: LOG_TARGET, "Dry run with overrides failed {err:?}");
+				Err(ClientError::TransactError(err.0))
+			},
+			Ok(result) => Ok(result.0),
+		}
+	}
+
+	/// Simulate the given blocks of calls, see `eth_simulateV1`.
+	pub async fn simulate(
+		&self,
+		blocks: Vec<SimulateBlock>,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<Vec<SimulatedBlock>, ClientError> {
+		self.ensure_revive_api_version("eth_simulate", REVIVE_API_DRY_RUN_OVERRIDES_VERSION)
+			.await?;
+		let blocks = blocks.into_iter().map(Into::into).collect();
+		let payload = subxt_client::apis()
+			.revive_api()
+			.eth_simulate(blocks, dry_run_config(&block).into());
+
+		match self.0.call(payload).await? {
+			Err(err) => {
+				log::debug!(target: LOG_TARGET, "Simulation failed {err:?}");
+				Err(ClientError::TransactError(err.0))
+			},
+			Ok(result) => Ok(result.into_iter().map(|block| block.0).collect()),
+		}
+	}
+
 	/// Get the nonce of the given address.
 	pub async fn nonce(&self, address: H160) -> Result<U256, ClientError> {
 		let address = address.0.into();
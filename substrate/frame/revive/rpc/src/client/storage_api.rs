@@ -21,9 +21,9 @@ use crate::{
 		runtime_types::pallet_revive::storage::{AccountType, ContractInfo},
 		SrcChainConfig,
 	},
-	ClientError, H160,
+	ClientError, H160, H256,
 };
-use subxt::{storage::Storage, OnlineClient};
+use subxt::{storage::Storage, utils::AccountId32, OnlineClient};
 
 /// A wrapper around the Substrate Storage API.
 #[derive(Clone)]
@@ -55,9 +55,40 @@ impl StorageApi {
 		Ok(contract_info)
 	}
 
+	/// Get the account id the given address is mapped to.
+	///
+	/// Addresses without an explicit mapping use the fallback account id of the
+	/// `AccountId32Mapper`: the address followed by `0xEE` bytes.
+	pub async fn get_account_id(&self, address: &H160) -> Result<AccountId32, ClientError> {
+		let query = subxt_client::storage().revive().original_account(address.0.into());
+		if let Some(account_id) = self.0.fetch(&query).await? {
+			return Ok(account_id);
+		}
+
+		let mut account_id = [0xEE; 32];
+		account_id[..20].copy_from_slice(address.as_bytes());
+		Ok(AccountId32(account_id))
+	}
+
 	/// Get the contract trie id for the given contract address.
 	pub async fn get_contract_trie_id(&self, address: &H160) -> Result<Vec<u8>, ClientError> {
 		let ContractInfo { trie_id, .. } = self.get_contract_info(address).await?;
 		Ok(trie_id.0)
 	}
+
+	/// Get the root of the storage child trie with the given prefixed child storage key.
+	pub async fn get_child_storage_root(
+		&self,
+		prefixed_child_key: &[u8],
+	) -> Result<Option<H256>, ClientError> {
+		let Some(root) = self.0.fetch_raw(prefixed_child_key.to_vec()).await? else {
+			return Ok(None);
+		};
+
+		if root.len() != H256::len_bytes() {
+			return Err(ClientError::ConversionFailed);
+		}
+
+		Ok(Some(H256::from_slice(&root)))
+	}
 }
@@ -27,6 +27,7 @@ pub enum TransactionType {
 	Eip2930,
 	Eip1559,
 	Eip4844,
+	Eip7702,
 }
 
 /// Transaction builder.
@@ -37,6 +38,7 @@ pub struct TransactionBuilder<Client: EthRpcClient + Sync + Send> {
 	input: Bytes,
 	to: Option<H160>,
 	nonce: Option<U256>,
+	authorization_list: Vec<AuthorizationListEntry>,
 	mutate: Box<dyn FnOnce(&mut TransactionUnsigned)>,
 }
 
@@ -96,6 +98,7 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 			input: Bytes::default(),
 			to: None,
 			nonce: None,
+			authorization_list: vec![],
 			mutate: Box::new(|_| {}),
 		}
 	}
@@ -129,6 +132,12 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 		self
 	}
 
+	/// Set the EIP-7702 authorization list, sent with [`TransactionType::Eip7702`].
+	pub fn authorization_list(mut self, authorization_list: Vec<AuthorizationListEntry>) -> Self {
+		self.authorization_list = authorization_list;
+		self
+	}
+
 	/// Set a mutation function, that mutates the transaction before sending.
 	pub fn mutate(mut self, mutate: impl FnOnce(&mut TransactionUnsigned) + 'static) -> Self {
 		self.mutate = Box::new(mutate);
@@ -137,7 +146,7 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 
 	/// Call eth_call to get the result of a view function
 	pub async fn eth_call(self) -> anyhow::Result<Vec<u8>> {
-		let TransactionBuilder { client, signer, value, input, to, .. } = self;
+		let TransactionBuilder { client, signer, value, input, to, authorization_list, .. } = self;
 
 		let from = signer.address();
 		let result = client
@@ -147,9 +156,11 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 					input: input.into(),
 					value: Some(value),
 					to,
+					authorization_list,
 					..Default::default()
 				},
 				None,
+				None,
 			)
 			.await
 			.map_err(|e| anyhow::anyhow!("eth_call failed: {e}"))?;
@@ -166,7 +177,16 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 		self,
 		tx_type: TransactionType,
 	) -> anyhow::Result<SubmittedTransaction<Client>> {
-		let TransactionBuilder { client, signer, value, input, to, nonce, mutate } = self;
+		let TransactionBuilder {
+			client,
+			signer,
+			value,
+			input,
+			to,
+			nonce,
+			authorization_list,
+			mutate,
+		} = self;
 
 		let from = signer.address();
 		let chain_id = client.chain_id().await?;
@@ -191,6 +211,7 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 					..Default::default()
 				},
 				None,
+				None,
 			)
 			.await
 			.with_context(|| "Failed to fetch gas estimate")?;
@@ -258,6 +279,26 @@ impl<Client: EthRpcClient + Send + Sync> TransactionBuilder<Client> {
 				}
 				.into()
 			},
+			TransactionType::Eip7702 => {
+				let to = to.ok_or_else(|| {
+					anyhow::anyhow!("EIP-7702 transactions require a destination address")
+				})?;
+				Transaction7702Unsigned {
+					gas,
+					nonce,
+					to,
+					value,
+					input,
+					gas_price,
+					max_fee_per_gas: gas_price,
+					max_priority_fee_per_gas: U256::zero(),
+					chain_id,
+					access_list: vec![],
+					authorization_list,
+					r#type: TypeEip7702,
+				}
+				.into()
+			},
 		};
 		mutate(&mut unsigned_tx);
 
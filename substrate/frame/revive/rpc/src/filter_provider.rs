@@ -0,0 +1,503 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{client::SubstrateBlockNumber, ClientError};
+use jsonrpsee::{ConnectionId, Extensions};
+use pallet_revive::evm::{BlockNumberOrTag, Filter};
+use sc_rpc_server::ConnectionClosed;
+use sp_core::{H256, U256};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+const LOG_TARGET: &str = "eth-rpc::filter_provider";
+
+/// The default duration after which a filter that has not been polled is removed.
+pub const DEFAULT_FILTER_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The default maximum number of filters that a single connection can install.
+pub const DEFAULT_MAX_FILTERS_PER_CONNECTION: usize = 128;
+
+/// The default maximum number of filters installed by all connections together.
+pub const DEFAULT_MAX_FILTERS: usize = 4096;
+
+/// The maximum number of hashes buffered by a block or pending transaction filter between two
+/// polls. Older hashes are dropped first.
+const MAX_BUFFERED_HASHES: usize = 1024;
+
+/// The state of an installed filter.
+#[derive(Debug, Clone)]
+enum FilterKind {
+	/// A log filter, and the next block to query logs from.
+	Logs { filter: Filter, next_block: SubstrateBlockNumber },
+	/// A new block filter, and the block hashes received since the last poll.
+	Blocks(VecDeque<H256>),
+	/// A new pending transaction filter, and the transaction hashes received since the last poll.
+	PendingTransactions(VecDeque<H256>),
+}
+
+#[derive(Debug)]
+struct InstalledFilter {
+	/// The websocket connection that installed the filter, and that alone can use it.
+	owner: Option<ConnectionId>,
+	/// The last time the filter was installed or polled.
+	last_poll: Instant,
+	/// The filter state.
+	kind: FilterKind,
+}
+
+/// The connection a filter call was made over.
+#[derive(Clone)]
+pub struct FilterConnection {
+	/// The connection id.
+	id: ConnectionId,
+	/// Resolves when the connection is closed, only available for websocket connections.
+	closed: Option<ConnectionClosed>,
+}
+
+impl FilterConnection {
+	/// Create a new [`FilterConnection`] for a websocket connection, if `closed` is set.
+	pub fn new(id: ConnectionId, closed: Option<ConnectionClosed>) -> Self {
+		Self { id, closed }
+	}
+
+	/// Get the connection a call was made over from the call extensions.
+	pub fn from_extensions(ext: &Extensions) -> Self {
+		let id = ext
+			.get::<ConnectionId>()
+			.copied()
+			.expect("ConnectionId is always set by jsonrpsee; qed");
+		Self::new(id, ext.get::<ConnectionClosed>().cloned())
+	}
+
+	/// The owner of the filters installed over this connection.
+	///
+	/// Every HTTP request gets its own connection, so the filters installed over HTTP are not
+	/// owned by any, and are only protected by their random id, as they are in geth.
+	fn owner(&self) -> Option<ConnectionId> {
+		self.closed.as_ref().map(|_| self.id)
+	}
+}
+
+/// What a poll of an installed filter resolved to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterPoll {
+	/// The new block or transaction hashes since the last poll.
+	Hashes(Vec<H256>),
+	/// The logs matching this filter should be returned.
+	Logs(Filter),
+	/// There are no new logs since the last poll.
+	NoLogs,
+}
+
+/// Keeps track of the filters installed with `eth_newFilter`, `eth_newBlockFilter` and
+/// `eth_newPendingTransactionFilter`.
+#[derive(Clone)]
+pub struct FilterProvider {
+	/// The installed filters, by id.
+	filters: Arc<Mutex<HashMap<U256, InstalledFilter>>>,
+	/// The websocket connections whose filters are removed when they are closed.
+	watched_connections: Arc<Mutex<HashSet<ConnectionId>>>,
+	/// The duration after which a filter that has not been polled is removed.
+	idle_timeout: Duration,
+	/// The maximum number of filters that a single connection can install.
+	max_filters_per_connection: usize,
+	/// The maximum number of filters installed by all connections together.
+	///
+	/// This also bounds the filters installed over HTTP, which are not owned by any connection.
+	max_filters: usize,
+}
+
+impl Default for FilterProvider {
+	fn default() -> Self {
+		Self::new(
+			DEFAULT_FILTER_IDLE_TIMEOUT,
+			DEFAULT_MAX_FILTERS_PER_CONNECTION,
+			DEFAULT_MAX_FILTERS,
+		)
+	}
+}
+
+/// Convert a block number or tag to a block number, tags are resolved to `None`.
+fn as_block_number(block: &Option<BlockNumberOrTag>) -> Option<SubstrateBlockNumber> {
+	match block {
+		Some(BlockNumberOrTag::U256(n)) => {
+			Some((*n).try_into().unwrap_or(SubstrateBlockNumber::MAX))
+		},
+		_ => None,
+	}
+}
+
+impl FilterProvider {
+	/// Create a new [`FilterProvider`].
+	pub fn new(
+		idle_timeout: Duration,
+		max_filters_per_connection: usize,
+		max_filters: usize,
+	) -> Self {
+		Self {
+			filters: Default::default(),
+			watched_connections: Default::default(),
+			idle_timeout,
+			max_filters_per_connection,
+			max_filters,
+		}
+	}
+
+	/// Remove the filters that have not been polled within the idle timeout.
+	fn prune_idle(&self, filters: &mut HashMap<U256, InstalledFilter>) {
+		let idle_timeout = self.idle_timeout;
+		filters.retain(|id, filter| {
+			let keep = filter.last_poll.elapsed() < idle_timeout;
+			if !keep {
+				log::debug!(target: LOG_TARGET, "Removing idle filter {id:?}");
+			}
+			keep
+		});
+	}
+
+	/// Get the filter with the given id, if it can be used from the given connection.
+	fn get_mut<'a>(
+		filters: &'a mut HashMap<U256, InstalledFilter>,
+		id: &U256,
+		connection: &FilterConnection,
+	) -> Result<&'a mut InstalledFilter, ClientError> {
+		filters
+			.get_mut(id)
+			.filter(|installed| {
+				installed.owner.is_none_or(|owner| Some(owner) == connection.owner())
+			})
+			.ok_or(ClientError::FilterNotFound)
+	}
+
+	async fn install(
+		&self,
+		connection: FilterConnection,
+		kind: FilterKind,
+	) -> Result<U256, ClientError> {
+		let mut filters = self.filters.lock().await;
+		self.prune_idle(&mut filters);
+
+		if filters.len() >= self.max_filters {
+			return Err(ClientError::TooManyFilters(self.max_filters));
+		}
+
+		let owner = connection.owner();
+		if owner.is_some() {
+			let installed = filters.values().filter(|filter| filter.owner == owner).count();
+			if installed >= self.max_filters_per_connection {
+				return Err(ClientError::FilterLimitReached(self.max_filters_per_connection));
+			}
+		}
+
+		let id = loop {
+			let id = U256::from(rand::random::<u128>());
+			if !filters.contains_key(&id) {
+				break id;
+			}
+		};
+
+		log::trace!(target: LOG_TARGET, "Installing filter {id:?}: {kind:?}");
+		filters.insert(id, InstalledFilter { owner, last_poll: Instant::now(), kind });
+		drop(filters);
+
+		if let Some(closed) = connection.closed {
+			self.watch_connection(connection.id, closed).await;
+		}
+		Ok(id)
+	}
+
+	/// Remove the filters of the given connection once it is closed.
+	async fn watch_connection(&self, connection_id: ConnectionId, closed: ConnectionClosed) {
+		if !self.watched_connections.lock().await.insert(connection_id) {
+			return;
+		}
+
+		let provider = self.clone();
+		tokio::spawn(async move {
+			closed.await;
+			provider.watched_connections.lock().await.remove(&connection_id);
+			provider
+				.filters
+				.lock()
+				.await
+				.retain(|_, filter| filter.owner != Some(connection_id));
+			log::debug!(target: LOG_TARGET, "Removed the filters of closed connection {connection_id:?}");
+		});
+	}
+
+	/// Install a new log filter, matching logs from blocks after `latest_block`.
+	pub async fn new_filter(
+		&self,
+		connection: FilterConnection,
+		filter: Filter,
+		latest_block: SubstrateBlockNumber,
+	) -> Result<U256, ClientError> {
+		let next_block = latest_block.saturating_add(1);
+		self.install(connection, FilterKind::Logs { filter, next_block }).await
+	}
+
+	/// Install a new block filter.
+	pub async fn new_block_filter(
+		&self,
+		connection: FilterConnection,
+	) -> Result<U256, ClientError> {
+		self.install(connection, FilterKind::Blocks(Default::default())).await
+	}
+
+	/// Install a new pending transaction filter.
+	pub async fn new_pending_transaction_filter(
+		&self,
+		connection: FilterConnection,
+	) -> Result<U256, ClientError> {
+		self.install(connection, FilterKind::PendingTransactions(Default::default()))
+			.await
+	}
+
+	/// Uninstall the filter with the given id, returns `true` if the filter existed.
+	pub async fn uninstall(&self, id: &U256, connection: &FilterConnection) -> bool {
+		let mut filters = self.filters.lock().await;
+		self.prune_idle(&mut filters);
+		if Self::get_mut(&mut filters, id, connection).is_err() {
+			return false;
+		}
+		filters.remove(id).is_some()
+	}
+
+	/// Poll the filter with the given id for changes since the last poll.
+	pub async fn poll(
+		&self,
+		id: &U256,
+		connection: &FilterConnection,
+		latest_block: SubstrateBlockNumber,
+	) -> Result<FilterPoll, ClientError> {
+		let mut filters = self.filters.lock().await;
+		self.prune_idle(&mut filters);
+
+		let installed = Self::get_mut(&mut filters, id, connection)?;
+		installed.last_poll = Instant::now();
+
+		let poll = match &mut installed.kind {
+			FilterKind::Blocks(hashes) | FilterKind::PendingTransactions(hashes) => {
+				FilterPoll::Hashes(hashes.drain(..).collect())
+			},
+			FilterKind::Logs { filter, next_block } if filter.block_hash.is_some() => {
+				// A block hash filter only ever matches a single block.
+				if *next_block == SubstrateBlockNumber::MAX {
+					FilterPoll::NoLogs
+				} else {
+					*next_block = SubstrateBlockNumber::MAX;
+					FilterPoll::Logs(filter.clone())
+				}
+			},
+			FilterKind::Logs { filter, next_block } => {
+				let from = as_block_number(&filter.from_block).unwrap_or(0).max(*next_block);
+				let to =
+					as_block_number(&filter.to_block).unwrap_or(latest_block).min(latest_block);
+				*next_block = (*next_block).max(latest_block.saturating_add(1));
+
+				if from > to {
+					FilterPoll::NoLogs
+				} else {
+					FilterPoll::Logs(Filter {
+						from_block: Some(U256::from(from).into()),
+						to_block: Some(U256::from(to).into()),
+						..filter.clone()
+					})
+				}
+			},
+		};
+
+		Ok(poll)
+	}
+
+	/// Get the log filter with the given id.
+	pub async fn log_filter(
+		&self,
+		id: &U256,
+		connection: &FilterConnection,
+	) -> Result<Filter, ClientError> {
+		let mut filters = self.filters.lock().await;
+		self.prune_idle(&mut filters);
+
+		let installed = Self::get_mut(&mut filters, id, connection)?;
+		installed.last_poll = Instant::now();
+		match &installed.kind {
+			FilterKind::Logs { filter, .. } => Ok(filter.clone()),
+			_ => Err(ClientError::FilterNotFound),
+		}
+	}
+
+	/// Notify the block filters of a new best block.
+	pub async fn on_new_block(&self, hash: H256) {
+		self.push_hash(hash, |kind| matches!(kind, FilterKind::Blocks(_))).await
+	}
+
+	/// Notify the pending transaction filters of a new pending transaction.
+	pub async fn on_pending_transaction(&self, hash: H256) {
+		self.push_hash(hash, |kind| matches!(kind, FilterKind::PendingTransactions(_)))
+			.await
+	}
+
+	/// Returns `true` if a pending transaction filter is installed.
+	pub async fn has_pending_transaction_filters(&self) -> bool {
+		let filters = self.filters.lock().await;
+		filters
+			.values()
+			.any(|installed| matches!(installed.kind, FilterKind::PendingTransactions(_)))
+	}
+
+	async fn push_hash(&self, hash: H256, is_target: impl Fn(&FilterKind) -> bool) {
+		let mut filters = self.filters.lock().await;
+		self.prune_idle(&mut filters);
+
+		for installed in filters.values_mut().filter(|installed| is_target(&installed.kind)) {
+			if let FilterKind::Blocks(hashes) | FilterKind::PendingTransactions(hashes) =
+				&mut installed.kind
+			{
+				if hashes.len() >= MAX_BUFFERED_HASHES {
+					hashes.pop_front();
+				}
+				hashes.push_back(hash);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	fn http() -> FilterConnection {
+		FilterConnection::new(ConnectionId(0), None)
+	}
+
+	fn ws(id: usize) -> (FilterConnection, tokio::sync::oneshot::Sender<()>) {
+		let (close, closed) = tokio::sync::oneshot::channel::<()>();
+		let closed = ConnectionClosed::new(async move {
+			let _ = closed.await;
+		});
+		(FilterConnection::new(ConnectionId(id), Some(closed)), close)
+	}
+
+	#[tokio::test]
+	async fn block_and_pending_transaction_filters_work() {
+		let provider = FilterProvider::default();
+		let block_filter = provider.new_block_filter(http()).await.unwrap();
+		let tx_filter = provider.new_pending_transaction_filter(http()).await.unwrap();
+
+		provider.on_new_block(H256::repeat_byte(1)).await;
+		provider.on_pending_transaction(H256::repeat_byte(2)).await;
+
+		assert_eq!(
+			provider.poll(&block_filter, &http(), 1).await.unwrap(),
+			FilterPoll::Hashes(vec![H256::repeat_byte(1)])
+		);
+		assert_eq!(
+			provider.poll(&block_filter, &http(), 1).await.unwrap(),
+			FilterPoll::Hashes(vec![])
+		);
+		assert_eq!(
+			provider.poll(&tx_filter, &http(), 1).await.unwrap(),
+			FilterPoll::Hashes(vec![H256::repeat_byte(2)])
+		);
+
+		assert!(provider.uninstall(&block_filter, &http()).await);
+		assert!(!provider.uninstall(&block_filter, &http()).await);
+		assert!(matches!(
+			provider.poll(&block_filter, &http(), 1).await,
+			Err(ClientError::FilterNotFound)
+		));
+	}
+
+	#[tokio::test]
+	async fn log_filter_tracks_polled_blocks() {
+		let provider = FilterProvider::default();
+		let id = provider.new_filter(http(), Filter::default(), 10).await.unwrap();
+
+		// No new block since the filter was installed.
+		assert_eq!(provider.poll(&id, &http(), 10).await.unwrap(), FilterPoll::NoLogs);
+
+		let expected = Filter {
+			from_block: Some(U256::from(11).into()),
+			to_block: Some(U256::from(12).into()),
+			..Default::default()
+		};
+		assert_eq!(provider.poll(&id, &http(), 12).await.unwrap(), FilterPoll::Logs(expected));
+		assert_eq!(provider.poll(&id, &http(), 12).await.unwrap(), FilterPoll::NoLogs);
+		assert_eq!(provider.log_filter(&id, &http()).await.unwrap(), Filter::default());
+	}
+
+	#[tokio::test]
+	async fn filters_are_capped_per_connection() {
+		let provider = FilterProvider::new(DEFAULT_FILTER_IDLE_TIMEOUT, 1, DEFAULT_MAX_FILTERS);
+		let (first, _close) = ws(0);
+		let (second, _close) = ws(1);
+		provider.new_block_filter(first.clone()).await.unwrap();
+		assert!(matches!(
+			provider.new_block_filter(first).await,
+			Err(ClientError::FilterLimitReached(1))
+		));
+		provider.new_block_filter(second).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn filters_are_capped_globally() {
+		let provider = FilterProvider::new(DEFAULT_FILTER_IDLE_TIMEOUT, 1, 2);
+		let (first, _close) = ws(0);
+		provider.new_block_filter(first).await.unwrap();
+		provider.new_block_filter(http()).await.unwrap();
+		assert!(matches!(
+			provider.new_block_filter(http()).await,
+			Err(ClientError::TooManyFilters(2))
+		));
+	}
+
+	#[tokio::test]
+	async fn filters_are_owned_by_their_websocket_connection() {
+		let provider = FilterProvider::default();
+		let (owner, close) = ws(0);
+		let (other, _close) = ws(1);
+		let id = provider.new_block_filter(owner.clone()).await.unwrap();
+		let other_id = provider.new_block_filter(other.clone()).await.unwrap();
+		assert_ne!(id, other_id);
+
+		// Other connections can't use the filter.
+		assert!(matches!(provider.poll(&id, &other, 1).await, Err(ClientError::FilterNotFound)));
+		assert!(matches!(provider.poll(&id, &http(), 1).await, Err(ClientError::FilterNotFound)));
+		assert!(!provider.uninstall(&id, &other).await);
+		assert_eq!(provider.poll(&id, &owner, 1).await.unwrap(), FilterPoll::Hashes(vec![]));
+
+		// The filters are removed when the connection is closed.
+		close.send(()).unwrap();
+		tokio::time::sleep(Duration::from_millis(10)).await;
+		assert!(matches!(provider.poll(&id, &owner, 1).await, Err(ClientError::FilterNotFound)));
+		assert_eq!(provider.poll(&other_id, &other, 1).await.unwrap(), FilterPoll::Hashes(vec![]));
+	}
+
+	#[tokio::test]
+	async fn idle_filters_expire() {
+		let provider = FilterProvider::new(Duration::from_millis(10), 1, 1);
+		let id = provider.new_block_filter(http()).await.unwrap();
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		assert!(matches!(provider.poll(&id, &http(), 1).await, Err(ClientError::FilterNotFound)));
+		provider.new_block_filter(http()).await.unwrap();
+	}
+}
@@ -44,6 +44,9 @@ pub use receipt_provider::*;
 mod fee_history_provider;
 pub use fee_history_provider::*;
 
+mod filter_provider;
+pub use filter_provider::*;
+
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
@@ -166,6 +169,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTag>,
+		state_overrides: Option<StateOverride>,
 	) -> RpcResult<U256> {
 		log::trace!(target: LOG_TARGET, "estimate_gas transaction={transaction:?} block={block:?}");
 		let block = block.unwrap_or_else(|| {
@@ -177,7 +181,14 @@ impl EthRpcServer for EthRpcServerImpl {
 		});
 		let hash = self.client.block_hash_for_tag(block.clone().into()).await?;
 		let runtime_api = self.client.runtime_api(hash);
-		let dry_run = runtime_api.dry_run(transaction, block.into()).await?;
+		let dry_run = match state_overrides {
+			Some(state_overrides) => {
+				runtime_api
+					.dry_run_with_overrides(transaction, state_overrides, block.into())
+					.await?
+			},
+			None => runtime_api.dry_run(transaction, block.into()).await?,
+		};
 		log::trace!(target: LOG_TARGET, "estimate_gas result={dry_run:?}");
 		Ok(dry_run.eth_gas)
 	}
@@ -186,14 +197,32 @@ impl EthRpcServer for EthRpcServerImpl {
 		&self,
 		transaction: GenericTransaction,
 		block: Option<BlockNumberOrTagOrHash>,
+		state_overrides: Option<StateOverride>,
 	) -> RpcResult<Bytes> {
 		let block = block.unwrap_or_default();
 		let hash = self.client.block_hash_for_tag(block.clone()).await?;
 		let runtime_api = self.client.runtime_api(hash);
-		let dry_run = runtime_api.dry_run(transaction, block).await?;
+		let dry_run = match state_overrides {
+			Some(state_overrides) => {
+				runtime_api.dry_run_with_overrides(transaction, state_overrides, block).await?
+			},
+			None => runtime_api.dry_run(transaction, block).await?,
+		};
 		Ok(dry_run.data.into())
 	}
 
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		log::trace!(target: LOG_TARGET, "simulate_v1 payload={payload:?} block={block:?}");
+		let block = block.unwrap_or_default();
+		let hash = self.client.block_hash_for_tag(block.clone()).await?;
+		let runtime_api = self.client.runtime_api(hash);
+		Ok(runtime_api.simulate(payload.block_state_calls, block).await?)
+	}
+
 	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
 		let hash = H256(keccak_256(&transaction.0));
 		log::trace!(target: LOG_TARGET, "send_raw_transaction transaction: {transaction:?} ethereum_hash: {hash:?}");
@@ -241,6 +270,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		})?;
 
 		log::trace!(target: LOG_TARGET, "send_raw_transaction with hash: {hash:?}");
+		self.client.notify_pending_transaction(hash).await;
 
 		// Wait for the transaction to be included in a block if automine is enabled
 		if let Some(mut receiver) = receiver {
@@ -287,7 +317,7 @@ impl EthRpcServer for EthRpcServerImpl {
 			.ok_or(EthRpcError::AccountNotFound(from))?;
 
 		if transaction.gas.is_none() {
-			transaction.gas = Some(self.estimate_gas(transaction.clone(), None).await?);
+			transaction.gas = Some(self.estimate_gas(transaction.clone(), None, None).await?);
 		}
 
 		if transaction.gas_price.is_none() {
@@ -331,6 +361,16 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(self.client.chain_id().into())
 	}
 
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		log::trace!(target: LOG_TARGET, "create_access_list transaction={transaction:?} block={block:?}");
+		let block = block.unwrap_or_default();
+		Ok(self.client.create_access_list(transaction, block).await?)
+	}
+
 	async fn gas_price(&self) -> RpcResult<U256> {
 		let hash = self.client.block_hash_for_tag(BlockTag::Latest.into()).await?;
 		let runtime_api = self.client.runtime_api(hash);
@@ -365,6 +405,20 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(block)
 	}
 
+	async fn get_block_receipts(
+		&self,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<Option<Vec<ReceiptInfo>>> {
+		let block_hash = match self.client.block_hash_for_tag(block).await {
+			Ok(block_hash) => block_hash,
+			Err(ClientError::BlockNotFound | ClientError::EthereumBlockNotFound) => {
+				return Ok(None)
+			},
+			Err(err) => return Err(err.into()),
+		};
+		Ok(self.client.receipts_by_block_hash(&block_hash).await?)
+	}
+
 	async fn get_block_transaction_count_by_hash(
 		&self,
 		block_hash: Option<H256>,
@@ -404,6 +458,15 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof> {
+		Ok(self.client.get_proof(address, storage_keys, block).await?)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
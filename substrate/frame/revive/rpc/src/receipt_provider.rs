@@ -16,8 +16,8 @@
 // limitations under the License.
 use crate::{
 	client::{SubstrateBlock, SubstrateBlockNumber},
-	Address, AddressOrAddresses, BlockInfoProvider, BlockNumberOrTag, BlockTag, Bytes, ClientError,
-	FilterTopic, ReceiptExtractor, SubxtBlockInfoProvider,
+	Address, BlockInfoProvider, BlockNumberOrTag, BlockTag, Bytes, ClientError, FilterTopic,
+	ReceiptExtractor, SubxtBlockInfoProvider,
 };
 use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned};
 use sp_core::{H256, U256};
@@ -405,8 +405,8 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			Some(BlockNumberOrTag::BlockTag(tag)) => anyhow::bail!("Unsupported tag: {tag:?}"),
 		};
 
-		let from_block = as_block_number(filter.from_block)?;
-		let to_block = as_block_number(filter.to_block)?;
+		let from_block = as_block_number(filter.from_block.clone())?;
+		let to_block = as_block_number(filter.to_block.clone())?;
 
 		match (from_block, to_block, filter.block_hash) {
 			(Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
@@ -442,42 +442,28 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			},
 		}
 
-		if let Some(addresses) = filter.address {
-			match addresses {
-				AddressOrAddresses::Address(addr) => {
-					qb.push(" AND address = ").push_bind(addr.0.to_vec());
-				},
-				AddressOrAddresses::Addresses(addrs) => {
-					qb.push(" AND address IN (");
-					let mut separated = qb.separated(", ");
-					for addr in addrs {
-						separated.push_bind(addr.0.to_vec());
-					}
-					separated.push_unseparated(")");
-				},
+		if let Some(addresses) = filter.address_criteria() {
+			qb.push(" AND address IN (");
+			let mut separated = qb.separated(", ");
+			for addr in addresses {
+				separated.push_bind(addr.0.to_vec());
 			}
+			separated.push_unseparated(")");
 		}
 
-		if let Some(topics) = filter.topics {
-			if topics.len() > 4 {
-				return Err(anyhow::anyhow!("exceed max topics"));
+		let topics = filter.topic_criteria().map_err(|err| anyhow::anyhow!(err))?;
+		for (i, hashes) in topics.into_iter().enumerate() {
+			if hashes.is_empty() {
+				qb.push(format_args!(" AND topic_{i} IS NOT NULL"));
+				continue;
 			}
 
-			for (i, topic) in topics.into_iter().enumerate() {
-				match topic {
-					FilterTopic::Single(hash) => {
-						qb.push(format_args!(" AND topic_{i} = ")).push_bind(hash.0.to_vec());
-					},
-					FilterTopic::Multiple(hashes) => {
-						qb.push(format_args!(" AND topic_{i} IN ("));
-						let mut separated = qb.separated(", ");
-						for hash in hashes {
-							separated.push_bind(hash.0.to_vec());
-						}
-						separated.push_unseparated(")");
-					},
-				}
+			qb.push(format_args!(" AND topic_{i} IN ("));
+			let mut separated = qb.separated(", ");
+			for hash in hashes {
+				separated.push_bind(hash.0.to_vec());
 			}
+			separated.push_unseparated(")");
 		}
 
 		qb.push(" LIMIT 10000");
@@ -580,6 +566,20 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 		Some(receipt)
 	}
 
+	/// Get the receipts of all the transactions of the given block, ordered by transaction index.
+	///
+	/// Returns `None` if the block is not found.
+	pub async fn receipts_by_block_hash(
+		&self,
+		block_hash: &H256,
+	) -> Result<Option<Vec<ReceiptInfo>>, ClientError> {
+		let Some(block) = self.block_provider.block_by_hash(block_hash).await? else {
+			return Ok(None);
+		};
+		let receipts = self.receipts_from_block(&block).await?;
+		Ok(Some(receipts.into_iter().map(|(_, receipt)| receipt).collect()))
+	}
+
 	/// Get the receipt for the given transaction hash.
 	pub async fn receipt_by_hash(&self, transaction_hash: &H256) -> Option<ReceiptInfo> {
 		let (block_hash, transaction_index) = self.find_transaction(transaction_hash).await?;
@@ -992,6 +992,39 @@ mod tests {
 			}))
 			.await?;
 		assert_eq!(logs, vec![log1.clone(), log2.clone()]);
+
+		// Empty address and topic lists match anything
+		let logs = provider
+			.logs(Some(Filter {
+				from_block: Some(U256::from(0).into()),
+				address: Some(Vec::<H160>::new().into()),
+				topics: Some(vec![
+					FilterTopic::Multiple(vec![]),
+					FilterTopic::Single(log2.topics[1]),
+				]),
+				..Default::default()
+			}))
+			.await?;
+		assert_eq!(logs, vec![log2.clone()]);
+
+		// A topic position matches logs that have a topic there
+		let logs = provider
+			.logs(Some(Filter {
+				from_block: Some(U256::from(0).into()),
+				topics: Some(vec![FilterTopic::Multiple(vec![]); 3]),
+				..Default::default()
+			}))
+			.await?;
+		assert_eq!(logs, vec![]);
+
+		// Too many topics
+		assert!(provider
+			.logs(Some(Filter {
+				topics: Some(vec![FilterTopic::Multiple(vec![]); 5]),
+				..Default::default()
+			}))
+			.await
+			.is_err());
 		Ok(())
 	}
 
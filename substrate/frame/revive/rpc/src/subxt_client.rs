@@ -47,6 +47,18 @@ pub use subxt::config::PolkadotConfig as SrcChainConfig;
 		path = "pallet_revive::evm::api::rpc_types_gen::GenericTransaction",
 		with = "::subxt::utils::Static<::pallet_revive::evm::GenericTransaction>"
 	),
+	substitute_type(
+		path = "pallet_revive::evm::api::simulate_rpc_types::StateOverride",
+		with = "::subxt::utils::Static<::pallet_revive::evm::StateOverride>"
+	),
+	substitute_type(
+		path = "pallet_revive::evm::api::simulate_rpc_types::SimulateBlock",
+		with = "::subxt::utils::Static<::pallet_revive::evm::SimulateBlock>"
+	),
+	substitute_type(
+		path = "pallet_revive::evm::api::simulate_rpc_types::SimulatedBlock",
+		with = "::subxt::utils::Static<::pallet_revive::evm::SimulatedBlock>"
+	),
 	substitute_type(
 		path = "pallet_revive::evm::api::rpc_types::DryRunConfig<M>",
 		with = "::subxt::utils::Static<::pallet_revive::evm::DryRunConfig<M>>"
@@ -24,7 +24,7 @@ use crate::{
 	subxt_client::{
 		self, src_chain::runtime_types::pallet_revive::primitives::Code, SrcChainConfig,
 	},
-	EthRpcClient,
+	DebugRpcClient, EthPubSubRpcClient, EthRpcClient, TraceRpcClient,
 };
 use anyhow::anyhow;
 use clap::Parser;
@@ -32,10 +32,14 @@ use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pallet_revive::{
 	create1,
 	evm::{
-		Account, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag,
-		HashesOrTransactionInfos, TransactionInfo, TransactionUnsigned, H256, U256,
+		Account, AccountOverride, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag,
+		CallTracerConfig, Filter, FilterTopic, GenericTransaction, HashesOrTransactionInfos,
+		ParityAction, SimulateBlock, SimulatePayload, StateOverride, SubscriptionItem,
+		SubscriptionKind, Trace, TraceFilter, TracerConfig, TransactionInfo, TransactionUnsigned,
+		H160, H256, U256,
 	},
 };
+use sp_core::keccak_256;
 use std::{sync::Arc, thread};
 use subxt::{
 	backend::rpc::RpcClient,
@@ -317,6 +321,12 @@ async fn run_all_eth_rpc_tests_inner() -> anyhow::Result<()> {
 		test_multiple_transactions_in_block,
 		test_mixed_evm_substrate_transactions,
 		test_runtime_pallets_address_upload_code,
+		test_subscribe_new_heads_and_pending_transactions,
+		test_subscribe_logs,
+		test_get_proof,
+		test_simulate_v1,
+		test_parity_traces,
+		test_get_block_receipts,
 	);
 
 	log::debug!(target: LOG_TARGET, "All tests completed successfully!");
@@ -861,3 +871,235 @@ async fn test_fibonacci_large_value_runs_out_of_gas() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+async fn test_subscribe_new_heads_and_pending_transactions() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let mut pending_txs =
+		client.eth_subscribe(SubscriptionKind::NewPendingTransactions, None).await?;
+	let mut new_heads = client.eth_subscribe(SubscriptionKind::NewHeads, None).await?;
+
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let tx = TransactionBuilder::new(client.clone())
+		.value(1_000_000_000_000_000u128.into())
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let Some(SubscriptionItem::TransactionHash(hash)) = pending_txs.next().await.transpose()?
+	else {
+		anyhow::bail!("Expected a pending transaction hash");
+	};
+	assert_eq!(hash, receipt.transaction_hash);
+
+	loop {
+		let Some(SubscriptionItem::Header(block)) = new_heads.next().await.transpose()? else {
+			anyhow::bail!("Expected a new head");
+		};
+		if block.number >= receipt.block_number {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+async fn test_subscribe_logs() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let topic = H256::repeat_byte(42);
+	let mut logs = client
+		.eth_subscribe(
+			SubscriptionKind::Logs,
+			Some(Filter { topics: Some(vec![FilterTopic::Single(topic)]), ..Default::default() }),
+		)
+		.await?;
+	let mut other_logs = client
+		.eth_subscribe(
+			SubscriptionKind::Logs,
+			Some(Filter {
+				topics: Some(vec![FilterTopic::Single(H256::repeat_byte(1))]),
+				..Default::default()
+			}),
+		)
+		.await?;
+
+	let (bytes, _) = pallet_revive_fixtures::compile_module("event_and_return_on_deploy")?;
+	let tx = TransactionBuilder::new(client.clone()).input(bytes).send().await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let Some(SubscriptionItem::Log(log)) = logs.next().await.transpose()? else {
+		anyhow::bail!("Expected a log");
+	};
+	assert_eq!(log, receipt.logs[0]);
+	assert_eq!(log.topics, vec![topic]);
+	assert_eq!(log.data, Some(vec![1u8, 2, 3, 4].into()));
+	assert!(!log.removed);
+
+	// The log does not match the other subscription.
+	assert!(tokio::time::timeout(tokio::time::Duration::from_secs(1), other_logs.next())
+		.await
+		.is_err());
+
+	// Subscriptions with too many topics are rejected.
+	assert!(client
+		.eth_subscribe(
+			SubscriptionKind::Logs,
+			Some(Filter { topics: Some(vec![Default::default(); 5]), ..Default::default() }),
+		)
+		.await
+		.is_err());
+
+	Ok(())
+}
+
+async fn test_get_proof() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let account = Account::default();
+
+	let (bytes, _) = pallet_revive_fixtures::compile_module("dummy")?;
+	let nonce = client.get_transaction_count(account.address(), BlockTag::Latest.into()).await?;
+	let tx = TransactionBuilder::new(client.clone()).input(bytes).send().await?;
+	tx.wait_for_receipt().await?;
+	let contract_address = create1(&account.address(), nonce.try_into().unwrap());
+
+	let slot = U256::zero();
+	let proof = client.get_proof(account.address(), vec![slot], BlockTag::Latest.into()).await?;
+	assert_eq!(proof.address, account.address());
+	assert_eq!(
+		proof.balance,
+		client.get_balance(account.address(), BlockTag::Latest.into()).await?
+	);
+	assert_eq!(proof.nonce, nonce + 1);
+	assert_eq!(proof.code_hash, H256(keccak_256(&[])));
+	assert_eq!(proof.storage_hash, H256::zero());
+	assert!(!proof.account_proof.is_empty());
+	assert!(proof.storage_proof[0].proof.is_empty());
+
+	let proof = client.get_proof(contract_address, vec![slot], BlockTag::Latest.into()).await?;
+	assert_ne!(proof.storage_hash, H256::zero());
+	assert!(!proof.account_proof.is_empty());
+	assert_eq!(proof.storage_proof.len(), 1);
+	assert_eq!(proof.storage_proof[0].key, slot);
+	assert!(!proof.storage_proof[0].proof.is_empty());
+	Ok(())
+}
+
+async fn test_simulate_v1() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let from = H160::repeat_byte(0x41);
+	let to = H160::repeat_byte(0x42);
+	let value = U256::from(1_000_000_000_000u128);
+
+	// The sender is unfunded, its balance is provided by the state overrides.
+	let state_overrides = StateOverride(
+		[(from, AccountOverride { balance: Some(value * 10), ..Default::default() })]
+			.into_iter()
+			.collect(),
+	);
+	let transfer = GenericTransaction {
+		from: Some(from),
+		to: Some(to),
+		value: Some(value),
+		..Default::default()
+	};
+	let payload = SimulatePayload {
+		block_state_calls: vec![SimulateBlock {
+			state_overrides: Some(state_overrides.clone()),
+			calls: vec![transfer.clone(), transfer.clone()],
+		}],
+	};
+
+	let blocks = client.simulate_v1(payload, None).await?;
+	assert_eq!(blocks.len(), 1);
+	assert_eq!(blocks[0].calls.len(), 2);
+	for call in &blocks[0].calls {
+		assert_eq!(call.status, U256::one(), "call failed: {:?}", call.error);
+	}
+
+	// The overrides are not persisted.
+	assert!(client.call(transfer.clone(), None, None).await.is_err());
+	client.call(transfer, None, Some(state_overrides)).await?;
+	Ok(())
+}
+
+async fn test_parity_traces() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let value = U256::from(1_000_000_000_000u128);
+	let tx = TransactionBuilder::new(client.clone())
+		.value(value)
+		.to(ethan.address())
+		.send()
+		.await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let traces = TraceRpcClient::trace_transaction(&*client, receipt.transaction_hash)
+		.await?
+		.ok_or(anyhow!("transaction not found"))?;
+	assert_eq!(traces.len(), 1);
+	let trace = &traces[0];
+	assert_eq!(trace.transaction_hash, receipt.transaction_hash);
+	assert_eq!(trace.block_hash, receipt.block_hash);
+	assert_eq!(U256::from(trace.block_number), receipt.block_number);
+	assert_eq!(U256::from(trace.transaction_position), receipt.transaction_index);
+	let ParityAction::Call(action) = &trace.action else { panic!("expected a call action") };
+	assert_eq!((action.to, action.value), (ethan.address(), value));
+
+	let block_traces = client
+		.trace_block(BlockNumberOrTag::U256(receipt.block_number))
+		.await?
+		.ok_or(anyhow!("block not found"))?;
+	assert!(block_traces.contains(trace));
+
+	let filter = TraceFilter {
+		from_block: Some(BlockNumberOrTag::U256(receipt.block_number)),
+		to_block: Some(BlockNumberOrTag::U256(receipt.block_number)),
+		to_address: Some(vec![ethan.address()]),
+		..Default::default()
+	};
+	assert_eq!(client.trace_filter(filter).await?, traces);
+
+	let tracer_config = TracerConfig { config: CallTracerConfig::default().into(), timeout: None };
+	let debug_traces = client.trace_block_by_hash(receipt.block_hash, Some(tracer_config)).await?;
+	let debug_trace = debug_traces
+		.into_iter()
+		.find(|trace| trace.tx_hash == receipt.transaction_hash)
+		.ok_or(anyhow!("transaction trace not found"))?;
+	assert!(matches!(debug_trace.trace, Trace::Call(call) if call.to == ethan.address()));
+	Ok(())
+}
+
+async fn test_get_block_receipts() -> anyhow::Result<()> {
+	let client = Arc::new(SharedResources::client().await);
+	let alith = Account::default();
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let amount = U256::from(1_000_000_000_000_000_000u128);
+
+	let transactions =
+		prepare_evm_transactions(client.clone(), alith, ethan.address(), amount, 5).await?;
+	let submitted_txs = submit_evm_transactions(transactions).await?;
+	let receipt = submitted_txs[0].2.wait_for_receipt().await?;
+
+	let receipts = client
+		.get_block_receipts(BlockNumberOrTagOrHash::BlockHash(receipt.block_hash))
+		.await?
+		.ok_or(anyhow!("block not found"))?;
+	for (_, _, tx) in &submitted_txs {
+		let expected = client
+			.get_transaction_receipt(tx.hash())
+			.await?
+			.ok_or(anyhow!("receipt not found"))?;
+		assert!(receipts.contains(&expected));
+	}
+
+	let by_number = client
+		.get_block_receipts(BlockNumberOrTagOrHash::BlockNumber(receipt.block_number))
+		.await?;
+	assert_eq!(by_number, Some(receipts));
+
+	let unknown = client
+		.get_block_receipts(BlockNumberOrTagOrHash::BlockHash(H256::zero()))
+		.await?;
+	assert_eq!(unknown, None);
+	Ok(())
+}
@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracking of the storage slots accessed during a transaction.
+//!
+//! A storage slot that was accessed before in the same transaction (warm) is served from the
+//! storage overlay, and is therefore cheaper to read than a slot that is accessed for the first
+//! time (cold). The slots of the [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list
+//! of a transaction are paid for upfront and are warm from the start.
+
+use crate::{evm::AccessListEntry, Key};
+use alloc::{collections::BTreeSet, vec::Vec};
+use sp_core::H160;
+
+/// The set of storage slots accessed during a transaction.
+///
+/// Accesses are never rolled back, even when the frame that performed them reverts.
+#[derive(Default)]
+pub struct AccessedStorage {
+	/// The accessed slots, identified by contract address and hashed storage key.
+	slots: BTreeSet<(H160, Vec<u8>)>,
+}
+
+impl AccessedStorage {
+	/// Create a new set, pre-warmed with the storage keys of the given access list.
+	pub fn new(access_list: &[AccessListEntry]) -> Self {
+		let slots = access_list
+			.iter()
+			.flat_map(|entry| {
+				entry.storage_keys.iter().map(|key| (entry.address, Key::Fix(key.0).hash()))
+			})
+			.collect();
+		Self { slots }
+	}
+
+	/// Mark the storage `key` of the contract at `address` as accessed.
+	///
+	/// Returns `true` if the slot was already accessed before (warm).
+	pub fn access(&mut self, address: H160, key: &Key) -> bool {
+		!self.slots.insert((address, key.hash()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	#[test]
+	fn access_list_slots_are_warm() {
+		let address = H160::repeat_byte(1);
+		let mut accessed = AccessedStorage::new(&[AccessListEntry {
+			address,
+			storage_keys: vec![H256::repeat_byte(2)],
+		}]);
+
+		assert!(accessed.access(address, &Key::Fix([2; 32])));
+		assert!(!accessed.access(H160::repeat_byte(3), &Key::Fix([2; 32])));
+		assert!(!accessed.access(address, &Key::Fix([3; 32])));
+		assert!(accessed.access(address, &Key::Fix([3; 32])));
+	}
+}
@@ -21,8 +21,8 @@
 use crate::{
 	call_builder::{caller_funding, default_deposit_limit, CallSetup, Contract, VmBinaryModule},
 	evm::{
-		block_hash::EthereumBlockBuilder, block_storage, TransactionLegacyUnsigned,
-		TransactionSigned, TransactionUnsigned,
+		block_hash::EthereumBlockBuilder, block_storage, tx_extension::apply_authorizations,
+		AuthorizationListEntry, TransactionLegacyUnsigned, TransactionSigned, TransactionUnsigned,
 	},
 	exec::{Key, Origin as ExecOrigin, PrecompileExt},
 	limits,
@@ -3054,6 +3054,47 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// Benchmark the application of a single EIP-7702 authorization.
+	///
+	/// The worst case delegates the code of an authority without a delegation, which recovers
+	/// the authority, checks and bumps its nonce, stores the delegation and holds its deposit.
+	#[benchmark(pov_mode = Measured)]
+	fn apply_authorization() {
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(&payer, caller_funding::<T>());
+		let (_, signer_key, authority) = create_test_signer::<T>();
+		let target = H160::from_low_u64_be(0x1234);
+		let chain_id = U256::from(T::ChainId::get());
+		let nonce = U256::from(
+			frame_system::Pallet::<T>::account_nonce(&T::AddressMapper::to_account_id(&authority))
+				.saturated_into::<u64>(),
+		);
+
+		let payload = AuthorizationListEntry::signing_payload(chain_id, target, nonce);
+		let (signature, recovery_id) = signer_key
+			.sign_prehash_recoverable(&sp_io::hashing::keccak_256(&payload))
+			.expect("signing success");
+		let signature = signature.to_bytes();
+		let authorization = AuthorizationListEntry {
+			chain_id,
+			address: target,
+			nonce,
+			y_parity: U256::from(recovery_id.to_byte()),
+			r: U256::from_big_endian(&signature[..32]),
+			s: U256::from_big_endian(&signature[32..]),
+		};
+
+		#[block]
+		{
+			apply_authorizations::<T>(&payer, &[authorization]);
+		}
+
+		assert_eq!(
+			DelegationOf::<T>::get(authority).map(|delegation| delegation.target),
+			Some(target)
+		);
+	}
+
 	impl_benchmark_test_suite!(
 		Contracts,
 		crate::tests::ExtBuilder::default().build(),
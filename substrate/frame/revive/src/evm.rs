@@ -24,7 +24,9 @@ pub(crate) use call::*;
 mod tracing;
 pub use tracing::*;
 pub mod fees;
+pub mod proof;
 pub mod runtime;
+pub mod simulate;
 pub mod tx_extension;
 pub use alloy_core::sol_types::decode_revert_reason;
 
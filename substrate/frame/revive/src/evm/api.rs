@@ -30,6 +30,18 @@ pub use type_id::*;
 mod debug_rpc_types;
 pub use debug_rpc_types::*;
 
+mod parity_trace_types;
+pub use parity_trace_types::*;
+
+mod pubsub_rpc_types;
+pub use pubsub_rpc_types::*;
+
+mod simulate_rpc_types;
+pub use simulate_rpc_types::*;
+
+mod txpool_rpc_types;
+pub use txpool_rpc_types::*;
+
 mod rpc_types;
 pub use rpc_types::DryRunConfig;
 
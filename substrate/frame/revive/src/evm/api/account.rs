@@ -16,8 +16,8 @@
 // limitations under the License.
 //! Utilities for working with Ethereum accounts.
 use crate::{
-	evm::{TransactionSigned, TransactionUnsigned},
-	H160,
+	evm::{AuthorizationListEntry, TransactionSigned, TransactionUnsigned},
+	H160, U256,
 };
 use sp_runtime::AccountId32;
 
@@ -62,6 +62,25 @@ impl Account {
 		let signature = self.0.sign(&payload).0;
 		tx.with_signature(signature)
 	}
+
+	/// Sign an EIP-7702 authorization to delegate the code of this account to `address`.
+	pub fn sign_authorization(
+		&self,
+		chain_id: U256,
+		address: H160,
+		nonce: U256,
+	) -> AuthorizationListEntry {
+		let payload = AuthorizationListEntry::signing_payload(chain_id, address, nonce);
+		let signature = self.0.sign(&payload).0;
+		AuthorizationListEntry {
+			chain_id,
+			address,
+			nonce,
+			y_parity: U256::from(signature[64]),
+			r: U256::from_big_endian(&signature[..32]),
+			s: U256::from_big_endian(&signature[32..64]),
+		}
+	}
 }
 
 #[test]
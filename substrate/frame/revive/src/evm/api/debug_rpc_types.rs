@@ -37,7 +37,8 @@ pub enum TracerType {
 	/// A tracer that traces the prestate.
 	PrestateTracer(Option<PrestateTracerConfig>),
 
-	/// A tracer that traces opcodes and syscalls.
+	/// A tracer that traces opcodes and syscalls, also known as the geth struct logger.
+	#[serde(alias = "structLogger")]
 	ExecutionTracer(Option<ExecutionTracerConfig>),
 }
 
@@ -288,6 +289,13 @@ fn test_tracer_config_serialization() {
 				timeout: None,
 			},
 		),
+		(
+			r#"{"tracer": "structLogger", "tracerConfig": { "enableMemory": true }}"#,
+			TracerConfig {
+				config: ExecutionTracerConfig { enable_memory: true, ..Default::default() }.into(),
+				timeout: None,
+			},
+		),
 		(
 			r#"{ "enableMemory": true }"#,
 			TracerConfig {
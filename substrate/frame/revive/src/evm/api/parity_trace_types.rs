@@ -0,0 +1,494 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the Parity `trace_*` JSON-RPC methods.
+//!
+//! The traces are built from the [`CallTrace`] of the call tracer, flattened into a list where
+//! each call is located in the call tree by its `traceAddress`.
+
+use super::{BlockNumberOrTag, Bytes, CallTrace, CallType};
+use alloc::{string::String, vec, vec::Vec};
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The error reported by Parity traces for calls that reverted.
+const REVERTED_ERROR: &str = "Reverted";
+
+/// The type of a Parity trace.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParityTraceType {
+	/// A message call.
+	Call,
+	/// A contract creation.
+	Create,
+	/// A contract self-destruction.
+	Suicide,
+}
+
+/// The type of a Parity call action.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParityCallType {
+	/// A regular call.
+	Call,
+	/// A read-only call.
+	StaticCall,
+	/// A delegate call.
+	DelegateCall,
+}
+
+/// The opcode used to create a contract.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParityCreationMethod {
+	/// The contract was created with `CREATE`.
+	Create,
+	/// The contract was created with `CREATE2`.
+	Create2,
+}
+
+/// The action of a message call.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParityCallAction {
+	/// Address of the sender.
+	pub from: H160,
+	/// Address of the receiver.
+	pub to: H160,
+	/// Amount of value transferred.
+	pub value: U256,
+	/// Amount of gas provided for the call.
+	#[serde(with = "super::hex_serde")]
+	pub gas: u64,
+	/// Call input data.
+	pub input: Bytes,
+	/// Type of call.
+	pub call_type: ParityCallType,
+}
+
+/// The action of a contract creation.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParityCreateAction {
+	/// Address of the creator.
+	pub from: H160,
+	/// Amount of value transferred to the new contract.
+	pub value: U256,
+	/// Amount of gas provided for the creation.
+	#[serde(with = "super::hex_serde")]
+	pub gas: u64,
+	/// The init code.
+	pub init: Bytes,
+	/// The opcode used to create the contract.
+	pub creation_method: ParityCreationMethod,
+}
+
+/// The action of a contract self-destruction.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParitySuicideAction {
+	/// Address of the destroyed contract.
+	pub address: H160,
+	/// Address receiving the remaining balance.
+	pub refund_address: H160,
+	/// The balance transferred to the refund address.
+	pub balance: U256,
+}
+
+/// The action of a Parity trace.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum ParityAction {
+	/// A message call.
+	Call(ParityCallAction),
+	/// A contract creation.
+	Create(ParityCreateAction),
+	/// A contract self-destruction.
+	Suicide(ParitySuicideAction),
+}
+
+/// The result of a successful message call.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParityCallResult {
+	/// Amount of gas used.
+	#[serde(with = "super::hex_serde")]
+	pub gas_used: u64,
+	/// Return data.
+	pub output: Bytes,
+}
+
+/// The result of a successful contract creation.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParityCreateResult {
+	/// Amount of gas used.
+	#[serde(with = "super::hex_serde")]
+	pub gas_used: u64,
+	/// The code of the created contract.
+	pub code: Bytes,
+	/// Address of the created contract.
+	pub address: H160,
+}
+
+/// The result of a Parity trace.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum ParityTraceResult {
+	/// The result of a contract creation.
+	Create(ParityCreateResult),
+	/// The result of a message call.
+	Call(ParityCallResult),
+}
+
+/// A flat Parity trace, as returned by `trace_block`, `trace_transaction` and `trace_filter`.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParityTrace {
+	/// The action performed by the call.
+	pub action: ParityAction,
+	/// Hash of the block containing the transaction.
+	pub block_hash: H256,
+	/// Number of the block containing the transaction.
+	pub block_number: u64,
+	/// The error message if the call failed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The result of the call, `None` if the call failed or for self-destructions.
+	pub result: Option<ParityTraceResult>,
+	/// Number of direct sub-calls.
+	pub subtraces: usize,
+	/// Position of the call in the call tree of the transaction.
+	pub trace_address: Vec<usize>,
+	/// Hash of the transaction.
+	pub transaction_hash: H256,
+	/// Index of the transaction in the block.
+	pub transaction_position: u64,
+	/// The type of the trace.
+	#[serde(rename = "type")]
+	pub trace_type: ParityTraceType,
+}
+
+/// The filter used by `trace_filter`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+	/// The first block to trace, defaults to the latest block.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to trace, defaults to the latest block.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Only return the traces sent from one of these addresses.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub from_address: Option<Vec<H160>>,
+	/// Only return the traces sent to one of these addresses.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub to_address: Option<Vec<H160>>,
+	/// The number of matching traces to skip.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub after: Option<u64>,
+	/// The maximum number of traces to return.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub count: Option<u64>,
+}
+
+impl TraceFilter {
+	/// Returns `true` if the given trace matches the address criteria of this filter.
+	///
+	/// The block range and pagination criteria are ignored, they are expected to be applied by the
+	/// caller.
+	pub fn matches(&self, trace: &ParityTrace) -> bool {
+		let (from, to) = match (&trace.action, &trace.result) {
+			(ParityAction::Call(action), _) => (action.from, Some(action.to)),
+			(ParityAction::Create(action), Some(ParityTraceResult::Create(result))) => {
+				(action.from, Some(result.address))
+			},
+			(ParityAction::Create(action), _) => (action.from, None),
+			(ParityAction::Suicide(action), _) => (action.address, Some(action.refund_address)),
+		};
+
+		let matches = |addresses: &Option<Vec<H160>>, address: Option<H160>| match addresses {
+			None => true,
+			Some(addresses) if addresses.is_empty() => true,
+			Some(addresses) => address.is_some_and(|address| addresses.contains(&address)),
+		};
+
+		matches(&self.from_address, Some(from)) && matches(&self.to_address, to)
+	}
+}
+
+impl CallTrace {
+	/// Flatten this call trace into a list of Parity traces, in depth-first order.
+	pub fn into_parity_traces(
+		self,
+		block_hash: H256,
+		block_number: u64,
+		transaction_hash: H256,
+		transaction_position: u64,
+	) -> Vec<ParityTrace> {
+		let mut traces = Vec::new();
+		let mut stack = vec![(self, Vec::new())];
+
+		while let Some((trace, trace_address)) = stack.pop() {
+			let CallTrace {
+				from,
+				gas,
+				gas_used,
+				to,
+				input,
+				output,
+				error,
+				calls,
+				value,
+				call_type,
+				..
+			} = trace;
+			let value = value.unwrap_or_default();
+			let error = error.map(|error| {
+				if error == "execution reverted" {
+					REVERTED_ERROR.into()
+				} else {
+					error
+				}
+			});
+
+			let (trace_type, action, result) = match call_type {
+				CallType::Create | CallType::Create2 => (
+					ParityTraceType::Create,
+					ParityAction::Create(ParityCreateAction {
+						from,
+						value,
+						gas,
+						init: input,
+						creation_method: if call_type == CallType::Create {
+							ParityCreationMethod::Create
+						} else {
+							ParityCreationMethod::Create2
+						},
+					}),
+					ParityTraceResult::Create(ParityCreateResult {
+						gas_used,
+						code: output,
+						address: to,
+					}),
+				),
+				CallType::Selfdestruct => {
+					let action =
+						ParitySuicideAction { address: from, refund_address: to, balance: value };
+					traces.push(ParityTrace {
+						action: ParityAction::Suicide(action),
+						block_hash,
+						block_number,
+						error,
+						result: None,
+						subtraces: 0,
+						trace_address,
+						transaction_hash,
+						transaction_position,
+						trace_type: ParityTraceType::Suicide,
+					});
+					continue;
+				},
+				CallType::Call | CallType::StaticCall | CallType::DelegateCall => (
+					ParityTraceType::Call,
+					ParityAction::Call(ParityCallAction {
+						from,
+						to,
+						value,
+						gas,
+						input,
+						call_type: match call_type {
+							CallType::StaticCall => ParityCallType::StaticCall,
+							CallType::DelegateCall => ParityCallType::DelegateCall,
+							_ => ParityCallType::Call,
+						},
+					}),
+					ParityTraceResult::Call(ParityCallResult { gas_used, output }),
+				),
+			};
+
+			traces.push(ParityTrace {
+				action,
+				block_hash,
+				block_number,
+				result: if error.is_none() { Some(result) } else { None },
+				error,
+				subtraces: calls.len(),
+				trace_address: trace_address.clone(),
+				transaction_hash,
+				transaction_position,
+				trace_type,
+			});
+
+			// Push the children in reverse order, so that they are popped in call order.
+			for (index, call) in calls.into_iter().enumerate().rev() {
+				let mut child_address = trace_address.clone();
+				child_address.push(index);
+				stack.push((call, child_address));
+			}
+		}
+
+		traces
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn call_trace() -> CallTrace {
+		CallTrace {
+			from: H160::repeat_byte(1),
+			to: H160::repeat_byte(2),
+			gas: 100,
+			gas_used: 50,
+			calls: vec![
+				CallTrace {
+					from: H160::repeat_byte(2),
+					to: H160::repeat_byte(3),
+					call_type: CallType::Create2,
+					output: Bytes(vec![0xfe]),
+					calls: vec![CallTrace {
+						from: H160::repeat_byte(3),
+						to: H160::repeat_byte(4),
+						call_type: CallType::StaticCall,
+						..Default::default()
+					}],
+					..Default::default()
+				},
+				CallTrace {
+					from: H160::repeat_byte(2),
+					to: H160::repeat_byte(5),
+					error: Some("execution reverted".into()),
+					..Default::default()
+				},
+				CallTrace {
+					from: H160::repeat_byte(2),
+					to: H160::repeat_byte(6),
+					value: Some(U256::from(7)),
+					call_type: CallType::Selfdestruct,
+					..Default::default()
+				},
+			],
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn into_parity_traces_works() {
+		let traces =
+			call_trace().into_parity_traces(H256::repeat_byte(9), 42, H256::repeat_byte(8), 3);
+
+		let addresses: Vec<_> = traces.iter().map(|t| t.trace_address.clone()).collect();
+		assert_eq!(addresses, vec![vec![], vec![0], vec![0, 0], vec![1], vec![2]]);
+
+		let subtraces: Vec<_> = traces.iter().map(|t| t.subtraces).collect();
+		assert_eq!(subtraces, vec![3, 1, 0, 0, 0]);
+
+		let types: Vec<_> = traces.iter().map(|t| t.trace_type).collect();
+		assert_eq!(
+			types,
+			vec![
+				ParityTraceType::Call,
+				ParityTraceType::Create,
+				ParityTraceType::Call,
+				ParityTraceType::Call,
+				ParityTraceType::Suicide
+			]
+		);
+
+		assert!(traces.iter().all(|t| t.block_number == 42 && t.transaction_position == 3));
+		assert_eq!(
+			traces[1].result,
+			Some(ParityTraceResult::Create(ParityCreateResult {
+				gas_used: 0,
+				code: Bytes(vec![0xfe]),
+				address: H160::repeat_byte(3),
+			}))
+		);
+		assert_eq!(traces[3].error, Some("Reverted".into()));
+		assert_eq!(traces[3].result, None);
+		assert_eq!(
+			traces[4].action,
+			ParityAction::Suicide(ParitySuicideAction {
+				address: H160::repeat_byte(2),
+				refund_address: H160::repeat_byte(6),
+				balance: U256::from(7),
+			})
+		);
+	}
+
+	#[test]
+	fn parity_trace_serialization_works() {
+		let trace = call_trace().into_parity_traces(H256::zero(), 1, H256::zero(), 0).remove(0);
+		let json = serde_json::to_value(&trace).unwrap();
+
+		assert_eq!(json["type"], "call");
+		assert_eq!(json["action"]["callType"], "call");
+		assert_eq!(json["action"]["gas"], "0x64");
+		assert_eq!(json["result"]["gasUsed"], "0x32");
+		assert_eq!(json["traceAddress"], serde_json::json!([]));
+		assert_eq!(json["blockNumber"], 1);
+		assert!(json.get("error").is_none());
+		assert_eq!(serde_json::from_value::<ParityTrace>(json).unwrap(), trace);
+	}
+
+	#[test]
+	fn trace_filter_matches_works() {
+		let traces = call_trace().into_parity_traces(H256::zero(), 1, H256::zero(), 0);
+		let matching = |filter: TraceFilter| {
+			traces
+				.iter()
+				.filter(|t| filter.matches(t))
+				.map(|t| t.trace_address.clone())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(matching(TraceFilter::default()).len(), traces.len());
+		assert_eq!(
+			matching(TraceFilter {
+				from_address: Some(vec![H160::repeat_byte(2)]),
+				..Default::default()
+			}),
+			vec![vec![0], vec![1], vec![2]]
+		);
+		assert_eq!(
+			matching(TraceFilter {
+				to_address: Some(vec![H160::repeat_byte(3)]),
+				..Default::default()
+			}),
+			vec![vec![0]]
+		);
+		assert_eq!(
+			matching(TraceFilter {
+				from_address: Some(vec![H160::repeat_byte(2)]),
+				to_address: Some(vec![H160::repeat_byte(6)]),
+				..Default::default()
+			}),
+			vec![vec![2]]
+		);
+
+		let filter: TraceFilter = serde_json::from_str(
+			r#"{"fromBlock":"0x1","toBlock":"latest","toAddress":["0x0303030303030303030303030303030303030303"],"after":1,"count":10}"#,
+		)
+		.unwrap();
+		assert_eq!(filter.after, Some(1));
+		assert_eq!(filter.count, Some(10));
+		assert_eq!(filter.to_address, Some(vec![H160::repeat_byte(3)]));
+	}
+}
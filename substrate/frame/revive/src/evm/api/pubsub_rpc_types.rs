@@ -0,0 +1,176 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_subscribe` / `eth_unsubscribe` JSON-RPC methods.
+
+use super::{Address, AddressOrAddresses, Block, Filter, FilterTopic, Log};
+use alloc::{boxed::Box, vec::Vec};
+use derive_more::From;
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// The kind of subscription requested via `eth_subscribe`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// Fires a notification each time a new header is appended to the chain.
+	NewHeads,
+	/// Returns logs that are included in new imported blocks and match the given filter.
+	Logs,
+	/// Returns the hash of every transaction that is added to the pending state.
+	NewPendingTransactions,
+}
+
+/// An item sent to an `eth_subscribe` subscriber.
+#[derive(Debug, Clone, Serialize, Deserialize, From, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A new block header, sent to `newHeads` subscribers.
+	Header(Box<Block>),
+	/// A log, sent to `logs` subscribers.
+	Log(Log),
+	/// A transaction hash, sent to `newPendingTransactions` subscribers.
+	TransactionHash(H256),
+}
+
+/// The maximum number of topics a log can have, and hence that a [`Filter`] can match on.
+pub const MAX_FILTER_TOPICS: usize = 4;
+
+impl Filter {
+	/// Returns the addresses a log must be emitted by to match this filter, or `None` if logs
+	/// emitted by any address match.
+	pub fn address_criteria(&self) -> Option<&[Address]> {
+		match &self.address {
+			Some(AddressOrAddresses::Address(address)) => Some(core::slice::from_ref(address)),
+			Some(AddressOrAddresses::Addresses(addresses)) if !addresses.is_empty() => {
+				Some(addresses)
+			},
+			_ => None,
+		}
+	}
+
+	/// Returns, for each topic position of this filter, the topics a log can have at that
+	/// position to match.
+	///
+	/// An empty list matches any topic, but the log must still have a topic at that position.
+	/// Returns an error if the filter has more than [`MAX_FILTER_TOPICS`] positions.
+	pub fn topic_criteria(&self) -> Result<Vec<&[H256]>, &'static str> {
+		let Some(topics) = &self.topics else { return Ok(Vec::new()) };
+		if topics.len() > MAX_FILTER_TOPICS {
+			return Err("exceed max topics");
+		}
+
+		Ok(topics
+			.iter()
+			.map(|topic| match topic {
+				FilterTopic::Single(hash) => core::slice::from_ref(hash),
+				FilterTopic::Multiple(hashes) => &hashes[..],
+			})
+			.collect())
+	}
+
+	/// Returns `true` if the given log matches the address and topics criteria of this filter.
+	///
+	/// The block range and block hash criteria are ignored, they are expected to be checked by the
+	/// caller.
+	pub fn matches_log(&self, log: &Log) -> bool {
+		if self
+			.address_criteria()
+			.is_some_and(|addresses| !addresses.contains(&log.address))
+		{
+			return false;
+		}
+
+		let Ok(topics) = self.topic_criteria() else { return false };
+		topics.into_iter().enumerate().all(|(i, topics)| {
+			log.topics
+				.get(i)
+				.is_some_and(|topic| topics.is_empty() || topics.contains(topic))
+		})
+	}
+}
+
+#[test]
+fn subscription_kind_deserialization_works() {
+	for (json, kind) in [
+		("\"newHeads\"", SubscriptionKind::NewHeads),
+		("\"logs\"", SubscriptionKind::Logs),
+		("\"newPendingTransactions\"", SubscriptionKind::NewPendingTransactions),
+	] {
+		assert_eq!(serde_json::from_str::<SubscriptionKind>(json).unwrap(), kind);
+	}
+}
+
+#[test]
+fn filter_matches_log_works() {
+	use sp_core::H160;
+
+	let log = Log {
+		address: H160::repeat_byte(1),
+		topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+		..Default::default()
+	};
+
+	assert!(Filter::default().matches_log(&log));
+	assert!(Filter { address: Some(H160::repeat_byte(1).into()), ..Default::default() }
+		.matches_log(&log));
+	assert!(!Filter { address: Some(H160::repeat_byte(2).into()), ..Default::default() }
+		.matches_log(&log));
+	assert!(Filter {
+		address: Some(vec![H160::repeat_byte(2), H160::repeat_byte(1)].into()),
+		..Default::default()
+	}
+	.matches_log(&log));
+
+	assert!(Filter {
+		topics: Some(vec![
+			FilterTopic::Single(H256::repeat_byte(2)),
+			FilterTopic::Multiple(vec![H256::repeat_byte(4), H256::repeat_byte(3)]),
+		]),
+		..Default::default()
+	}
+	.matches_log(&log));
+	assert!(!Filter {
+		topics: Some(vec![FilterTopic::Single(H256::repeat_byte(3))]),
+		..Default::default()
+	}
+	.matches_log(&log));
+	assert!(!Filter {
+		topics: Some(vec![
+			FilterTopic::Multiple(vec![]),
+			FilterTopic::Multiple(vec![]),
+			FilterTopic::Single(H256::repeat_byte(3)),
+		]),
+		..Default::default()
+	}
+	.matches_log(&log));
+
+	// Empty lists match anything.
+	assert!(Filter {
+		address: Some(Vec::<H160>::new().into()),
+		topics: Some(vec![
+			FilterTopic::Multiple(vec![]),
+			FilterTopic::Single(H256::repeat_byte(3))
+		]),
+		..Default::default()
+	}
+	.matches_log(&log));
+
+	// Too many topics.
+	assert!(Filter { topics: Some(vec![Default::default(); 5]), ..Default::default() }
+		.topic_criteria()
+		.is_err());
+}
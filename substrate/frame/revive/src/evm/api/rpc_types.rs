@@ -62,6 +62,20 @@ impl From<TransactionSigned> for TransactionUnsigned {
 	}
 }
 
+impl TransactionSigned {
+	/// Returns the EIP-2930 access list of the transaction, empty for legacy transactions.
+	pub fn access_list(&self) -> &[AccessListEntry] {
+		use TransactionSigned::*;
+		match self {
+			Transaction7702Signed(tx) => &tx.transaction_7702_unsigned.access_list,
+			Transaction4844Signed(tx) => &tx.transaction_4844_unsigned.access_list,
+			Transaction1559Signed(tx) => &tx.transaction_1559_unsigned.access_list,
+			Transaction2930Signed(tx) => &tx.transaction_2930_unsigned.access_list,
+			TransactionLegacySigned(_) => &[],
+		}
+	}
+}
+
 impl TransactionInfo {
 	/// Create a new [`TransactionInfo`] from a receipt and a signed transaction.
 	pub fn new(receipt: &ReceiptInfo, transaction_signed: TransactionSigned) -> Self {
@@ -327,9 +341,20 @@ impl GenericTransaction {
 		tx
 	}
 
+	/// The type of the transaction when none is set: EIP-7702 if the transaction carries an
+	/// authorization list, legacy otherwise.
+	pub fn default_type(&self) -> u8 {
+		if self.authorization_list.is_empty() {
+			TYPE_LEGACY
+		} else {
+			TYPE_EIP7702
+		}
+	}
+
 	/// Convert to a [`TransactionUnsigned`].
 	pub fn try_into_unsigned(self) -> Result<TransactionUnsigned, ()> {
-		match self.r#type.unwrap_or_default().0 {
+		let r#type = self.r#type.as_ref().map_or_else(|| self.default_type(), |r#type| r#type.0);
+		match r#type {
 			TYPE_LEGACY => Ok(TransactionLegacyUnsigned {
 				r#type: TypeLegacy {},
 				chain_id: self.chain_id,
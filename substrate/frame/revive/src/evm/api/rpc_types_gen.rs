@@ -554,7 +554,9 @@ impl HashesOrTransactionInfos {
 }
 
 /// log
-#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
 #[serde(rename_all = "camelCase")]
 pub struct Log {
 	/// address
@@ -919,6 +921,19 @@ pub struct AccessListEntry {
 	pub storage_keys: Vec<H256>,
 }
 
+/// Access list result
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// The access list of the transaction
+	pub access_list: AccessList,
+	/// The error message, if the transaction failed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<alloc::string::String>,
+	/// The gas used by the transaction when executed with the access list
+	pub gas_used: U256,
+}
+
 /// Filter Topic List Entry
 #[derive(Debug, Clone, Serialize, Deserialize, From, TryInto, Eq, PartialEq)]
 #[serde(untagged)]
@@ -1108,6 +1123,38 @@ pub struct FeeHistoryResult {
 	pub reward: Vec<Vec<U256>>,
 }
 
+/// Account proof
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+	/// address
+	pub address: Address,
+	/// accountProof
+	pub account_proof: Vec<Bytes>,
+	/// balance
+	pub balance: U256,
+	/// codeHash
+	pub code_hash: H256,
+	/// nonce
+	pub nonce: U256,
+	/// storageHash
+	pub storage_hash: H256,
+	/// Storage proofs
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// Storage proof
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+	/// key
+	pub key: U256,
+	/// value
+	pub value: U256,
+	/// proof
+	pub proof: Vec<Bytes>,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -17,6 +17,7 @@
 //! Ethereum signature utilities
 
 use super::*;
+use alloc::vec::Vec;
 use sp_core::{H160, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 
@@ -182,6 +183,44 @@ impl TransactionSigned {
 	}
 }
 
+/// Half of the order of the secp256k1 curve, the upper bound of the `s` value of a signature.
+const SECP256K1N_HALF: U256 =
+	U256([0xdfe92f46681b20a0, 0x5d576e7357a4501d, 0xffffffffffffffff, 0x7fffffffffffffff]);
+
+impl AuthorizationListEntry {
+	/// The magic byte prepended to the authorization tuple before hashing it.
+	/// See <https://eips.ethereum.org/EIPS/eip-7702>
+	pub const MAGIC: u8 = 0x05;
+
+	/// Get the payload signed by the authority.
+	pub fn signing_payload(chain_id: U256, address: H160, nonce: U256) -> Vec<u8> {
+		let mut s = rlp::RlpStream::new_list(3);
+		s.append(&chain_id);
+		s.append(&address);
+		s.append(&nonce);
+		[&[Self::MAGIC][..], &s.out()].concat()
+	}
+
+	/// Recover the address of the authority that signed this authorization.
+	pub fn recover_authority(&self) -> Result<H160, ()> {
+		if self.s > SECP256K1N_HALF {
+			return Err(());
+		}
+
+		let mut signature = [0u8; 65];
+		self.r.write_as_big_endian(signature[0..32].as_mut());
+		self.s.write_as_big_endian(signature[32..64].as_mut());
+		signature[64] = self.y_parity.try_into().map_err(|_| ())?;
+
+		let payload = Self::signing_payload(self.chain_id, self.address, self.nonce);
+		let hash = keccak_256(&payload);
+		let mut addr = H160::default();
+		let pk = secp256k1_ecdsa_recover(&signature, &hash).map_err(|_| ())?;
+		addr.assign_from_slice(&keccak_256(&pk[..])[12..]);
+		Ok(addr)
+	}
+}
+
 #[test]
 fn sign_and_recover_work() {
 	use crate::evm::TransactionUnsigned;
@@ -211,3 +250,18 @@ fn sign_and_recover_work() {
 		assert_eq!(tx, signed);
 	}
 }
+
+#[test]
+fn sign_and_recover_authorization_work() {
+	let account = Account::default();
+	let authorization = account.sign_authorization(1.into(), H160::repeat_byte(1), 7.into());
+	assert_eq!(authorization.recover_authority(), Ok(account.address()));
+
+	// The signature does not match a different authorization tuple.
+	let other = AuthorizationListEntry { nonce: 8.into(), ..authorization.clone() };
+	assert_ne!(other.recover_authority(), Ok(account.address()));
+
+	// High `s` values are rejected.
+	let malleable = AuthorizationListEntry { s: U256::MAX, ..authorization };
+	assert_eq!(malleable.recover_authority(), Err(()));
+}
@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `eth_simulateV1` JSON-RPC method and by the state overrides of `eth_call`
+//! and `eth_estimateGas`.
+
+use super::{Bytes, GenericTransaction, Log};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The error code of a simulated call that reverted.
+pub const SIMULATE_REVERTED_ERROR_CODE: i32 = 3;
+
+/// The error code of a simulated call that failed to execute.
+pub const SIMULATE_EXECUTION_ERROR_CODE: i32 = -32015;
+
+/// Overrides of the state of an account, applied before executing a call.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Fake balance to set for the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// Fake nonce to set for the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<U256>,
+	/// Fake EVM bytecode (or PVM blob) to inject into the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Fake key-value mapping replacing all the slots of the account storage.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Fake key-value mapping overriding individual slots of the account storage.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// The state overrides of a call, keyed by account address.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(transparent)]
+pub struct StateOverride(pub BTreeMap<H160, AccountOverride>);
+
+/// A block of calls to simulate with `eth_simulateV1`.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+	/// The state overrides applied before executing the calls of the block.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state_overrides: Option<StateOverride>,
+	/// The calls to execute, in order.
+	#[serde(default)]
+	pub calls: Vec<GenericTransaction>,
+}
+
+/// The payload of `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+	/// The blocks to simulate, in order.
+	pub block_state_calls: Vec<SimulateBlock>,
+}
+
+/// The error of a simulated call.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+pub struct SimulateCallError {
+	/// The error code, [`SIMULATE_REVERTED_ERROR_CODE`] if the call reverted.
+	pub code: i32,
+	/// The error message.
+	pub message: String,
+}
+
+/// The result of a simulated call.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallResult {
+	/// The data returned by the call, or the revert data if the call reverted.
+	pub return_data: Bytes,
+	/// The logs emitted by the call, empty if the call failed.
+	pub logs: Vec<Log>,
+	/// The gas used by the call, zero if the call failed.
+	pub gas_used: U256,
+	/// `1` if the call succeeded, `0` otherwise.
+	pub status: U256,
+	/// The error of the call, if it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<SimulateCallError>,
+}
+
+/// The result of a simulated block.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+	/// The number of the simulated block.
+	pub number: U256,
+	/// The gas used by all the calls of the block.
+	pub gas_used: U256,
+	/// The results of the calls of the block, in order.
+	pub calls: Vec<SimulateCallResult>,
+}
+
+#[test]
+fn simulate_payload_deserialization_works() {
+	let json = r#"{
+		"blockStateCalls": [{
+			"stateOverrides": {
+				"0x0101010101010101010101010101010101010101": {
+					"balance": "0x10",
+					"stateDiff": {
+						"0x0000000000000000000000000000000000000000000000000000000000000001":
+						"0x0000000000000000000000000000000000000000000000000000000000000002"
+					}
+				}
+			},
+			"calls": [{ "to": "0x0101010101010101010101010101010101010101" }]
+		}],
+		"validation": false
+	}"#;
+
+	let payload = serde_json::from_str::<SimulatePayload>(json).unwrap();
+	let address = H160::repeat_byte(1);
+	let mut state_diff = BTreeMap::new();
+	state_diff.insert(H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+	let mut overrides = BTreeMap::new();
+	overrides.insert(
+		address,
+		AccountOverride {
+			balance: Some(16.into()),
+			state_diff: Some(state_diff),
+			..Default::default()
+		},
+	);
+
+	assert_eq!(
+		payload,
+		SimulatePayload {
+			block_state_calls: vec![SimulateBlock {
+				state_overrides: Some(StateOverride(overrides)),
+				calls: vec![GenericTransaction { to: Some(address), ..Default::default() }],
+			}],
+		}
+	);
+}
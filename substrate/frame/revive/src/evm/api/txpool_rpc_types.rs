@@ -0,0 +1,184 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by the `txpool_*` JSON-RPC methods.
+//!
+//! The transactions of the pool are grouped by sender address and nonce, the nonce being
+//! formatted as a decimal string, as done by Geth.
+
+use super::GenericTransaction;
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// Transactions grouped by sender address and nonce.
+pub type TxPoolTransactions<T> = BTreeMap<H160, BTreeMap<String, T>>;
+
+/// The number of transactions in the pool, returned by `txpool_status`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TxPoolStatus {
+	/// The number of transactions that are ready to be included in a block.
+	pub pending: U256,
+	/// The number of transactions that are waiting for a nonce gap to be filled.
+	pub queued: U256,
+}
+
+/// A transaction of the pool, returned by `txpool_content`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxPoolTransaction {
+	/// Always `None`, the transaction is not included in a block yet.
+	pub block_hash: Option<H256>,
+	/// Always `None`, the transaction is not included in a block yet.
+	pub block_number: Option<U256>,
+	/// Always `None`, the transaction is not included in a block yet.
+	pub transaction_index: Option<U256>,
+	/// The transaction hash.
+	pub hash: H256,
+	/// The transaction.
+	#[serde(flatten)]
+	pub transaction: GenericTransaction,
+}
+
+impl TxPoolTransaction {
+	/// A textual summary of the transaction, as returned by `txpool_inspect`.
+	///
+	/// For example: `0x1234…: 1 wei + 21000 gas × 1000 wei`.
+	pub fn summary(&self) -> String {
+		let tx = &self.transaction;
+		let to = tx.to.map_or_else(|| String::from("contract creation"), |to| format!("{to:?}"));
+		let gas_price = tx.gas_price.or(tx.max_fee_per_gas).unwrap_or_default();
+		format!(
+			"{to}: {} wei + {} gas × {gas_price} wei",
+			tx.value.unwrap_or_default(),
+			tx.gas.unwrap_or_default(),
+		)
+	}
+}
+
+/// The transactions of the pool, returned by `txpool_content`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TxPoolContent {
+	/// The transactions that are ready to be included in a block.
+	pub pending: TxPoolTransactions<TxPoolTransaction>,
+	/// The transactions that are waiting for a nonce gap to be filled.
+	pub queued: TxPoolTransactions<TxPoolTransaction>,
+}
+
+/// A summary of the transactions of the pool, returned by `txpool_inspect`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TxPoolInspect {
+	/// The summaries of the transactions that are ready to be included in a block.
+	pub pending: TxPoolTransactions<String>,
+	/// The summaries of the transactions that are waiting for a nonce gap to be filled.
+	pub queued: TxPoolTransactions<String>,
+}
+
+impl TxPoolContent {
+	/// Group the given `pending` and `queued` transactions by sender and nonce.
+	///
+	/// Transactions without a sender are ignored.
+	pub fn new(pending: Vec<TxPoolTransaction>, queued: Vec<TxPoolTransaction>) -> Self {
+		Self { pending: group_by_sender(pending), queued: group_by_sender(queued) }
+	}
+
+	/// The number of pending and queued transactions.
+	pub fn status(&self) -> TxPoolStatus {
+		let count = |txs: &TxPoolTransactions<TxPoolTransaction>| {
+			U256::from(txs.values().map(|by_nonce| by_nonce.len()).sum::<usize>())
+		};
+		TxPoolStatus { pending: count(&self.pending), queued: count(&self.queued) }
+	}
+
+	/// The summaries of the pending and queued transactions.
+	pub fn inspect(&self) -> TxPoolInspect {
+		let summarize = |txs: &TxPoolTransactions<TxPoolTransaction>| {
+			txs.iter()
+				.map(|(sender, by_nonce)| {
+					let summaries =
+						by_nonce.iter().map(|(nonce, tx)| (nonce.clone(), tx.summary())).collect();
+					(*sender, summaries)
+				})
+				.collect()
+		};
+		TxPoolInspect { pending: summarize(&self.pending), queued: summarize(&self.queued) }
+	}
+}
+
+fn group_by_sender(transactions: Vec<TxPoolTransaction>) -> TxPoolTransactions<TxPoolTransaction> {
+	let mut grouped = TxPoolTransactions::<TxPoolTransaction>::new();
+	for tx in transactions {
+		let Some(sender) = tx.transaction.from else { continue };
+		let nonce = tx.transaction.nonce.unwrap_or_default();
+		grouped.entry(sender).or_default().insert(format!("{nonce}"), tx);
+	}
+	grouped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn transaction(from: H160, nonce: u64, to: Option<H160>) -> TxPoolTransaction {
+		TxPoolTransaction {
+			hash: H256::from_low_u64_be(nonce),
+			transaction: GenericTransaction {
+				from: Some(from),
+				to,
+				nonce: Some(nonce.into()),
+				value: Some(1.into()),
+				gas: Some(21_000.into()),
+				gas_price: Some(1_000.into()),
+				..Default::default()
+			},
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn content_is_grouped_by_sender_and_nonce() {
+		let alice = H160::repeat_byte(1);
+		let bob = H160::repeat_byte(2);
+		let content = TxPoolContent::new(
+			vec![
+				transaction(alice, 10, Some(bob)),
+				transaction(alice, 11, None),
+				transaction(bob, 0, Some(alice)),
+			],
+			vec![],
+		);
+
+		assert_eq!(content.pending[&alice].keys().collect::<Vec<_>>(), vec!["10", "11"]);
+		assert_eq!(content.pending[&bob].keys().collect::<Vec<_>>(), vec!["0"]);
+		assert_eq!(content.status(), TxPoolStatus { pending: 3.into(), queued: 0.into() });
+
+		let inspect = content.inspect();
+		assert_eq!(inspect.pending[&alice]["10"], format!("{bob:?}: 1 wei + 21000 gas × 1000 wei"));
+		assert_eq!(
+			inspect.pending[&alice]["11"],
+			"contract creation: 1 wei + 21000 gas × 1000 wei"
+		);
+	}
+
+	#[test]
+	fn pending_transaction_has_null_block_fields() {
+		let tx = transaction(H160::repeat_byte(1), 0, None);
+		let json = serde_json::to_value(&tx).unwrap();
+		assert_eq!(json["blockHash"], serde_json::Value::Null);
+		assert_eq!(json["nonce"], "0x0");
+		assert_eq!(serde_json::from_value::<TxPoolTransaction>(json).unwrap(), tx);
+	}
+}
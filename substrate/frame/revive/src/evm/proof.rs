@@ -0,0 +1,182 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Verification of the account and storage proofs returned by `eth_getProof`.
+//!
+//! Unlike Ethereum, contract state is not stored in a Merkle-Patricia trie keyed by the account
+//! address. An account proof is a substrate storage proof of the [`AccountInfo`] of the address,
+//! of its account mapping, of the `frame_system` account it is mapped to (and of the root of its
+//! storage child trie, for contracts) against the block state root. A storage proof is a substrate
+//! storage proof of a slot against the root of the contract child trie.
+
+use crate::{exec::Key, storage::AccountType, AccountInfo, AccountInfoOf, Config, OriginalAccount};
+use alloc::vec::Vec;
+use codec::Decode;
+use frame_support::storage::child::ChildInfo;
+use sp_core::{H160, H256};
+use sp_runtime::traits::Hash;
+use sp_trie::{read_trie_value, LayoutV1, StorageProof};
+
+/// The hasher used by the state trie of the runtime.
+type HasherOf<T> = <T as frame_system::Config>::Hashing;
+
+/// An error returned when verifying a proof.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofError {
+	/// The proof is incomplete or does not match the given root.
+	InvalidProof,
+	/// A value found in the proof could not be decoded.
+	DecodingFailed,
+	/// The proven balance or nonce does not match the claimed one.
+	ValueMismatch,
+}
+
+/// The account information proven by an account proof.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifiedAccount<T: Config> {
+	/// The account info of the address, `None` if the account does not exist.
+	pub account_info: Option<AccountInfo<T>>,
+	/// The root of the storage child trie of the account, `None` if the account is not a
+	/// contract.
+	pub storage_root: Option<H256>,
+}
+
+/// The storage key of the [`AccountInfo`] of the given address in the state trie.
+pub fn account_info_key<T: Config>(address: &H160) -> Vec<u8> {
+	AccountInfoOf::<T>::hashed_key_for(address)
+}
+
+/// The storage key of the account id the given address is mapped to in the state trie.
+pub fn original_account_key<T: Config>(address: &H160) -> Vec<u8> {
+	OriginalAccount::<T>::hashed_key_for(address)
+}
+
+/// The storage key of the `frame_system` account of the given account id in the state trie.
+pub fn system_account_key<T: Config>(account_id: &T::AccountId) -> Vec<u8> {
+	frame_system::Account::<T>::hashed_key_for(account_id)
+}
+
+/// The storage key of the root of the given contract child trie in the state trie.
+pub fn storage_root_key(trie_id: &[u8]) -> Vec<u8> {
+	ChildInfo::new_default(trie_id).prefixed_storage_key().into_inner()
+}
+
+/// The storage key of the given storage slot in a contract child trie.
+pub fn storage_slot_key(slot: [u8; 32]) -> Vec<u8> {
+	Key::Fix(slot).hash()
+}
+
+fn read_value<T: Config>(
+	root: H256,
+	proof: &StorageProof,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, ProofError>
+where
+	HasherOf<T>: Hash<Output = H256>,
+{
+	let db = proof.to_memory_db::<HasherOf<T>>();
+	read_trie_value::<LayoutV1<HasherOf<T>>, _>(&db, &root, key, None, None)
+		.map_err(|_| ProofError::InvalidProof)
+}
+
+/// Read the value of `key` from the proof and copy it into `ext`.
+#[cfg(feature = "std")]
+fn read_into<T: Config>(
+	ext: &mut sp_io::TestExternalities,
+	root: H256,
+	proof: &StorageProof,
+	key: Vec<u8>,
+) -> Result<Option<Vec<u8>>, ProofError>
+where
+	HasherOf<T>: Hash<Output = H256>,
+{
+	let value = read_value::<T>(root, proof, &key)?;
+	if let Some(value) = &value {
+		ext.insert(key, value.clone());
+	}
+	Ok(value)
+}
+
+/// Verify the account proof of `address` against the given block `state_root`, and that it
+/// proves the claimed `balance` and `nonce` of the account.
+///
+/// The balance depends on the currency of the runtime, so it is computed by
+/// [`Pallet::evm_balance`](crate::Pallet::evm_balance) on top of the proven storage only.
+///
+/// On success, returns the proven account info, and the root of the account storage child trie
+/// that the storage proofs can be verified against with [`verify_storage_proof`].
+#[cfg(feature = "std")]
+pub fn verify_account_proof<T: Config>(
+	state_root: H256,
+	address: &H160,
+	balance: sp_core::U256,
+	nonce: sp_core::U256,
+	proof: Vec<Vec<u8>>,
+) -> Result<VerifiedAccount<T>, ProofError>
+where
+	HasherOf<T>: Hash<Output = H256>,
+	T::Nonce: Into<u32>,
+{
+	let proof = StorageProof::new(proof);
+	let mut ext = sp_io::TestExternalities::new_empty();
+
+	let account_info =
+		read_into::<T>(&mut ext, state_root, &proof, account_info_key::<T>(address))?
+			.map(|value| AccountInfo::<T>::decode(&mut &value[..]))
+			.transpose()
+			.map_err(|_| ProofError::DecodingFailed)?;
+
+	read_into::<T>(&mut ext, state_root, &proof, original_account_key::<T>(address))?;
+	let account_id =
+		ext.execute_with(|| <T::AddressMapper as crate::AddressMapper<T>>::to_account_id(address));
+	read_into::<T>(&mut ext, state_root, &proof, system_account_key::<T>(&account_id))?;
+
+	let (proven_balance, proven_nonce) = ext.execute_with(|| {
+		(crate::Pallet::<T>::evm_balance(address), crate::Pallet::<T>::evm_nonce(address))
+	});
+	if proven_balance != balance || sp_core::U256::from(proven_nonce) != nonce {
+		return Err(ProofError::ValueMismatch);
+	}
+
+	let storage_root = match &account_info {
+		Some(AccountInfo { account_type: AccountType::Contract(info), .. }) => {
+			let key = storage_root_key(&info.trie_id);
+			let root =
+				read_value::<T>(state_root, &proof, &key)?.ok_or(ProofError::InvalidProof)?;
+			if root.len() != H256::len_bytes() {
+				return Err(ProofError::DecodingFailed);
+			}
+			Some(H256::from_slice(&root))
+		},
+		_ => None,
+	};
+
+	Ok(VerifiedAccount { account_info, storage_root })
+}
+
+/// Verify the proof of the storage `slot` against the `storage_root` of a contract.
+///
+/// On success, returns the proven value of the slot, `None` if the slot is empty.
+pub fn verify_storage_proof<T: Config>(
+	storage_root: H256,
+	slot: [u8; 32],
+	proof: Vec<Vec<u8>>,
+) -> Result<Option<Vec<u8>>, ProofError>
+where
+	HasherOf<T>: Hash<Output = H256>,
+{
+	read_value::<T>(storage_root, &StorageProof::new(proof), &storage_slot_key(slot))
+}
@@ -290,9 +290,12 @@ pub trait EthExtra {
 			crate::evm::api::TransactionSigned::TransactionLegacySigned(_) => {
 				// Supported transaction types, continue processing
 			},
-			crate::evm::api::TransactionSigned::Transaction7702Signed(_) => {
-				log::debug!(target: LOG_TARGET, "EIP-7702 transactions are not supported");
-				return Err(InvalidTransaction::Call);
+			crate::evm::api::TransactionSigned::Transaction7702Signed(tx) => {
+				// The authorizations are applied by the `SetOrigin` transaction extension.
+				if tx.transaction_7702_unsigned.authorization_list.is_empty() {
+					log::debug!(target: LOG_TARGET, "EIP-7702 transaction with an empty authorization list");
+					return Err(InvalidTransaction::Call);
+				}
 			},
 			crate::evm::api::TransactionSigned::Transaction4844Signed(_) => {
 				log::debug!(target: LOG_TARGET, "EIP-4844 transactions are not supported");
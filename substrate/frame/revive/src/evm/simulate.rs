@@ -0,0 +1,283 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Simulation of Ethereum transactions on top of state overrides.
+//!
+//! This backs the state overrides of `eth_call` and `eth_estimateGas`, and the multi-call
+//! simulation of `eth_simulateV1`. Overrides are written directly to storage, so this is only
+//! meant to be used from a runtime API call, where all changes are discarded afterwards.
+
+use crate::{
+	evm::{
+		runtime::SetWeightLimit, AccountOverride, GenericTransaction, Log, SimulateBlock,
+		SimulateCallError, SimulateCallResult, SimulatedBlock, StateOverride,
+		SIMULATE_EXECUTION_ERROR_CODE, SIMULATE_REVERTED_ERROR_CODE,
+	},
+	exec::{Executable, Key, MomentOf},
+	primitives::ExecReturnValue,
+	tracing::{trace, Tracing},
+	vm::ContractBlob,
+	AccountInfo, AddressMapper, CallOf, CodeInfoOf, Config, ContractInfo, DispatchError,
+	DryRunConfig, EthTransactError, Pallet, PristineCode, Weight,
+};
+use alloc::{format, vec::Vec};
+use frame_support::storage::child;
+use sp_core::{H160, H256, U256};
+use sp_runtime::SaturatedConversion;
+
+/// Apply the given state overrides.
+///
+/// Storage overrides bypass the storage deposit accounting of the contract.
+pub fn apply_state_override<T: Config>(
+	state_override: StateOverride,
+) -> Result<(), EthTransactError> {
+	for (address, account_override) in state_override.0 {
+		let AccountOverride { balance, nonce, code, state, state_diff } = account_override;
+		let account_id = T::AddressMapper::to_account_id(&address);
+
+		if let Some(code) = code {
+			set_code::<T>(&address, &account_id, code.0)?;
+		}
+
+		if let Some(nonce) = nonce {
+			let nonce: u64 = nonce.try_into().map_err(|_| {
+				EthTransactError::Message(format!("Nonce override of {address:?} overflows"))
+			})?;
+			frame_system::Account::<T>::mutate(&account_id, |info| {
+				info.nonce = nonce.saturated_into();
+			});
+		}
+
+		if let Some(balance) = balance {
+			Pallet::<T>::set_evm_balance(&address, balance).map_err(|err| {
+				EthTransactError::Message(format!(
+					"Failed to override the balance of {address:?}: {err:?}"
+				))
+			})?;
+		}
+
+		let (slots, clear) = match (state, state_diff) {
+			(Some(_), Some(_)) => {
+				return Err(EthTransactError::Message(format!(
+					"Both state and stateDiff overrides are set for {address:?}"
+				)))
+			},
+			(Some(state), None) => (state, true),
+			(None, Some(state_diff)) => (state_diff, false),
+			(None, None) => continue,
+		};
+
+		let Some(info) = AccountInfo::<T>::load_contract(&address) else {
+			return Err(EthTransactError::Message(format!(
+				"Cannot override the storage of {address:?}, it is not a contract"
+			)));
+		};
+
+		if clear {
+			let _ = child::clear_storage(&info.child_trie_info(), None, None);
+		}
+
+		for (key, value) in slots {
+			let value = (value != H256::zero()).then(|| value.0.to_vec());
+			info.write(&Key::from_fixed(key.0), value, None, false).map_err(|err| {
+				EthTransactError::Message(format!(
+					"Failed to override the storage of {address:?} at key {key:?}: {err:?}"
+				))
+			})?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Replace the code of the account at `address`, turning it into a contract if needed.
+fn set_code<T: Config>(
+	address: &H160,
+	account_id: &T::AccountId,
+	code: Vec<u8>,
+) -> Result<(), EthTransactError> {
+	let blob = if code.starts_with(&polkavm_common::program::BLOB_MAGIC) {
+		ContractBlob::<T>::from_pvm_code(code.clone(), Pallet::<T>::account_id())
+	} else {
+		ContractBlob::<T>::from_evm_runtime_code(code.clone(), account_id.clone())
+	}
+	.map_err(|err| {
+		EthTransactError::Message(format!("Invalid code override for {address:?}: {err:?}"))
+	})?;
+
+	let code_hash = *blob.code_hash();
+	let info = match AccountInfo::<T>::load_contract(address) {
+		Some(mut info) => {
+			info.code_hash = code_hash;
+			info
+		},
+		None => ContractInfo::<T>::new(address, 0u32.into(), code_hash).map_err(|err| {
+			EthTransactError::Message(format!(
+				"Failed to create the contract info of {address:?}: {err:?}"
+			))
+		})?,
+	};
+
+	AccountInfo::<T>::insert_contract(address, info);
+	<PristineCode<T>>::insert(code_hash, code);
+	<CodeInfoOf<T>>::insert(code_hash, blob.code_info().clone());
+	Ok(())
+}
+
+/// A tracer collecting the logs emitted by a transaction.
+///
+/// The logs emitted by a frame are discarded when the frame reverts or fails.
+#[derive(Default)]
+struct LogTracer {
+	/// The logs of the frames being executed, the last entry being the innermost frame.
+	frames: Vec<Vec<Log>>,
+	/// The logs of the completed top level frames.
+	logs: Vec<Log>,
+}
+
+impl Tracing for LogTracer {
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_delegate_call: Option<H160>,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_limit: u64,
+	) {
+		self.frames.push(Vec::new());
+	}
+
+	fn log_event(&mut self, address: H160, topics: &[H256], data: &[u8]) {
+		if let Some(frame) = self.frames.last_mut() {
+			frame.push(Log {
+				address,
+				topics: topics.to_vec(),
+				data: Some(data.to_vec().into()),
+				..Default::default()
+			});
+		}
+	}
+
+	fn exit_child_span(
+		&mut self,
+		output: &ExecReturnValue,
+		_gas_used: u64,
+		_weight_consumed: Weight,
+	) {
+		let logs = self.frames.pop().unwrap_or_default();
+		if output.did_revert() {
+			return;
+		}
+		match self.frames.last_mut() {
+			Some(parent) => parent.extend(logs),
+			None => self.logs.extend(logs),
+		}
+	}
+
+	fn exit_child_span_with_error(
+		&mut self,
+		_error: DispatchError,
+		_gas_used: u64,
+		_weight_consumed: Weight,
+	) {
+		self.frames.pop();
+	}
+}
+
+/// Execute the calls of the given blocks in order, each one on top of the state left by the
+/// previous ones.
+///
+/// The state overrides of a block are applied before executing its calls. All the blocks are
+/// executed in the context of the block following the current one. The simulated blocks are
+/// numbered after it.
+pub fn simulate<T: Config>(
+	blocks: Vec<SimulateBlock>,
+	dry_run_config: DryRunConfig<MomentOf<T>>,
+) -> Result<Vec<SimulatedBlock>, EthTransactError>
+where
+	T::Nonce: Into<U256>,
+	CallOf<T>: SetWeightLimit,
+{
+	let current_number: U256 =
+		frame_system::Pallet::<T>::block_number().saturated_into::<u64>().into();
+	let mut results = Vec::with_capacity(blocks.len());
+
+	for (index, SimulateBlock { state_overrides, calls }) in blocks.into_iter().enumerate() {
+		if let Some(state_override) = state_overrides {
+			apply_state_override::<T>(state_override)?;
+		}
+
+		let number = current_number.saturating_add((index + 1).into());
+		let mut block = SimulatedBlock { number, ..Default::default() };
+		for (transaction_index, tx) in calls.into_iter().enumerate() {
+			let call = simulate_call::<T>(tx, dry_run_config.clone());
+			let log_index = block.calls.iter().map(|call| call.logs.len()).sum::<usize>();
+			let logs = call
+				.logs
+				.into_iter()
+				.enumerate()
+				.map(|(i, log)| Log {
+					block_number: number,
+					transaction_index: transaction_index.into(),
+					log_index: (log_index + i).into(),
+					..log
+				})
+				.collect();
+			block.gas_used = block.gas_used.saturating_add(call.gas_used);
+			block.calls.push(SimulateCallResult { logs, ..call });
+		}
+		results.push(block);
+	}
+
+	Ok(results)
+}
+
+/// Execute a single call, collecting its logs.
+fn simulate_call<T: Config>(
+	tx: GenericTransaction,
+	dry_run_config: DryRunConfig<MomentOf<T>>,
+) -> SimulateCallResult
+where
+	T::Nonce: Into<U256>,
+	CallOf<T>: SetWeightLimit,
+{
+	let mut tracer = LogTracer::default();
+	let result = trace(&mut tracer, || Pallet::<T>::dry_run_eth_transact(tx, dry_run_config));
+
+	match result {
+		Ok(info) => SimulateCallResult {
+			return_data: info.data.into(),
+			logs: tracer.logs,
+			gas_used: info.eth_gas,
+			status: 1.into(),
+			error: None,
+		},
+		Err(EthTransactError::Data(data)) => SimulateCallResult {
+			return_data: data.into(),
+			error: Some(SimulateCallError {
+				code: SIMULATE_REVERTED_ERROR_CODE,
+				message: "execution reverted".into(),
+			}),
+			..Default::default()
+		},
+		Err(EthTransactError::Message(message)) => SimulateCallResult {
+			error: Some(SimulateCallError { code: SIMULATE_EXECUTION_ERROR_CODE, message }),
+			..Default::default()
+		},
+	}
+}
@@ -35,8 +35,8 @@ use sp_core::{H160, U256};
 /// Used to accumulate child call consumption for CALL-like opcodes.
 #[derive(Default, Debug, Clone, PartialEq)]
 struct PendingStep {
-	/// Index of this step in the `steps` vector.
-	step_index: usize,
+	/// Index of this step in the `steps` vector, `None` if the step is not captured.
+	step_index: Option<usize>,
 	/// Accumulated gas consumed by child calls.
 	child_gas: u64,
 	/// Accumulated weight consumed by child calls.
@@ -60,9 +60,12 @@ pub struct ExecutionTracer {
 	/// Current call depth.
 	depth: u16,
 
-	/// Number of steps captured (for limiting).
+	/// Number of steps executed so far (for limiting).
 	step_count: u64,
 
+	/// Whether the step being executed is captured.
+	capturing: bool,
+
 	/// Total gas used by the transaction.
 	total_gas_used: u64,
 
@@ -91,6 +94,7 @@ impl ExecutionTracer {
 			pending: Vec::new(),
 			depth: 0,
 			step_count: 0,
+			capturing: false,
 			total_gas_used: 0,
 			base_call_weight: Default::default(),
 			weight_consumed: Default::default(),
@@ -116,10 +120,49 @@ impl ExecutionTracer {
 
 	/// Record an error in the current step.
 	fn record_error(&mut self, error: String) {
+		if !self.capturing {
+			return;
+		}
 		if let Some(last_step) = self.steps.last_mut() {
 			last_step.error = Some(error);
 		}
 	}
+
+	/// Start a new step, returns whether it falls within the captured window.
+	///
+	/// Steps outside of the window are still tracked as pending, so that the costs of the
+	/// captured steps enclosing them are computed correctly.
+	fn begin_step(&mut self) -> bool {
+		let index = self.step_count;
+		self.step_count += 1;
+
+		self.capturing = self.config.limit.map_or(true, |limit| index < limit);
+		if !self.capturing {
+			self.pending.push(PendingStep::default());
+		}
+		self.capturing
+	}
+
+	/// Push a captured step.
+	fn push_step(&mut self, step: ExecutionStep) {
+		let step_index = Some(self.steps.len());
+		self.steps.push(step);
+		self.pending
+			.push(PendingStep { step_index, child_gas: 0, child_weight: Weight::zero() });
+	}
+
+	/// Attach the storage accessed by the current call to the current step.
+	fn record_storage(&mut self) {
+		if !self.capturing {
+			return;
+		}
+		let Some(storage) = self.storages_per_call.last() else { return };
+		if let Some(step) = self.steps.last_mut() {
+			if let ExecutionStepKind::EVMOpcode { storage: ref mut step_storage, .. } = step.kind {
+				*step_storage = Some(storage.clone());
+			}
+		}
+	}
 }
 
 impl Tracing for ExecutionTracer {
@@ -133,7 +176,7 @@ impl Tracing for ExecutionTracer {
 	}
 
 	fn enter_opcode(&mut self, pc: u64, opcode: u8, trace_info: &dyn EVMFrameTraceInfo) {
-		if self.config.limit.map(|l| self.step_count >= l).unwrap_or(false) {
+		if !self.begin_step() {
 			return;
 		}
 
@@ -172,15 +215,11 @@ impl Tracing for ExecutionTracer {
 			},
 		};
 
-		let step_index = self.steps.len();
-		self.steps.push(step);
-		self.pending
-			.push(PendingStep { step_index, child_gas: 0, child_weight: Weight::zero() });
-		self.step_count += 1;
+		self.push_step(step);
 	}
 
 	fn enter_ecall(&mut self, ecall: &'static str, args: &[u64], trace_info: &dyn FrameTraceInfo) {
-		if self.config.limit.map(|l| self.step_count >= l).unwrap_or(false) {
+		if !self.begin_step() {
 			return;
 		}
 
@@ -210,16 +249,14 @@ impl Tracing for ExecutionTracer {
 			},
 		};
 
-		let step_index = self.steps.len();
-		self.steps.push(step);
-		self.pending
-			.push(PendingStep { step_index, child_gas: 0, child_weight: Weight::zero() });
-		self.step_count += 1;
+		self.push_step(step);
 	}
 
 	fn exit_step(&mut self, trace_info: &dyn FrameTraceInfo, returned: Option<u64>) {
 		let Some(pending) = self.pending.pop() else { return };
-		let Some(step) = self.steps.get_mut(pending.step_index) else { return };
+		let Some(step) = pending.step_index.and_then(|index| self.steps.get_mut(index)) else {
+			return;
+		};
 
 		// Calculate opcode cost: total consumption minus child consumption
 		let total_gas = step.gas.saturating_sub(trace_info.gas_left());
@@ -322,15 +359,8 @@ impl Tracing for ExecutionTracer {
 				new_value.map(|v| v.to_vec()).unwrap_or_else(|| alloc::vec![0u8; 32]),
 			);
 			storage.insert(key_bytes, value_bytes);
-
-			if let Some(step) = self.steps.last_mut() {
-				if let ExecutionStepKind::EVMOpcode { storage: ref mut step_storage, .. } =
-					step.kind
-				{
-					*step_storage = Some(storage.clone());
-				}
-			}
 		}
+		self.record_storage();
 	}
 
 	fn storage_read(&mut self, key: &Key, value: Option<&[u8]>) {
@@ -344,14 +374,7 @@ impl Tracing for ExecutionTracer {
 			storage.entry(key_bytes).or_insert_with(|| {
 				crate::evm::Bytes(value.map(|v| v.to_vec()).unwrap_or_else(|| alloc::vec![0u8; 32]))
 			});
-
-			if let Some(step) = self.steps.last_mut() {
-				if let ExecutionStepKind::EVMOpcode { storage: ref mut step_storage, .. } =
-					step.kind
-				{
-					*step_storage = Some(storage.clone());
-				}
-			}
 		}
+		self.record_storage();
 	}
 }
@@ -17,19 +17,150 @@
 
 //! Contains transaction extensions needed for ethereum compatability.
 
-use crate::{CallOf, Config, Origin, OriginFor};
-use codec::{Decode, DecodeWithMemTracking, Encode};
+use crate::{
+	evm::{AuthorizationListEntry, TransactionSigned},
+	exec::AccountIdOf,
+	storage::Delegation,
+	AccountInfo, AddressMapper, CallOf, Config, DelegationOf, HoldReason, Origin, OriginFor,
+	WeightInfo, LOG_TARGET,
+};
+use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::{
 	pallet_prelude::{InvalidTransaction, TransactionSource},
+	traits::{fungible::MutateHold, tokens::Precision, Get, IsSubType},
 	DebugNoBound, DefaultNoBound,
 };
+use frame_system::Pallet as System;
 use scale_info::TypeInfo;
+use sp_core::{H160, U256};
 use sp_runtime::{
-	impl_tx_ext_default,
 	traits::{DispatchInfoOf, TransactionExtension, ValidateResult},
-	Weight,
+	transaction_validity::TransactionValidityError,
+	SaturatedConversion, Saturating, Weight,
 };
 
+/// The prefix of the code of an account that delegated its code ([EIP-7702]).
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// The length of a delegation designator, the prefix followed by the target address.
+pub const DELEGATION_DESIGNATOR_LEN: usize = DELEGATION_PREFIX.len() + 20;
+
+/// The code reported for an account that delegated its code to `target`.
+pub fn delegation_designator(target: &H160) -> [u8; DELEGATION_DESIGNATOR_LEN] {
+	let mut designator = [0u8; DELEGATION_DESIGNATOR_LEN];
+	designator[..DELEGATION_PREFIX.len()].copy_from_slice(&DELEGATION_PREFIX);
+	designator[DELEGATION_PREFIX.len()..].copy_from_slice(target.as_bytes());
+	designator
+}
+
+/// The authorization list of the EIP-7702 transaction carried by `call`, if any.
+fn authorization_list<T: Config>(call: &CallOf<T>) -> Option<Vec<AuthorizationListEntry>> {
+	let transaction_encoded = match call.is_sub_type()? {
+		crate::Call::eth_call { transaction_encoded, .. } |
+		crate::Call::eth_instantiate_with_code { transaction_encoded, .. } |
+		crate::Call::eth_substrate_call { transaction_encoded, .. } => transaction_encoded,
+		_ => return None,
+	};
+
+	match TransactionSigned::decode(transaction_encoded) {
+		Ok(TransactionSigned::Transaction7702Signed(tx)) => {
+			Some(tx.transaction_7702_unsigned.authorization_list)
+		},
+		_ => None,
+	}
+}
+
+/// The weight of processing a single authorization tuple.
+fn authorization_weight<T: Config>() -> Weight {
+	T::WeightInfo::apply_authorization()
+}
+
+/// The deposit for storing a delegation in [`DelegationOf`].
+fn delegation_deposit<T: Config>() -> crate::BalanceOf<T> {
+	// each entry stores the authority (20 bytes) as key next to the delegation
+	let len = Delegation::<T>::max_encoded_len().saturating_add(20);
+	T::DepositPerByte::get()
+		.saturating_mul((len as u32).into())
+		.saturating_add(T::DepositPerItem::get())
+}
+
+/// Apply the authorizations of an EIP-7702 transaction signed by `payer`.
+///
+/// Each valid authorization delegates the code of its authority to the authorized address, or
+/// clears the delegation if the authorized address is zero, and bumps the nonce of the
+/// authority. A new delegation holds a deposit on the `payer`, which is released to it once the
+/// delegation is cleared. Invalid authorizations are skipped.
+pub fn apply_authorizations<T: Config>(
+	payer: &AccountIdOf<T>,
+	authorization_list: &[AuthorizationListEntry],
+) {
+	for authorization in authorization_list {
+		if let Err(reason) = apply_authorization::<T>(payer, authorization) {
+			log::debug!(target: LOG_TARGET, "Skipping authorization {authorization:?}: {reason}");
+		}
+	}
+}
+
+fn apply_authorization<T: Config>(
+	payer: &AccountIdOf<T>,
+	authorization: &AuthorizationListEntry,
+) -> Result<(), &'static str> {
+	let chain_id = authorization.chain_id;
+	if !chain_id.is_zero() && chain_id != U256::from(T::ChainId::get()) {
+		return Err("invalid chain id");
+	}
+
+	if authorization.nonce >= U256::from(u64::MAX) {
+		return Err("invalid nonce");
+	}
+
+	let authority = authorization.recover_authority().map_err(|_| "invalid signature")?;
+	if AccountInfo::<T>::is_contract(&authority) {
+		return Err("authority is a contract");
+	}
+
+	let account_id = T::AddressMapper::to_account_id(&authority);
+	let nonce: u64 = System::<T>::account_nonce(&account_id).saturated_into();
+	if authorization.nonce != U256::from(nonce) {
+		return Err("nonce mismatch");
+	}
+
+	match (<DelegationOf<T>>::get(authority), authorization.address.is_zero()) {
+		(None, true) => {},
+		(Some(delegation), true) => {
+			T::Currency::release(
+				&HoldReason::CodeDelegation.into(),
+				&delegation.depositor,
+				delegation.deposit,
+				Precision::BestEffort,
+			)
+			.map_err(|_| "failed to release the delegation deposit")?;
+			<DelegationOf<T>>::remove(authority);
+		},
+		// replacing a delegation keeps the deposit of the existing one
+		(Some(delegation), false) => {
+			<DelegationOf<T>>::insert(
+				authority,
+				Delegation { target: authorization.address, ..delegation },
+			);
+		},
+		(None, false) => {
+			let deposit = delegation_deposit::<T>();
+			T::Currency::hold(&HoldReason::CodeDelegation.into(), payer, deposit)
+				.map_err(|_| "insufficient funds for the delegation deposit")?;
+			<DelegationOf<T>>::insert(
+				authority,
+				Delegation { target: authorization.address, depositor: payer.clone(), deposit },
+			);
+		},
+	}
+	System::<T>::inc_account_nonce(&account_id);
+	Ok(())
+}
+
 /// An extension that sets the origin to [`Origin::EthTransaction`] in case it originated from an
 /// eth transaction.
 ///
@@ -70,31 +201,54 @@ where
 	const IDENTIFIER: &'static str = "EthSetOrigin";
 	type Implicit = ();
 	type Pre = ();
-	type Val = ();
+	type Val = Option<(AccountIdOf<T>, Vec<AuthorizationListEntry>)>;
+
+	fn weight(&self, call: &CallOf<T>) -> Weight {
+		if !self.is_eth_transaction {
+			return Default::default();
+		}
 
-	fn weight(&self, _: &CallOf<T>) -> Weight {
-		Default::default()
+		let authorizations = authorization_list::<T>(call).map_or(0, |list| list.len());
+		authorization_weight::<T>().saturating_mul(authorizations as u64)
 	}
 
 	fn validate(
 		&self,
 		origin: OriginFor<T>,
-		_call: &CallOf<T>,
+		call: &CallOf<T>,
 		_info: &DispatchInfoOf<CallOf<T>>,
 		_len: usize,
 		_self_implicit: Self::Implicit,
 		_inherited_implication: &impl Encode,
 		_source: TransactionSource,
 	) -> ValidateResult<Self::Val, CallOf<T>> {
-		let origin = if self.is_eth_transaction {
-			let signer =
-				frame_system::ensure_signed(origin).map_err(|_| InvalidTransaction::BadProof)?;
-			Origin::EthTransaction(signer).into()
-		} else {
-			origin
-		};
-		Ok((Default::default(), Default::default(), origin))
+		if !self.is_eth_transaction {
+			return Ok((Default::default(), Default::default(), origin));
+		}
+
+		let signer =
+			frame_system::ensure_signed(origin).map_err(|_| InvalidTransaction::BadProof)?;
+		let authorization_list = authorization_list::<T>(call).unwrap_or_default();
+		Ok((
+			Default::default(),
+			Some((signer.clone(), authorization_list)),
+			Origin::EthTransaction(signer).into(),
+		))
 	}
 
-	impl_tx_ext_default!(CallOf<T>; prepare);
+	fn prepare(
+		self,
+		val: Self::Val,
+		_origin: &OriginFor<T>,
+		_call: &CallOf<T>,
+		_info: &DispatchInfoOf<CallOf<T>>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		// The authorizations are applied before the dispatch, so that they are kept even if the
+		// execution of the transaction fails.
+		if let Some((signer, authorization_list)) = val {
+			apply_authorizations::<T>(&signer, &authorization_list);
+		}
+		Ok(())
+	}
 }
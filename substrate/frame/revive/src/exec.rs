@@ -16,8 +16,12 @@
 // limitations under the License.
 
 use crate::{
+	access_list::AccessedStorage,
 	address::{self, AddressMapper},
-	evm::{block_storage, transfer_with_dust},
+	evm::{
+		block_storage, transfer_with_dust,
+		tx_extension::{delegation_designator, DELEGATION_DESIGNATOR_LEN},
+	},
 	limits,
 	metering::{ChargedAmount, Diff, FrameMeter, ResourceMeter, State, Token, TransactionMeter},
 	precompiles::{All as AllPrecompiles, Instance as PrecompileInstance, Precompiles},
@@ -542,6 +546,12 @@ pub trait PrecompileExt: sealing::Sealed {
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError>;
 
+	/// Marks the storage entry of the executing account at `key` as accessed.
+	///
+	/// Returns `true` if the entry was already accessed before in this transaction, either by a
+	/// contract or through the access list of the transaction.
+	fn access_storage(&mut self, key: &Key) -> bool;
+
 	/// Charges `diff` from the meter.
 	fn charge_storage(&mut self, diff: &Diff) -> DispatchResult;
 }
@@ -636,6 +646,8 @@ pub struct Stack<'a, T: Config, E> {
 	first_frame: Frame<T>,
 	/// Transient storage used to store data, which is kept for the duration of a transaction.
 	transient_storage: TransientStorage<T>,
+	/// The storage slots accessed during this transaction.
+	accessed_storage: AccessedStorage,
 	/// Global behavior determined by the creater of this stack.
 	exec_config: &'a ExecConfig<T>,
 	/// No executable is held by the struct but influences its behaviour.
@@ -1006,7 +1018,7 @@ where
 		input_data: &Vec<u8>,
 	) -> Result<Option<(Self, ExecutableOrPrecompile<T, E, Self>)>, ExecError> {
 		origin.ensure_mapped()?;
-		let Some((first_frame, executable)) = Self::new_frame(
+		let Some((mut first_frame, executable)) = Self::new_frame(
 			args,
 			value,
 			transaction_meter,
@@ -1020,6 +1032,16 @@ where
 			return Ok(None);
 		};
 
+		let access_list = &exec_config.access_list;
+		if !access_list.is_empty() {
+			let storage_keys =
+				access_list.iter().map(|entry| entry.storage_keys.len() as u32).sum::<u32>();
+			first_frame.frame_meter.charge_weight_token(RuntimeCosts::AccessList {
+				addresses: access_list.len() as u32,
+				storage_keys,
+			})?;
+		}
+
 		let mut timestamp = T::Time::now();
 		let mut block_number = <frame_system::Pallet<T>>::block_number();
 		// if dry run with timestamp override is provided we simulate the run in a `pending` block
@@ -1040,6 +1062,7 @@ where
 			first_frame,
 			frames: Default::default(),
 			transient_storage: TransientStorage::new(limits::TRANSIENT_STORAGE_BYTES),
+			accessed_storage: AccessedStorage::new(access_list),
 			exec_config,
 			_phantom: Default::default(),
 		};
@@ -1069,12 +1092,18 @@ where
 				// is a delegate call or not
 				let mut contract = match (cached_info, &precompile) {
 					(Some(info), _) => CachedContract::Cached(info),
-					(None, None) => {
-						if let Some(info) = AccountInfo::<T>::load_contract(&address) {
+					(None, None) => match AccountInfo::<T>::load_contract(&address) {
+						Some(info) if !info.code_hash.is_zero() => CachedContract::Cached(info),
+						// An externally owned account that delegated its code executes the code
+						// of the delegation target on its own storage.
+						info if AccountInfo::<T>::delegation_of(&address).is_some() => {
+							let info = match info {
+								Some(info) => info,
+								None => ContractInfo::new(&address, 0u32.into(), H256::zero())?,
+							};
 							CachedContract::Cached(info)
-						} else {
-							return Ok(None);
-						}
+						},
+						_ => return Ok(None),
 					},
 					(None, Some(precompile)) if precompile.has_contract_info() => {
 						log::trace!(target: LOG_TARGET, "found precompile for address {address:?}");
@@ -1103,11 +1132,12 @@ where
 							_phantom: Default::default(),
 						}
 					} else {
-						let Some(info) = AccountInfo::<T>::load_contract(&delegated_call.callee)
+						let Some(code_hash) =
+							AccountInfo::<T>::executable_code_hash(&delegated_call.callee)
 						else {
 							return Ok(None);
 						};
-						let executable = E::from_storage(info.code_hash, meter)?;
+						let executable = E::from_storage(code_hash, meter)?;
 						ExecutableOrPrecompile::Executable(executable)
 					}
 				} else {
@@ -1117,13 +1147,21 @@ where
 							_phantom: Default::default(),
 						}
 					} else {
-						let executable = E::from_storage(
-							contract
-								.as_contract()
-								.expect("When not a precompile the contract was loaded above; qed")
-								.code_hash,
-							meter,
-						)?;
+						let code_hash = contract
+							.as_contract()
+							.expect("When not a precompile the contract was loaded above; qed")
+							.code_hash;
+						// Only a delegating account has a contract info without code.
+						let code_hash = if code_hash.is_zero() {
+							let Some(code_hash) = AccountInfo::<T>::executable_code_hash(&address)
+							else {
+								return Ok(None);
+							};
+							code_hash
+						} else {
+							code_hash
+						};
+						let executable = E::from_storage(code_hash, meter)?;
 						ExecutableOrPrecompile::Executable(executable)
 					}
 				};
@@ -2274,8 +2312,13 @@ where
 			return sp_io::hashing::keccak_256(code).into();
 		}
 
+		if let Some(target) = <AccountInfo<T>>::delegation_of(address) {
+			return sp_io::hashing::keccak_256(&delegation_designator(&target)).into();
+		}
+
 		<AccountInfo<T>>::load_contract(&address)
 			.map(|contract| contract.code_hash)
+			.filter(|code_hash| !code_hash.is_zero())
 			.unwrap_or_else(|| {
 				if System::<T>::account_exists(&T::AddressMapper::to_account_id(address)) {
 					return EMPTY_CODE_HASH;
@@ -2294,6 +2337,10 @@ where
 			return code.len() as u64;
 		}
 
+		if <AccountInfo<T>>::delegation_of(address).is_some() {
+			return DELEGATION_DESIGNATOR_LEN as u64;
+		}
+
 		<AccountInfo<T>>::load_contract(&address)
 			.and_then(|contract| CodeInfoOf::<T>::get(contract.code_hash))
 			.map(|info| info.code_len())
@@ -2436,8 +2483,12 @@ where
 			return;
 		}
 
-		let code_hash = self.code_hash(address);
-		let code = crate::PristineCode::<T>::get(&code_hash).unwrap_or_default();
+		let code = if let Some(target) = <AccountInfo<T>>::delegation_of(address) {
+			delegation_designator(&target).to_vec()
+		} else {
+			let code_hash = self.code_hash(address);
+			crate::PristineCode::<T>::get(&code_hash).unwrap_or_default()
+		};
 
 		let len = len.min(code.len().saturating_sub(code_offset));
 		if len > 0 {
@@ -2517,6 +2568,11 @@ where
 		)
 	}
 
+	fn access_storage(&mut self, key: &Key) -> bool {
+		let address = self.address();
+		self.accessed_storage.access(address, key)
+	}
+
 	fn charge_storage(&mut self, diff: &Diff) -> DispatchResult {
 		assert!(self.has_contract_info());
 		self.top_frame_mut().frame_meter.record_contract_storage_changes(diff)
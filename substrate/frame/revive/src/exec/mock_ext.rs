@@ -256,6 +256,10 @@ impl<T: Config> PrecompileExt for MockExt<T> {
 		panic!("MockExt::set_storage")
 	}
 
+	fn access_storage(&mut self, _key: &Key) -> bool {
+		panic!("MockExt::access_storage")
+	}
+
 	fn charge_storage(&mut self, _diff: &Diff) -> DispatchResult {
 		Ok(())
 	}
@@ -22,6 +22,7 @@
 
 extern crate alloc;
 
+mod access_list;
 mod address;
 mod benchmarking;
 mod call_builder;
@@ -50,10 +51,10 @@ use crate::{
 	evm::{
 		block_hash::EthereumBlockBuilderIR, block_storage, fees::InfoT as FeeInfo,
 		runtime::SetWeightLimit, CallTracer, CreateCallMode, ExecutionTracer, GenericTransaction,
-		PrestateTracer, Trace, Tracer, TracerType, TYPE_EIP1559,
+		PrestateTracer, Trace, Tracer, TracerType, TYPE_EIP1559, TYPE_EIP7702, TYPE_LEGACY,
 	},
 	exec::{AccountIdOf, ExecError, ReentrancyProtection, Stack as ExecStack},
-	storage::{AccountType, DeletionQueueManager},
+	storage::{AccountType, Delegation, DeletionQueueManager},
 	tracing::if_tracing,
 	vm::{pvm::extract_code_and_data, CodeInfo, RuntimeCosts},
 	weightinfo_extension::OnFinalizeBlockParts,
@@ -97,7 +98,7 @@ pub use crate::{
 	debug::DebugSettings,
 	evm::{
 		block_hash::ReceiptGasInfo, Address as EthAddress, Block as EthBlock, DryRunConfig,
-		ReceiptInfo,
+		ReceiptInfo, SimulateBlock, SimulatedBlock, StateOverride,
 	},
 	exec::{CallResources, DelegateInfo, Executable, Key, MomentOf, Origin as ExecOrigin},
 	limits::TRANSIENT_STORAGE_BYTES as TRANSIENT_STORAGE_LIMIT,
@@ -628,6 +629,8 @@ pub mod pallet {
 		StorageDepositReserve,
 		/// Deposit for creating an address mapping in [`OriginalAccount`].
 		AddressMapping,
+		/// Deposit for storing a code delegation in [`DelegationOf`].
+		CodeDelegation,
 	}
 
 	#[derive(
@@ -653,6 +656,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type AccountInfoOf<T: Config> = StorageMap<_, Identity, H160, AccountInfo<T>>;
 
+	/// The code delegation of an externally owned account ([EIP-7702]).
+	///
+	/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+	#[pallet::storage]
+	pub(crate) type DelegationOf<T: Config> = StorageMap<_, Identity, H160, Delegation<T>>;
+
 	/// The immutable data associated with a given account.
 	#[pallet::storage]
 	pub(crate) type ImmutableDataOf<T: Config> = StorageMap<_, Identity, H160, ImmutableData>;
@@ -1287,6 +1296,7 @@ pub mod pallet {
 			let base_info = T::FeeInfo::base_dispatch_info(&mut call);
 			drop(call);
 
+			let access_list = Self::eth_access_list(&transaction_encoded);
 			block_storage::with_ethereum_context::<T>(transaction_encoded, || {
 				let extra_weight = base_info.total_weight();
 				let output = Self::bare_instantiate(
@@ -1300,7 +1310,8 @@ pub mod pallet {
 					Code::Upload(code),
 					data,
 					None,
-					&ExecConfig::new_eth_tx(effective_gas_price, encoded_len, extra_weight),
+					&ExecConfig::new_eth_tx(effective_gas_price, encoded_len, extra_weight)
+						.with_access_list(access_list),
 				);
 
 				block_storage::EthereumCallResult::new::<T>(
@@ -1366,6 +1377,7 @@ pub mod pallet {
 			let base_info = T::FeeInfo::base_dispatch_info(&mut call);
 			drop(call);
 
+			let access_list = Self::eth_access_list(&transaction_encoded);
 			block_storage::with_ethereum_context::<T>(transaction_encoded, || {
 				let extra_weight = base_info.total_weight();
 				let output = Self::bare_call(
@@ -1378,7 +1390,8 @@ pub mod pallet {
 						eth_tx_info: EthTxInfo::new(encoded_len, extra_weight),
 					},
 					data,
-					&ExecConfig::new_eth_tx(effective_gas_price, encoded_len, extra_weight),
+					&ExecConfig::new_eth_tx(effective_gas_price, encoded_len, extra_weight)
+						.with_access_list(access_list),
 				);
 
 				block_storage::EthereumCallResult::new::<T>(
@@ -1804,7 +1817,16 @@ impl<T: Config> Pallet<T> {
 			tx.gas = Some(Self::evm_block_gas_limit());
 		}
 		if tx.r#type.is_none() {
-			tx.r#type = Some(TYPE_EIP1559.into());
+			let r#type = match tx.default_type() {
+				TYPE_LEGACY => TYPE_EIP1559,
+				r#type => r#type,
+			};
+			tx.r#type = Some(r#type.into());
+		}
+		if tx.r#type == Some(TYPE_EIP7702.into()) && tx.to.is_none() {
+			return Err(EthTransactError::Message(
+				"EIP-7702 transactions require a destination".into(),
+			));
 		}
 
 		// Store values before moving the tx
@@ -1812,6 +1834,8 @@ impl<T: Config> Pallet<T> {
 		let input = tx.input.clone().to_vec();
 		let from = tx.from;
 		let to = tx.to;
+		let access_list = tx.access_list.clone().unwrap_or_default();
+		let authorization_list = tx.authorization_list.clone();
 
 		// we need to parse the weight from the transaction so that it is run
 		// using the exact weight limit passed by the eth wallet
@@ -1826,7 +1850,8 @@ impl<T: Config> Pallet<T> {
 		let base_weight = base_info.total_weight();
 		let exec_config =
 			ExecConfig::new_eth_tx(effective_gas_price, call_info.encoded_len, base_weight)
-				.with_dry_run(dry_run_config);
+				.with_dry_run(dry_run_config)
+				.with_access_list(access_list);
 
 		// emulate transaction behavior
 		let fees = call_info.tx_fee.saturating_add(call_info.storage_deposit);
@@ -1843,6 +1868,9 @@ impl<T: Config> Pallet<T> {
 		// the deposit is done when the transaction is transformed from an `eth_transact`
 		// we emulate this behavior for the dry-run here
 		T::FeeInfo::deposit_txfee(T::Currency::issue(fees));
+		// like `CheckNonce` for a transaction, `prepare_dry_run` bumped the nonce of the origin
+		// above, so that it can authorize a delegation with its next nonce
+		evm::tx_extension::apply_authorizations::<T>(&origin, &authorization_list);
 
 		let extract_error = |err| {
 			if err == Error::<T>::StorageDepositNotEnoughFunds.into() {
@@ -2018,6 +2046,37 @@ impl<T: Config> Pallet<T> {
 		Ok(dry_run)
 	}
 
+	/// Dry-run Ethereum calls on top of the given state overrides.
+	///
+	/// See [`Self::dry_run_eth_transact`] and [`evm::simulate::apply_state_override`].
+	pub fn dry_run_eth_transact_with_overrides(
+		tx: GenericTransaction,
+		state_override: StateOverride,
+		dry_run_config: DryRunConfig<<<T as Config>::Time as Time>::Moment>,
+	) -> Result<EthTransactInfo<BalanceOf<T>>, EthTransactError>
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		evm::simulate::apply_state_override::<T>(state_override)?;
+		Self::dry_run_eth_transact(tx, dry_run_config)
+	}
+
+	/// Simulate blocks of Ethereum calls, each call executing on top of the state left by the
+	/// previous ones.
+	///
+	/// See [`evm::simulate::simulate`].
+	pub fn simulate_eth_transact(
+		blocks: Vec<SimulateBlock>,
+		dry_run_config: DryRunConfig<<<T as Config>::Time as Time>::Moment>,
+	) -> Result<Vec<SimulatedBlock>, EthTransactError>
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		evm::simulate::simulate::<T>(blocks, dry_run_config)
+	}
+
 	/// Get the balance with EVM decimals of the given `address`.
 	///
 	/// Returns the spendable balance excluding the existential deposit.
@@ -2309,6 +2368,9 @@ impl<T: Config> Pallet<T> {
 		if let Some(code) = <All<T>>::code(address.as_fixed_bytes()) {
 			return code.into();
 		}
+		if let Some(target) = AccountInfo::<T>::delegation_of(address) {
+			return evm::tx_extension::delegation_designator(&target).to_vec();
+		}
 		AccountInfo::<T>::load_contract(&address)
 			.and_then(|contract| <PristineCode<T>>::get(contract.code_hash))
 			.map(|code| code.into())
@@ -2496,6 +2558,13 @@ impl<T: Config> Pallet<T> {
 		<frame_system::Pallet<T>>::deposit_event(<T as Config>::RuntimeEvent::from(event))
 	}
 
+	/// Returns the EIP-2930 access list of the given RLP encoded Ethereum transaction.
+	fn eth_access_list(transaction_encoded: &[u8]) -> evm::AccessList {
+		evm::TransactionSigned::decode(transaction_encoded)
+			.map(|tx| tx.access_list().to_vec())
+			.unwrap_or_default()
+	}
+
 	// Returns Ok with the account that signed the eth transaction.
 	fn ensure_eth_signed(origin: OriginFor<T>) -> Result<AccountIdOf<T>, DispatchError> {
 		match <T as Config>::RuntimeOrigin::from(origin).into() {
@@ -2619,6 +2688,25 @@ sp_api::decl_runtime_apis! {
 			config: DryRunConfig<Moment>,
 		) -> Result<EthTransactInfo<Balance>, EthTransactError>;
 
+		/// Perform an Ethereum call on top of the given state overrides.
+		///
+		/// See [`crate::Pallet::dry_run_eth_transact_with_overrides`]
+		#[api_version(2)]
+		fn eth_transact_with_overrides(
+			tx: GenericTransaction,
+			state_override: StateOverride,
+			config: DryRunConfig<Moment>,
+		) -> Result<EthTransactInfo<Balance>, EthTransactError>;
+
+		/// Simulate blocks of Ethereum calls.
+		///
+		/// See [`crate::Pallet::simulate_eth_transact`]
+		#[api_version(2)]
+		fn eth_simulate(
+			blocks: Vec<SimulateBlock>,
+			config: DryRunConfig<Moment>,
+		) -> Result<Vec<SimulatedBlock>, EthTransactError>;
+
 		/// Upload new code without instantiating a contract from it.
 		///
 		/// See [`crate::Pallet::bare_upload_code`].
@@ -2736,6 +2824,7 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 			$($rest)*
 
 
+			#[api_version(2)]
 			impl pallet_revive::ReviveApi<Block, AccountId, Balance, Nonce, BlockNumber, __ReviveMacroMoment> for $Runtime
 			{
 				fn eth_block() -> $crate::EthBlock {
@@ -2800,6 +2889,25 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 					$crate::Pallet::<Self>::dry_run_eth_transact(tx, config)
 				}
 
+				fn eth_transact_with_overrides(
+					tx: $crate::evm::GenericTransaction,
+					state_override: $crate::evm::StateOverride,
+					config: $crate::DryRunConfig<__ReviveMacroMoment>,
+				) -> Result<$crate::EthTransactInfo<Balance>, $crate::EthTransactError> {
+					$crate::Pallet::<Self>::dry_run_eth_transact_with_overrides(
+						tx,
+						state_override,
+						config,
+					)
+				}
+
+				fn eth_simulate(
+					blocks: Vec<$crate::evm::SimulateBlock>,
+					config: $crate::DryRunConfig<__ReviveMacroMoment>,
+				) -> Result<Vec<$crate::evm::SimulatedBlock>, $crate::EthTransactError> {
+					$crate::Pallet::<Self>::simulate_eth_transact(blocks, config)
+				}
+
 				fn call(
 					origin: AccountId,
 					dest: $crate::H160,
@@ -30,7 +30,10 @@ mod builtin;
 mod tests;
 
 pub use crate::{
-	exec::{ExecError, PrecompileExt as Ext, PrecompileWithInfoExt as ExtWithInfo},
+	exec::{
+		CallResources, ExecError, PrecompileExt as Ext, PrecompileWithInfoExt as ExtWithInfo,
+		ReentrancyProtection,
+	},
 	metering::{Diff, Token},
 	vm::RuntimeCosts,
 	AddressMapper, TransactionLimits,
@@ -18,8 +18,11 @@
 //! A crate that hosts a common definitions that are relevant for the pallet-revive.
 
 use crate::{
-	evm::DryRunConfig, mock::MockHandler, storage::WriteOutcome,
-	transient_storage::TransientStorage, BalanceOf, Config, Time, H160, U256,
+	evm::{AccessList, DryRunConfig},
+	mock::MockHandler,
+	storage::WriteOutcome,
+	transient_storage::TransientStorage,
+	BalanceOf, Config, Time, H160, U256,
 };
 use alloc::{boxed::Box, fmt::Debug, string::String, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -400,6 +403,11 @@ pub struct ExecConfig<T: Config> {
 	/// This is only used for testing purposes and should be `None` in production
 	/// environments.
 	pub test_env_transient_storage: Option<RefCell<TransientStorage<T>>>,
+	/// The EIP-2930 access list of the transaction.
+	///
+	/// The storage slots in the list are charged upfront and are warm from the start of the
+	/// execution.
+	pub access_list: AccessList,
 }
 
 impl<T: Config> ExecConfig<T> {
@@ -412,6 +420,7 @@ impl<T: Config> ExecConfig<T> {
 			is_dry_run: None,
 			mock_handler: None,
 			test_env_transient_storage: None,
+			access_list: Default::default(),
 		}
 	}
 
@@ -423,6 +432,7 @@ impl<T: Config> ExecConfig<T> {
 			mock_handler: None,
 			is_dry_run: None,
 			test_env_transient_storage: None,
+			access_list: Default::default(),
 		}
 	}
 
@@ -435,6 +445,7 @@ impl<T: Config> ExecConfig<T> {
 			mock_handler: None,
 			is_dry_run: None,
 			test_env_transient_storage: None,
+			access_list: Default::default(),
 		}
 	}
 
@@ -447,6 +458,12 @@ impl<T: Config> ExecConfig<T> {
 		self
 	}
 
+	/// Set the EIP-2930 access list of the transaction.
+	pub fn with_access_list(mut self, access_list: AccessList) -> Self {
+		self.access_list = access_list;
+		self
+	}
+
 	/// Almost clone for testing (does not clone mock_handler)
 	#[cfg(test)]
 	pub fn clone(&self) -> Self {
@@ -457,6 +474,7 @@ impl<T: Config> ExecConfig<T> {
 			is_dry_run: self.is_dry_run.clone(),
 			mock_handler: None,
 			test_env_transient_storage: None,
+			access_list: self.access_list.clone(),
 		}
 	}
 }
@@ -23,8 +23,8 @@ use crate::{
 	metering::FrameMeter,
 	tracing::if_tracing,
 	weights::WeightInfo,
-	AccountInfoOf, BalanceOf, BalanceWithDust, Config, DeletionQueue, DeletionQueueCounter, Error,
-	TrieId, SENTINEL,
+	AccountInfoOf, BalanceOf, BalanceWithDust, Config, DelegationOf, DeletionQueue,
+	DeletionQueueCounter, Error, TrieId, SENTINEL,
 };
 use alloc::vec::Vec;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -39,7 +39,7 @@ use frame_support::{
 	CloneNoBound, DebugNoBound, DefaultNoBound,
 };
 use scale_info::TypeInfo;
-use sp_core::{Get, H160};
+use sp_core::{Get, H160, H256};
 use sp_io::KillStorageResult;
 use sp_runtime::{
 	traits::{Hash, Saturating, Zero},
@@ -83,6 +83,20 @@ pub enum AccountType<T: Config> {
 	EOA,
 }
 
+/// The code delegation of an externally owned account ([EIP-7702]).
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+#[derive(Encode, Decode, CloneNoBound, PartialEq, Eq, DebugNoBound, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct Delegation<T: Config> {
+	/// The address the code is delegated to.
+	pub target: H160,
+	/// The account that paid the deposit for storing the delegation.
+	pub depositor: AccountIdOf<T>,
+	/// The deposit held on the `depositor` for storing the delegation.
+	pub deposit: BalanceOf<T>,
+}
+
 /// Information for managing an account and its sub trie abstraction.
 /// This is the required info to cache for an account.
 #[derive(Encode, Decode, CloneNoBound, PartialEq, Eq, DebugNoBound, TypeInfo, MaxEncodedLen)]
@@ -141,9 +155,30 @@ impl<T: Config> From<ContractInfo<T>> for AccountType<T> {
 
 impl<T: Config> AccountInfo<T> {
 	/// Returns true if the account is a contract.
+	///
+	/// An externally owned account that delegated its code keeps its storage in a contract info
+	/// without code. It is not considered a contract.
 	pub fn is_contract(address: &H160) -> bool {
 		let Some(info) = <AccountInfoOf<T>>::get(address) else { return false };
-		matches!(info.account_type, AccountType::Contract(_))
+		matches!(info.account_type, AccountType::Contract(contract) if !contract.code_hash.is_zero())
+	}
+
+	/// Returns the address the externally owned account at `address` delegated its code to.
+	pub fn delegation_of(address: &H160) -> Option<H160> {
+		<DelegationOf<T>>::get(address).map(|delegation| delegation.target)
+	}
+
+	/// Returns the hash of the code executed when calling into `address`.
+	///
+	/// The code of an externally owned account that delegated its code is the code of the
+	/// delegation target. Delegations are not followed recursively.
+	pub fn executable_code_hash(address: &H160) -> Option<H256> {
+		let code_hash = |address: &H160| {
+			Self::load_contract(address)
+				.map(|contract| contract.code_hash)
+				.filter(|code_hash| !code_hash.is_zero())
+		};
+		code_hash(address).or_else(|| code_hash(&Self::delegation_of(address)?))
 	}
 
 	/// Returns the balance of the account at the given address.
@@ -18,7 +18,9 @@
 mod block_hash;
 mod pallet_dummy;
 mod precompiles;
+mod proof;
 mod pvm;
+mod simulate;
 mod sol;
 mod stipends;
 
@@ -312,6 +314,7 @@ impl pallet_proxy::Config for Test {
 	type AnnouncementDepositBase = ConstU64<1>;
 	type AnnouncementDepositFactor = ConstU64<1>;
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type SpendingInspector = ();
 }
 
 parameter_types! {
@@ -0,0 +1,128 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the `eth_getProof` account and storage proof verification.
+
+use crate::{
+	evm::proof::{
+		account_info_key, original_account_key, storage_root_key, storage_slot_key,
+		system_account_key, verify_account_proof, verify_storage_proof, ProofError,
+	},
+	tests::{test_utils::set_balance, ExtBuilder, Test},
+	AccountInfo, AddressMapper, Config, ContractInfo, Key, Pallet, U256,
+};
+use frame_support::storage::child::ChildInfo;
+use sp_core::{H160, H256};
+use sp_state_machine::{prove_child_read, prove_read};
+
+#[test]
+fn account_and_storage_proofs_verify() {
+	let address = H160::repeat_byte(1);
+	let eoa = H160::repeat_byte(2);
+	let slot = [1u8; 32];
+	let empty_slot = [2u8; 32];
+	let account_id = |address| <Test as Config>::AddressMapper::to_account_id(address);
+
+	let mut ext = ExtBuilder::default().build();
+	let (trie_id, balances) = ext.execute_with(|| {
+		let info = ContractInfo::<Test>::new(&address, 0, H256::zero()).unwrap();
+		info.write(&Key::Fix(slot), Some(vec![42]), None, false).unwrap();
+		AccountInfo::<Test>::insert_contract(&address, info.clone());
+		set_balance(&account_id(&address), 1_000);
+		set_balance(&account_id(&eoa), 500);
+		frame_system::Pallet::<Test>::inc_account_nonce(account_id(&eoa));
+
+		let balance_and_nonce = |address| {
+			(Pallet::<Test>::evm_balance(address), U256::from(Pallet::<Test>::evm_nonce(address)))
+		};
+		(info.trie_id.to_vec(), [balance_and_nonce(&address), balance_and_nonce(&eoa)])
+	});
+	ext.commit_all().unwrap();
+	let state_root = *ext.as_backend().root();
+	let [(balance, nonce), (eoa_balance, eoa_nonce)] = balances;
+	assert_eq!(eoa_nonce, U256::one());
+
+	let account_proof = prove_read(
+		ext.as_backend(),
+		[
+			account_info_key::<Test>(&address),
+			original_account_key::<Test>(&address),
+			system_account_key::<Test>(&account_id(&address)),
+			storage_root_key(&trie_id),
+			account_info_key::<Test>(&eoa),
+			original_account_key::<Test>(&eoa),
+			system_account_key::<Test>(&account_id(&eoa)),
+		],
+	)
+	.unwrap()
+	.into_iter_nodes()
+	.collect::<Vec<_>>();
+
+	let verified =
+		verify_account_proof::<Test>(state_root, &address, balance, nonce, account_proof.clone())
+			.unwrap();
+	assert!(verified.account_info.is_some());
+	let storage_root = verified.storage_root.expect("address is a contract");
+
+	let verified = verify_account_proof::<Test>(
+		state_root,
+		&eoa,
+		eoa_balance,
+		eoa_nonce,
+		account_proof.clone(),
+	)
+	.unwrap();
+	assert_eq!(verified.account_info, None);
+	assert_eq!(verified.storage_root, None);
+
+	// the balance and the nonce are proven
+	assert_eq!(
+		verify_account_proof::<Test>(state_root, &eoa, balance, eoa_nonce, account_proof.clone()),
+		Err(ProofError::ValueMismatch)
+	);
+	assert_eq!(
+		verify_account_proof::<Test>(state_root, &eoa, eoa_balance, nonce, account_proof.clone()),
+		Err(ProofError::ValueMismatch)
+	);
+
+	assert_eq!(
+		verify_account_proof::<Test>(H256::repeat_byte(1), &address, balance, nonce, account_proof),
+		Err(ProofError::InvalidProof)
+	);
+
+	let storage_proof = prove_child_read(
+		ext.as_backend(),
+		&ChildInfo::new_default(&trie_id),
+		[storage_slot_key(slot), storage_slot_key(empty_slot)],
+	)
+	.unwrap()
+	.into_iter_nodes()
+	.collect::<Vec<_>>();
+
+	assert_eq!(
+		verify_storage_proof::<Test>(storage_root, slot, storage_proof.clone()),
+		Ok(Some(vec![42]))
+	);
+	assert_eq!(
+		verify_storage_proof::<Test>(storage_root, empty_slot, storage_proof.clone()),
+		Ok(None)
+	);
+	assert_eq!(
+		verify_storage_proof::<Test>(H256::repeat_byte(1), slot, storage_proof),
+		Err(ProofError::InvalidProof)
+	);
+}
@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Tests for the simulation of Ethereum calls on top of state overrides.
+
+use crate::{
+	evm::{
+		AccountOverride, GenericTransaction, SimulateBlock, StateOverride,
+		SIMULATE_REVERTED_ERROR_CODE,
+	},
+	test_utils::{ALICE, ALICE_ADDR},
+	tests::{ExtBuilder, Test},
+	Config, Pallet,
+};
+use alloc::collections::BTreeMap;
+use frame_support::traits::fungible::Mutate;
+use sp_core::{H160, H256, U256};
+
+/// Runtime code that logs and returns the value of slot 0, and then increments it.
+fn counter_code() -> Vec<u8> {
+	use revm::bytecode::opcode::{ADD, DUP1, LOG0, MSTORE, PUSH0, PUSH1, RETURN, SLOAD, SSTORE};
+
+	vec![
+		PUSH0, SLOAD, DUP1, PUSH0, MSTORE, PUSH1, 0x20, PUSH0, LOG0, PUSH1, 1, ADD, PUSH0, SSTORE,
+		PUSH1, 0x20, PUSH0, RETURN,
+	]
+}
+
+fn slot(value: u64) -> BTreeMap<H256, H256> {
+	[(H256::zero(), H256::from_low_u64_be(value))].into_iter().collect()
+}
+
+#[test]
+fn simulate_applies_overrides_and_chains_calls() {
+	use revm::bytecode::opcode::{PUSH0, REVERT};
+
+	let counter = H160::repeat_byte(1);
+	let reverter = H160::repeat_byte(2);
+	let call =
+		|to| GenericTransaction { from: Some(ALICE_ADDR), to: Some(to), ..Default::default() };
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 10_000_000_000_000);
+
+		let overrides = [
+			(
+				counter,
+				AccountOverride {
+					code: Some(counter_code().into()),
+					state_diff: Some(slot(7)),
+					..Default::default()
+				},
+			),
+			(
+				reverter,
+				AccountOverride {
+					code: Some(vec![PUSH0, PUSH0, REVERT].into()),
+					..Default::default()
+				},
+			),
+		];
+
+		let blocks = Pallet::<Test>::simulate_eth_transact(
+			vec![
+				SimulateBlock {
+					state_overrides: Some(StateOverride(overrides.into_iter().collect())),
+					calls: vec![call(counter), call(counter), call(reverter)],
+				},
+				SimulateBlock {
+					state_overrides: Some(StateOverride(
+						[(counter, AccountOverride { state: Some(slot(1)), ..Default::default() })]
+							.into_iter()
+							.collect(),
+					)),
+					calls: vec![call(counter)],
+				},
+			],
+			Default::default(),
+		)
+		.unwrap();
+
+		let returned = |value: u64| U256::from(value).to_big_endian().to_vec();
+		let [first, second] = &blocks[..] else { panic!("expected two blocks: {blocks:?}") };
+		assert_eq!(second.number, first.number + 1);
+
+		// Calls see the state left by the previous ones.
+		assert_eq!(first.calls[0].return_data.0, returned(7));
+		assert_eq!(first.calls[1].return_data.0, returned(8));
+		assert_eq!(first.calls[1].status, 1.into());
+		assert_eq!(first.calls[1].logs.len(), 1);
+		assert_eq!(first.calls[1].logs[0].address, counter);
+		assert_eq!(first.calls[1].logs[0].data.as_ref().unwrap().0, returned(8));
+		assert_eq!(first.calls[1].logs[0].log_index, 1.into());
+		assert_eq!(first.calls[1].logs[0].transaction_index, 1.into());
+
+		// Reverted calls report the revert and no logs.
+		assert_eq!(first.calls[2].status, 0.into());
+		assert_eq!(first.calls[2].error.as_ref().unwrap().code, SIMULATE_REVERTED_ERROR_CODE);
+		assert!(first.calls[2].logs.is_empty());
+
+		assert_eq!(first.gas_used, first.calls[0].gas_used + first.calls[1].gas_used);
+
+		// A state override replaces the whole storage.
+		assert_eq!(second.calls[0].return_data.0, returned(1));
+	});
+}
+
+#[test]
+fn dry_run_with_overrides_works() {
+	let counter = H160::repeat_byte(1);
+
+	ExtBuilder::default().build().execute_with(|| {
+		let overrides = StateOverride(
+			[
+				(
+					ALICE_ADDR,
+					AccountOverride {
+						balance: Some(U256::from(10).pow(20.into())),
+						..Default::default()
+					},
+				),
+				(
+					counter,
+					AccountOverride {
+						code: Some(counter_code().into()),
+						state_diff: Some(slot(42)),
+						..Default::default()
+					},
+				),
+			]
+			.into_iter()
+			.collect(),
+		);
+
+		let info = Pallet::<Test>::dry_run_eth_transact_with_overrides(
+			GenericTransaction { from: Some(ALICE_ADDR), to: Some(counter), ..Default::default() },
+			overrides,
+			Default::default(),
+		)
+		.unwrap();
+
+		assert_eq!(info.data, U256::from(42).to_big_endian().to_vec());
+	});
+}
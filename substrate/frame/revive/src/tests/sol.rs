@@ -46,6 +46,7 @@ mod bitwise;
 mod block_info;
 mod contract;
 mod control;
+mod delegation;
 mod host;
 mod memory;
 mod stack;
@@ -826,3 +827,32 @@ fn execution_tracing_works() {
 		}
 	}
 }
+
+#[test]
+fn execution_tracing_limit_keeps_first_steps() {
+	use crate::evm::{ExecutionTrace, ExecutionTracer, ExecutionTracerConfig};
+
+	// Reads slot 0 and returns it.
+	let runtime_code = vec![PUSH0, SLOAD, PUSH0, MSTORE, PUSH1, 0x20, PUSH0, RETURN];
+	let code = make_initcode_from_runtime_code(&runtime_code);
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let trace_steps = |limit: Option<u64>| -> ExecutionTrace {
+			let config = ExecutionTracerConfig { limit, ..Default::default() };
+			let mut tracer = ExecutionTracer::new(config);
+			trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+			tracer.collect_trace()
+		};
+
+		let full = trace_steps(None);
+		assert_eq!(full.struct_logs.len(), runtime_code.len() - 1);
+
+		let limited = trace_steps(Some(4));
+		assert_eq!(limited.struct_logs, full.struct_logs[..4]);
+		assert_eq!(limited.gas, full.gas);
+	});
+}
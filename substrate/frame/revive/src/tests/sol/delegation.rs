@@ -0,0 +1,249 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Tests for EIP-7702 code delegation of externally owned accounts.
+use crate::{
+	address::AddressMapper,
+	evm::{
+		tx_extension::{apply_authorizations, delegation_designator},
+		Account, GenericTransaction,
+	},
+	test_utils::{builder::Contract, ALICE},
+	tests::{builder, sol::make_initcode_from_runtime_code, ExtBuilder, Test},
+	AccountInfo, Code, Config, DelegationOf, HoldReason, Pallet, System, H160, H256, U256,
+};
+use frame_support::traits::fungible::{InspectHold, Mutate};
+use pretty_assertions::assert_eq;
+use revm::bytecode::opcode::*;
+
+fn chain_id() -> U256 {
+	<Test as Config>::ChainId::get().into()
+}
+
+fn delegation_target(authority: &H160) -> Option<H160> {
+	<DelegationOf<Test>>::get(authority).map(|delegation| delegation.target)
+}
+
+fn delegation_deposit_of(account: &<Test as frame_system::Config>::AccountId) -> u64 {
+	<Test as Config>::Currency::balance_on_hold(&HoldReason::CodeDelegation.into(), account)
+}
+
+fn nonce_of(address: &H160) -> <Test as frame_system::Config>::Nonce {
+	System::<Test>::account_nonce(&<Test as Config>::AddressMapper::to_account_id(address))
+}
+
+/// Deploy a contract that stores its own address in slot 0, and returns it.
+fn deploy_self_storing_contract() -> H160 {
+	let runtime_code =
+		vec![ADDRESS, PUSH0, SSTORE, ADDRESS, PUSH0, MSTORE, PUSH1, 0x20, PUSH0, RETURN];
+	let code = make_initcode_from_runtime_code(&runtime_code);
+	let Contract { addr, .. } =
+		builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+	addr
+}
+
+#[test]
+fn delegated_account_executes_target_code_on_own_storage() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let target = deploy_self_storing_contract();
+		let account = Account::default();
+		let authority = account.address();
+		let _ = <Test as Config>::Currency::set_balance(&account.substrate_account(), 1_000_000);
+
+		apply_authorizations::<Test>(
+			&ALICE,
+			&[account.sign_authorization(chain_id(), target, 0.into())],
+		);
+		assert_eq!(delegation_target(&authority), Some(target));
+		assert_eq!(nonce_of(&authority), 1);
+		assert_eq!(Pallet::<Test>::code(&authority), delegation_designator(&target).to_vec());
+
+		let result = builder::bare_call(authority).build_and_unwrap_result();
+		let expected = H256::from(authority);
+		assert_eq!(result.data, expected.as_bytes().to_vec());
+
+		// The code ran on the storage of the delegating account.
+		assert_eq!(Pallet::<Test>::get_storage(authority, [0; 32]), Ok(Some(expected.0.to_vec())));
+		assert_eq!(Pallet::<Test>::get_storage(target, [0; 32]), Ok(None));
+		assert!(!AccountInfo::<Test>::is_contract(&authority));
+	});
+}
+
+#[test]
+fn extcode_of_delegated_account_is_the_designator() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let target = deploy_self_storing_contract();
+		let account = Account::default();
+		let authority = account.address();
+		apply_authorizations::<Test>(
+			&ALICE,
+			&[account.sign_authorization(chain_id(), target, 0.into())],
+		);
+
+		let probe = |opcode: u8| {
+			let runtime_code = [
+				vec![PUSH20],
+				authority.as_bytes().to_vec(),
+				vec![opcode, PUSH0, MSTORE, PUSH1, 0x20, PUSH0, RETURN],
+			]
+			.concat();
+			let code = make_initcode_from_runtime_code(&runtime_code);
+			let Contract { addr, .. } =
+				builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+			builder::bare_call(addr).build_and_unwrap_result().data
+		};
+
+		let designator = delegation_designator(&target);
+		assert_eq!(U256::from_big_endian(&probe(EXTCODESIZE)), U256::from(designator.len()));
+		assert_eq!(probe(EXTCODEHASH), sp_io::hashing::keccak_256(&designator).to_vec());
+	});
+}
+
+#[test]
+fn invalid_authorizations_are_skipped() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let target = H160::repeat_byte(1);
+		let account = Account::default();
+		let authority = account.address();
+
+		let wrong_chain = account.sign_authorization(chain_id() + 1, target, 0.into());
+		let wrong_nonce = account.sign_authorization(chain_id(), target, 1.into());
+		let mut high_s = account.sign_authorization(chain_id(), target, 0.into());
+		high_s.s = U256::MAX;
+		apply_authorizations::<Test>(&ALICE, &[wrong_chain, wrong_nonce, high_s]);
+		assert_eq!(delegation_target(&authority), None);
+		assert_eq!(nonce_of(&authority), 0);
+
+		// Authorizations for any chain are accepted.
+		let any_chain = account.sign_authorization(0.into(), target, 0.into());
+		apply_authorizations::<Test>(&ALICE, &[any_chain]);
+		assert_eq!(delegation_target(&authority), Some(target));
+		assert_eq!(nonce_of(&authority), 1);
+	});
+}
+
+#[test]
+fn delegation_to_zero_address_clears_delegation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let target = deploy_self_storing_contract();
+		let account = Account::default();
+		let authority = account.address();
+		let _ = <Test as Config>::Currency::set_balance(&account.substrate_account(), 1_000_000);
+
+		apply_authorizations::<Test>(
+			&ALICE,
+			&[
+				account.sign_authorization(chain_id(), target, 0.into()),
+				account.sign_authorization(chain_id(), target, 1.into()),
+			],
+		);
+		assert_eq!(nonce_of(&authority), 2);
+		builder::bare_call(authority).build_and_unwrap_result();
+
+		apply_authorizations::<Test>(
+			&ALICE,
+			&[account.sign_authorization(chain_id(), H160::zero(), 2.into())],
+		);
+		assert_eq!(delegation_target(&authority), None);
+		assert_eq!(Pallet::<Test>::code(&authority), Vec::<u8>::new());
+
+		// The account is a plain account again, but keeps its storage.
+		let result = builder::bare_call(authority).build_and_unwrap_result();
+		assert!(result.data.is_empty());
+		assert!(Pallet::<Test>::get_storage(authority, [0; 32]).unwrap().is_some());
+	});
+}
+
+#[test]
+fn delegation_holds_deposit_until_cleared() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let account = Account::default();
+		let authority = account.address();
+
+		apply_authorizations::<Test>(
+			&ALICE,
+			&[account.sign_authorization(chain_id(), H160::repeat_byte(1), 0.into())],
+		);
+		let deposit = delegation_deposit_of(&ALICE);
+		assert!(deposit > 0);
+		assert_eq!(<DelegationOf<Test>>::get(authority).unwrap().deposit, deposit);
+
+		// Replacing the delegation keeps the deposit of the first one.
+		let payer = account.substrate_account();
+		let _ = <Test as Config>::Currency::set_balance(&payer, 1_000_000);
+		apply_authorizations::<Test>(
+			&payer,
+			&[account.sign_authorization(chain_id(), H160::repeat_byte(2), 1.into())],
+		);
+		assert_eq!(delegation_target(&authority), Some(H160::repeat_byte(2)));
+		assert_eq!(delegation_deposit_of(&ALICE), deposit);
+		assert_eq!(delegation_deposit_of(&payer), 0);
+
+		// Clearing the delegation releases the deposit to the one who paid it.
+		apply_authorizations::<Test>(
+			&payer,
+			&[account.sign_authorization(chain_id(), H160::zero(), 2.into())],
+		);
+		assert_eq!(delegation_target(&authority), None);
+		assert_eq!(delegation_deposit_of(&ALICE), 0);
+	});
+}
+
+#[test]
+fn delegation_without_deposit_is_skipped() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = Account::default();
+		let authority = account.address();
+
+		apply_authorizations::<Test>(
+			&ALICE,
+			&[account.sign_authorization(chain_id(), H160::repeat_byte(1), 0.into())],
+		);
+		assert_eq!(delegation_target(&authority), None);
+		assert_eq!(nonce_of(&authority), 0);
+	});
+}
+
+#[test]
+fn dry_run_applies_self_sponsored_authorization() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let target = deploy_self_storing_contract();
+		let account = Account::default();
+		let authority = account.address();
+		let _ =
+			<Test as Config>::Currency::set_balance(&account.substrate_account(), 100_000_000_000);
+
+		// The nonce of the sender is bumped before the authorizations are applied, so a
+		// self-sponsored authorization must use the next nonce.
+		let info = Pallet::<Test>::dry_run_eth_transact(
+			GenericTransaction {
+				from: Some(authority),
+				to: Some(authority),
+				authorization_list: vec![account.sign_authorization(chain_id(), target, 1.into())],
+				..Default::default()
+			},
+			Default::default(),
+		)
+		.unwrap();
+		assert_eq!(info.data, H256::from(authority).as_bytes().to_vec());
+	});
+}
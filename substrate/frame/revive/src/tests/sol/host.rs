@@ -18,10 +18,14 @@
 //! The pallet-revive shared VM integration test suite.
 use crate::{
 	address::AddressMapper,
+	evm::AccessListEntry,
 	metering::TransactionLimits,
 	test_utils::{builder::Contract, ALICE, BOB, BOB_ADDR},
-	tests::{builder, test_utils, test_utils::get_contract, ExtBuilder, RuntimeEvent, Test},
-	Code, Config, Error, Key, System, H256, U256,
+	tests::{
+		builder, sol::make_initcode_from_runtime_code, test_utils, test_utils::get_contract,
+		ExtBuilder, RuntimeEvent, Test,
+	},
+	Code, Config, Error, ExecConfig, Key, RuntimeCosts, System, WeightToken, H256, U256,
 };
 use frame_support::assert_err_ignore_postinfo;
 
@@ -400,6 +404,46 @@ fn sload_error_reading_non_32_byte_value() {
 	});
 }
 
+#[test]
+fn sload_charges_warm_and_cold_access() {
+	use revm::bytecode::opcode::{CALLDATALOAD, POP, PUSH0, SLOAD, STOP};
+
+	// Reads slot 0, and then the slot given in the call data.
+	let runtime_code = vec![PUSH0, SLOAD, POP, PUSH0, CALLDATALOAD, SLOAD, POP, STOP];
+	let code = make_initcode_from_runtime_code(&runtime_code);
+
+	ExtBuilder::default().build().execute_with(|| {
+		<Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let weight_consumed = |slot: u64, access_list: Vec<AccessListEntry>| {
+			builder::bare_call(addr)
+				.data(U256::from(slot).to_big_endian().to_vec())
+				.exec_config(ExecConfig::new_substrate_tx().with_access_list(access_list))
+				.build()
+				.weight_consumed
+		};
+		let cost = |token: RuntimeCosts| <RuntimeCosts as WeightToken<Test>>::weight(&token);
+		let warm_discount = cost(RuntimeCosts::GetStorage(32))
+			.saturating_sub(cost(RuntimeCosts::WarmGetStorage(32)));
+
+		// Reading slot 0 a second time is warm.
+		let cold = weight_consumed(1, vec![]);
+		let warm = weight_consumed(0, vec![]);
+		assert_eq!(cold.saturating_sub(warm), warm_discount);
+
+		// Slots in the access list are warm, but are paid for upfront.
+		let access_list =
+			vec![AccessListEntry { address: addr, storage_keys: vec![H256::from_low_u64_be(1)] }];
+		let prewarmed = weight_consumed(1, access_list);
+		assert_eq!(
+			prewarmed.saturating_add(warm_discount),
+			cold.saturating_add(cost(RuntimeCosts::AccessList { addresses: 1, storage_keys: 1 })),
+		);
+	});
+}
+
 #[test_case(FixtureType::Solc)]
 #[test_case(FixtureType::Resolc)]
 fn sstore_works(fixture_type: FixtureType) {
@@ -111,9 +111,14 @@ pub fn blockhash<E: Ext>(interpreter: &mut Interpreter<E>) -> ControlFlow<Halt>
 /// Loads a word from storage.
 pub fn sload<E: Ext>(interpreter: &mut Interpreter<E>) -> ControlFlow<Halt> {
 	let ([], index) = interpreter.stack.popn_top()?;
-	// NB: SLOAD loads 32 bytes from storage (i.e. U256).
-	interpreter.ext.charge_or_halt(RuntimeCosts::GetStorage(32))?;
 	let key = Key::Fix(index.to_big_endian());
+	// NB: SLOAD loads 32 bytes from storage (i.e. U256).
+	let cost = if interpreter.ext.access_storage(&key) {
+		RuntimeCosts::WarmGetStorage(32)
+	} else {
+		RuntimeCosts::GetStorage(32)
+	};
+	interpreter.ext.charge_or_halt(cost)?;
 	let value = interpreter.ext.get_storage(&key);
 
 	*index = if let Some(storage_value) = value {
@@ -168,7 +173,10 @@ pub fn sstore<E: Ext>(interpreter: &mut Interpreter<E>) -> ControlFlow<Halt> {
 	store_helper(
 		interpreter,
 		RuntimeCosts::SetStorage { new_bytes: 32, old_bytes },
-		|ext, key, value, take_old| ext.set_storage(key, value, take_old),
+		|ext, key, value, take_old| {
+			ext.access_storage(key);
+			ext.set_storage(key, value, take_old)
+		},
 		|new_bytes, old_bytes| RuntimeCosts::SetStorage { new_bytes, old_bytes },
 	)
 }
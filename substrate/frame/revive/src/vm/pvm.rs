@@ -518,6 +518,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		let write_outcome = if transient {
 			self.ext.set_transient_storage(&key, value, false)?
 		} else {
+			self.ext.access_storage(&key);
 			self.ext.set_storage(&key, value, false)?
 		};
 
@@ -561,23 +562,22 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		read_mode: StorageReadMode,
 	) -> Result<ReturnErrorCode, TrapReason> {
 		let transient = Self::is_transient(flags)?;
-		let costs = |len| {
-			if transient {
-				RuntimeCosts::GetTransientStorage(len)
-			} else {
-				RuntimeCosts::GetStorage(len)
-			}
+		let costs = |len, warm| match (transient, warm) {
+			(true, _) => RuntimeCosts::GetTransientStorage(len),
+			(false, true) => RuntimeCosts::WarmGetStorage(len),
+			(false, false) => RuntimeCosts::GetStorage(len),
 		};
-		let charged = self.charge_gas(costs(limits::STORAGE_BYTES))?;
+		let charged = self.charge_gas(costs(limits::STORAGE_BYTES, false))?;
 		let key = self.decode_key(memory, key_ptr, key_len)?;
-		let outcome = if transient {
-			self.ext.get_transient_storage(&key)
+		let (outcome, warm) = if transient {
+			(self.ext.get_transient_storage(&key), false)
 		} else {
-			self.ext.get_storage(&key)
+			let warm = self.ext.access_storage(&key);
+			(self.ext.get_storage(&key), warm)
 		};
 
 		if let Some(value) = outcome {
-			self.adjust_gas(charged, costs(value.len() as u32));
+			self.adjust_gas(charged, costs(value.len() as u32, warm));
 
 			match read_mode {
 				StorageReadMode::FixedOutput32 => {
@@ -607,7 +607,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 				},
 			}
 		} else {
-			self.adjust_gas(charged, costs(0));
+			self.adjust_gas(charged, costs(0, warm));
 
 			match read_mode {
 				StorageReadMode::FixedOutput32 => {
@@ -109,6 +109,9 @@ pub enum RuntimeCosts {
 	ContainsStorage(u32),
 	/// Weight of calling `seal_get_storage` with the specified size in storage.
 	GetStorage(u32),
+	/// Weight of calling `seal_get_storage` with the specified size in storage, for an item that
+	/// was already accessed in this transaction.
+	WarmGetStorage(u32),
 	/// Weight of calling the `takeStorage` function of the `Storage` pre-compile
 	/// for the given size.
 	TakeStorage(u32),
@@ -178,6 +181,8 @@ pub enum RuntimeCosts {
 	Blake2F(u32),
 	/// Weight of calling `Modexp` precompile
 	Modexp(u64),
+	/// Weight of warming the entries of the EIP-2930 access list of a transaction.
+	AccessList { addresses: u32, storage_keys: u32 },
 }
 
 /// For functions that modify storage, benchmarks are performed with one item in the
@@ -283,6 +288,9 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			ClearStorage(len) => cost_storage!(write, clear_storage, len),
 			ContainsStorage(len) => cost_storage!(read, contains_storage, len),
 			GetStorage(len) => cost_storage!(read, seal_get_storage, len),
+			// A warm item is already in the overlay, so we don't charge for reading it from a full
+			// storage.
+			WarmGetStorage(len) => T::WeightInfo::seal_get_storage(len),
 			TakeStorage(len) => cost_storage!(write, take_storage, len),
 			SetTransientStorage { new_bytes, old_bytes } => {
 				cost_storage!(write_transient, seal_set_transient_storage, new_bytes, old_bytes)
@@ -340,6 +348,14 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			Identity(len) => T::WeightInfo::identity(len),
 			Blake2F(rounds) => T::WeightInfo::blake2f(rounds),
 			Modexp(gas) => Weight::from_parts(gas.saturating_mul(WEIGHT_PER_GAS), 0),
+			// Each storage key is charged the overhead of a cold read, so that subsequent reads
+			// can be charged as warm.
+			AccessList { addresses, storage_keys } => cost_args!(noop_host_fn, addresses)
+				.saturating_add(
+					T::WeightInfo::get_storage_full()
+						.saturating_sub(T::WeightInfo::get_storage_empty())
+						.saturating_mul(storage_keys.into()),
+				),
 		}
 	}
 }
@@ -1140,4 +1140,5 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositFactor = ConstU128<1>;
 	type WeightInfo = ();
 	type BlockNumberProvider = System;
+	type SpendingInspector = ();
 }
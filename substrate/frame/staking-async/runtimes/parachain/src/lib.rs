@@ -57,7 +57,8 @@ use frame_support::{
 		fungibles,
 		tokens::{imbalance::ResolveAssetTo, nonfungibles_v2::Inspect},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
-		ConstantStoragePrice, Equals, InstanceFilter, TransformOrigin, WithdrawReasons,
+		ConstantStoragePrice, Contains, Equals, EverythingBut, InstanceFilter, TransformOrigin,
+		WithdrawReasons,
 	},
 	weights::{ConstantMultiplier, Weight, WeightToFee as _},
 	BoundedVec, PalletId,
@@ -173,9 +174,20 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// A type to identify calls creating stored multisig accounts. These are filtered until
+/// `pallet_multisig` is benchmarked for this runtime. The other stored multisig calls act on
+/// stored multisig accounts only and fail without them.
+pub struct IsStoredMultisigCall;
+impl Contains<RuntimeCall> for IsStoredMultisigCall {
+	fn contains(c: &RuntimeCall) -> bool {
+		matches!(c, RuntimeCall::Multisig(pallet_multisig::Call::create_multisig { .. }))
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 #[derive_impl(frame_system::config_preludes::ParaChainDefaultConfig)]
 impl frame_system::Config for Runtime {
+	type BaseCallFilter = EverythingBut<IsStoredMultisigCall>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type AccountId = AccountId;
@@ -446,6 +458,9 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type MaxPriceObservations = ConstU32<1024>;
+	type AmplificationOrigin = AssetsForceOrigin;
+	type MinAmplificationRampDuration = ConstU32<{ DAYS }>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = weights::pallet_asset_conversion::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -607,6 +622,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	// TODO add migration.
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
@@ -798,6 +814,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	// TODO add migration.
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
+	type SpendingInspector = ();
 }
 
 parameter_types! {
@@ -1036,6 +1053,7 @@ parameter_types! {
 	pub const NftsMetadataDepositBase: Balance = UniquesMetadataDepositBase::get();
 	pub const NftsAttributeDepositBase: Balance = UniquesAttributeDepositBase::get();
 	pub const NftsDepositPerByte: Balance = UniquesDepositPerByte::get();
+	pub const NftsOperatorDeposit: Balance = deposit(1, 132);
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -1051,6 +1069,7 @@ impl pallet_nfts::Config for Runtime {
 	type MetadataDepositBase = NftsMetadataDepositBase;
 	type AttributeDepositBase = NftsAttributeDepositBase;
 	type DepositPerByte = NftsDepositPerByte;
+	type OperatorDeposit = NftsOperatorDeposit;
 	type StringLimit = ConstU32<256>;
 	type KeyLimit = ConstU32<64>;
 	type ValueLimit = ConstU32<256>;
@@ -1532,6 +1551,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -1549,6 +1569,14 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: xcm::v5::Location, asset2: xcm::v5::Location) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn get_twap(asset1: xcm::v5::Location, asset2: xcm::v5::Location, from: BlockNumber, to: BlockNumber) -> Option<sp_runtime::FixedU128> {
+			AssetConversion::get_twap(asset1, asset2, from, to)
+		}
+
+		fn find_best_route(asset_in: xcm::v5::Location, asset_out: xcm::v5::Location, amount_in: Balance) -> Option<pallet_asset_conversion::SwapRoute<xcm::v5::Location, Balance>> {
+			AssetConversion::find_best_route(asset_in, asset_out, amount_in)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
@@ -252,9 +252,19 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// A type to identify calls creating stored multisig accounts. These are filtered until
+/// `pallet_multisig` is benchmarked for this runtime. The other stored multisig calls act on
+/// stored multisig accounts only and fail without them.
+pub struct IsStoredMultisigCall;
+impl Contains<RuntimeCall> for IsStoredMultisigCall {
+	fn contains(c: &RuntimeCall) -> bool {
+		matches!(c, RuntimeCall::Multisig(pallet_multisig::Call::create_multisig { .. }))
+	}
+}
+
 #[derive_impl(frame_system::config_preludes::RelayChainDefaultConfig)]
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = EverythingBut<IsIdentityCall>;
+	type BaseCallFilter = EverythingBut<(IsIdentityCall, IsStoredMultisigCall)>;
 	type BlockWeights = BlockWeights;
 	type BlockLength = BlockLength;
 	type Nonce = Nonce;
@@ -1234,6 +1244,7 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type MaxCallLen = ConstU32<{ 16 * 1024 }>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
 }
@@ -1437,6 +1448,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type SpendingInspector = ();
 }
 
 impl parachains_origin::Config for Runtime {}
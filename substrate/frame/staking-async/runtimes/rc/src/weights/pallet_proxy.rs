@@ -56,20 +56,25 @@ impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
 	}
 	/// Storage: `Proxy::Proxies` (r:1 w:0)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
 	/// The range of component `p` is `[1, 31]`.
 	fn proxy(p: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `89 + p * (37 ±0)`
-		//  Estimated: `4706`
+		//  Measured:  `89 + p * (136 ±0)`
+		//  Estimated: `6674`
 		// Minimum execution time: 17_743_000 picoseconds.
 		Weight::from_parts(18_436_629, 0)
-			.saturating_add(Weight::from_parts(0, 4706))
+			.saturating_add(Weight::from_parts(0, 6674))
 			// Standard Error: 2_056
 			.saturating_add(Weight::from_parts(43_916, 0).saturating_mul(p.into()))
-			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
 	}
 	/// Storage: `Proxy::Proxies` (r:1 w:0)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
 	/// Storage: `Proxy::Announcements` (r:1 w:1)
 	/// Proof: `Proxy::Announcements` (`max_values`: None, `max_size`: Some(2233), added: 4708, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
@@ -78,17 +83,17 @@ impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
 	/// The range of component `p` is `[1, 31]`.
 	fn proxy_announced(a: u32, p: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `416 + a * (68 ±0) + p * (37 ±0)`
-		//  Estimated: `5698`
+		//  Measured:  `416 + a * (68 ±0) + p * (136 ±0)`
+		//  Estimated: `6674`
 		// Minimum execution time: 41_728_000 picoseconds.
 		Weight::from_parts(42_605_142, 0)
-			.saturating_add(Weight::from_parts(0, 5698))
+			.saturating_add(Weight::from_parts(0, 6674))
 			// Standard Error: 2_675
 			.saturating_add(Weight::from_parts(173_815, 0).saturating_mul(a.into()))
 			// Standard Error: 2_764
 			.saturating_add(Weight::from_parts(29_849, 0).saturating_mul(p.into()))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: `Proxy::Announcements` (r:1 w:1)
 	/// Proof: `Proxy::Announcements` (`max_values`: None, `max_size`: Some(2233), added: 4708, mode: `MaxEncodedLen`)
@@ -169,33 +174,37 @@ impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
 	}
 	/// Storage: `Proxy::Proxies` (r:1 w:1)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
 	/// The range of component `p` is `[1, 31]`.
 	fn remove_proxy(p: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `89 + p * (37 ±0)`
-		//  Estimated: `4706`
+		//  Estimated: `6674`
 		// Minimum execution time: 28_231_000 picoseconds.
 		Weight::from_parts(29_589_594, 0)
-			.saturating_add(Weight::from_parts(0, 4706))
+			.saturating_add(Weight::from_parts(0, 6674))
 			// Standard Error: 1_950
 			.saturating_add(Weight::from_parts(54_339, 0).saturating_mul(p.into()))
-			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `Proxy::Proxies` (r:1 w:1)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
 	/// The range of component `p` is `[1, 31]`.
 	fn remove_proxies(p: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `89 + p * (37 ±0)`
-		//  Estimated: `4706`
+		//  Estimated: `6674`
 		// Minimum execution time: 25_116_000 picoseconds.
 		Weight::from_parts(26_314_944, 0)
-			.saturating_add(Weight::from_parts(0, 4706))
+			.saturating_add(Weight::from_parts(0, 6674))
 			// Standard Error: 1_968
 			.saturating_add(Weight::from_parts(39_294, 0).saturating_mul(p.into()))
-			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `Proxy::Proxies` (r:1 w:1)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
@@ -214,17 +223,53 @@ impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
 	}
 	/// Storage: `Proxy::Proxies` (r:1 w:1)
 	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
 	/// The range of component `p` is `[0, 30]`.
 	fn kill_pure(p: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `126 + p * (37 ±0)`
-		//  Estimated: `4706`
+		//  Estimated: `6674`
 		// Minimum execution time: 26_141_000 picoseconds.
 		Weight::from_parts(27_309_074, 0)
-			.saturating_add(Weight::from_parts(0, 4706))
+			.saturating_add(Weight::from_parts(0, 6674))
 			// Standard Error: 1_808
 			.saturating_add(Weight::from_parts(37_564, 0).saturating_mul(p.into()))
-			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Proxy::Proxies` (r:1 w:1)
+	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 31]`.
+	fn add_constrained_proxy(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `194 + p * (136 ±0)`
+		//  Estimated: `6674`
+		// Minimum execution time: 43_259_000 picoseconds.
+		Weight::from_parts(44_695_156, 0)
+			.saturating_add(Weight::from_parts(0, 6674))
+			// Standard Error: 4_913
+			.saturating_add(Weight::from_parts(85_900, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Proxy::Constraints` (r:1 w:1)
+	/// Proof: `Proxy::Constraints` (`max_values`: None, `max_size`: Some(3209), added: 5684, mode: `MaxEncodedLen`)
+	/// Storage: `Proxy::Proxies` (r:1 w:1)
+	/// Proof: `Proxy::Proxies` (`max_values`: None, `max_size`: Some(1241), added: 3716, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 31]`.
+	fn remove_expired_proxy(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `390 + p * (136 ±0)`
+		//  Estimated: `6674`
+		// Minimum execution time: 45_859_000 picoseconds.
+		Weight::from_parts(47_719_279, 0)
+			.saturating_add(Weight::from_parts(0, 6674))
+			// Standard Error: 6_437
+			.saturating_add(Weight::from_parts(100_144, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 }
@@ -367,6 +367,15 @@ fn generate_runtime_api_base_structures() -> Result<TokenStream> {
 				fn register_extension<E: #crate_::Extension>(&mut self, extension: E) {
 					std::cell::RefCell::borrow_mut(&self.extensions).register(extension);
 				}
+
+				fn set_storage(
+					&mut self,
+					key: std::vec::Vec<u8>,
+					value: std::option::Option<std::vec::Vec<u8>>,
+				) -> std::result::Result<(), #crate_::ApiError> {
+					std::cell::RefCell::borrow_mut(&self.changes).set_storage(key, value);
+					Ok(())
+				}
 			}
 
 			#[automatically_derived]
@@ -652,6 +652,17 @@ pub trait ApiExt<Block: BlockT> {
 
 	/// Register an [`Extension`] that will be accessible while executing a runtime api call.
 	fn register_extension<E: Extension>(&mut self, extension: E);
+
+	/// Set `key` to `value` in the storage seen by the runtime api calls done by this instance.
+	///
+	/// A `value` of `None` removes the key. The change is part of the storage changes returned by
+	/// [`Self::into_storage_changes`].
+	///
+	/// Returns an error if the implementation does not support overriding the storage, which is
+	/// the default.
+	fn set_storage(&mut self, _key: Vec<u8>, _value: Option<Vec<u8>>) -> Result<(), ApiError> {
+		Err(ApiError::Application("`set_storage` is not supported by this runtime api".into()))
+	}
 }
 
 /// Parameters for [`CallApiAt::call_api_at`].
@@ -146,7 +146,12 @@ pub struct OpaqueKeyOwnershipProof(Vec<u8>);
 // Runtime API.
 sp_api::decl_runtime_apis! {
 	/// API necessary for block authorship with Sassafras.
+	#[api_version(2)]
 	pub trait SassafrasApi {
+		/// Slot duration used by the chain.
+		#[api_version(2)]
+		fn slot_duration() -> SlotDuration;
+
 		/// Get ring context to be used for ticket construction and verification.
 		fn ring_context() -> Option<vrf::RingContext>;
 
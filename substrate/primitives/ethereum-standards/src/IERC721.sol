@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: MIT
+// This interface combines methods from the following OpenZeppelin contracts:
+//
+// IERC721.sol (base ERC-721 interface)
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/IERC721.sol
+//
+// IERC165.sol (interface detection)
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/introspection/IERC165.sol
+//
+// IERC721Metadata.sol (only `tokenURI` of the ERC-721 metadata extension)
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/extensions/IERC721Metadata.sol
+//
+// IERC721Receiver.sol (receiver interface)
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/IERC721Receiver.sol
+//
+pragma solidity ^0.8.20;
+
+///
+/// @dev Interface combining the ERC-721 standard, ERC-165 and `tokenURI`.
+/// Note: Due to ABI generation constraints, the interfaces are merged into a single contract.
+///
+interface IERC721 {
+    // ============================================================
+    // IERC721 - Base ERC-721 Interface
+    // https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/IERC721.sol
+    // ============================================================
+
+    /// @dev Emitted when `tokenId` token is transferred from `from` to `to`.
+    event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+
+    /// @dev Emitted when `owner` enables `approved` to manage the `tokenId` token.
+    event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+
+    /// @dev Emitted when `owner` enables or disables (`approved`) `operator` to manage all of its
+    /// assets.
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+    /// @dev Returns the number of tokens in ``owner``'s account.
+    function balanceOf(address owner) external view returns (uint256 balance);
+
+    /// @dev Returns the owner of the `tokenId` token.
+    ///
+    /// Requirements:
+    ///
+    /// - `tokenId` must exist.
+    function ownerOf(uint256 tokenId) external view returns (address owner);
+
+    /// @dev Safely transfers `tokenId` token from `from` to `to`.
+    ///
+    /// Requirements:
+    ///
+    /// - `from` cannot be the zero address.
+    /// - `to` cannot be the zero address.
+    /// - `tokenId` token must exist and be owned by `from`.
+    /// - If the caller is not `from`, it must be approved to move this token by either {approve}
+    ///   or {setApprovalForAll}.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
+    ///
+    /// Emits a {Transfer} event.
+    function safeTransferFrom(address from, address to, uint256 tokenId, bytes calldata data) external;
+
+    /// @dev Safely transfers `tokenId` token from `from` to `to`, checking first that contract
+    /// recipients are aware of the ERC-721 protocol to prevent tokens from being forever locked.
+    ///
+    /// Emits a {Transfer} event.
+    function safeTransferFrom(address from, address to, uint256 tokenId) external;
+
+    /// @dev Transfers `tokenId` token from `from` to `to`.
+    ///
+    /// WARNING: Note that the caller is responsible to confirm that the recipient is capable of
+    /// receiving ERC-721 or else they may be permanently lost. Usage of {safeTransferFrom}
+    /// prevents loss, though the caller must understand this adds an external call which
+    /// potentially creates a reentrancy vulnerability.
+    ///
+    /// Emits a {Transfer} event.
+    function transferFrom(address from, address to, uint256 tokenId) external;
+
+    /// @dev Gives permission to `to` to transfer `tokenId` token to another account.
+    /// The approval is cleared when the token is transferred.
+    ///
+    /// Only a single account can be approved at a time, so approving the zero address clears
+    /// previous approvals.
+    ///
+    /// Emits an {Approval} event.
+    function approve(address to, uint256 tokenId) external;
+
+    /// @dev Approve or remove `operator` as an operator for the caller.
+    /// Operators can call {transferFrom} or {safeTransferFrom} for any token owned by the caller.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    function setApprovalForAll(address operator, bool approved) external;
+
+    /// @dev Returns the account approved for `tokenId` token.
+    ///
+    /// Requirements:
+    ///
+    /// - `tokenId` must exist.
+    function getApproved(uint256 tokenId) external view returns (address operator);
+
+    /// @dev Returns if the `operator` is allowed to manage all of the assets of `owner`.
+    function isApprovedForAll(address owner, address operator) external view returns (bool);
+
+    // ============================================================
+    // IERC165 - Interface detection
+    // https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/introspection/IERC165.sol
+    // ============================================================
+
+    /// @dev Returns true if this contract implements the interface defined by `interfaceId`.
+    function supportsInterface(bytes4 interfaceId) external view returns (bool);
+
+    // ============================================================
+    // IERC721Metadata - ERC-721 Metadata Extension
+    // https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/extensions/IERC721Metadata.sol
+    // ============================================================
+
+    /// @dev Returns the Uniform Resource Identifier (URI) for `tokenId` token.
+    function tokenURI(uint256 tokenId) external view returns (string memory);
+}
+
+///
+/// @dev Interface for any contract that wants to support safe transfers from ERC-721 asset
+/// contracts.
+///
+interface IERC721Receiver {
+    /// @dev Whenever an {IERC721} `tokenId` token is transferred to this contract via
+    /// {IERC721-safeTransferFrom} by `operator` from `from`, this function is called.
+    ///
+    /// It must return its Solidity selector to confirm the token transfer.
+    function onERC721Received(
+        address operator,
+        address from,
+        uint256 tokenId,
+        bytes calldata data
+    ) external returns (bytes4);
+}
@@ -509,6 +509,28 @@ pub const TEST_RUNTIME_BABE_EPOCH_CONFIGURATION: BabeEpochConfiguration = BabeEp
 	allowed_slots: AllowedSlots::PrimaryAndSecondaryPlainSlots,
 };
 
+/// Storage key of the Sassafras epoch served by the `SassafrasApi`.
+///
+/// The test runtime doesn't run Sassafras: the tests of `sc-consensus-sassafras` put the genesis
+/// epoch under this key and deposit the epoch change digests themselves.
+pub const SASSAFRAS_EPOCH_KEY: &[u8] = b":test_sassafras_epoch:";
+
+/// Slot duration of the `SassafrasApi`, in milliseconds.
+pub const SASSAFRAS_SLOT_DURATION: u64 = 1000;
+
+fn sassafras_epoch() -> sp_consensus_sassafras::Epoch {
+	frame_support::storage::unhashed::get(SASSAFRAS_EPOCH_KEY).unwrap_or_else(|| {
+		sp_consensus_sassafras::Epoch {
+			index: 0,
+			start: Slot::from(0),
+			length: EpochDuration::get() as u32,
+			randomness: Default::default(),
+			authorities: Vec::new(),
+			config: Default::default(),
+		}
+	})
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -734,6 +756,61 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
+	impl sp_consensus_sassafras::SassafrasApi<Block> for Runtime {
+		fn slot_duration() -> sp_consensus_sassafras::SlotDuration {
+			sp_consensus_sassafras::SlotDuration::from_millis(SASSAFRAS_SLOT_DURATION)
+		}
+
+		fn ring_context() -> Option<sp_consensus_sassafras::vrf::RingContext> {
+			None
+		}
+
+		fn submit_tickets_unsigned_extrinsic(
+			_tickets: Vec<sp_consensus_sassafras::TicketEnvelope>,
+		) -> bool {
+			false
+		}
+
+		fn slot_ticket_id(_slot: Slot) -> Option<sp_consensus_sassafras::TicketId> {
+			None
+		}
+
+		fn slot_ticket(
+			_slot: Slot,
+		) -> Option<(sp_consensus_sassafras::TicketId, sp_consensus_sassafras::TicketBody)> {
+			None
+		}
+
+		fn current_epoch() -> sp_consensus_sassafras::Epoch {
+			sassafras_epoch()
+		}
+
+		fn next_epoch() -> sp_consensus_sassafras::Epoch {
+			let epoch = sassafras_epoch();
+			sp_consensus_sassafras::Epoch {
+				index: epoch.index + 1,
+				start: epoch.start + epoch.length as u64,
+				..epoch
+			}
+		}
+
+		fn generate_key_ownership_proof(
+			_authority_id: sp_consensus_sassafras::AuthorityId,
+		) -> Option<sp_consensus_sassafras::OpaqueKeyOwnershipProof> {
+			None
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_sassafras::EquivocationProof<
+			<Block as BlockT>::Header,
+			>,
+			_key_owner_proof: sp_consensus_sassafras::OpaqueKeyOwnershipProof,
+		) -> bool {
+			false
+		}
+	}
+
 	impl sp_offchain::OffchainWorkerApi<Block> for Runtime {
 		fn offchain_worker(header: &<Block as BlockT>::Header) {
 			let ext = Extrinsic::new_bare(
@@ -555,6 +555,10 @@ pub use pallet_nft_fractionalization;
 #[cfg(feature = "pallet-nfts")]
 pub use pallet_nfts;
 
+/// Provides precompiles for `pallet-nfts`.
+#[cfg(feature = "pallet-nfts-precompiles")]
+pub use pallet_nfts_precompiles;
+
 /// Runtime API for the FRAME NFTs pallet.
 #[cfg(feature = "pallet-nfts-runtime-api")]
 pub use pallet_nfts_runtime_api;